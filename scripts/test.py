@@ -83,6 +83,13 @@ class CheckFile:
                 if line == directive[1]:
                     return state
             raise Exception("No matching line found")
+        elif dirname == "CHECK-NOT":
+            for line in copy(state):
+                line = line.split("//")[0].strip()
+                line = self.ansi_escape.sub("", line)
+                if line == directive[1]:
+                    raise Exception("Unexpected matching line found")
+            return state
         else:
             raise Exception("Unknown directive `{}`".format(directive[0]))
 
@@ -86,8 +86,21 @@ fn main() {
         .arg(
             Arg::with_name("preproc")
                 .short("E")
+                .long("pp")
                 .help("Write preprocessed input files to stdout"),
         )
+        .arg(
+            Arg::with_name("preproc-directives")
+                .long("pp-directives")
+                .requires("preproc")
+                .help("Show consumed preprocessor directives as comments in the -E output"),
+        )
+        .arg(
+            Arg::with_name("deps")
+                .long("deps")
+                .conflicts_with("preproc")
+                .help("Write Makefile-style include dependencies of input files to stdout"),
+        )
         .arg(
             Arg::with_name("dump-ast")
                 .long("dump-ast")
@@ -112,6 +125,35 @@ fn main() {
                 .takes_value(true)
                 .number_of_values(1),
         )
+        .arg(
+            Arg::with_name("warn-overflow")
+                .long("warn-overflow")
+                .help("Warn when constant arithmetic overflows its result width")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .value_name("SEED")
+                .help("Sets the global seed of the random number generators")
+                .default_value("0")
+                .takes_value(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("list-instances")
+                .long("list-instances")
+                .help("List the elaborated instance hierarchy instead of emitting code"),
+        )
+        .arg(
+            Arg::with_name("dump-hierarchy")
+                .long("dump-hierarchy")
+                .value_name("FORMAT")
+                .help("Dump the elaborated instance hierarchy instead of emitting code")
+                .possible_values(&["json"])
+                .takes_value(true)
+                .number_of_values(1),
+        )
         .arg(
             Arg::with_name("lib")
                 .short("l")
@@ -161,6 +203,10 @@ fn main() {
         };
     }
     session.opts.opt_level = matches.value_of("opt-level").unwrap().parse().unwrap();
+    session.opts.warn_overflow = matches.is_present("warn-overflow");
+    session.opts.list_instances = matches.is_present("list-instances");
+    session.opts.dump_hierarchy = matches.value_of("dump-hierarchy") == Some("json");
+    session.opts.seed = matches.value_of("seed").unwrap().parse().unwrap();
 
     // Invoke the compiler.
     score(&session, &matches);
@@ -238,9 +284,16 @@ fn score(sess: &Session, matches: &ArgMatches) {
         // Parse the file.
         match language {
             Language::SystemVerilog | Language::Verilog => {
-                let preproc = svlog::preproc::Preprocessor::new(source, &include_paths, &defines);
+                let mut preproc =
+                    svlog::preproc::Preprocessor::new(source, &include_paths, &defines);
                 if matches.is_present("preproc") {
-                    for token in preproc {
+                    if matches.is_present("preproc-directives") {
+                        preproc = preproc.with_directives();
+                    }
+                    while let Some(token) = preproc.next() {
+                        for dir in preproc.take_directives() {
+                            print_directive(&dir.extract());
+                        }
                         print!(
                             "{}",
                             match token {
@@ -253,6 +306,30 @@ fn score(sess: &Session, matches: &ArgMatches) {
                             }
                         );
                     }
+                    for dir in preproc.take_directives() {
+                        print_directive(&dir.extract());
+                    }
+                    for warning in preproc.take_warnings() {
+                        sess.emit(warning);
+                    }
+                    continue;
+                }
+
+                if matches.is_present("deps") {
+                    for token in &mut preproc {
+                        if let Err(diag) = token {
+                            sess.emit(diag);
+                            failed = true;
+                        }
+                    }
+                    for warning in preproc.take_warnings() {
+                        sess.emit(warning);
+                    }
+                    print!("{}:", make_escape(&filename));
+                    for dep in preproc.dependencies() {
+                        print!(" \\\n  {}", make_escape(&dep.to_string_lossy()));
+                    }
+                    println!();
                     continue;
                 }
 
@@ -271,7 +348,7 @@ fn score(sess: &Session, matches: &ArgMatches) {
     if failed || sess.failed() {
         std::process::exit(1);
     }
-    if matches.is_present("preproc") {
+    if matches.is_present("preproc") || matches.is_present("deps") {
         return;
     }
 
@@ -467,6 +544,30 @@ fn elaborate_name(ctx: &ScoreContext, lib_id: score::LibRef, input_name: &str) -
                 svlog::InstVerbosityVisitor::new(ctx.svlog).visit_node_with_id(m, false);
             }
 
+            // List the elaborated instances if requested.
+            if ctx.sess.opts.list_instances {
+                use svlog::Context;
+                for inst in ctx.svlog.instances(m)?.iter() {
+                    let params = inst
+                        .params
+                        .iter()
+                        .map(|(name, value)| format!("{}={}", name, value.kind))
+                        .collect::<Vec<_>>();
+                    if params.is_empty() {
+                        println!("{} {}", inst.path, inst.target);
+                    } else {
+                        println!("{} {} #({})", inst.path, inst.target, params.join(", "));
+                    }
+                }
+                return Ok(());
+            }
+
+            // Dump the elaborated hierarchy if requested.
+            if ctx.sess.opts.dump_hierarchy {
+                println!("{}", svlog::hierarchy_json(ctx.svlog, m)?);
+                return Ok(());
+            }
+
             let mut cg = svlog::CodeGenerator::new(ctx.svlog);
             cg.emit_module(m)?;
             let mut module = cg.finalize();
@@ -550,6 +651,34 @@ fn parse_elaborate_name<S: AsRef<str>>(name: S) -> Result<(Option<Name>, Name, O
     Ok((lib, ent, third))
 }
 
+/// Print a directive recorded by the preprocessor as a comment.
+///
+/// The directive is printed as a block comment, such that code following it
+/// on the same line is kept. Whitespace the directive consumed after itself,
+/// such as the newline after a `define, is printed outside the comment to
+/// keep the line structure.
+fn print_directive(text: &str) {
+    let body = text.trim_end();
+    let comment = body.replace("*/", "*\\/");
+    print!("/* {} */{}", comment, &text[body.len()..]);
+}
+
+/// Escape a path for use as a target or prerequisite in a Makefile rule.
+fn make_escape(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '$' => escaped.push_str("$$"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// A visitor that emits detailed type information to stdout.
 pub struct TypeVerbosityVisitor<'a, 'gcx>(&'a svlog::GlobalContext<'gcx>, svlog::ParamEnv);
 
@@ -194,7 +194,8 @@ impl fmt::Display for DiagBuilder2 {
                     let c = sp.source.get_content();
 
                     // Look for the start of the line.
-                    let (line, col, line_offset) = sp.begin().human();
+                    let (_, col, line_offset) = sp.begin().human();
+                    let (path, line) = sp.begin().human_file_and_line();
 
                     // Print the line in question.
                     let text: String = c
@@ -205,7 +206,7 @@ impl fmt::Display for DiagBuilder2 {
                     write!(
                         f,
                         "  --> {}:{}:{}-{}:\n",
-                        sp.source.get_path(),
+                        path,
                         line,
                         col,
                         col + sp.extract().len()
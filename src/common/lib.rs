@@ -75,6 +75,16 @@ pub struct SessionOptions {
     pub verbosity: Verbosity,
     /// The optimization level.
     pub opt_level: usize,
+    /// Warn about constant arithmetic that overflows its result width.
+    pub warn_overflow: bool,
+    /// List the elaborated instances instead of emitting code.
+    pub list_instances: bool,
+    /// Dump the elaborated instance hierarchy as JSON instead of emitting
+    /// code.
+    pub dump_hierarchy: bool,
+    /// The global seed from which the random number generators of the
+    /// individual processes are seeded.
+    pub seed: u32,
 }
 
 bitflags! {
@@ -137,6 +137,16 @@ pub trait SourceContent {
 pub struct SourceManager {
     map: RefCell<HashMap<RcStr, Source>>,
     vect: RefCell<Vec<Box<dyn SourceFile>>>,
+    lines: RefCell<HashMap<Source, Vec<LineDirective>>>,
+}
+
+/// A remapping of line numbers and file name established by a `` `line``
+/// directive.
+#[derive(Debug, Clone)]
+struct LineDirective {
+    offset: usize,
+    line: usize,
+    filename: RcStr,
 }
 
 impl SourceManager {
@@ -144,6 +154,7 @@ impl SourceManager {
         SourceManager {
             map: RefCell::new(HashMap::new()),
             vect: RefCell::new(Vec::new()),
+            lines: RefCell::new(HashMap::new()),
         }
     }
 
@@ -234,6 +245,33 @@ impl SourceManager {
         }));
         new_id
     }
+
+    /// Remap the line numbers and file name of a source file.
+    ///
+    /// The line following the byte offset `offset` in `source` is reported as
+    /// line `line` of file `filename` in diagnostics. Subsequent lines are
+    /// numbered consecutively until the next remapping.
+    pub fn add_line_directive(&self, source: Source, offset: usize, line: usize, filename: &str) {
+        let mut lines = self.lines.borrow_mut();
+        let dirs = lines.entry(source).or_insert_with(Vec::new);
+        dirs.retain(|d| d.offset < offset);
+        dirs.push(LineDirective {
+            offset,
+            line,
+            filename: RcStr::new(filename),
+        });
+    }
+
+    /// Find the line remapping in effect at a location, if any.
+    fn line_directive(&self, loc: Location) -> Option<LineDirective> {
+        self.lines
+            .borrow()
+            .get(&loc.source)?
+            .iter()
+            .rev()
+            .find(|d| d.offset <= loc.offset)
+            .cloned()
+    }
 }
 
 /// Get the global source manager.
@@ -409,6 +447,19 @@ impl Location {
         (line, col, line_offset)
     }
 
+    /// Determine the file name and line at this location, as remapped by any
+    /// `` `line`` directives in effect.
+    pub fn human_file_and_line(self) -> (RcStr, usize) {
+        let line = self.human_line();
+        match get_source_manager().line_directive(self) {
+            Some(dir) => {
+                let dir_line = Location::new(self.source, dir.offset).human_line();
+                (dir.filename, (dir.line + line).saturating_sub(dir_line + 1))
+            }
+            None => (self.source.get_path(), line),
+        }
+    }
+
     /// Determine the line at this location.
     pub fn human_line(self) -> usize {
         self.human().0
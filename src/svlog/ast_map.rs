@@ -79,6 +79,8 @@ pub enum AstNode<'ast> {
     GenCase(&'ast ast::GenerateCase<'ast>),
     /// A genvar declaration.
     GenvarDecl(&'ast ast::GenvarDecl<'ast>),
+    /// A foreach-loop index variable.
+    ForeachIndex(&'ast ast::ForeachIndex<'ast>),
     /// A typedef.
     Typedef(&'ast ast::Typedef<'ast>),
     /// A continuous assignment.
@@ -127,6 +129,7 @@ impl<'a> AstNode<'a> {
             AstNode::GenFor(x) => Some(x),
             AstNode::GenCase(x) => Some(x),
             AstNode::GenvarDecl(x) => Some(x),
+            AstNode::ForeachIndex(x) => Some(x),
             AstNode::Typedef(x) => Some(x),
             AstNode::ContAssign(x, _, _) => Some(x),
             AstNode::StructMember(x, _, _) => Some(x),
@@ -174,6 +177,7 @@ impl<'a> AstNode<'a> {
             AllNode::GenerateFor(x) => Box::new(Some(AstNode::GenFor(x)).into_iter()),
             AllNode::GenerateCase(x) => Box::new(Some(AstNode::GenCase(x)).into_iter()),
             AllNode::GenvarDecl(x) => Box::new(Some(AstNode::GenvarDecl(x)).into_iter()),
+            AllNode::ForeachIndex(x) => Box::new(Some(AstNode::ForeachIndex(x)).into_iter()),
             AllNode::Typedef(x) => Box::new(Some(AstNode::Typedef(x)).into_iter()),
             AllNode::ContAssign(x) => Box::new(
                 x.assignments
@@ -218,6 +222,7 @@ impl<'ast> HasSpan for AstNode<'ast> {
             AstNode::GenFor(x) => x.span(),
             AstNode::GenCase(x) => x.span(),
             AstNode::GenvarDecl(x) => x.span(),
+            AstNode::ForeachIndex(x) => x.span(),
             AstNode::Typedef(x) => x.span(),
             AstNode::ContAssign(x, _, _) => x.span(),
             AstNode::StructMember(_, x, _) => x.span(),
@@ -249,6 +254,7 @@ impl<'ast> HasSpan for AstNode<'ast> {
             AstNode::GenFor(x) => x.human_span(),
             AstNode::GenCase(x) => x.human_span(),
             AstNode::GenvarDecl(x) => x.human_span(),
+            AstNode::ForeachIndex(x) => x.human_span(),
             AstNode::Typedef(x) => x.human_span(),
             AstNode::ContAssign(x, _, _) => x.human_span(),
             AstNode::StructMember(x, _, _) => x.human_span(),
@@ -283,6 +289,7 @@ impl<'ast> HasDesc for AstNode<'ast> {
             AstNode::GenFor(x) => "for-generate statement",
             AstNode::GenCase(x) => "case-generate statement",
             AstNode::GenvarDecl(x) => "genvar",
+            AstNode::ForeachIndex(x) => "index variable",
             AstNode::Typedef(x) => "typedef",
             AstNode::ContAssign(x, _, _) => "continuous assignment",
             AstNode::StructMember(x, _, _) => "struct member",
@@ -314,6 +321,7 @@ impl<'ast> HasDesc for AstNode<'ast> {
             AstNode::GenFor(x) => x.to_definite_string(),
             AstNode::GenCase(x) => x.to_definite_string(),
             AstNode::GenvarDecl(x) => x.to_definite_string(),
+            AstNode::ForeachIndex(x) => x.to_definite_string(),
             AstNode::Typedef(x) => x.to_definite_string(),
             AstNode::ContAssign(x, _, _) => x.to_definite_string(),
             AstNode::StructMember(x, _, _) => x.to_definite_string(),
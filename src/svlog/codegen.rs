@@ -3,15 +3,18 @@
 //! This module implements LLHD code generation.
 
 use crate::{
+    ast_map::AstNode,
     crate_prelude::*,
-    hir::{AccessedNode, HirNode},
+    hir::{AccessedNode, HirNode, Visitor as _},
+    inst_details::{is_inst_elaborated, DumpVar},
     port_list::PortList,
     resolver::InstTarget,
     ty::UnpackedType,
     value::{Value, ValueKind},
     ParamEnv,
 };
-use num::{BigInt, One, ToPrimitive, Zero};
+use bit_vec::BitVec;
+use num::{BigInt, BigRational, One, ToPrimitive, Zero};
 use std::{
     collections::{HashMap, HashSet},
     iter::{once, repeat},
@@ -114,6 +117,8 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
             interned_lvalues: Default::default(),
             interned_rvalues: Default::default(),
             shadows: Default::default(),
+            loops: Default::default(),
+            labeled: Default::default(),
         };
 
         // Assign proper port names and collect ports into a lookup table.
@@ -139,6 +144,7 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
         }
 
         // Emit the actual contents of the entity.
+        gen.check_multiple_drivers(hir, env)?;
         gen.emit_module_block(id, env, &hir.block, &entity_name)?;
 
         // Assign default values to undriven output ports.
@@ -209,6 +215,24 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
                                 }
                             }
                         }
+                        ast::ModportPortData::Tf { dir, ref port } => {
+                            if dir.value == ast::ModportTfDir::Export {
+                                let modport = intf.modport.unwrap();
+                                self.emit(
+                                    DiagBuilder2::error(format!(
+                                        "unsupported: modport `{}` exports `{}`",
+                                        modport.name, port[0].name
+                                    ))
+                                    .span(dir.span)
+                                    .add_note(
+                                        "Exported tasks and functions must be defined by the \
+                                         module connected to the modport, which is not \
+                                         supported yet",
+                                    ),
+                                );
+                                return Err(());
+                            }
+                        }
                     }
                 }
                 trace!("    Modport-derived directions: {:?}", dirs);
@@ -322,6 +346,8 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
             HirNode::Proc(x) => x,
             _ => unreachable!(),
         };
+        crate::dataflow::check_procedure(self.cx, id, env)?;
+        self.check_sensitivity_list(hir, env)?;
 
         // Find the accessed nodes.
         let acc = self.accessed_nodes(hir.stmt, env)?;
@@ -330,29 +356,13 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
         let mut inputs = vec![];
         let mut outputs = vec![];
         for &id in acc.read.iter().filter(|id| !acc.written.contains(id)) {
-            sig.add_input(llhd::signal_ty(self.emit_type(match id {
-                AccessedNode::Regular(id) => self.type_of(id, env)?,
-                AccessedNode::Intf(intf, id) => {
-                    let intf_ty = self.type_of(intf, env)?;
-                    let intf_ty_inner = intf_ty.resolve_full().core.get_interface().unwrap();
-                    let mut sig_ty = self.type_of(id, intf_ty_inner.env)?.clone();
-                    sig_ty.dims.extend(&intf_ty.dims);
-                    sig_ty.intern(self.cx)
-                }
-            })?));
+            let ty = self.accessed_node_type(id, env)?;
+            sig.add_input(llhd::signal_ty(self.emit_type(ty)?));
             inputs.push(id);
         }
         for &id in acc.written.iter() {
-            sig.add_output(llhd::signal_ty(self.emit_type(match id {
-                AccessedNode::Regular(id) => self.type_of(id, env)?,
-                AccessedNode::Intf(intf, id) => {
-                    let intf_ty = self.type_of(intf, env)?;
-                    let intf_ty_inner = intf_ty.resolve_full().core.get_interface().unwrap();
-                    let mut sig_ty = self.type_of(id, intf_ty_inner.env)?.clone();
-                    sig_ty.dims.extend(&intf_ty.dims);
-                    sig_ty.intern(self.cx)
-                }
-            })?));
+            let ty = self.accessed_node_type(id, env)?;
+            sig.add_output(llhd::signal_ty(self.emit_type(ty)?));
             outputs.push(id);
         }
         trace!("Process Inputs: {:?}", inputs);
@@ -436,6 +446,8 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
             interned_lvalues: Default::default(),
             interned_rvalues: Default::default(),
             shadows: Default::default(),
+            loops: Default::default(),
+            labeled: Default::default(),
         };
         let entry_blk = pg.add_nameless_block();
         pg.builder.append_to(entry_blk);
@@ -519,6 +531,189 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
         })
     }
 
+    /// Emit a process for a `$monitor` call.
+    ///
+    /// The process prints the arguments once at the start of the simulation,
+    /// and again whenever one of the signals they read changes. Note that this
+    /// starts monitoring at time zero, rather than once the procedure
+    /// containing the call reaches it.
+    fn emit_monitor(
+        &mut self,
+        id: NodeId,
+        env: ParamEnv,
+        name_prefix: &str,
+    ) -> Result<EmittedProcedure> {
+        // Find the accessed nodes.
+        let acc = self.accessed_nodes(id, env)?;
+        let mut sig = llhd::ir::Signature::new();
+        let mut inputs = vec![];
+        for &id in acc.read.iter() {
+            let ty = self.accessed_node_type(id, env)?;
+            sig.add_input(llhd::signal_ty(self.emit_type(ty)?));
+            inputs.push(id);
+        }
+
+        // Create process and entry block.
+        let proc_name = format!("{}.monitor.{}.{}", name_prefix, id.as_usize(), env.0);
+        let mut prok = llhd::ir::UnitData::new(
+            llhd::ir::UnitKind::Process,
+            llhd::ir::UnitName::Local(proc_name),
+            sig,
+        );
+        let mut builder = llhd::ir::UnitBuilder::new_anonymous(&mut prok);
+        let mut values = HashMap::new();
+        for (&id, arg) in inputs.iter().zip(builder.input_args()) {
+            values.insert(id.into(), arg);
+        }
+        let mut pg = UnitGenerator {
+            gen: self,
+            builder: &mut builder,
+            values: &mut values,
+            interned_consts: Default::default(),
+            interned_lvalues: Default::default(),
+            interned_rvalues: Default::default(),
+            shadows: Default::default(),
+            loops: Default::default(),
+            labeled: Default::default(),
+        };
+        let entry_blk = pg.add_nameless_block();
+        pg.builder.append_to(entry_blk);
+
+        // Print the arguments, then wait for any of them to change.
+        let body_blk = pg.add_named_block("body");
+        let check_blk = pg.add_named_block("check");
+        pg.builder.ins().br(body_blk);
+        pg.builder.append_to(check_blk);
+        let trigger_on = inputs.iter().map(|id| pg.emitted_value(*id)).collect();
+        pg.builder.ins().wait(body_blk, trigger_on);
+        pg.builder.append_to(body_blk);
+        pg.flush_mir();
+        let mir = pg.mir_rvalue(id, env);
+        pg.emit_print(mir)?;
+        pg.builder.ins().br(check_blk);
+
+        Ok(EmittedProcedure {
+            unit: self.into.add_unit(prok),
+            inputs,
+            outputs: vec![],
+        })
+    }
+
+    /// Emit a process that forwards a signal to the target of a continuous
+    /// assignment or net declaration after a delay.
+    ///
+    /// These delays are inertial: a new value only reaches the target if the
+    /// input holds it for the entire delay, such that pulses shorter than the
+    /// delay are filtered out. The process waits for the delay to pass or the
+    /// input to change, and only drives the target if the input still carries
+    /// the value sampled before the wait. For integer values with a fall delay,
+    /// transitions to zero use the fall delay and all others the rise delay.
+    fn emit_inertial_delay(
+        &mut self,
+        id: NodeId,
+        env: ParamEnv,
+        delay: &hir::Delay3,
+        ty: llhd::Type,
+        name_prefix: &str,
+        index: usize,
+    ) -> Result<llhd::ir::UnitId> {
+        let mut sig = llhd::ir::Signature::new();
+        sig.add_input(llhd::signal_ty(ty.clone()));
+        sig.add_output(llhd::signal_ty(ty.clone()));
+
+        // Create process and entry block.
+        let proc_name = format!(
+            "{}.delay.{}.{}.{}",
+            name_prefix,
+            id.as_usize(),
+            env.0,
+            index
+        );
+        let mut prok = llhd::ir::UnitData::new(
+            llhd::ir::UnitKind::Process,
+            llhd::ir::UnitName::Local(proc_name),
+            sig,
+        );
+        let mut builder = llhd::ir::UnitBuilder::new_anonymous(&mut prok);
+        let input = builder.input_arg(0);
+        let output = builder.output_arg(0);
+        builder.set_name(input, "in".to_string());
+        builder.set_name(output, "out".to_string());
+        let mut values = HashMap::new();
+        let mut pg = UnitGenerator {
+            gen: self,
+            builder: &mut builder,
+            values: &mut values,
+            interned_consts: Default::default(),
+            interned_lvalues: Default::default(),
+            interned_rvalues: Default::default(),
+            shadows: Default::default(),
+            loops: Default::default(),
+            labeled: Default::default(),
+        };
+        let entry_blk = pg.add_nameless_block();
+        pg.builder.append_to(entry_blk);
+        let (rise, fall) = pg.emit_assign_delays(delay, env)?;
+        let epsilon = pg
+            .builder
+            .ins()
+            .const_time(llhd::value::TimeValue::new(num::zero(), 0, 1));
+        let sample_blk = pg.add_named_block("sample");
+        let check_blk = pg.add_named_block("check");
+        let apply_blk = pg.add_named_block("apply");
+        pg.builder.ins().br(sample_blk);
+
+        // Sample the input and wait for the delay to pass, or the input to
+        // change in the meantime.
+        pg.builder.append_to(sample_blk);
+        let value = pg.builder.ins().prb(input);
+        pg.builder.set_name(value, "value".to_string());
+        let delay = match fall {
+            Some(fall) if ty.is_int() => {
+                let zero = pg.builder.ins().const_int((ty.unwrap_int(), num::zero()));
+                let is_zero = pg.builder.ins().eq(value, zero);
+                let delays = pg.builder.ins().array(vec![rise, fall]);
+                pg.builder.ins().mux(delays, is_zero)
+            }
+            _ => rise,
+        };
+        pg.builder.ins().wait_time(check_blk, delay, vec![input]);
+
+        // Discard the sampled value if the input changed before the delay
+        // passed, and start over with the new value.
+        pg.builder.append_to(check_blk);
+        let current = pg.builder.ins().prb(input);
+        pg.builder.set_name(current, "current".to_string());
+        let held = pg.builder.ins().eq(value, current);
+        pg.builder.set_name(held, "held".to_string());
+        pg.builder.ins().br_cond(held, sample_blk, apply_blk);
+
+        // Drive the target and wait for the next change of the input.
+        pg.builder.append_to(apply_blk);
+        pg.builder.ins().drv(output, value, epsilon);
+        pg.builder.ins().wait(sample_blk, vec![input]);
+
+        Ok(self.into.add_unit(prok))
+    }
+
+    /// Determine the type of a node accessed by a process.
+    fn accessed_node_type(
+        &self,
+        id: AccessedNode,
+        env: ParamEnv,
+    ) -> Result<&'gcx UnpackedType<'gcx>> {
+        Ok(match id {
+            AccessedNode::Regular(id) => self.type_of(id, env)?,
+            AccessedNode::Intf(intf, id) => {
+                let intf_ty = self.type_of(intf, env)?;
+                let intf_ty_inner = intf_ty.resolve_full().core.get_interface().unwrap();
+                let mut sig_ty = self.type_of(id, intf_ty_inner.env)?.clone();
+                sig_ty.dims.extend(&intf_ty.dims);
+                sig_ty.intern(self.cx)
+            }
+        })
+    }
+
     /// Map a type to an LLHD type (interned).
     fn emit_type(&mut self, ty: &'gcx UnpackedType<'gcx>) -> Result<llhd::Type> {
         if let Some(x) = self.tables.interned_types.get(&ty) {
@@ -546,7 +741,13 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
         if let Some(dim) = ty.outermost_dim() {
             let size = match dim.get_size() {
                 Some(size) => size,
-                None => panic!("cannot map unsized array `{}` to LLHD", ty),
+                None => {
+                    self.emit(DiagBuilder2::error(format!(
+                        "unsupported: `{}` cannot be mapped to LLHD since its size is dynamic",
+                        ty
+                    )));
+                    return Err(());
+                }
             };
             let inner = ty.pop_dim(self.cx).unwrap();
             return Ok(llhd::array_ty(size, self.emit_type(inner)?));
@@ -573,90 +774,196 @@ impl<'a, 'gcx, C: Context<'gcx>> CodeGenerator<'gcx, &'a C> {
             };
         }
 
+        // Events are modeled as a single bit that toggles upon every trigger.
+        if ty.is_event() {
+            return Ok(llhd::int_ty(1));
+        }
+
         // Everything else we cannot do.
         error!("Cannot map type {:#?}", ty);
         panic!("cannot map `{}` to LLHD", ty);
     }
+}
 
-    /// Execute the initialization step of a generate loop.
-    fn execute_genvar_init(&mut self, id: NodeId, env: ParamEnv) -> Result<ParamEnv> {
-        let hir = self.hir_of(id)?;
-        match hir {
-            HirNode::GenvarDecl(_) => Ok(env),
-            HirNode::Stmt(stmt) => match stmt.kind {
-                hir::StmtKind::Assign {
-                    lhs,
-                    rhs,
-                    kind: hir::AssignKind::Block(ast::AssignOp::Identity),
-                } => {
-                    let target_id = self.resolve_node(lhs, env)?;
-                    let init_value = self.constant_value_of(rhs, env);
-                    let mut env_data = self.param_env_data(env).clone();
-                    env_data.set_value(target_id, init_value);
-                    Ok(self.intern_param_env(env_data))
-                }
-                _ => unreachable!(),
-            },
+/// Execute the initialization step of a generate loop.
+pub(crate) fn execute_genvar_init<'a>(
+    cx: &impl Context<'a>,
+    id: NodeId,
+    env: ParamEnv,
+) -> Result<ParamEnv> {
+    let hir = cx.hir_of(id)?;
+    match hir {
+        HirNode::GenvarDecl(decl) => match decl.init {
+            // Bind the initial value explicitly, such that every iteration
+            // of the loop sees its own binding of the genvar.
+            Some(init) => {
+                let init_value = cx.constant_value_of(init, env);
+                let mut env_data = cx.param_env_data(env).clone();
+                env_data.set_value(decl.id, init_value);
+                Ok(cx.intern_param_env(env_data))
+            }
+            None => Ok(env),
+        },
+        HirNode::Stmt(stmt) => match stmt.kind {
+            hir::StmtKind::Assign {
+                lhs,
+                rhs,
+                kind: hir::AssignKind::Block(ast::AssignOp::Identity),
+            } => {
+                let target_id = cx.resolve_node(lhs, env)?;
+                let init_value = cx.constant_value_of(rhs, env);
+                let mut env_data = cx.param_env_data(env).clone();
+                env_data.set_value(target_id, init_value);
+                Ok(cx.intern_param_env(env_data))
+            }
             _ => unreachable!(),
-        }
+        },
+        _ => unreachable!(),
     }
+}
 
-    /// Execute the iteration step of a generate loop.
-    fn execute_genvar_step(&mut self, id: NodeId, env: ParamEnv) -> Result<ParamEnv> {
-        let hir = self.hir_of(id)?;
-        let mut env_data = self.param_env_data(env).clone();
-        let next = match hir {
-            HirNode::Expr(expr) => match expr.kind {
-                hir::ExprKind::Unary(op, target_id) => {
-                    let target_id = self.resolve_node(target_id, env)?;
-                    let current_value = self.constant_value_of(target_id, env);
-                    let next_value = match current_value.kind {
-                        ValueKind::Int(ref v, ..) => match op {
-                            hir::UnaryOp::PostInc | hir::UnaryOp::PreInc => Some(v + 1),
-                            hir::UnaryOp::PostDec | hir::UnaryOp::PreDec => Some(v - 1),
-                            _ => None,
-                        }
-                        .map(|v| value::make_int(current_value.ty, v)),
-                        _ => unreachable!(),
-                    };
-                    next_value.map(|v| (target_id, self.intern_value(v)))
-                }
-                hir::ExprKind::Assign { .. } => {
-                    let mir = self.mir_rvalue(id, env);
-                    match mir.kind {
-                        mir::RvalueKind::Error => return Err(()),
-                        mir::RvalueKind::Assignment { lvalue, rvalue, .. } => {
-                            let target_id = match lvalue.kind {
-                                mir::LvalueKind::Error => return Err(()),
-                                mir::LvalueKind::Genvar(id) => id,
-                                _ => unreachable!(),
-                            };
-                            let next_value = self.const_mir_rvalue(Ref(rvalue));
-                            Some((target_id, next_value))
-                        }
-                        _ => unreachable!(),
+/// Execute the iteration step of a generate loop.
+pub(crate) fn execute_genvar_step<'a>(
+    cx: &impl Context<'a>,
+    id: NodeId,
+    env: ParamEnv,
+) -> Result<ParamEnv> {
+    let hir = cx.hir_of(id)?;
+    let mut env_data = cx.param_env_data(env).clone();
+    let next = match hir {
+        HirNode::Expr(expr) => match expr.kind {
+            hir::ExprKind::Unary(op, target_id) => {
+                let target_id = cx.resolve_node(target_id, env)?;
+                let current_value = cx.constant_value_of(target_id, env);
+                let next_value = match current_value.kind {
+                    ValueKind::Int(ref v, ..) => match op {
+                        hir::UnaryOp::PostInc | hir::UnaryOp::PreInc => Some(v + 1),
+                        hir::UnaryOp::PostDec | hir::UnaryOp::PreDec => Some(v - 1),
+                        _ => None,
+                    }
+                    .map(|v| value::make_int(current_value.ty, v)),
+                    _ => unreachable!(),
+                };
+                next_value.map(|v| (target_id, cx.intern_value(v)))
+            }
+            hir::ExprKind::Assign { .. } => {
+                let mir = cx.mir_rvalue(id, env);
+                match mir.kind {
+                    mir::RvalueKind::Error => return Err(()),
+                    mir::RvalueKind::Assignment { lvalue, rvalue, .. } => {
+                        let target_id = match lvalue.kind {
+                            mir::LvalueKind::Error => return Err(()),
+                            mir::LvalueKind::Genvar(id) => id,
+                            _ => unreachable!(),
+                        };
+                        let next_value = cx.const_mir_rvalue(Ref(rvalue));
+                        Some((target_id, next_value))
                     }
+                    _ => unreachable!(),
                 }
-                _ => None,
-            },
+            }
             _ => None,
-        };
-        match next {
-            Some((target_id, next_value)) => {
-                env_data.set_value(target_id, next_value);
-                return Ok(self.intern_param_env(env_data));
+        },
+        _ => None,
+    };
+    match next {
+        Some((target_id, next_value)) => {
+            env_data.set_value(target_id, next_value);
+            return Ok(cx.intern_param_env(env_data));
+        }
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "{} is not a valid genvar iteration step",
+                    hir.desc_full()
+                ))
+                .span(hir.human_span()),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Determine which way of a case-generate statement is selected.
+///
+/// The case expression and the item labels are evaluated as constants. Returns
+/// the index of the first way with a label equal to the expression, or `None`
+/// if no label matches and the default item applies.
+pub(crate) fn select_generate_case<'a>(
+    cx: &impl Context<'a>,
+    expr: NodeId,
+    ways: &[(Vec<NodeId>, hir::ModuleBlock)],
+    env: ParamEnv,
+) -> Option<usize> {
+    let value = cx.constant_value_of(expr, env);
+    ways.iter().position(|(labels, _)| {
+        labels.iter().any(|&label| {
+            let label = cx.constant_value_of(label, env);
+            match (&value.kind, &label.kind) {
+                // Integers of different types still match if they are equal.
+                (ValueKind::Int(a, ..), ValueKind::Int(b, ..)) => a == b,
+                (a, b) => a == b,
             }
-            None => {
-                self.emit(
-                    DiagBuilder2::error(format!(
-                        "{} is not a valid genvar iteration step",
-                        hir.desc_full()
-                    ))
-                    .span(hir.human_span()),
-                );
-                Err(())
+        })
+    })
+}
+
+/// Compute the mask of the bits compared against a case item label.
+///
+/// Returns `None` if all bits are compared.
+fn case_label_mask(kind: ast::CaseKind, special_bits: &BitVec, x_bits: &BitVec) -> Option<BigInt> {
+    let bits = match kind {
+        ast::CaseKind::Normal => return None,
+        ast::CaseKind::DontCareZ => {
+            let mut mask = special_bits.clone();
+            mask.difference(x_bits);
+            mask.negate();
+            mask
+        }
+        ast::CaseKind::DontCareXZ => {
+            let mut mask = special_bits.clone();
+            mask.negate();
+            mask
+        }
+    };
+    let mut mask = BigInt::zero();
+    for b in &bits {
+        mask <<= 1;
+        if b {
+            mask |= BigInt::one();
+        }
+    }
+    Some(mask)
+}
+
+/// A visitor for the HIR that collects the `$monitor` calls in a procedure.
+struct MonitorCollector<'a, C> {
+    cx: &'a C,
+    calls: Vec<NodeId>,
+}
+
+impl<'a, 'gcx: 'a, C> hir::Visitor<'gcx> for MonitorCollector<'a, C>
+where
+    C: Context<'gcx>,
+{
+    type Context = C;
+    fn context(&self) -> &C {
+        self.cx
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt) {
+        if let hir::StmtKind::Expr(expr_id) = stmt.kind {
+            if let Ok(HirNode::Expr(expr)) = self.cx.hir_of(expr_id) {
+                if let hir::ExprKind::Builtin(hir::BuiltinCall::Display(
+                    hir::DisplayTask::Monitor,
+                    _,
+                )) = expr.kind
+                {
+                    self.calls.push(expr_id);
+                }
             }
         }
+        hir::walk_stmt(self, stmt);
     }
 }
 
@@ -677,6 +984,13 @@ struct UnitGenerator<'a, 'gcx, C> {
     /// The shadow variables introduced to handle signals which are both read
     /// and written in a process.
     shadows: HashMap<AccessedNode, llhd::ir::Value>,
+    /// The `break` and `continue` targets of the loops currently being emitted,
+    /// innermost last.
+    loops: Vec<(llhd::ir::Block, llhd::ir::Block)>,
+    /// The labeled statements currently being emitted, innermost last, together
+    /// with the block that a `disable` of the statement branches to. The block
+    /// is only created once a `disable` needs it.
+    labeled: Vec<(NodeId, Option<llhd::ir::Block>)>,
 }
 
 impl<'a, 'gcx, C> Deref for UnitGenerator<'a, 'gcx, C> {
@@ -727,6 +1041,303 @@ where
         self.interned_rvalues.clear();
     }
 
+    /// Emit a call to an LLHD intrinsic such as `llhd.print`.
+    fn emit_intrinsic_call(
+        &mut self,
+        name: &str,
+        args: Vec<(llhd::Type, llhd::ir::Value)>,
+        return_type: llhd::Type,
+    ) -> llhd::ir::Inst {
+        let mut sig = llhd::ir::Signature::new();
+        let mut values = vec![];
+        for (ty, value) in args {
+            sig.add_input(ty);
+            values.push(value);
+        }
+        sig.set_return_type(return_type);
+        let name = llhd::ir::UnitName::Global(name.to_string());
+        let ext_unit = self.builder.add_extern(name, sig);
+        self.builder.ins().call(ext_unit, values)
+    }
+
+    /// Emit a non-empty string as an array of bytes, to be passed to an
+    /// intrinsic.
+    fn emit_byte_array(&mut self, text: &str) -> (llhd::Type, llhd::ir::Value) {
+        let bytes = text
+            .bytes()
+            .map(|b| self.builder.ins().const_int((8, BigInt::from(b))))
+            .collect();
+        let ty = llhd::array_ty(text.len(), llhd::int_ty(8));
+        (ty, self.builder.ins().array(bytes))
+    }
+
+    /// Emit a call to the `llhd.print` or `llhd.fprint` intrinsic for an MIR
+    /// print.
+    ///
+    /// The format string is passed as an array of bytes, followed by the
+    /// values it formats. Prints into a file are additionally passed the file
+    /// descriptor as the first argument.
+    fn emit_print(&mut self, mir: &'gcx mir::Rvalue<'gcx>) -> Result<()> {
+        let (file, format, args) = match mir.kind {
+            mir::RvalueKind::Print {
+                file,
+                ref format,
+                ref args,
+            } => (file, format, args),
+            mir::RvalueKind::Error => return Err(()),
+            _ => unreachable!(),
+        };
+        if format.is_empty() {
+            return Ok(());
+        }
+        let mut values = vec![];
+        if let Some(file) = file {
+            values.push((llhd::int_ty(32), self.emit_mir_rvalue(file)?));
+        }
+        values.push(self.emit_byte_array(format));
+        for &arg in args {
+            values.push((self.emit_type(arg.ty)?, self.emit_mir_rvalue(arg)?));
+        }
+        let name = match file {
+            Some(_) => "llhd.fprint",
+            None => "llhd.print",
+        };
+        self.emit_intrinsic_call(name, values, llhd::void_ty());
+        Ok(())
+    }
+
+    /// Emit a call to the `llhd.finish` or `llhd.stop` intrinsic for an MIR
+    /// simulation control task.
+    ///
+    /// The intrinsic is passed the exit status and the verbosity of the
+    /// diagnostics printed at the end of the simulation. The process halts
+    /// after `llhd.finish`, such that no statements after a `$finish` run.
+    fn emit_finish(&mut self, mir: &'gcx mir::Rvalue<'gcx>) -> Result<()> {
+        let (stop, verbosity, status, message) = match mir.kind {
+            mir::RvalueKind::Finish {
+                stop,
+                verbosity,
+                status,
+                message,
+            } => (stop, verbosity, status, message),
+            mir::RvalueKind::Error => return Err(()),
+            _ => unreachable!(),
+        };
+        if let Some(message) = message {
+            self.emit_print(message)?;
+        }
+        let status = self.builder.ins().const_int((32, BigInt::from(status)));
+        let verbosity = self.builder.ins().const_int((32, BigInt::from(verbosity)));
+        let name = if stop { "llhd.stop" } else { "llhd.finish" };
+        let args = vec![(llhd::int_ty(32), status), (llhd::int_ty(32), verbosity)];
+        self.emit_intrinsic_call(name, args, llhd::void_ty());
+        if !stop {
+            self.builder.ins().halt();
+            let dead_blk = self.add_named_block("unreachable");
+            self.builder.append_to(dead_blk);
+        }
+        Ok(())
+    }
+
+    /// Emit calls to the `llhd.dumpvar` intrinsic for an MIR waveform dump
+    /// task.
+    ///
+    /// The intrinsic is called once for every variable, and is passed the
+    /// hierarchical name of the variable relative to the calling instance,
+    /// its VCD variable type, and its width.
+    fn emit_dump_vars(&mut self, vars: &[DumpVar]) {
+        for var in vars {
+            let width = self.builder.ins().const_int((32, BigInt::from(var.width)));
+            let args = vec![
+                self.emit_byte_array(&var.path),
+                self.emit_byte_array(var.kind),
+                (llhd::int_ty(32), width),
+            ];
+            self.emit_intrinsic_call("llhd.dumpvar", args, llhd::void_ty());
+        }
+    }
+
+    /// Emit the rise and fall delays of a continuous assignment.
+    ///
+    /// The turn-off delay is not emitted, since values never transition to
+    /// high impedance in the two-valued logic we currently generate.
+    fn emit_assign_delays(
+        &mut self,
+        delay: &hir::Delay3,
+        env: ParamEnv,
+    ) -> Result<(llhd::ir::Value, Option<llhd::ir::Value>)> {
+        let rise = self.emit_const_delay(delay.rise, env)?;
+        let fall = match delay.fall {
+            Some(fall) => Some(self.emit_const_delay(fall, env)?),
+            None => None,
+        };
+        Ok((rise, fall))
+    }
+
+    /// Drive a target signal from an input signal through an inertial delay.
+    ///
+    /// See `emit_inertial_delay` for the process that implements the delay.
+    fn emit_delayed_drive(
+        &mut self,
+        id: NodeId,
+        env: ParamEnv,
+        delay: &hir::Delay3,
+        input: llhd::ir::Value,
+        target: llhd::ir::Value,
+        name_prefix: &str,
+        index: usize,
+    ) -> Result<()> {
+        let ty = self.llhd_type(input).unwrap_signal().clone();
+        let unit = self.emit_inertial_delay(id, env, delay, ty, name_prefix, index)?;
+        let ext_unit = self.builder.add_extern(
+            self.into.unit(unit).name().clone(),
+            self.into.unit(unit).sig().clone(),
+        );
+        self.builder.ins().inst(ext_unit, vec![input], vec![target]);
+        Ok(())
+    }
+
+    /// Emit a constant delay value as a time constant.
+    fn emit_const_delay(&mut self, id: NodeId, env: ParamEnv) -> Result<llhd::ir::Value> {
+        match self.const_delay_of(id, env)? {
+            Some(time) => Ok(self.emit_delay_time(time)),
+            None => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "delay `{}` is not a constant",
+                        self.span(id).extract()
+                    ))
+                    .span(self.span(id)),
+                );
+                Err(())
+            }
+        }
+    }
+
+    /// Emit a delay value.
+    ///
+    /// Returns `None` for non-constant integer delays, which cannot be
+    /// converted to a time value and must be waited for bit by bit instead.
+    fn emit_delay(&mut self, id: NodeId, env: ParamEnv) -> Result<Option<llhd::ir::Value>> {
+        if let Some(time) = self.const_delay_of(id, env)? {
+            return Ok(Some(self.emit_delay_time(time)));
+        }
+        let ty = self.type_of(id, env)?;
+        if ty.get_packed().map(|ty| ty.is_time()).unwrap_or(false) {
+            return Ok(Some(self.emit_rvalue(id, env)?));
+        }
+        if ty.get_simple_bit_vector().is_some() {
+            return Ok(None);
+        }
+        self.emit(
+            DiagBuilder2::error(format!(
+                "unsupported: non-constant delay `{}` of type `{}`",
+                self.span(id).extract(),
+                ty
+            ))
+            .span(self.span(id))
+            .add_note("Non-constant delays must be integers or `time` values"),
+        );
+        Err(())
+    }
+
+    /// Determine the value of a constant delay in seconds.
+    ///
+    /// Delays without a time unit, such as `#5` or `#2.5`, are multiples of the
+    /// time unit in effect. All delays are rounded to the time precision.
+    /// Returns `None` if the delay is not a constant.
+    fn const_delay_of(&mut self, id: NodeId, env: ParamEnv) -> Result<Option<BigRational>> {
+        let scale = self.time_scale_of(id);
+        if let HirNode::Expr(expr) = self.hir_of(id)? {
+            if let hir::ExprKind::RealConst(ref k) = expr.kind {
+                return Ok(Some(scale.round(k * &scale.unit)));
+            }
+        }
+        if !self.mir_rvalue(id, env).is_const() {
+            return Ok(None);
+        }
+        let value = self.constant_value_of(id, env);
+        let time = match value.kind {
+            ValueKind::Time(ref k) => k.clone(),
+            ValueKind::Int(ref k, ..) => BigRational::from_integer(k.clone()) * &scale.unit,
+            ValueKind::Error => return Err(()),
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a valid delay",
+                        self.span(id).extract()
+                    ))
+                    .span(self.span(id)),
+                );
+                return Err(());
+            }
+        };
+        if time < BigRational::zero() {
+            self.emit(
+                DiagBuilder2::error(format!("delay `{}` is negative", self.span(id).extract()))
+                    .span(self.span(id)),
+            );
+            return Err(());
+        }
+        Ok(Some(scale.round(time)))
+    }
+
+    /// Emit a time in seconds as a delay.
+    ///
+    /// Zero delays are mapped to a single epsilon step, such that the delayed
+    /// event still happens after the current one.
+    fn emit_delay_time(&mut self, time: BigRational) -> llhd::ir::Value {
+        let time = if time.is_zero() {
+            llhd::value::TimeValue::new(time, 0, 1)
+        } else {
+            llhd::value::TimeValue::new(time, 0, 0)
+        };
+        self.builder.ins().const_time(time)
+    }
+
+    /// Determine the time unit and precision in effect for a node.
+    ///
+    /// These are given by the `timeunit` and `timeprecision` declarations of
+    /// the enclosing module. Without them, the time unit is one nanosecond,
+    /// and the precision is one picosecond or the time unit, if finer.
+    fn time_scale_of(&self, node_id: NodeId) -> TimeScale {
+        let mut next = Some(node_id);
+        let timeunits = loop {
+            match next {
+                Some(id) => match self.ast_of(id) {
+                    Ok(AstNode::Module(m)) => break Some(&m.timeunits),
+                    _ => next = self.parent_node_id(id),
+                },
+                None => break None,
+            }
+        };
+        let unit = match timeunits.and_then(|t| t.unit.as_ref()) {
+            Some(unit) => time_value_in_seconds(&unit.value),
+            None => BigRational::new(One::one(), 1_000_000_000.into()),
+        };
+        let precision = match timeunits.and_then(|t| t.prec.as_ref()) {
+            Some(prec) => time_value_in_seconds(&prec.value),
+            None => std::cmp::min(
+                unit.clone(),
+                BigRational::new(One::one(), 1_000_000_000_000u64.into()),
+            ),
+        };
+        TimeScale { unit, precision }
+    }
+
+    /// Determine the delay of the net an lvalue assigns to, if it was
+    /// declared with one.
+    fn net_delay_of(&mut self, lvalue: &'gcx mir::Lvalue<'gcx>) -> Result<Option<hir::Delay3>> {
+        let root_id = match lvalue.root_id() {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+        match self.hir_of(root_id)? {
+            HirNode::VarDecl(decl) => Ok(decl.delay),
+            _ => Ok(None),
+        }
+    }
+
     /// Emit the code for the contents of a module.
     fn emit_module_block(
         &mut self,
@@ -735,6 +1346,9 @@ where
         hir: &hir::ModuleBlock,
         name_prefix: &str,
     ) -> Result<()> {
+        // Resolve nets driven multiple times.
+        let overridden = self.resolve_net_drivers(hir, env)?;
+
         // Emit declarations.
         for &decl_id in &hir.decls {
             let hir = match self.hir_of(decl_id)? {
@@ -742,9 +1356,21 @@ where
                 _ => unreachable!(),
             };
             let ty = self.type_of(decl_id, env)?;
-            let value = self.emit_varnet_decl(decl_id, ty, env, hir.init)?;
+            let init = hir.init.filter(|_| !overridden.contains(&decl_id));
+
+            // The declaration assignment of a net with a delay drives the
+            // net through that delay, rather than being connected directly.
+            let (init, delayed_init) = match hir.delay {
+                Some(delay) => (None, init.map(|init| (delay, init))),
+                None => (init, None),
+            };
+            let value = self.emit_varnet_decl(decl_id, ty, env, init)?;
             self.builder.set_name(value, hir.name.value.into());
             self.values.insert(decl_id.into(), value.into());
+            if let Some((delay, init)) = delayed_init {
+                let init = self.emit_rvalue_mode(init, env, Mode::Signal)?;
+                self.emit_delayed_drive(decl_id, env, &delay, init, value, name_prefix, 0)?;
+            }
         }
 
         // Emit interface instances.
@@ -754,6 +1380,9 @@ where
                 HirNode::Inst(x) => x,
                 _ => unreachable!(),
             };
+            if !is_inst_elaborated(self.cx, inst, env) {
+                continue;
+            }
             let inst = self.inst_details(Ref(inst), env)?;
 
             // Compute the array dimensions for the signals.
@@ -817,8 +1446,11 @@ where
             }
         }
 
-        // Emit assignments.
+        // Emit assignments, except for the ones overridden by stronger drivers.
         for &assign_id in &hir.assigns {
+            if overridden.contains(&assign_id) {
+                continue;
+            }
             let hir = match self.hir_of(assign_id)? {
                 HirNode::Assign(x) => x,
                 _ => unreachable!(),
@@ -841,16 +1473,44 @@ where
                 }
             }
 
-            // Emit the assignments.
+            // Emit the assignments. Delayed assignments and assignments to
+            // nets declared with a delay drive their target through an
+            // inertial delay process. If both carry a delay, the assignment
+            // delay is applied first, followed by the net delay.
             let delay = llhd::value::TimeValue::new(num::zero(), 0, 1);
             let delay = self.builder.ins().const_time(delay);
-            for &assign in &simplified {
+            for (index, &assign) in simplified.iter().enumerate() {
                 let lhs = self.emit_mir_lvalue(assign.lhs)?;
-                let rhs = self.emit_mir_rvalue(assign.rhs)?;
-                self.builder.ins().drv(lhs.0, rhs, delay);
-            }
-        }
-
+                let net_delay = self.net_delay_of(assign.lhs)?;
+                let delays: Vec<_> = hir.delay.iter().chain(net_delay.iter()).collect();
+                if delays.is_empty() {
+                    let rhs = self.emit_mir_rvalue(assign.rhs)?;
+                    self.builder.ins().drv(lhs.0, rhs, delay);
+                    continue;
+                }
+                let mut input = self.emit_mir_rvalue_mode(assign.rhs, Mode::Signal)?;
+                for (i, &assign_delay) in delays.iter().enumerate() {
+                    let target = if i + 1 == delays.len() {
+                        lhs.0
+                    } else {
+                        let ty = self.llhd_type(input).unwrap_signal().clone();
+                        let init = self.emit_zero_for_type(&ty);
+                        self.builder.ins().sig(init)
+                    };
+                    self.emit_delayed_drive(
+                        assign_id,
+                        env,
+                        assign_delay,
+                        input,
+                        target,
+                        name_prefix,
+                        index * 2 + i,
+                    )?;
+                    input = target;
+                }
+            }
+        }
+
         // Emit module instantiations.
         for &inst_id in &hir.insts {
             // Resolve the instantiation details.
@@ -858,6 +1518,9 @@ where
                 HirNode::Inst(x) => x,
                 _ => unreachable!(),
             };
+            if !is_inst_elaborated(self.cx, inst, env) {
+                continue;
+            }
             let inst = self.inst_details(Ref(inst), env)?;
             let target_module = match inst.target.kind {
                 InstTarget::Module(x) => self.hir_of_module(x)?,
@@ -921,20 +1584,47 @@ where
                 } => {
                     let mut local_env = env;
                     for &i in init {
-                        local_env = self.execute_genvar_init(i, local_env)?;
+                        local_env = execute_genvar_init(self.cx, i, local_env)?;
                     }
                     while self.constant_value_of(cond, local_env).is_true() {
                         self.emit_module_block(id, local_env, body, name_prefix)?;
-                        local_env = self.execute_genvar_step(step, local_env)?;
+                        local_env = execute_genvar_step(self.cx, step, local_env)?;
+                    }
+                }
+                hir::GenKind::Case {
+                    expr,
+                    ref ways,
+                    ref default,
+                } => {
+                    let body = match select_generate_case(self.cx, expr, ways, env) {
+                        Some(index) => Some(&ways[index].1),
+                        None => default.as_ref(),
+                    };
+                    if let Some(body) = body {
+                        self.emit_module_block(id, env, body, name_prefix)?;
                     }
                 }
                 _ => return self.unimp_msg("code generation for", hir),
             }
         }
 
-        // Emit and instantiate procedures.
+        // Emit procedures, and separate processes for the `$monitor` calls
+        // they contain.
+        let mut emitted = vec![];
         for &proc_id in &hir.procs {
-            let prok = self.emit_procedure(proc_id, env, name_prefix)?;
+            emitted.push((proc_id, self.emit_procedure(proc_id, env, name_prefix)?));
+            let mut collector = MonitorCollector {
+                cx: self.cx,
+                calls: vec![],
+            };
+            collector.visit_node_with_id(proc_id, false);
+            for call_id in collector.calls {
+                emitted.push((call_id, self.emit_monitor(call_id, env, name_prefix)?));
+            }
+        }
+
+        // Instantiate the procedures.
+        for (proc_id, prok) in emitted {
             let lookup_value = |&id: &AccessedNode| match self.values.get(&id) {
                 Some(v) => v.clone(),
                 None => {
@@ -1029,6 +1719,16 @@ where
                     if mir.is_error() {
                         return Err(());
                     }
+                    if self.net_delay_of(mir)?.is_some() {
+                        self.emit(
+                            DiagBuilder2::warning(format!(
+                                "unsupported: net delay not applied to output port `{}`",
+                                port.name
+                            ))
+                            .span(mir.span)
+                            .add_note("The net is driven by the instance without delay."),
+                        );
+                    }
                     let mir = match port.kind {
                         ModulePortKind::Port => mir,
                         ModulePortKind::IntfSignal { decl_id, env, .. } => {
@@ -1649,6 +2349,27 @@ where
                 "runtime string comparisons not implemented"
             ),
 
+            mir::RvalueKind::Random(seed) => {
+                let seed = self.emit_mir_rvalue(seed)?;
+                let args = vec![(llhd::int_ty(32), seed)];
+                let inst = self.emit_intrinsic_call("llhd.random", args, llhd::int_ty(32));
+                Ok(self.builder.inst_result(inst))
+            }
+
+            mir::RvalueKind::FileOpen { ref name, ref mode } => {
+                let args = vec![self.emit_byte_array(name), self.emit_byte_array(mode)];
+                let inst = self.emit_intrinsic_call("llhd.fopen", args, llhd::int_ty(32));
+                Ok(self.builder.inst_result(inst))
+            }
+
+            mir::RvalueKind::Print { .. }
+            | mir::RvalueKind::Finish { .. }
+            | mir::RvalueKind::FileClose(_)
+            | mir::RvalueKind::DumpFile(_)
+            | mir::RvalueKind::DumpVars(..) => {
+                bug_span!(mir.span, self.cx, "system task used as a value")
+            }
+
             mir::RvalueKind::Error => Err(()),
         };
 
@@ -1836,6 +2557,51 @@ where
             // Transmutes are no-ops in code generation.
             mir::LvalueKind::Transmute(value) => self.emit_mir_lvalue(value),
 
+            // Truncations only remain for output port connections, since
+            // assignments slice the assigned value instead. Connect the lower
+            // bits of an auxiliary signal of the full width to the target.
+            mir::LvalueKind::Truncate(value) => {
+                let (target, _) = self.emit_mir_lvalue(value)?;
+                let ty = self.emit_type(mir.ty)?;
+                let init = self.emit_zero_for_type(&ty);
+                let aux = self.builder.ins().sig(init);
+                let size = value.ty.simple_bit_vector(self.cx, value.span).size;
+                let slice = self.builder.ins().ext_slice(aux, 0, size);
+                self.builder.ins().con(target, slice);
+                Ok((aux, None))
+            }
+
+            // Extensions likewise only remain for output port connections.
+            // Drive the extended value of an auxiliary signal of the narrower
+            // width onto the target.
+            mir::LvalueKind::Extend(value, signed) => {
+                let (target, _) = self.emit_mir_lvalue(value)?;
+                let ty = self.emit_type(mir.ty)?;
+                let init = self.emit_zero_for_type(&ty);
+                let aux = self.builder.ins().sig(init);
+                let width = mir.ty.simple_bit_vector(self.cx, mir.span).size;
+                let target_ty = self.emit_type(value.ty)?;
+                let zeros = self.emit_zero_for_type(&target_ty);
+                let narrow = self.builder.ins().prb(aux);
+                let result = if signed {
+                    let sign = self.builder.ins().ext_slice(narrow, width - 1, 1);
+                    let ones = self.builder.ins().not(zeros);
+                    let mux = self.builder.ins().array(vec![zeros, ones]);
+                    let mux = self.builder.ins().mux(mux, sign);
+                    let result = self.builder.ins().ins_slice(mux, narrow, 0, width);
+                    self.builder.set_name(result, "sext".to_string());
+                    result
+                } else {
+                    let result = self.builder.ins().ins_slice(zeros, narrow, 0, width);
+                    self.builder.set_name(result, "zext".to_string());
+                    result
+                };
+                let delay = llhd::value::TimeValue::new(num::zero(), 0, 1);
+                let delay = self.builder.ins().const_time(delay);
+                self.builder.ins().drv(target, result, delay);
+                Ok((aux, None))
+            }
+
             // Variables and ports trivially return their declaration value.
             // This is either the `var` or `sig` instruction which introduced
             // them.
@@ -1870,7 +2636,9 @@ where
                 self.emit_lvalue_index(value.ty, inner, base, length)
             }
 
-            mir::LvalueKind::Repeat(..) | mir::LvalueKind::Concat(..) => {
+            mir::LvalueKind::Repeat(..)
+            | mir::LvalueKind::Concat(..)
+            | mir::LvalueKind::Overlay(..) => {
                 bug_span!(
                     mir.span,
                     self.cx,
@@ -1986,6 +2754,17 @@ where
     fn emit_stmt(&mut self, stmt_id: NodeId, env: ParamEnv) -> Result<()> {
         self.flush_mir();
         match self.hir_of(stmt_id)? {
+            HirNode::Stmt(x) if x.label.is_some() => {
+                self.labeled.push((stmt_id, None));
+                let result = self.emit_stmt_regular(stmt_id, x, env);
+                let (_, exit_blk) = self.labeled.pop().unwrap();
+                result?;
+                if let Some(exit_blk) = exit_blk {
+                    self.builder.ins().br(exit_blk);
+                    self.builder.append_to(exit_blk);
+                }
+                Ok(())
+            }
             HirNode::Stmt(x) => self.emit_stmt_regular(stmt_id, x, env),
             HirNode::VarDecl(x) => self.emit_stmt_var_decl(stmt_id, x, env),
             _ => unreachable!(),
@@ -2039,6 +2818,20 @@ where
                             self.emit_blocking_assign_llhd(lhs_lv, rhs_rv)?;
                         }
                     }
+                    // LLHD has no way to hold a value against the other
+                    // drivers of a signal.
+                    hir::AssignKind::Override(kind) => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "unsupported: `{}` cannot hold its value until `{}`",
+                                kind.keyword(),
+                                kind.release_keyword()
+                            ))
+                            .span(hir.span)
+                            .add_note("Procedural continuous assignments are not supported"),
+                        );
+                        return Err(());
+                    }
                     hir::AssignKind::Nonblock => {
                         let delay = llhd::value::TimeValue::new(num::zero(), 1, 0);
                         let delay_const = self.builder.ins().const_time(delay);
@@ -2048,8 +2841,24 @@ where
                             self.builder.ins().drv(lhs_lv.0, rhs_rv, delay_const);
                         }
                     }
-                    hir::AssignKind::NonblockDelay(delay) => {
-                        let delay = self.emit_rvalue(delay, env)?;
+                    hir::AssignKind::NonblockDelay(delay_id) => {
+                        let delay = match self.emit_delay(delay_id, env)? {
+                            Some(delay) => delay,
+                            None => {
+                                self.emit(
+                                    DiagBuilder2::error(format!(
+                                        "unsupported: non-constant intra-assignment delay `{}`",
+                                        self.span(delay_id).extract()
+                                    ))
+                                    .span(self.span(delay_id))
+                                    .add_note(
+                                        "Only constant delays and `time` values can delay a \
+                                         non-blocking assignment",
+                                    ),
+                                );
+                                return Err(());
+                            }
+                        };
                         for &assign in &simplified {
                             let lhs_lv = self.emit_mir_lvalue(assign.lhs)?;
                             let rhs_rv = self.emit_mir_rvalue(assign.rhs)?;
@@ -2063,8 +2872,12 @@ where
                 stmt,
             } => {
                 let resume_blk = self.add_nameless_block();
-                let duration = self.emit_rvalue(expr_id, env)?.into();
-                self.builder.ins().wait_time(resume_blk, duration, vec![]);
+                match self.emit_delay(expr_id, env)? {
+                    Some(duration) => {
+                        self.builder.ins().wait_time(resume_blk, duration, vec![]);
+                    }
+                    None => self.emit_delay_bits(expr_id, env, resume_blk)?,
+                }
                 self.builder.append_to(resume_blk);
                 self.flush_mir(); // ensure we don't reuse earlier expr probe
                 self.emit_shadow_update();
@@ -2079,64 +2892,47 @@ where
                     _ => unreachable!(),
                 };
                 trace!("would now emit event checking code for {:#?}", expr_hir);
+                let events: Vec<_> = expr_hir
+                    .events
+                    .iter()
+                    .map(|event| (EventValue::Expr(event.expr), event.edge, &event.iff[..]))
+                    .collect();
+                self.emit_event_wait(&events, env)?;
 
-                // Store initial values of the expressions the event is
-                // sensitive to.
-                let init_blk = self.add_named_block("init");
-                self.builder.ins().br(init_blk);
-                self.builder.append_to(init_blk);
-                let mut init_values = vec![];
-                for event in &expr_hir.events {
-                    init_values.push(self.emit_rvalue(event.expr, env)?);
-                }
-
-                // Wait for any of the inputs to those expressions to change.
-                let check_blk = self.add_named_block("check");
-                let mut trigger_on = vec![];
-                for event in &expr_hir.events {
-                    let acc = self.accessed_nodes(event.expr, env)?;
-                    for &id in &acc.read {
-                        trigger_on.push(self.emitted_value(id).clone());
-                    }
-                }
-                self.builder.ins().wait(check_blk, trigger_on);
-                self.builder.append_to(check_blk);
-                self.flush_mir(); // ensure we don't reuse earlier expr probe
-                self.emit_shadow_update();
+                // Emit the actual statement.
+                self.emit_stmt(stmt, env)?;
+            }
+            hir::StmtKind::Timed {
+                control: hir::TimingControl::Cycle { count, event },
+                stmt,
+            } => {
+                let expr_hir = match self.hir_of(event)? {
+                    HirNode::EventExpr(x) => x,
+                    _ => unreachable!(),
+                };
+                let events: Vec<_> = expr_hir
+                    .events
+                    .iter()
+                    .map(|event| (EventValue::Expr(event.expr), event.edge, &event.iff[..]))
+                    .collect();
 
-                // Check if any of the events happened and produce a single bit
-                // value that represents this.
-                let mut event_cond = None;
-                for (event, init_value) in expr_hir.events.iter().zip(init_values.into_iter()) {
-                    trace!(
-                        "would now emit check if {:?} changed according to {:#?}",
-                        init_value,
-                        event
-                    );
-                    let now_value = self.emit_rvalue(event.expr, env)?;
-                    let mut trigger = self.emit_event_trigger(event.edge, init_value, now_value)?;
-                    for &iff in &event.iff {
-                        let iff_value = self.emit_rvalue_bool(iff, env)?;
-                        trigger = self.builder.ins().and(trigger, iff_value);
-                        self.builder.set_name(trigger, "iff".to_string());
+                // Wait for the clocking event once per cycle.
+                let cycles = self.constant_int_value_of(count, env)?;
+                let cycles = match cycles.to_usize() {
+                    Some(x) => x,
+                    None => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "cycle delay `##{}` must be a non-negative number of cycles",
+                                self.span(count).extract()
+                            ))
+                            .span(self.span(count)),
+                        );
+                        return Err(());
                     }
-                    event_cond = Some(match event_cond {
-                        Some(chain) => {
-                            let value = self.builder.ins().or(chain, trigger);
-                            self.builder.set_name(value, "event_or".to_string());
-                            value
-                        }
-                        None => trigger,
-                    });
-                }
-
-                // If the event happened, branch to a new block which will
-                // contain the subsequent statements. Otherwise jump back up to
-                // the initial block.
-                if let Some(event_cond) = event_cond {
-                    let event_blk = self.add_named_block("event");
-                    self.builder.ins().br_cond(event_cond, init_blk, event_blk);
-                    self.builder.append_to(event_blk);
+                };
+                for _ in 0..cycles {
+                    self.emit_event_wait(&events, env)?;
                 }
 
                 // Emit the actual statement.
@@ -2146,23 +2942,111 @@ where
                 control: hir::TimingControl::ImplicitEvent,
                 stmt,
             } => {
-                // Wait for any of the inputs to the statement to change.
-                let trigger_blk = self.add_named_block("trigger");
+                // Expand `@*` to an edge-insensitive event on every signal the
+                // statement reads.
+                let events: Vec<_> = self
+                    .implicit_sensitivity(stmt, env)?
+                    .into_iter()
+                    .map(|id| (EventValue::Signal(id), ast::EdgeIdent::Implicit, &[][..]))
+                    .collect();
+                self.emit_event_wait(&events, env)?;
+
+                // Emit the actual statement.
+                self.emit_stmt(stmt, env)?;
+            }
+            hir::StmtKind::Wait { cond, stmt } => {
+                // Check the condition, and wait for any of its inputs to
+                // change while it does not hold.
+                let check_blk = self.add_named_block("wait_check");
+                self.builder.ins().br(check_blk);
+                self.builder.append_to(check_blk);
+                self.flush_mir(); // ensure we don't reuse earlier expr probe
+                self.emit_shadow_update();
+                let cond_value = self.emit_rvalue_bool(cond, env)?;
+                let suspend_blk = self.add_named_block("wait_suspend");
+                let resume_blk = self.add_named_block("wait_resume");
+                self.builder
+                    .ins()
+                    .br_cond(cond_value, suspend_blk, resume_blk);
+                self.builder.append_to(suspend_blk);
                 let mut trigger_on = vec![];
-                let acc = self.accessed_nodes(stmt, env)?;
+                let acc = self.accessed_nodes(cond, env)?;
                 for &id in &acc.read {
                     trigger_on.push(self.emitted_value(id).clone());
                 }
-                self.builder.ins().wait(trigger_blk, trigger_on);
-                self.builder.append_to(trigger_blk);
-                self.flush_mir(); // ensure we don't reuse earlier expr probe
-                self.emit_shadow_update();
+                self.builder.ins().wait(check_blk, trigger_on);
+                self.builder.append_to(resume_blk);
 
                 // Emit the actual statement.
                 self.emit_stmt(stmt, env)?;
             }
+            hir::StmtKind::Trigger(target) => {
+                let lv = self.mir_lvalue(target, env);
+                if lv.is_error() {
+                    return Err(());
+                }
+                if !lv.ty.is_event() {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` is not an event; only named events can be triggered",
+                            lv.span.extract()
+                        ))
+                        .span(lv.span)
+                        .add_note(format!(
+                            "`{}` has type `{}`",
+                            lv.span.extract(),
+                            lv.ty
+                        )),
+                    );
+                    return Err(());
+                }
+
+                // Events are modeled as a bit that toggles whenever the event
+                // is triggered, which wakes up all processes waiting for it.
+                let value = self.emit_rvalue(target, env)?;
+                let value = self.builder.ins().not(value);
+                let lv = self.emit_mir_lvalue(lv)?;
+                self.emit_blocking_assign_llhd(lv, value)?;
+            }
             hir::StmtKind::Expr(expr_id) => {
-                self.emit_rvalue(expr_id, env)?;
+                if let HirNode::Expr(expr) = self.hir_of(expr_id)? {
+                    // Calls to `$monitor` are emitted as separate processes.
+                    if let hir::ExprKind::Builtin(hir::BuiltinCall::Display(
+                        hir::DisplayTask::Monitor,
+                        _,
+                    )) = expr.kind
+                    {
+                        return Ok(());
+                    }
+                }
+                // Casts to `void` only evaluate their argument.
+                let mir = self.mir_rvalue(expr_id, env);
+                match mir.kind {
+                    mir::RvalueKind::Transmute(value) if mir.ty.is_void() => {
+                        self.emit_mir_rvalue(value)?;
+                    }
+                    mir::RvalueKind::Print { .. } => {
+                        self.emit_print(mir)?;
+                    }
+                    mir::RvalueKind::Finish { .. } => {
+                        self.emit_finish(mir)?;
+                    }
+                    mir::RvalueKind::FileClose(file) => {
+                        let file = self.emit_mir_rvalue(file)?;
+                        let args = vec![(llhd::int_ty(32), file)];
+                        self.emit_intrinsic_call("llhd.fclose", args, llhd::void_ty());
+                    }
+                    mir::RvalueKind::DumpFile(ref name) => {
+                        let args = vec![self.emit_byte_array(name)];
+                        self.emit_intrinsic_call("llhd.dumpfile", args, llhd::void_ty());
+                    }
+                    mir::RvalueKind::DumpVars(ref vars) => {
+                        self.emit_dump_vars(vars);
+                    }
+                    _ => {
+                        self.emit_mir_rvalue(mir)?;
+                    }
+                }
             }
             hir::StmtKind::If {
                 cond,
@@ -2229,9 +3113,15 @@ where
                 }
 
                 // Emit the loop body.
-                self.emit_stmt(body, env)?;
+                let next_blk = self.add_named_block("loop_next");
+                self.loops.push((exit_blk, next_blk));
+                let result = self.emit_stmt(body, env);
+                self.loops.pop();
+                result?;
 
                 // Emit the epilogue.
+                self.builder.ins().br(next_blk);
+                self.builder.append_to(next_blk);
                 let continue_cond = match kind {
                     hir::LoopKind::Forever => None,
                     hir::LoopKind::Repeat(_) => {
@@ -2263,13 +3153,74 @@ where
                     self.emit_stmt(stmt, env)?;
                 }
             }
+            hir::StmtKind::Foreach {
+                array,
+                ref indices,
+                body,
+            } => {
+                let loops = self.foreach_ranges(array, indices, env)?;
+                let exit_blk = self.add_named_block("foreach_exit");
+                self.emit_foreach(&loops, body, exit_blk, exit_blk, env)?;
+                self.builder.ins().br(exit_blk);
+                self.builder.append_to(exit_blk);
+            }
+            hir::StmtKind::Break | hir::StmtKind::Continue => {
+                let &(break_blk, continue_blk) = match self.loops.last() {
+                    Some(x) => x,
+                    None => bug_span!(hir.span, self.cx, "`break` or `continue` outside loop"),
+                };
+                let target = match hir.kind {
+                    hir::StmtKind::Break => break_blk,
+                    _ => continue_blk,
+                };
+                self.builder.ins().br(target);
+                let dead_blk = self.add_named_block("unreachable");
+                self.builder.append_to(dead_blk);
+            }
+            hir::StmtKind::Disable(target) => {
+                let index = match self.labeled.iter().rposition(|&(id, _)| id == target) {
+                    Some(x) => x,
+                    None => bug_span!(hir.span, self.cx, "`disable` outside of disabled statement"),
+                };
+                let exit_blk = match self.labeled[index].1 {
+                    Some(x) => x,
+                    None => {
+                        let blk = self.add_named_block("disable_exit");
+                        self.labeled[index].1 = Some(blk);
+                        blk
+                    }
+                };
+                self.builder.ins().br(exit_blk);
+                let dead_blk = self.add_named_block("unreachable");
+                self.builder.append_to(dead_blk);
+            }
+            hir::StmtKind::Release { target, kind } => {
+                let lv = self.mir_lvalue(target, env);
+                if !crate::mir::lower::assign::check_override_target(self.cx, kind, lv, hir.span) {
+                    return Err(());
+                }
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "unsupported: `{}` cannot restore the value before `{}`",
+                        kind.release_keyword(),
+                        kind.keyword()
+                    ))
+                    .span(hir.span)
+                    .add_note("Procedural continuous assignments are not supported"),
+                );
+                return Err(());
+            }
 
             hir::StmtKind::Case {
                 expr,
                 ref ways,
                 default,
                 kind,
+                up,
             } => {
+                if let Some(up) = up {
+                    self.check_case_items(up, expr, ways, default, kind, env)?;
+                }
                 let expr = self.emit_rvalue(expr, env)?;
                 let final_blk = self.add_named_block("case_exit");
                 for &(ref way_exprs, stmt) in ways {
@@ -2285,30 +3236,8 @@ where
                         let way_width = self.llhd_type(way_expr).unwrap_int();
 
                         // Generate the comparison mask based on the case kind.
-                        let mask = match kind {
-                            ast::CaseKind::Normal => None,
-                            ast::CaseKind::DontCareZ => {
-                                let mut mask = special_bits.clone();
-                                mask.difference(x_bits);
-                                mask.negate();
-                                Some(mask)
-                            }
-                            ast::CaseKind::DontCareXZ => {
-                                let mut mask = special_bits.clone();
-                                mask.negate();
-                                Some(mask)
-                            }
-                        };
-                        let mask = mask.map(|bits| {
-                            let mut mask = BigInt::zero();
-                            for b in &bits {
-                                mask <<= 1;
-                                if b {
-                                    mask |= BigInt::one();
-                                }
-                            }
-                            self.builder.ins().const_int((way_width, mask))
-                        });
+                        let mask = case_label_mask(kind, special_bits, x_bits)
+                            .map(|mask| self.builder.ins().const_int((way_width, mask)));
 
                         // Filter the comparison values through the mask.
                         let (lhs, rhs) = match mask {
@@ -2340,6 +3269,47 @@ where
                 self.builder.append_to(final_blk);
             }
 
+            hir::StmtKind::CaseInside {
+                expr,
+                ref ways,
+                default,
+            } => {
+                let final_blk = self.add_named_block("case_exit");
+
+                // Emit the case expression once, such that it is evaluated
+                // only once rather than for every item.
+                let expr_mir = self.mir_rvalue(expr, env);
+                let expr_value = self.emit_mir_rvalue(expr_mir)?;
+
+                for &(ref ranges, stmt) in ways {
+                    // The bodies of earlier items flush the emitted MIR, so
+                    // reinstate the value of the case expression.
+                    self.interned_rvalues
+                        .insert((expr_mir.id, Mode::Value), Ok((expr_value, Mode::Value)));
+
+                    // Check whether the expression is contained in any of the
+                    // item's values or value ranges.
+                    let check = crate::mir::lower::rvalue::lower_case_inside_item(
+                        self.cx, hir.id, expr, ranges, env,
+                    );
+                    let last_check = self.emit_mir_rvalue(check)?;
+                    let taken_blk = self.add_named_block("case_body");
+                    let untaken_blk = self.add_nameless_block();
+                    self.builder
+                        .ins()
+                        .br_cond(last_check, untaken_blk, taken_blk);
+                    self.builder.append_to(taken_blk);
+                    self.emit_stmt(stmt, env)?;
+                    self.builder.ins().br(final_blk);
+                    self.builder.append_to(untaken_blk);
+                }
+                if let Some(default) = default {
+                    self.emit_stmt(default, env)?;
+                }
+                self.builder.ins().br(final_blk);
+                self.builder.append_to(final_blk);
+            }
+
             _ => {
                 error!("{:#?}", hir);
                 return self.unimp_msg("code generation for", hir);
@@ -2348,6 +3318,563 @@ where
         Ok(())
     }
 
+    /// Check the items of a `unique`, `unique0`, or `priority` case statement.
+    ///
+    /// Warns about items of a `unique` or `unique0` case that overlap, and about
+    /// values not covered by a `unique` or `priority` case without default.
+    /// Only constant labels are checked, and coverage only for case
+    /// expressions up to 16 bits wide.
+    fn check_case_items(
+        &mut self,
+        up: ast::UniquePriority,
+        expr: NodeId,
+        ways: &[(Vec<NodeId>, NodeId)],
+        default: Option<NodeId>,
+        kind: ast::CaseKind,
+        env: ParamEnv,
+    ) -> Result<()> {
+        let keyword = match up {
+            ast::UniquePriority::Unique => "unique",
+            ast::UniquePriority::Unique0 => "unique0",
+            ast::UniquePriority::Priority => "priority",
+        };
+        let width = match self.type_of(expr, env)?.get_bit_size() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let all_ones = (BigInt::one() << width) - BigInt::one();
+
+        // Collect the labels which only match two-valued inputs, together
+        // with the bits they compare.
+        let mut labels = vec![];
+        for (way, &(ref way_exprs, _)) in ways.iter().enumerate() {
+            for &way_expr in way_exprs {
+                let value = self.constant_value_of(way_expr, env);
+                let (value, special_bits, x_bits) = match &value.kind {
+                    ValueKind::Int(v, s, x) => (v, s, x),
+                    _ => continue,
+                };
+                let mask = case_label_mask(kind, special_bits, x_bits);
+                if mask.is_none() && special_bits.any() {
+                    continue;
+                }
+                let mask = mask.unwrap_or_else(|| all_ones.clone());
+                labels.push((way, way_expr, value & &mask, mask));
+            }
+        }
+
+        // Items of a unique case must not overlap.
+        if up != ast::UniquePriority::Priority {
+            for (i, (way, label, value, mask)) in labels.iter().enumerate() {
+                let earlier = labels[..i]
+                    .iter()
+                    .find(|(w, _, v, m)| w != way && (v ^ value) & m & mask == BigInt::zero());
+                if let Some(&(_, earlier, ..)) = earlier {
+                    self.emit(
+                        DiagBuilder2::warning(format!("items of `{} case` overlap", keyword))
+                            .span(self.span(*label))
+                            .add_note("Overlaps with this earlier item:")
+                            .span(self.span(earlier)),
+                    );
+                }
+            }
+        }
+
+        // Items of a unique or priority case must cover all values.
+        if up != ast::UniquePriority::Unique0 && default.is_none() && width <= 16 {
+            let uncovered = (0u32..1 << width)
+                .map(BigInt::from)
+                .find(|x| !labels.iter().any(|(_, _, value, mask)| x & mask == *value));
+            if let Some(uncovered) = uncovered {
+                self.emit(
+                    DiagBuilder2::warning(format!("`{} case` does not cover all values", keyword))
+                        .span(self.span(expr))
+                        .add_note(format!(
+                            "No item matches if `{}` is {}; add a `default` item to handle \
+                             such values",
+                            self.span(expr).extract(),
+                            uncovered
+                        )),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the constant drivers of the nets in a module block by strength.
+    ///
+    /// Nets may be driven by their declaration assignment, by continuous
+    /// assignments, and implicitly by being `supply0` or `supply1` nets. For
+    /// nets where all drivers are constant, this determines for each bit the
+    /// driver with the highest strength. Warns about bits driven to different
+    /// values with equal strength. Returns the drivers which are overridden by
+    /// stronger ones on all bits, and should therefore not be emitted.
+    fn resolve_net_drivers(
+        &mut self,
+        hir: &hir::ModuleBlock,
+        env: ParamEnv,
+    ) -> Result<HashSet<NodeId>> {
+        struct Driver {
+            id: Option<NodeId>,
+            span: Span,
+            strength: hir::DriveStrength,
+            value: Option<BigInt>,
+        }
+        let const_int = |gen: &mut Self, expr: NodeId| -> Option<BigInt> {
+            let mir = gen.mir_rvalue(expr, env);
+            if !mir.is_const() || mir.is_error() {
+                return None;
+            }
+            match gen.const_mir_rvalue(mir.into()).kind {
+                ValueKind::Int(ref v, ref special, _) if !special.any() => Some(v.clone()),
+                _ => None,
+            }
+        };
+
+        // Collect the drivers of each net.
+        let mut nets: Vec<(&hir::VarDecl, Vec<Driver>)> = vec![];
+        for &decl_id in &hir.decls {
+            let decl = match self.hir_of(decl_id)? {
+                HirNode::VarDecl(x) => x,
+                _ => unreachable!(),
+            };
+            let net_type = match decl.kind {
+                ast::VarKind::Net { ty, .. } => ty,
+                ast::VarKind::Var => continue,
+            };
+            let strength = decl.drive.unwrap_or(hir::DriveStrength::STRONG);
+            let mut drivers = vec![];
+            let supply = match net_type {
+                ast::NetType::Supply0 => Some(BigInt::zero()),
+                ast::NetType::Supply1 => Some(-BigInt::one()),
+                _ => None,
+            };
+            if let Some(value) = supply {
+                drivers.push(Driver {
+                    id: None,
+                    span: decl.human_span(),
+                    strength: hir::DriveStrength {
+                        zero: ast::DriveStrength::Supply0,
+                        one: ast::DriveStrength::Supply1,
+                    },
+                    value: Some(value),
+                });
+            }
+            if let Some(init) = decl.init {
+                drivers.push(Driver {
+                    id: Some(decl_id),
+                    span: self.span(init),
+                    strength,
+                    value: const_int(self, init),
+                });
+            }
+            nets.push((decl, drivers));
+        }
+        for &assign_id in &hir.assigns {
+            let assign = match self.hir_of(assign_id)? {
+                HirNode::Assign(x) => x,
+                _ => unreachable!(),
+            };
+            match self.hir_of(assign.lhs)? {
+                HirNode::Expr(hir::Expr {
+                    kind: hir::ExprKind::Ident(..),
+                    ..
+                }) => (),
+                _ => continue,
+            }
+            let target = match self.resolve_node(assign.lhs, env) {
+                Ok(x) => x,
+                Err(()) => continue,
+            };
+            let value = const_int(self, assign.rhs);
+            if let Some((_, drivers)) = nets.iter_mut().find(|(decl, _)| decl.id == target) {
+                drivers.push(Driver {
+                    id: Some(assign_id),
+                    span: assign.span,
+                    strength: assign.drive.unwrap_or(hir::DriveStrength::STRONG),
+                    value,
+                });
+            }
+        }
+
+        // Resolve the multiply-driven nets whose drivers are all constant.
+        let mut overridden = HashSet::new();
+        for (decl, drivers) in nets {
+            if drivers.len() < 2 || drivers.iter().any(|d| d.value.is_none()) {
+                continue;
+            }
+            let width = match self.type_of(decl.id, env)?.get_bit_size() {
+                Some(x) => x,
+                None => continue,
+            };
+            let mut needed = vec![false; drivers.len()];
+            let mut conflicts = vec![false; drivers.len()];
+            for bit in 0..width {
+                let bits: Vec<_> = drivers
+                    .iter()
+                    .map(|d| {
+                        let value = (d.value.as_ref().unwrap() >> bit) & BigInt::one();
+                        let value = !value.is_zero();
+                        (value, d.strength.of_bit(value).level())
+                    })
+                    .collect();
+                let max = bits.iter().map(|&(_, level)| level).max().unwrap_or(0);
+                if max == 0 {
+                    continue;
+                }
+                let winners: Vec<_> = (0..drivers.len()).filter(|&i| bits[i].1 == max).collect();
+                let conflict = winners.iter().any(|&i| bits[i].0 != bits[winners[0]].0);
+                for i in winners {
+                    needed[i] = true;
+                    conflicts[i] |= conflict;
+                }
+            }
+            if conflicts.iter().any(|&c| c) {
+                let mut d = DiagBuilder2::warning(format!(
+                    "net `{}` is driven to different values with equal strength",
+                    decl.name
+                ))
+                .span(decl.human_span());
+                for (driver, _) in drivers.iter().zip(conflicts).filter(|&(_, c)| c) {
+                    d = d.add_note("Driven here:").span(driver.span);
+                }
+                self.emit(d);
+            }
+            for (driver, needed) in drivers.iter().zip(needed) {
+                if !needed {
+                    overridden.extend(driver.id);
+                }
+            }
+        }
+        Ok(overridden)
+    }
+
+    /// Warn about `always @(...)` procedures that do not list all signals
+    /// they read.
+    ///
+    /// Procedures sensitive to an edge, like `always @(posedge clk)`, describe
+    /// sequential logic and are exempt. Signals written by the procedure itself
+    /// are not required in the list.
+    fn check_sensitivity_list(&mut self, hir: &hir::Proc, env: ParamEnv) -> Result<()> {
+        if hir.kind != ast::ProcedureKind::Always {
+            return Ok(());
+        }
+        let (expr_id, body) = match self.hir_of(hir.stmt)? {
+            HirNode::Stmt(hir::Stmt {
+                kind:
+                    hir::StmtKind::Timed {
+                        control: hir::TimingControl::ExplicitEvent(expr_id),
+                        stmt,
+                    },
+                ..
+            }) => (*expr_id, *stmt),
+            _ => return Ok(()),
+        };
+        let expr = match self.hir_of(expr_id)? {
+            HirNode::EventExpr(x) => x,
+            _ => unreachable!(),
+        };
+        if expr
+            .events
+            .iter()
+            .any(|event| event.edge != ast::EdgeIdent::Implicit)
+        {
+            return Ok(());
+        }
+
+        // Compare the signals read in the body against the listed ones.
+        let mut listed = HashSet::new();
+        for event in &expr.events {
+            listed.extend(self.accessed_nodes(event.expr, env)?.read.iter().cloned());
+        }
+        let acc = self.accessed_nodes(body, env)?;
+        let mut missing = vec![];
+        for id in &acc.read {
+            if !acc.written.contains(id) && !listed.contains(id) {
+                let name = self.hir_of(id.id())?.human_span().extract();
+                missing.push(format!("`{}`", name));
+            }
+        }
+        if missing.is_empty() {
+            return Ok(());
+        }
+        self.emit(
+            DiagBuilder2::warning(format!(
+                "sensitivity list is missing {}",
+                missing.join(", ")
+            ))
+            .span(expr.span)
+            .add_note(
+                "Signals read by the procedure but missing from its sensitivity list do not \
+                 trigger it, which simulates differently than the synthesized logic",
+            )
+            .add_note("Add the missing signals, or use `always_comb` or `@*` instead"),
+        );
+        Ok(())
+    }
+
+    /// Warn about signals driven by more than one combinational process.
+    ///
+    /// Considers `always_comb` procedures and continuous assignments without
+    /// an explicit drive strength. Drivers only conflict if the parts of the
+    /// signal they assign overlap, such that assigning disjoint struct fields
+    /// or constant part-selects from different processes is fine.
+    fn check_multiple_drivers(&mut self, hir: &hir::Module<'gcx>, env: ParamEnv) -> Result<()> {
+        /// A step in the selection of the driven part of a signal.
+        enum Select {
+            Field(usize),
+            Range(Option<(BigInt, BigInt)>),
+        }
+
+        /// Check whether two selections are known to not overlap.
+        fn disjoint(a: &[Select], b: &[Select]) -> bool {
+            a.iter().zip(b.iter()).any(|pair| match pair {
+                (Select::Field(a), Select::Field(b)) => a != b,
+                (Select::Range(Some((alo, ahi))), Select::Range(Some((blo, bhi)))) => {
+                    ahi < blo || bhi < alo
+                }
+                _ => false,
+            })
+        }
+
+        // Collect the processes that drive combinationally.
+        let mut sources = HashSet::new();
+        for &proc_id in &hir.block.procs {
+            if let HirNode::Proc(prok) = self.hir_of(proc_id)? {
+                if prok.kind == ast::ProcedureKind::AlwaysComb {
+                    sources.insert(proc_id);
+                }
+            }
+        }
+        for &assign_id in &hir.block.assigns {
+            if let HirNode::Assign(assign) = self.hir_of(assign_id)? {
+                if assign.drive.is_none() {
+                    sources.insert(assign_id);
+                }
+            }
+        }
+
+        // Collect the nets and variables declared in the module, including
+        // the ports it may drive.
+        let mut signals = vec![];
+        for &decl_id in &hir.block.decls {
+            if let HirNode::VarDecl(decl) = self.hir_of(decl_id)? {
+                signals.push((decl_id, decl.name));
+            }
+        }
+        for port in &hir.ports_new.int {
+            if port.dir != ast::PortDir::Input {
+                signals.push((port.id, port.name));
+            }
+        }
+
+        for (signal_id, name) in signals {
+            let mut drivers: Vec<(NodeId, Vec<Select>, Span)> = vec![];
+            for lv in self.drivers_of(signal_id, env) {
+                // Find the process containing the driver. Processes are part
+                // of the rib chain, so the closest one is the right one.
+                let mut next = Some(lv.origin);
+                let source = loop {
+                    match next {
+                        Some(id) if sources.contains(&id) => break Some(id),
+                        Some(id) => next = self.parent_node_id(id),
+                        None => break None,
+                    }
+                };
+                let source = match source {
+                    Some(x) => x,
+                    None => continue,
+                };
+
+                // Determine the driven part of the signal, from the root
+                // outwards.
+                let mut selects = vec![];
+                lv.root(|step| match step.kind {
+                    mir::LvalueKind::Member { field, .. } => selects.push(Select::Field(field)),
+                    mir::LvalueKind::Index { base, length, .. } => {
+                        let range = if base.is_const() && !base.is_error() {
+                            self.const_mir_rvalue_int(base.into()).ok().map(|lo| {
+                                let hi = lo + BigInt::from(length.max(1)) - BigInt::one();
+                                (lo.clone(), hi)
+                            })
+                        } else {
+                            None
+                        };
+                        selects.push(Select::Range(range));
+                    }
+                    _ => (),
+                });
+                selects.reverse();
+
+                // Check for an overlap with the drivers from other processes.
+                let conflict = drivers.iter().find(|(other, other_selects, _)| {
+                    *other != source && !disjoint(&selects, other_selects)
+                });
+                if let Some(&(_, _, other_span)) = conflict {
+                    self.emit(
+                        DiagBuilder2::warning(format!(
+                            "`{}` is driven by multiple combinational processes",
+                            name
+                        ))
+                        .span(lv.span)
+                        .add_note("Also driven here:")
+                        .span(other_span),
+                    );
+                    break;
+                }
+                drivers.push((source, selects, lv.span));
+            }
+        }
+        Ok(())
+    }
+
+    /// Determine the range each index variable of a foreach loop iterates
+    /// over, ordered from the outermost to the innermost dimension.
+    fn foreach_ranges(
+        &mut self,
+        array: NodeId,
+        indices: &[NodeId],
+        env: ParamEnv,
+    ) -> Result<Vec<(NodeId, ty::Range)>> {
+        let ty = self.type_of(array, env)?;
+        if ty.is_error() {
+            return Err(());
+        }
+        let dims: Vec<_> = ty.dims().collect();
+        let mut ranges = vec![];
+        for &index_id in indices {
+            let index = match self.hir_of(index_id)? {
+                HirNode::ForeachIndex(x) => x,
+                _ => unreachable!(),
+            };
+            let dim = match dims.get(index.index) {
+                Some(&dim) => dim,
+                None => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "{} refers to dimension {}, but the array only has {}",
+                            index.desc_full(),
+                            index.index + 1,
+                            dims.len()
+                        ))
+                        .span(index.human_span())
+                        .add_note(format!("Array being iterated over is of type `{}`:", ty))
+                        .span(self.span(array)),
+                    );
+                    return Err(());
+                }
+            };
+            let range = match dim {
+                ty::Dim::Unpacked(ty::UnpackedDim::Array(size)) => ty::Range {
+                    size,
+                    dir: ty::RangeDir::Up,
+                    offset: 0,
+                },
+                _ => match dim.get_range() {
+                    Some(range) => range,
+                    None => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "unsupported: foreach over `{}` dimension of `{}`",
+                                dim, ty
+                            ))
+                            .span(index.human_span())
+                            .add_note(
+                                "Only dimensions with a fixed size can be iterated over at the \
+                                 moment",
+                            ),
+                        );
+                        return Err(());
+                    }
+                },
+            };
+            ranges.push((index.index, index_id, range));
+        }
+        ranges.sort_by_key(|&(level, _, _)| level);
+        Ok(ranges
+            .into_iter()
+            .map(|(_, index_id, range)| (index_id, range))
+            .collect())
+    }
+
+    /// Emit the nested loops of a foreach statement.
+    ///
+    /// Each index variable iterates from the left to the right bound of its
+    /// dimension, i.e. `[7:0]` is iterated as `7, 6, ..., 0`.
+    fn emit_foreach(
+        &mut self,
+        loops: &[(NodeId, ty::Range)],
+        body: NodeId,
+        break_blk: llhd::ir::Block,
+        continue_blk: llhd::ir::Block,
+        env: ParamEnv,
+    ) -> Result<()> {
+        let (&(index_id, range), inner) = match loops.split_first() {
+            Some(x) => x,
+            None => {
+                self.loops.push((break_blk, continue_blk));
+                let result = self.emit_stmt(body, env);
+                self.loops.pop();
+                return result;
+            }
+        };
+        let ty = self.type_of(index_id, env)?;
+        let name = match self.hir_of(index_id)? {
+            HirNode::ForeachIndex(x) => x.name,
+            _ => unreachable!(),
+        };
+        let span = self.span(index_id);
+        let body_blk = self.add_named_block("loop_body");
+        let exit_blk = self.add_named_block("loop_exit");
+
+        // Emit the index variable, starting at the left bound.
+        let left = self.emit_const(
+            self.intern_value(value::make_int(ty, range.left().into())),
+            env,
+            span,
+        )?;
+        let var = self.builder.ins().var(left);
+        self.builder.set_name(var, name.value.to_string());
+        self.set_emitted_value(index_id, var);
+
+        // Emit the loop prologue, which checks whether we have moved past the
+        // right bound.
+        self.builder.ins().br(body_blk);
+        self.builder.append_to(body_blk);
+        let end = self.emit_const(
+            self.intern_value(value::make_int(
+                ty,
+                (range.right() + range.increment()).into(),
+            )),
+            env,
+            span,
+        )?;
+        let value = self.builder.ins().ld(var);
+        let enter_cond = self.builder.ins().neq(value, end);
+        let entry_blk = self.add_named_block("loop_continue");
+        self.builder.ins().br_cond(enter_cond, exit_blk, entry_blk);
+        self.builder.append_to(entry_blk);
+
+        // Emit the inner loops and the body.
+        let next_blk = self.add_named_block("loop_next");
+        self.emit_foreach(inner, body, break_blk, next_blk, env)?;
+
+        // Emit the epilogue, which advances the index towards the right bound.
+        self.builder.ins().br(next_blk);
+        self.builder.append_to(next_blk);
+        let step = self.emit_const(
+            self.intern_value(value::make_int(ty, range.increment().into())),
+            env,
+            span,
+        )?;
+        let value = self.builder.ins().ld(var);
+        let value = self.builder.ins().add(value, step);
+        self.builder.ins().st(var, value);
+        self.builder.ins().br(body_blk);
+        self.builder.append_to(exit_blk);
+        Ok(())
+    }
+
     /// Emit the code for a variable declaration statement, given its HIR.
     fn emit_stmt_var_decl(
         &mut self,
@@ -2470,6 +3997,144 @@ where
         Ok(())
     }
 
+    /// Emit a wait for a non-constant integer delay.
+    ///
+    /// LLHD cannot convert an integer into a time at runtime. The delay is
+    /// therefore split into its bits, and each set bit waits for the matching
+    /// power of two of time units. This bounds the number of wakeups by the
+    /// width of the delay rather than its value. Zero and negative delays wait
+    /// for a single epsilon step, like `#0`. Control continues in
+    /// `resume_blk`.
+    fn emit_delay_bits(
+        &mut self,
+        id: NodeId,
+        env: ParamEnv,
+        resume_blk: llhd::ir::Block,
+    ) -> Result<()> {
+        let sbvt = self.type_of(id, env)?.get_simple_bit_vector().unwrap();
+        let width = sbvt.size;
+        let count = self.emit_rvalue(id, env)?;
+        let scale = self.time_scale_of(id);
+        let unit = scale.round(scale.unit.clone());
+
+        // Handle zero and negative delays separately.
+        let zero = self.builder.ins().const_int((width, 0));
+        let mut skip = self.builder.ins().eq(count, zero);
+        if sbvt.is_signed() {
+            let negative = self.builder.ins().slt(count, zero);
+            skip = self.builder.ins().or(skip, negative);
+        }
+        let zero_blk = self.add_named_block("delay_zero");
+        let mut next_blk = self.add_named_block("delay_bits");
+        self.builder.ins().br_cond(skip, next_blk, zero_blk);
+        self.builder.append_to(zero_blk);
+        let epsilon = self.emit_delay_time(BigRational::zero());
+        self.builder.ins().wait_time(resume_blk, epsilon, vec![]);
+
+        // Wait for each set bit. The sign bit is never set at this point.
+        let bits = if sbvt.is_signed() { width - 1 } else { width };
+        for i in 0..bits {
+            self.builder.append_to(next_blk);
+            let bit = self.builder.ins().ext_slice(count, i, 1);
+            let wait_blk = self.add_named_block("delay_step");
+            let after_blk = self.add_named_block("delay_bits");
+            self.builder.ins().br_cond(bit, after_blk, wait_blk);
+            self.builder.append_to(wait_blk);
+            let step = &unit * BigRational::from_integer(BigInt::one() << i);
+            let step = self.emit_delay_time(step);
+            self.builder.ins().wait_time(after_blk, step, vec![]);
+            next_blk = after_blk;
+        }
+        self.builder.append_to(next_blk);
+        self.builder.ins().br(resume_blk);
+        Ok(())
+    }
+
+    /// Emit the code that waits for one of a list of events to happen.
+    ///
+    /// Each event is given by the watched value, the edge to detect on it, and
+    /// the `iff` conditions that qualify it. Execution continues in a new block
+    /// once any of the events happens.
+    fn emit_event_wait(
+        &mut self,
+        events: &[(EventValue, ast::EdgeIdent, &[NodeId])],
+        env: ParamEnv,
+    ) -> Result<()> {
+        // Store initial values of the expressions the event is sensitive to.
+        let init_blk = self.add_named_block("init");
+        self.builder.ins().br(init_blk);
+        self.builder.append_to(init_blk);
+        let mut init_values = vec![];
+        for &(value, _, _) in events {
+            init_values.push(self.emit_event_value(value, env)?);
+        }
+
+        // Wait for any of the inputs to those expressions to change.
+        let check_blk = self.add_named_block("check");
+        let mut trigger_on = vec![];
+        for &(value, _, _) in events {
+            match value {
+                EventValue::Expr(expr_id) => {
+                    let acc = self.accessed_nodes(expr_id, env)?;
+                    for &id in &acc.read {
+                        trigger_on.push(self.emitted_value(id).clone());
+                    }
+                }
+                EventValue::Signal(id) => trigger_on.push(self.emitted_value(id).clone()),
+            }
+        }
+        self.builder.ins().wait(check_blk, trigger_on);
+        self.builder.append_to(check_blk);
+        self.flush_mir(); // ensure we don't reuse earlier expr probe
+        self.emit_shadow_update();
+
+        // Check if any of the events happened and produce a single bit value
+        // that represents this.
+        let mut event_cond = None;
+        for (&(value, edge, iffs), init_value) in events.iter().zip(init_values.into_iter()) {
+            trace!(
+                "would now emit check if {:?} changed according to {:?}",
+                init_value,
+                edge
+            );
+            let now_value = self.emit_event_value(value, env)?;
+            let mut trigger = self.emit_event_trigger(edge, init_value, now_value)?;
+            for &iff in iffs {
+                let iff_value = self.emit_rvalue_bool(iff, env)?;
+                trigger = self.builder.ins().and(trigger, iff_value);
+                self.builder.set_name(trigger, "iff".to_string());
+            }
+            event_cond = Some(match event_cond {
+                Some(chain) => {
+                    let value = self.builder.ins().or(chain, trigger);
+                    self.builder.set_name(value, "event_or".to_string());
+                    value
+                }
+                None => trigger,
+            });
+        }
+
+        // If the event happened, branch to a new block which will contain the
+        // subsequent statements. Otherwise jump back up to the initial block.
+        if let Some(event_cond) = event_cond {
+            let event_blk = self.add_named_block("event");
+            self.builder.ins().br_cond(event_cond, init_blk, event_blk);
+            self.builder.append_to(event_blk);
+        }
+        Ok(())
+    }
+
+    /// Emit the current value watched by an event.
+    fn emit_event_value(&mut self, value: EventValue, env: ParamEnv) -> Result<llhd::ir::Value> {
+        match value {
+            EventValue::Expr(expr_id) => self.emit_rvalue(expr_id, env),
+            EventValue::Signal(id) => {
+                let signal = self.emitted_value(id);
+                Ok(self.builder.ins().prb(signal))
+            }
+        }
+    }
+
     /// Emit the code to update the shadow variables of signals.
     fn emit_shadow_update(&mut self) {
         for (&id, &shadow) in &self.shadows {
@@ -2493,6 +4158,12 @@ where
             x => unreachable!("emit_varnet_decl on HIR {:?}", x),
         };
 
+        // Events have no initial value beyond the untriggered state.
+        if ty.is_event() {
+            let init = self.builder.ins().const_int((1, 0));
+            return Ok(self.builder.ins().sig(init));
+        }
+
         // Differentiate between variable and net declarations, which have
         // slightly different semantics regarding their initial value.
         if is_var {
@@ -2509,9 +4180,28 @@ where
             Ok(self.builder.ins().sig(init))
         } else {
             // For nets we simply emit the initial value as a signal, then
-            // short-circuit it with the net declaration.
-            let zero = self.emit_const(self.type_default_value(ty), env, self.span(decl_id))?;
-            let net = self.builder.ins().sig(zero);
+            // short-circuit it with the net declaration. Supply nets are
+            // permanently driven to zero or one.
+            let supply1 = match self.hir_of(decl_id)? {
+                HirNode::VarDecl(hir::VarDecl {
+                    kind:
+                        ast::VarKind::Net {
+                            ty: ast::NetType::Supply1,
+                            ..
+                        },
+                    ..
+                }) => true,
+                _ => false,
+            };
+            let value = match ty.get_simple_bit_vector() {
+                Some(sbv) if supply1 => self.intern_value(value::make_int(
+                    ty,
+                    (BigInt::one() << sbv.size) - BigInt::one(),
+                )),
+                _ => self.type_default_value(ty),
+            };
+            let value = self.emit_const(value, env, self.span(decl_id))?;
+            let net = self.builder.ins().sig(value);
             if let Some(default) = default {
                 let init = self.emit_rvalue_mode(default, env, Mode::Signal)?;
                 self.builder.ins().con(net, init);
@@ -2521,6 +4211,44 @@ where
     }
 }
 
+/// A value watched by an event control.
+#[derive(Copy, Clone, Debug)]
+enum EventValue {
+    /// An expression, as in `@(posedge clk)`.
+    Expr(NodeId),
+    /// A signal of an expanded implicit sensitivity list, as in `@*`.
+    Signal(AccessedNode),
+}
+
+/// The time unit and precision in effect for a delay.
+struct TimeScale {
+    /// The time unit in seconds, which scales delays given without a unit.
+    unit: BigRational,
+    /// The time precision in seconds, to which all delays are rounded.
+    precision: BigRational,
+}
+
+impl TimeScale {
+    /// Round a time in seconds to the time precision.
+    fn round(&self, time: BigRational) -> BigRational {
+        (time / &self.precision).round() * &self.precision
+    }
+}
+
+/// Convert a time unit or precision value such as `10ns` to seconds.
+fn time_value_in_seconds(value: &ast::TimeValue) -> BigRational {
+    use crate::syntax::token::TimeUnit;
+    let exponent = match value.unit {
+        TimeUnit::Second => 0,
+        TimeUnit::MilliSecond => 3,
+        TimeUnit::MicroSecond => 6,
+        TimeUnit::NanoSecond => 9,
+        TimeUnit::PicoSecond => 12,
+        TimeUnit::FemtoSecond => 15,
+    };
+    BigRational::new(value.magnitude.into(), BigInt::from(10).pow(exponent))
+}
+
 /// An rvalue emission mode.
 ///
 /// Upon code emission, rvalues may be emitted either as direct values,
@@ -0,0 +1,680 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! Dataflow analyses over the statements of a procedure.
+//!
+//! Automatic variables declared without an initial value hold the default
+//! value of their type whenever their scope is entered. Simulators and
+//! synthesis tools tend to disagree on what reading such a variable before
+//! assigning it means, so reads which may happen before any assignment on some
+//! control path are flagged. Assignments to struct fields are tracked per
+//! field.
+//!
+//! Similarly, an `always_comb` procedure that does not assign a signal on every
+//! control path has to hold the signal's previous value, which infers a latch.
+//! Such signals are flagged together with a path that leaves them unassigned.
+//!
+//! Finally, the assignments of a procedure are checked against common coding
+//! style rules: sequential logic in `always_ff` should only use nonblocking
+//! assignments, and a signal should not be assigned with both blocking and
+//! nonblocking assignments.
+
+use crate::crate_prelude::*;
+use crate::{
+    hir::{HirNode, Visitor as _},
+    mir::WalkVisitor as _,
+    ParamEnv,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Check a procedure for reads of uninitialized automatic variables, for its
+/// mix of blocking and nonblocking assignments, and, if it is an
+/// `always_comb`, for signals not assigned on every path.
+pub(crate) fn check_procedure<'gcx>(
+    cx: &impl Context<'gcx>,
+    proc_id: NodeId,
+    env: ParamEnv,
+) -> Result<()> {
+    let prok = match cx.hir_of(proc_id)? {
+        HirNode::Proc(x) => x,
+        _ => unreachable!(),
+    };
+    let mut checker = Checker {
+        cx,
+        env,
+        check_reads: true,
+        tracked: HashSet::new(),
+        reported: HashSet::new(),
+    };
+    let mut state = State::default();
+    checker.stmt(prok.stmt, &mut state)?;
+    let acc = cx.accessed_nodes(prok.stmt, env)?;
+    check_assign_kinds(cx, prok, env, &acc);
+    if prok.kind != ast::ProcedureKind::AlwaysComb {
+        return Ok(());
+    }
+
+    // Check that every signal written by the procedure is assigned on all
+    // paths through it.
+    checker.check_reads = false;
+    for node in &acc.written {
+        let id = match *node {
+            hir::AccessedNode::Regular(id) => id,
+            hir::AccessedNode::Intf(..) => continue,
+        };
+        if state.init.get(&id) == Some(&Init::Full) {
+            continue;
+        }
+        let path = match checker.unassigned_path(prok.stmt, id)? {
+            Some(path) => path,
+            None => continue,
+        };
+        let name = cx.hir_of(id)?.human_span().extract();
+        let mut d = DiagBuilder2::warning(format!(
+            "`{}` is not assigned on all paths through the `always_comb`; this infers a latch",
+            name
+        ))
+        .span(prok.span);
+        if !path.is_empty() {
+            d = d.add_note(format!("`{}` keeps its previous value if:", name));
+            for (span, desc) in path {
+                d = d.add_note(desc).span(span);
+            }
+        }
+        cx.emit(d.add_note(format!(
+            "Assign `{}` a default value at the beginning of the procedure",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Check the blocking and nonblocking assignments in a procedure.
+///
+/// Blocking assignments to signals in an `always_ff` are flagged, as are
+/// signals assigned with both blocking and nonblocking assignments. Variables
+/// local to the procedure are exempt.
+fn check_assign_kinds<'gcx>(
+    cx: &impl Context<'gcx>,
+    prok: &hir::Proc,
+    env: ParamEnv,
+    acc: &hir::AccessTable,
+) {
+    let mut collector = AssignCollector {
+        cx,
+        env,
+        assigns: vec![],
+    };
+    collector.visit_node_with_id(prok.stmt, false);
+
+    let mut blocking: HashMap<NodeId, Span> = HashMap::new();
+    let mut nonblocking: HashMap<NodeId, Span> = HashMap::new();
+    let mut reported = HashSet::new();
+    for (span, is_blocking, signal) in collector.assigns {
+        if !acc.written.contains(&hir::AccessedNode::Regular(signal)) {
+            continue;
+        }
+        let name = match cx.hir_of(signal) {
+            Ok(x) => x.human_span().extract(),
+            Err(()) => continue,
+        };
+        if is_blocking && prok.kind == ast::ProcedureKind::AlwaysFf {
+            cx.emit(
+                DiagBuilder2::warning(format!("blocking assignment to `{}` in `always_ff`", name))
+                    .span(span)
+                    .add_note(
+                        "Use a nonblocking assignment `<=` to assign signals in sequential logic",
+                    ),
+            );
+        }
+        let (this, other) = if is_blocking {
+            (&mut blocking, &nonblocking)
+        } else {
+            (&mut nonblocking, &blocking)
+        };
+        this.entry(signal).or_insert(span);
+        if let Some(&other_span) = other.get(&signal) {
+            if reported.insert(signal) {
+                let (b, nb) = if is_blocking {
+                    (span, other_span)
+                } else {
+                    (other_span, span)
+                };
+                cx.emit(
+                    DiagBuilder2::warning(format!(
+                        "`{}` is assigned with both blocking and nonblocking assignments",
+                        name
+                    ))
+                    .span(b)
+                    .add_note("Nonblocking assignment here:")
+                    .span(nb),
+                );
+            }
+        }
+    }
+}
+
+/// A visitor for the HIR that collects the signals assigned by assignment
+/// statements, and whether the assignment is blocking.
+struct AssignCollector<'a, C> {
+    cx: &'a C,
+    env: ParamEnv,
+    assigns: Vec<(Span, bool, NodeId)>,
+}
+
+impl<'a, 'gcx: 'a, C> hir::Visitor<'gcx> for AssignCollector<'a, C>
+where
+    C: Context<'gcx>,
+{
+    type Context = C;
+    fn context(&self) -> &C {
+        self.cx
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt) {
+        if let hir::StmtKind::Assign { lhs, kind, .. } = stmt.kind {
+            let is_blocking = match kind {
+                hir::AssignKind::Block(_) => true,
+                hir::AssignKind::Nonblock | hir::AssignKind::NonblockDelay(_) => false,
+                hir::AssignKind::Override(_) => return hir::walk_stmt(self, stmt),
+            };
+            let lv = self.cx.mir_lvalue(lhs, self.env);
+            let assigns = &mut self.assigns;
+            lv.for_each_part(&mut |part| match part.root(|_| ()).map(|root| &root.kind) {
+                Some(&mir::LvalueKind::Var(id)) | Some(&mir::LvalueKind::Port(id)) => {
+                    assigns.push((stmt.span, is_blocking, id))
+                }
+                _ => (),
+            });
+        }
+        hir::walk_stmt(self, stmt);
+    }
+}
+
+/// The initialization state of a variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Init {
+    /// The variable has been assigned as a whole.
+    Full,
+    /// Only the struct fields with the given indices have been assigned.
+    Fields(BTreeSet<usize>),
+}
+
+/// The initialization state of the variables at a point in a procedure.
+///
+/// Variables absent from the map have not been assigned yet.
+#[derive(Debug, Clone, Default)]
+struct State {
+    init: HashMap<NodeId, Init>,
+}
+
+impl State {
+    /// Merge the state at the end of another control path into this one.
+    ///
+    /// A variable is only initialized after the merge if it is initialized on
+    /// both paths.
+    fn join(&mut self, other: &State) {
+        let mut init = HashMap::new();
+        for (&id, a) in &self.init {
+            let merged = match (a, other.init.get(&id)) {
+                (_, None) => continue,
+                (Init::Full, Some(Init::Full)) => Init::Full,
+                (Init::Full, Some(Init::Fields(f))) | (Init::Fields(f), Some(Init::Full)) => {
+                    Init::Fields(f.clone())
+                }
+                (Init::Fields(a), Some(Init::Fields(b))) => {
+                    Init::Fields(a.intersection(b).cloned().collect())
+                }
+            };
+            init.insert(id, merged);
+        }
+        self.init = init;
+    }
+}
+
+/// Merge a control path into an optional accumulated state.
+fn merge(acc: &mut Option<State>, state: State) {
+    match acc {
+        Some(acc) => acc.join(&state),
+        None => *acc = Some(state),
+    }
+}
+
+/// The dataflow analysis of a single procedure.
+struct Checker<'a, C> {
+    cx: &'a C,
+    env: ParamEnv,
+    /// Whether reads of uninitialized variables are reported.
+    check_reads: bool,
+    /// The automatic variables declared without an initial value.
+    tracked: HashSet<NodeId>,
+    /// The variables already reported, to avoid duplicate warnings.
+    reported: HashSet<NodeId>,
+}
+
+impl<'a, 'gcx: 'a, C: Context<'gcx>> Checker<'a, C> {
+    /// Analyze a statement, updating the state to the one after it.
+    fn stmt(&mut self, id: NodeId, state: &mut State) -> Result<()> {
+        let stmt = match self.cx.hir_of(id)? {
+            HirNode::Stmt(x) => x,
+            HirNode::VarDecl(x) => {
+                if x.lifetime == ast::Lifetime::Automatic {
+                    match x.init {
+                        Some(init) => {
+                            self.expr(init, state);
+                            state.init.insert(x.id, Init::Full);
+                        }
+                        None => {
+                            self.tracked.insert(x.id);
+                            state.init.remove(&x.id);
+                        }
+                    }
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+        match stmt.kind {
+            hir::StmtKind::Null
+            | hir::StmtKind::Break
+            | hir::StmtKind::Continue
+            | hir::StmtKind::Disable(_)
+            | hir::StmtKind::Release { .. }
+            | hir::StmtKind::Trigger(_) => (),
+            hir::StmtKind::Block(ref stmts) | hir::StmtKind::InlineGroup { ref stmts, .. } => {
+                for &stmt in stmts {
+                    self.stmt(stmt, state)?;
+                }
+            }
+            hir::StmtKind::Assign { lhs, rhs, kind } => {
+                // Compound assignments such as `x += 1` read their target.
+                if let hir::AssignKind::Block(op) = kind {
+                    if op != ast::AssignOp::Identity {
+                        self.expr(lhs, state);
+                    }
+                }
+                self.expr(rhs, state);
+                let lv = self.cx.mir_lvalue(lhs, self.env);
+                lv.walk(&mut Reads {
+                    checker: self,
+                    state,
+                });
+                self.assign(lv, state);
+            }
+            hir::StmtKind::Timed { stmt, .. } => self.stmt(stmt, state)?,
+            hir::StmtKind::Wait { cond, stmt } => {
+                self.expr(cond, state);
+                self.stmt(stmt, state)?;
+            }
+            hir::StmtKind::Expr(expr) => self.expr(expr, state),
+            hir::StmtKind::If {
+                cond,
+                main_stmt,
+                else_stmt,
+            } => {
+                self.expr(cond, state);
+                let mut main = state.clone();
+                self.stmt(main_stmt, &mut main)?;
+                if let Some(else_stmt) = else_stmt {
+                    self.stmt(else_stmt, state)?;
+                }
+                state.join(&main);
+            }
+            hir::StmtKind::Loop { kind, body } => match kind {
+                // Loops whose body executes at least once.
+                hir::LoopKind::Forever => self.stmt(body, state)?,
+                hir::LoopKind::Do(cond) => {
+                    self.stmt(body, state)?;
+                    self.expr(cond, state);
+                }
+                // Loops whose body may not execute at all. Since assignments
+                // only ever add to the state, the state at the end of the body
+                // covers the one at its beginning and a single pass suffices.
+                hir::LoopKind::Repeat(cond) | hir::LoopKind::While(cond) => {
+                    self.expr(cond, state);
+                    self.stmt(body, &mut state.clone())?;
+                }
+                hir::LoopKind::For(init, cond, step) => {
+                    self.stmt(init, state)?;
+                    self.expr(cond, state);
+                    let mut inner = state.clone();
+                    self.stmt(body, &mut inner)?;
+                    self.expr(step, &mut inner);
+                }
+            },
+            hir::StmtKind::Case {
+                expr,
+                ref ways,
+                default,
+                ..
+            } => {
+                self.expr(expr, state);
+                let mut merged = None;
+                for &(ref exprs, body) in ways {
+                    for &expr in exprs {
+                        self.expr(expr, state);
+                    }
+                    let mut way = state.clone();
+                    self.stmt(body, &mut way)?;
+                    merge(&mut merged, way);
+                }
+                self.case_default(default, merged, state)?;
+            }
+            hir::StmtKind::CaseInside {
+                expr,
+                ref ways,
+                default,
+            } => {
+                self.expr(expr, state);
+                let mut merged = None;
+                for &(ref ranges, body) in ways {
+                    for range in ranges {
+                        match range.value {
+                            hir::InsideRange::Single(expr) => self.expr(expr, state),
+                            hir::InsideRange::Range(lo, hi) => {
+                                self.expr(lo, state);
+                                self.expr(hi, state);
+                            }
+                        }
+                    }
+                    let mut way = state.clone();
+                    self.stmt(body, &mut way)?;
+                    merge(&mut merged, way);
+                }
+                self.case_default(default, merged, state)?;
+            }
+            hir::StmtKind::Foreach { array, body, .. } => {
+                self.expr(array, state);
+                self.stmt(body, &mut state.clone())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Analyze the default of a case statement, and merge the state after it
+    /// with the states after the other ways.
+    ///
+    /// Without a default, none of the ways may be taken.
+    fn case_default(
+        &mut self,
+        default: Option<NodeId>,
+        mut merged: Option<State>,
+        state: &mut State,
+    ) -> Result<()> {
+        let mut after = state.clone();
+        if let Some(default) = default {
+            self.stmt(default, &mut after)?;
+        }
+        merge(&mut merged, after);
+        *state = merged.unwrap();
+        Ok(())
+    }
+
+    /// Check the reads in an expression.
+    fn expr(&mut self, id: NodeId, state: &mut State) {
+        let mir = self.cx.mir_rvalue(id, self.env);
+        mir.walk(&mut Reads {
+            checker: self,
+            state,
+        });
+    }
+
+    /// Mark the variables and ports assigned by an lvalue as initialized.
+    fn assign(&mut self, lv: &'gcx mir::Lvalue<'gcx>, state: &mut State) {
+        lv.for_each_part(&mut |part| {
+            // Find the assigned variable, and whether a struct field is
+            // accessed directly on it. Assignments to individual elements of
+            // an array or bits of a vector cannot be told apart, and count as
+            // initializing the entire variable.
+            let mut field = None;
+            let current = part.root(|step| {
+                field = match step.kind {
+                    mir::LvalueKind::Member { field, .. } => Some(field),
+                    _ => None,
+                }
+            });
+            let (current, id) = match current.map(|root| (root, &root.kind)) {
+                Some((root, &mir::LvalueKind::Var(id)))
+                | Some((root, &mir::LvalueKind::Port(id))) => (root, id),
+                _ => return,
+            };
+            let field = match field {
+                Some(field) => field,
+                None => {
+                    state.init.insert(id, Init::Full);
+                    return;
+                }
+            };
+            let num_fields = current
+                .ty
+                .get_struct()
+                .map(|s| s.members.len())
+                .unwrap_or(0);
+            let entry = state
+                .init
+                .entry(id)
+                .or_insert_with(|| Init::Fields(BTreeSet::new()));
+            let full = match entry {
+                Init::Full => true,
+                Init::Fields(fields) => {
+                    fields.insert(field);
+                    fields.len() >= num_fields
+                }
+            };
+            if full {
+                *entry = Init::Full;
+            }
+        });
+    }
+
+    /// Find a path through a statement that leaves a signal unassigned.
+    ///
+    /// Returns `None` if the statement assigns the signal on all paths.
+    /// Otherwise returns the conditions that lead to the signal not being
+    /// assigned. The "no item matches" path of `unique` and `priority` case
+    /// statements without a default is assumed to not be taken.
+    fn unassigned_path(
+        &mut self,
+        id: NodeId,
+        signal: NodeId,
+    ) -> Result<Option<Vec<(Span, String)>>> {
+        let stmt = match self.cx.hir_of(id)? {
+            HirNode::Stmt(x) => x,
+            _ => return Ok(Some(vec![])),
+        };
+        let prepend = |path: Option<Vec<(Span, String)>>, span: Span, desc: String| {
+            path.map(|mut path| {
+                path.insert(0, (span, desc));
+                path
+            })
+        };
+        Ok(match stmt.kind {
+            hir::StmtKind::Block(ref stmts) | hir::StmtKind::InlineGroup { ref stmts, .. } => {
+                let mut path = vec![];
+                for &stmt in stmts {
+                    match self.unassigned_path(stmt, signal)? {
+                        Some(p) => path.extend(p),
+                        None => return Ok(None),
+                    }
+                }
+                Some(path)
+            }
+            hir::StmtKind::Timed { stmt, .. } | hir::StmtKind::Wait { stmt, .. } => {
+                self.unassigned_path(stmt, signal)?
+            }
+            hir::StmtKind::If {
+                cond,
+                main_stmt,
+                else_stmt,
+            } => {
+                let span = self.cx.span(cond);
+                let main = self.unassigned_path(main_stmt, signal)?;
+                if main.is_some() {
+                    prepend(main, span, format!("`{}` is true", span.extract()))
+                } else {
+                    let alt = match else_stmt {
+                        Some(else_stmt) => self.unassigned_path(else_stmt, signal)?,
+                        None => Some(vec![]),
+                    };
+                    prepend(alt, span, format!("`{}` is false", span.extract()))
+                }
+            }
+            hir::StmtKind::Case {
+                expr,
+                ref ways,
+                default,
+                up,
+                ..
+            } => {
+                for &(ref exprs, body) in ways {
+                    let path = self.unassigned_path(body, signal)?;
+                    if path.is_some() {
+                        let span = exprs.first().map(|&e| self.cx.span(e)).unwrap_or(stmt.span);
+                        let desc = format!("case item `{}` is taken", span.extract());
+                        return Ok(prepend(path, span, desc));
+                    }
+                }
+                match (default, up) {
+                    (Some(default), _) => {
+                        let path = self.unassigned_path(default, signal)?;
+                        let span = self.cx.span(default);
+                        prepend(path, span, "the default case item is taken".to_string())
+                    }
+                    (None, Some(ast::UniquePriority::Unique))
+                    | (None, Some(ast::UniquePriority::Priority)) => None,
+                    (None, _) => {
+                        let span = self.cx.span(expr);
+                        Some(vec![(
+                            span,
+                            format!("no case item matches `{}`", span.extract()),
+                        )])
+                    }
+                }
+            }
+            hir::StmtKind::CaseInside {
+                expr,
+                ref ways,
+                default,
+            } => {
+                for &(ref ranges, body) in ways {
+                    let path = self.unassigned_path(body, signal)?;
+                    if path.is_some() {
+                        let span = ranges.first().map(|r| r.span).unwrap_or(stmt.span);
+                        let desc = format!("case item `{}` is taken", span.extract());
+                        return Ok(prepend(path, span, desc));
+                    }
+                }
+                match default {
+                    Some(default) => {
+                        let path = self.unassigned_path(default, signal)?;
+                        let span = self.cx.span(default);
+                        prepend(path, span, "the default case item is taken".to_string())
+                    }
+                    None => {
+                        let span = self.cx.span(expr);
+                        Some(vec![(
+                            span,
+                            format!("no case item matches `{}`", span.extract()),
+                        )])
+                    }
+                }
+            }
+            // Loops whose body executes at least once.
+            hir::StmtKind::Loop {
+                kind: hir::LoopKind::Forever,
+                body,
+            }
+            | hir::StmtKind::Loop {
+                kind: hir::LoopKind::Do(_),
+                body,
+            } => self.unassigned_path(body, signal)?,
+            // Loops whose body may not execute at all.
+            hir::StmtKind::Loop { kind, .. } => {
+                if let hir::LoopKind::For(init, ..) = kind {
+                    if self.unassigned_path(init, signal)?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                let desc = "the loop body is not executed".to_string();
+                Some(vec![(stmt.span, desc)])
+            }
+            hir::StmtKind::Foreach { .. } => {
+                let desc = "the loop body is not executed".to_string();
+                Some(vec![(stmt.span, desc)])
+            }
+            _ => {
+                let mut state = State::default();
+                self.stmt(id, &mut state)?;
+                if state.init.get(&signal) == Some(&Init::Full) {
+                    None
+                } else {
+                    Some(vec![])
+                }
+            }
+        })
+    }
+
+    /// Check a read of a variable, or one of its struct fields.
+    fn read(&mut self, id: NodeId, field: Option<usize>, span: Span, state: &State) {
+        if !self.check_reads || !self.tracked.contains(&id) || self.reported.contains(&id) {
+            return;
+        }
+        let ok = match (state.init.get(&id), field) {
+            (Some(Init::Full), _) => true,
+            (Some(Init::Fields(fields)), Some(field)) => fields.contains(&field),
+            _ => false,
+        };
+        if ok {
+            return;
+        }
+        self.reported.insert(id);
+        let name = match self.cx.hir_of(id) {
+            Ok(HirNode::VarDecl(x)) => x.name,
+            _ => return,
+        };
+        self.cx.emit(
+            DiagBuilder2::warning(format!(
+                "`{}` may be read before it is assigned",
+                span.extract()
+            ))
+            .span(span)
+            .add_note(format!(
+                "`{}` is an automatic variable declared without an initial value:",
+                name
+            ))
+            .span(name.span),
+        );
+    }
+}
+
+/// A visitor for the MIR that checks the reads in an rvalue.
+///
+/// Assignments nested in the rvalue, such as `x++`, update the state.
+struct Reads<'c, 's, 'a, C> {
+    checker: &'c mut Checker<'a, C>,
+    state: &'s mut State,
+}
+
+impl<'c, 's, 'a, 'gcx: 'a, C: Context<'gcx>> mir::Visitor<'gcx> for Reads<'c, 's, 'a, C> {
+    fn pre_visit_rvalue(&mut self, mir: &'gcx mir::Rvalue<'gcx>) -> bool {
+        match mir.kind {
+            mir::RvalueKind::Var(id) => {
+                self.checker.read(id, None, mir.span, self.state);
+                false
+            }
+            mir::RvalueKind::Member { value, field } => match value.kind {
+                mir::RvalueKind::Var(id) => {
+                    self.checker.read(id, Some(field), mir.span, self.state);
+                    false
+                }
+                _ => true,
+            },
+            mir::RvalueKind::Assignment { lvalue, rvalue, .. } => {
+                rvalue.walk(self);
+                lvalue.walk(self);
+                self.checker.assign(lvalue, self.state);
+                false
+            }
+            _ => true,
+        }
+    }
+}
@@ -3,9 +3,10 @@
 //! Lowering of AST nodes to HIR nodes.
 
 use crate::crate_prelude::*;
-use crate::{ast_map::AstNode, hir::HirNode};
+use crate::{ast::AcceptVisitor, ast_map::AstNode, common::arenas::Alloc, hir::HirNode};
 use bit_vec::BitVec;
 use num::BigInt;
+use std::{collections::HashMap, sync::Arc};
 
 /// A hint about how a node should be lowered to HIR.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -35,30 +36,28 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
         AstNode::InstTarget(ast) => {
             let mut named_params = vec![];
             let mut pos_params = vec![];
-            let mut is_pos = true;
             for param in &ast.params {
                 let value_id = cx.map_ast_with_parent(AstNode::TypeOrExpr(&param.expr), node_id);
                 if let Some(name) = param.name {
-                    is_pos = false;
                     named_params.push((
                         param.span,
                         Spanned::new(name.name, name.span),
                         Some(value_id),
                     ));
                 } else {
-                    if !is_pos {
-                        cx.emit(
-                            DiagBuilder2::warning("positional parameters must appear before named")
-                                .span(param.span)
-                                .add_note(format!(
-                                    "assuming this refers to argument #{}",
-                                    pos_params.len() + 1
-                                )),
-                        );
-                    }
                     pos_params.push((param.span, Some(value_id)));
                 }
             }
+            // Parameters are either all assigned by position, or all by name.
+            if let (Some(pos), Some(named)) = (pos_params.first(), named_params.first()) {
+                cx.emit(
+                    DiagBuilder2::error("cannot mix positional and named parameter assignments")
+                        .span(named.0)
+                        .add_note("Positional parameter assignment here:")
+                        .span(pos.0),
+                );
+                return Err(());
+            }
             let hir = hir::InstTarget {
                 ast,
                 pos_params,
@@ -155,6 +154,10 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                     .as_ref()
                     .map(|expr| cx.map_ast_with_parent(AstNode::Expr(expr), node_id)),
                 kind: ast::VarKind::Var,
+                drive: None,
+                charge: None,
+                lifetime: var_lifetime(decl),
+                delay: None,
             };
             Ok(HirNode::VarDecl(cx.arena().alloc_hir(hir)))
         }
@@ -173,6 +176,19 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                     ty: decl.net_type,
                     kind: decl.kind,
                 },
+                drive: match decl.strength {
+                    Some(ast::NetStrength::Drive(a, b)) => hir::DriveStrength::new(a, b),
+                    _ => None,
+                },
+                charge: match decl.strength {
+                    Some(ast::NetStrength::Charge(c)) => Some(c),
+                    _ => None,
+                },
+                lifetime: ast::Lifetime::Static,
+                delay: decl
+                    .delay
+                    .as_ref()
+                    .map(|delay| lower_delay3(cx, delay, node_id)),
             };
             Ok(HirNode::VarDecl(cx.arena().alloc_hir(hir)))
         }
@@ -201,6 +217,21 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                             .collect(),
                     )
                 }
+                ast::BlockingAssignStmt {
+                    ref lhs,
+                    ref rhs,
+                    op,
+                } if crate::resolver::resolve_clocking_signal(cx, lhs).is_some() => {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "clocking block signal `{}` can only be driven with a nonblocking \
+                             assignment",
+                            lhs.span.extract()
+                        ))
+                        .span(stmt.span),
+                    );
+                    return Err(());
+                }
                 ast::BlockingAssignStmt {
                     ref lhs,
                     ref rhs,
@@ -223,14 +254,31 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                                 )
                             }
                         },
-                        _ => {
-                            debug!("{:#?}", stmt);
-                            bug_span!(
-                                stmt.span(),
-                                cx,
-                                "lowering of timing control {} to hir not implemented",
-                                stmt
-                            );
+                        ast::TimingControl::Cycle(ref cd) => {
+                            let clocking = match cx.default_clocking(cx.scope_location(stmt))? {
+                                Some(x) => x,
+                                None => {
+                                    cx.emit(
+                                        DiagBuilder2::error(format!(
+                                            "cycle delay `{}` requires a default clocking block",
+                                            cd.span.extract()
+                                        ))
+                                        .span(cd.span)
+                                        .add_note(
+                                            "Declare a `default clocking` block in the enclosing \
+                                             module or interface",
+                                        ),
+                                    );
+                                    return Err(());
+                                }
+                            };
+                            hir::TimingControl::Cycle {
+                                count: cx.map_ast_with_parent(AstNode::Expr(&cd.expr), node_id),
+                                event: cx.map_ast_with_parent(
+                                    AstNode::EventExpr(&clocking.event),
+                                    node_id,
+                                ),
+                            }
                         }
                     };
                     hir::StmtKind::Timed {
@@ -238,6 +286,20 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                         stmt: cx.map_ast_with_parent(AstNode::Stmt(inner_stmt), node_id),
                     }
                 }
+                ast::ParallelBlock(ref stmts, join) => {
+                    check_fork_join(cx, stmt, stmts, join)?;
+                    let mut next_rib = node_id;
+                    hir::StmtKind::Block(
+                        stmts
+                            .iter()
+                            .map(|stmt| {
+                                let id = cx.map_ast_with_parent(AstNode::Stmt(stmt), next_rib);
+                                next_rib = id;
+                                id
+                            })
+                            .collect(),
+                    )
+                }
                 ast::IfStmt {
                     ref cond,
                     ref main_stmt,
@@ -300,7 +362,12 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                         Some(ref dc) => hir::AssignKind::NonblockDelay(
                             cx.map_ast_with_parent(AstNode::Expr(&dc.expr), node_id),
                         ),
-                        None => hir::AssignKind::Nonblock,
+                        None => match clocking_drive_skew(cx, lhs)? {
+                            Some(expr) => hir::AssignKind::NonblockDelay(
+                                cx.map_ast_with_parent(AstNode::Expr(expr), node_id),
+                            ),
+                            None => hir::AssignKind::Nonblock,
+                        },
                     },
                 },
                 ast::CaseStmt {
@@ -308,42 +375,140 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                     mode: ast::CaseMode::Normal,
                     ref items,
                     kind,
-                    ..
+                    up,
                 } => {
                     let expr = cx.map_ast_with_parent(AstNode::Expr(expr), node_id);
                     let mut ways = vec![];
-                    let mut default = None;
+                    let default = lower_case_default(cx, items, node_id);
                     for item in items {
-                        match *item {
-                            ast::CaseItem::Default(ref stmt) => {
-                                if default.is_none() {
-                                    default =
-                                        Some(cx.map_ast_with_parent(AstNode::Stmt(stmt), node_id));
-                                } else {
-                                    cx.emit(
-                                        DiagBuilder2::error("multiple default cases")
-                                            .span(stmt.human_span()),
-                                    );
+                        if let ast::CaseItem::Expr(ref ranges, ref stmt) = *item {
+                            let mut exprs = vec![];
+                            for range in ranges {
+                                match *range {
+                                    ast::ValueRange::Single(ref expr) => exprs
+                                        .push(cx.map_ast_with_parent(AstNode::Expr(expr), node_id)),
+                                    ast::ValueRange::Range { span, .. } => cx.emit(
+                                        DiagBuilder2::error(
+                                            "value ranges are only allowed in `case ... inside`",
+                                        )
+                                        .span(span),
+                                    ),
                                 }
                             }
-                            ast::CaseItem::Expr(ref exprs, ref stmt) => ways.push((
-                                exprs
+                            ways.push((
+                                exprs,
+                                cx.map_ast_with_parent(AstNode::Stmt(stmt), node_id),
+                            ));
+                        }
+                    }
+                    hir::StmtKind::Case {
+                        expr,
+                        ways,
+                        default,
+                        kind,
+                        up,
+                    }
+                }
+                ast::CaseStmt {
+                    ref expr,
+                    mode: ast::CaseMode::Inside,
+                    ref items,
+                    ..
+                } => {
+                    let expr = cx.map_ast_with_parent(AstNode::Expr(expr), node_id);
+                    let mut ways = vec![];
+                    let default = lower_case_default(cx, items, node_id);
+                    for item in items {
+                        if let ast::CaseItem::Expr(ref ranges, ref stmt) = *item {
+                            ways.push((
+                                ranges
                                     .iter()
-                                    .map(|expr| {
-                                        cx.map_ast_with_parent(AstNode::Expr(expr), node_id)
-                                    })
+                                    .map(|range| lower_value_range(cx, range, node_id))
                                     .collect(),
                                 cx.map_ast_with_parent(AstNode::Stmt(stmt), node_id),
-                            )),
+                            ));
                         }
                     }
-                    hir::StmtKind::Case {
+                    hir::StmtKind::CaseInside {
                         expr,
                         ways,
                         default,
+                    }
+                }
+                ast::ForeachStmt(ref array, ref indices, ref body) => {
+                    let array = cx.map_ast_with_parent(AstNode::Expr(array), node_id);
+                    let indices = indices
+                        .iter()
+                        .map(|index| cx.map_ast_with_parent(AstNode::ForeachIndex(index), node_id))
+                        .collect();
+                    hir::StmtKind::Foreach {
+                        array,
+                        indices,
+                        body: cx.map_ast_with_parent(AstNode::Stmt(body), node_id),
+                    }
+                }
+                ast::BreakStmt | ast::ContinueStmt => {
+                    let (kind, keyword) = match stmt.kind {
+                        ast::BreakStmt => (hir::StmtKind::Break, "break"),
+                        _ => (hir::StmtKind::Continue, "continue"),
+                    };
+                    if !is_within_loop(stmt) {
+                        cx.emit(
+                            DiagBuilder2::error(format!("`{}` outside of a loop", keyword))
+                                .span(stmt.human_span()),
+                        );
+                        return Err(());
+                    }
+                    kind
+                }
+                ast::DisableStmt(name) => {
+                    match enclosing_stmts(stmt).find(|s| s.label == Some(name)) {
+                        Some(target) => hir::StmtKind::Disable(target.id()),
+                        None => {
+                            cx.emit(
+                                DiagBuilder2::error(format!(
+                                    "no enclosing block or statement labeled `{}`",
+                                    name
+                                ))
+                                .span(stmt.human_span())
+                                .add_note(
+                                    "Only statements enclosing the `disable` can be disabled at \
+                                     the moment",
+                                ),
+                            );
+                            return Err(());
+                        }
+                    }
+                }
+                ast::ProceduralAssignStmt { ref lhs, ref rhs }
+                | ast::ForceStmt { ref lhs, ref rhs } => {
+                    let kind = match stmt.kind {
+                        ast::ForceStmt { .. } => hir::OverrideKind::Force,
+                        _ => hir::OverrideKind::Assign,
+                    };
+                    hir::StmtKind::Assign {
+                        lhs: cx.map_ast_with_parent(AstNode::Expr(lhs), node_id),
+                        rhs: cx.map_ast_with_parent(AstNode::Expr(rhs), node_id),
+                        kind: hir::AssignKind::Override(kind),
+                    }
+                }
+                ast::DeassignStmt(ref target) | ast::ReleaseStmt(ref target) => {
+                    let kind = match stmt.kind {
+                        ast::ReleaseStmt(..) => hir::OverrideKind::Force,
+                        _ => hir::OverrideKind::Assign,
+                    };
+                    hir::StmtKind::Release {
+                        target: cx.map_ast_with_parent(AstNode::Expr(target), node_id),
                         kind,
                     }
                 }
+                ast::WaitExprStmt(ref cond, ref inner_stmt) => hir::StmtKind::Wait {
+                    cond: cx.map_ast_with_parent(AstNode::Expr(cond), node_id),
+                    stmt: cx.map_ast_with_parent(AstNode::Stmt(inner_stmt), node_id),
+                },
+                ast::EventTriggerStmt(ref target) => {
+                    hir::StmtKind::Trigger(cx.map_ast_with_parent(AstNode::Expr(target), node_id))
+                }
                 ast::AssertionStmt { .. } => {
                     cx.emit(
                         DiagBuilder2::warning("unsupported: immediate assertion; ignored")
@@ -403,6 +568,40 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
             };
             Ok(HirNode::Gen(cx.arena().alloc_hir(hir)))
         }
+        AstNode::GenCase(gen) => {
+            let expr = cx.map_ast_with_parent(AstNode::Expr(&gen.expr), node_id);
+            let mut ways = vec![];
+            let mut default = None;
+            for item in &gen.items {
+                match item {
+                    ast::GenerateCaseItem::Expr(labels, block) => {
+                        let labels = labels
+                            .iter()
+                            .map(|label| cx.map_ast_with_parent(AstNode::Expr(label), node_id))
+                            .collect();
+                        let body = lower_module_block(cx, node_id, &block.items, false, false)?;
+                        ways.push((labels, body));
+                    }
+                    ast::GenerateCaseItem::Default(block) if default.is_none() => {
+                        default =
+                            Some(lower_module_block(cx, node_id, &block.items, false, false)?);
+                    }
+                    ast::GenerateCaseItem::Default(block) => {
+                        cx.emit(DiagBuilder2::error("multiple default cases").span(block.span()));
+                    }
+                }
+            }
+            let hir = hir::Gen {
+                id: node_id,
+                span: gen.span(),
+                kind: hir::GenKind::Case {
+                    expr,
+                    ways,
+                    default,
+                },
+            };
+            Ok(HirNode::Gen(cx.arena().alloc_hir(hir)))
+        }
         AstNode::GenFor(gen) => {
             let init = alloc_genvar_init(cx, &gen.init, node_id)?;
             let rib = *init.last().unwrap();
@@ -433,6 +632,23 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
             };
             Ok(HirNode::GenvarDecl(cx.arena().alloc_hir(hir)))
         }
+        AstNode::ForeachIndex(index) => {
+            let array = match index.get_parent().map(|p| p.as_all()) {
+                Some(ast::AllNode::Stmt(stmt)) => match stmt.kind {
+                    ast::ForeachStmt(ref array, ..) => array.id(),
+                    _ => unreachable!(),
+                },
+                _ => bug_span!(index.span(), cx, "index variable outside of foreach loop"),
+            };
+            let hir = hir::ForeachIndex {
+                id: node_id,
+                span: index.span(),
+                name: index.name,
+                array,
+                index: index.index,
+            };
+            Ok(HirNode::ForeachIndex(cx.arena().alloc_hir(hir)))
+        }
         AstNode::Typedef(def) => {
             let hir = hir::Typedef {
                 id: node_id,
@@ -445,12 +661,20 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
             };
             Ok(HirNode::Typedef(cx.arena().alloc_hir(hir)))
         }
-        AstNode::ContAssign(_, lhs, rhs) => {
+        AstNode::ContAssign(assign, lhs, rhs) => {
+            let map_expr = |expr| cx.map_ast_with_parent(AstNode::Expr(expr), node_id);
             let hir = hir::Assign {
                 id: node_id,
                 span: Span::union(lhs.span(), rhs.span()),
-                lhs: cx.map_ast_with_parent(AstNode::Expr(lhs), node_id),
-                rhs: cx.map_ast_with_parent(AstNode::Expr(rhs), node_id),
+                lhs: map_expr(lhs),
+                rhs: map_expr(rhs),
+                delay: assign
+                    .delay
+                    .as_ref()
+                    .map(|delay| lower_delay3(cx, delay, node_id)),
+                drive: assign
+                    .strength
+                    .and_then(|(a, b)| hir::DriveStrength::new(a, b)),
             };
             Ok(HirNode::Assign(cx.arena().alloc_hir(hir)))
         }
@@ -465,20 +689,31 @@ pub(crate) fn hir_of<'gcx>(cx: &impl Context<'gcx>, node_id: NodeId) -> Result<H
                     .as_ref()
                     .map(|expr| cx.map_ast_with_parent(AstNode::Expr(expr), ty)),
                 kind: ast::VarKind::Var,
+                drive: None,
+                charge: None,
+                lifetime: ast::Lifetime::Static,
+                delay: None,
             };
             Ok(HirNode::VarDecl(cx.arena().alloc_hir(hir)))
         }
         AstNode::Package(p) => lower_package(cx, node_id, p),
         AstNode::EnumVariant(var, decl, index) => {
+            // A name with a range declares multiple variants, of which only
+            // the first is assigned the explicit value.
+            let enm = var
+                .get_parent()
+                .and_then(|p| p.as_all().get_enum())
+                .expect("enum name should be within an enum");
             let hir = hir::EnumVariant {
                 id: node_id,
-                name: var.name,
+                name: cx.enum_variants(Ref(enm))?[index].0,
                 span: var.span,
                 enum_id: decl,
                 index,
                 value: var
                     .value
                     .as_ref()
+                    .filter(|_| node_id == var.id())
                     .map(|expr| cx.map_ast_with_parent(AstNode::Expr(expr), decl)),
             };
             Ok(HirNode::EnumVariant(cx.arena().alloc_hir(hir)))
@@ -531,7 +766,8 @@ pub(crate) fn hir_of_module<'a>(
     next_rib = ports_new.tail_rib;
 
     // Lower the module body.
-    let block = lower_module_block(cx, next_rib, &ast.items, true, false)?;
+    let mut block = lower_module_block(cx, next_rib, &ast.items, true, false)?;
+    lower_bound_insts(cx, ast, &mut block);
 
     // Create the HIR module.
     let hir = hir::Module {
@@ -581,6 +817,54 @@ pub(crate) fn hir_of_interface<'a>(
     Ok(hir)
 }
 
+/// Lower the instances that bind directives insert into a module.
+fn lower_bound_insts<'gcx>(
+    cx: &impl Context<'gcx>,
+    module: &'gcx ast::Module<'gcx>,
+    block: &mut hir::ModuleBlock,
+) {
+    for bind in crate::resolver::binds_into(cx, module) {
+        let inst = &bind.inst;
+        if crate::resolver::resolve_checker_target(cx, inst).is_some() {
+            cx.emit(
+                DiagBuilder2::warning("unsupported: checker instance; ignored").span(inst.span),
+            );
+            continue;
+        }
+        let target_id = cx.map_ast_with_parent(AstNode::InstTarget(inst), block.last_rib);
+        block.last_rib = target_id;
+        trace!(
+            "bound instantiation target `{}` => {:?}",
+            inst.target,
+            target_id
+        );
+        for inst in &inst.names {
+            let inst_id = cx.map_ast_with_parent(AstNode::Inst(inst, target_id), block.last_rib);
+            trace!("bound instantiation `{}` => {:?}", inst.name, inst_id);
+            block.last_rib = inst_id;
+            block.insts.push(inst_id);
+        }
+    }
+}
+
+/// Flatten the items of `generate` regions into the surrounding items.
+///
+/// Generate regions are purely syntactic and do not open a scope of their own.
+fn flatten_generate_regions<'gcx>(
+    items: impl IntoIterator<Item = &'gcx ast::Item<'gcx>>,
+) -> Vec<&'gcx ast::Item<'gcx>> {
+    let mut flat = vec![];
+    for item in items {
+        match item.data {
+            ast::ItemData::GenerateRegion(_, ref inner) => {
+                flat.extend(flatten_generate_regions(inner))
+            }
+            _ => flat.push(item),
+        }
+    }
+    flat
+}
+
 fn lower_module_block<'gcx>(
     cx: &impl Context<'gcx>,
     parent_rib: NodeId,
@@ -595,7 +879,7 @@ fn lower_module_block<'gcx>(
     let mut gens = Vec::new();
     let mut params = Vec::new();
     let mut assigns = Vec::new();
-    for item in items {
+    for item in flatten_generate_regions(items) {
         match item.data {
             ast::ItemData::Dummy => (),
             ast::ItemData::ModuleDecl(ref decl) => {
@@ -625,6 +909,13 @@ fn lower_module_block<'gcx>(
                     "unsupported: program declaration; ignored",
                 ));
             }
+            ast::ItemData::Inst(ref inst)
+                if crate::resolver::resolve_checker_target(cx, inst).is_some() =>
+            {
+                cx.emit(
+                    DiagBuilder2::warning("unsupported: checker instance; ignored").span(inst.span),
+                );
+            }
             ast::ItemData::Inst(ref inst) => {
                 let target_id = cx.map_ast_with_parent(AstNode::InstTarget(inst), next_rib);
                 next_rib = target_id;
@@ -646,6 +937,7 @@ fn lower_module_block<'gcx>(
                 let id = cx.map_ast_with_parent(AstNode::Proc(prok), next_rib);
                 next_rib = id;
                 procs.push(id);
+                check_discarded_returns(cx, &prok.stmt);
             }
             ast::ItemData::GenerateIf(ref gen) => {
                 let id = cx.map_ast_with_parent(AstNode::GenIf(gen), next_rib);
@@ -670,6 +962,9 @@ fn lower_module_block<'gcx>(
                 next_rib = id;
             }
             ast::ItemData::ContAssign(ref assign) => {
+                if let Some((a, b)) = assign.strength {
+                    check_drive_strength(cx, a, b, assign.span);
+                }
                 for &(ref lhs, ref rhs) in &assign.assignments {
                     let id =
                         cx.map_ast_with_parent(AstNode::ContAssign(assign, lhs, rhs), next_rib);
@@ -702,15 +997,26 @@ fn lower_module_block<'gcx>(
                         .add_note("Modport declarations can only appear in an interface"),
                 );
             }
+            ast::ItemData::ClockingDecl(ref decl) => check_clocking_decl(cx, decl),
             ast::ItemData::ClassDecl(ref decl) => {
                 cx.emit(
                     DiagBuilder2::warning("unsupported: class declaration; ignored")
                         .span(decl.span),
                 );
+                check_class_ctor(cx, decl);
+                check_class_random_props(cx, decl);
+                check_class_constraints(cx, decl);
+                check_class_props(cx, decl);
             }
             ast::ItemData::SubroutineDecl(ref decl) => {
                 let id = cx.map_ast_with_parent(AstNode::SubroutineDecl(decl), next_rib);
                 next_rib = id;
+                check_subroutine_lifetime(cx, decl);
+                for item in &decl.items {
+                    if let ast::SubroutineItem::Stmt(ref stmt) = *item {
+                        check_discarded_returns(cx, stmt);
+                    }
+                }
             }
             ast::ItemData::Assertion(ref assert) => {
                 cx.emit(
@@ -719,21 +1025,920 @@ fn lower_module_block<'gcx>(
                 );
             }
 
+            // Bind directives are lowered as part of the module they insert
+            // their instance into.
+            ast::ItemData::Bind(..) => (),
+
+            // Defparams are applied when the instances they override are
+            // elaborated.
+            ast::ItemData::Defparam(..) => (),
+
+            // The default clocking block is looked up by the cycle delays
+            // that use it.
+            ast::ItemData::DefaultClocking(..) => (),
+
             // The remaining items don't need an HIR representation.
             ast::ItemData::DpiDecl(..)
+            | ast::ItemData::CheckerDecl(..)
             | ast::ItemData::GenvarDecl(..)
             | ast::ItemData::GenerateRegion(..) => (),
         }
     }
-    Ok(hir::ModuleBlock {
-        insts,
-        decls,
-        procs,
-        gens,
-        params,
-        assigns,
-        last_rib: next_rib,
-    })
+    Ok(hir::ModuleBlock {
+        insts,
+        decls,
+        procs,
+        gens,
+        params,
+        assigns,
+        last_rib: next_rib,
+    })
+}
+
+/// Warn about function calls whose return value is discarded.
+///
+/// Calling a non-void function as a statement requires its return value to be
+/// discarded explicitly with a `void'(...)` cast. This is checked on the AST
+/// such that the warning is emitted once, rather than for every parameter
+/// environment the statement is lowered in.
+fn check_discarded_returns<'gcx>(cx: &impl Context<'gcx>, stmt: &'gcx ast::Stmt<'gcx>) {
+    let mut finder = DiscardedCalls { calls: vec![] };
+    stmt.accept(&mut finder);
+    for (call, callee) in finder.calls {
+        let name = match callee.data {
+            ast::IdentExpr(name) => name,
+            _ => continue,
+        };
+        let decl = match cx.resolve_local(name.value, cx.scope_location(callee), false) {
+            Ok(Some(def)) => match def.node.as_all().get_subroutine_decl() {
+                Some(x) => x,
+                None => continue,
+            },
+            _ => continue,
+        };
+        if decl.prototype.kind != ast::SubroutineKind::Func {
+            continue;
+        }
+        if let Some(ref retty) = decl.prototype.retty {
+            if let ast::VoidType = retty.kind.data {
+                continue;
+            }
+        }
+        cx.emit(
+            DiagBuilder2::warning(format!(
+                "return value of function `{}` is discarded",
+                decl.prototype.name
+            ))
+            .span(call.span)
+            .add_note("Use a `void'(...)` cast to discard it explicitly"),
+        );
+    }
+}
+
+/// A visitor that collects the function calls used as statements, together
+/// with their callee.
+struct DiscardedCalls<'a> {
+    calls: Vec<(&'a ast::Expr<'a>, &'a ast::Expr<'a>)>,
+}
+
+impl<'a> ast::Visitor<'a> for DiscardedCalls<'a> {
+    fn pre_visit_stmt(&mut self, node: &'a ast::Stmt<'a>) -> bool {
+        if let ast::ExprStmt(ref expr) = node.kind {
+            if let ast::CallExpr(ref callee, _) = expr.data {
+                self.calls.push((expr, &**callee));
+            }
+        }
+        true
+    }
+}
+
+/// Determine the lifetime of a subroutine's locals.
+///
+/// Subroutines without an explicit `static` or `automatic` qualifier inherit
+/// the default lifetime of the enclosing module, interface, or package. Class
+/// methods are always automatic.
+fn subroutine_lifetime(decl: &ast::SubroutineDecl) -> ast::Lifetime {
+    if let Some(lifetime) = decl.prototype.lifetime {
+        return lifetime;
+    }
+    let mut next = decl.get_parent();
+    while let Some(node) = next {
+        match node.as_all() {
+            ast::AllNode::Module(x) => return x.lifetime,
+            ast::AllNode::Interface(x) => return x.lifetime,
+            ast::AllNode::Package(x) => return x.lifetime,
+            ast::AllNode::ClassDecl(..) => return ast::Lifetime::Automatic,
+            _ => next = node.get_parent(),
+        }
+    }
+    ast::Lifetime::Static
+}
+
+/// Determine the lifetime of a variable.
+///
+/// Variables without an explicit `static` or `automatic` qualifier are static,
+/// except for the variables declared in the initialization of a `for` loop and
+/// the locals of automatic subroutines and class methods.
+fn var_lifetime(decl: &ast::VarDecl) -> ast::Lifetime {
+    if let Some(lifetime) = decl.lifetime {
+        return lifetime;
+    }
+    let mut next = decl.get_parent();
+    while let Some(node) = next {
+        match node.as_all() {
+            ast::AllNode::Stmt(stmt) => {
+                if let ast::ForStmt(ref init, ..) = stmt.kind {
+                    if let ast::VarDeclStmt(ref init_decl) = init.kind {
+                        if std::ptr::eq(init_decl, decl) {
+                            return ast::Lifetime::Automatic;
+                        }
+                    }
+                }
+            }
+            ast::AllNode::SubroutineDecl(x) => return subroutine_lifetime(x),
+            ast::AllNode::ClassDecl(..) => return ast::Lifetime::Automatic,
+            ast::AllNode::Module(..) | ast::AllNode::Interface(..) | ast::AllNode::Package(..) => {
+                break
+            }
+            _ => (),
+        }
+        next = node.get_parent();
+    }
+    ast::Lifetime::Static
+}
+
+/// Check the skews of a clocking block.
+///
+/// Signals are sampled at the clocking event, which matches the default
+/// `#1step` input skew, and driven after their output skew. Other input skews
+/// and skews relative to a clock edge are not supported.
+fn check_clocking_decl<'gcx>(cx: &impl Context<'gcx>, decl: &'gcx ast::ClockingDecl<'gcx>) {
+    for item in &decl.items {
+        let (input, output) = match *item {
+            ast::ClockingItem::Default {
+                ref input,
+                ref output,
+                ..
+            }
+            | ast::ClockingItem::Signals {
+                ref input,
+                ref output,
+                ..
+            } => (input, output),
+        };
+        for &(skew, is_output) in &[(input, false), (output, true)] {
+            let skew = match *skew {
+                Some(ref x) => x,
+                None => continue,
+            };
+            if skew.edge.is_some() {
+                cx.emit(
+                    DiagBuilder2::warning(format!(
+                        "unsupported: clock edge in clocking skew `{}`; ignored",
+                        skew.span.extract()
+                    ))
+                    .span(skew.span),
+                );
+            }
+            match skew.delay {
+                Some(ast::ClockingDelay::Step) if is_output => {
+                    cx.emit(
+                        DiagBuilder2::error("`#1step` is not a valid output skew")
+                            .span(skew.span)
+                            .add_note("Only inputs can be sampled one step before the event"),
+                    );
+                }
+                Some(ast::ClockingDelay::Expr(ref expr)) if !is_output => match expr.data {
+                    ast::LiteralExpr(Lit::Number(v, None)) if &*v.as_str() == "0" => (),
+                    _ => cx.emit(
+                        DiagBuilder2::warning(format!(
+                            "unsupported: input skew `{}`; signals are sampled at the clocking \
+                             event",
+                            skew.span.extract()
+                        ))
+                        .span(skew.span),
+                    ),
+                },
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Determine the output skew of a drive to a clocking block signal, such as
+/// `cb.a <= x`.
+///
+/// Returns the delay of the signal's output skew, or the default output skew of
+/// the clocking block. Returns `None` if the assignment does not drive a
+/// clocking block signal, or the skew is zero.
+///
+/// The skew delays the drive from the time the assignment executes. This
+/// matches §14.16 only for drives that execute in the time step of a clocking
+/// event, such as after `@(cb)` or `##n`. Drives at other times should be
+/// delayed until the next clocking event, which is not supported.
+fn clocking_drive_skew<'gcx>(
+    cx: &impl Context<'gcx>,
+    lhs: &'gcx ast::Expr<'gcx>,
+) -> Result<Option<&'gcx ast::Expr<'gcx>>> {
+    let signal = match crate::resolver::resolve_clocking_signal(cx, lhs) {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+    let decl = crate::resolver::clocking_decl_of(signal);
+    let mut default_skew = None;
+    let mut skew = None;
+    for item in &decl.items {
+        match *item {
+            ast::ClockingItem::Default { ref output, .. } => {
+                default_skew = output.as_ref().or(default_skew);
+            }
+            ast::ClockingItem::Signals {
+                dir,
+                ref output,
+                ref signals,
+                ..
+            } if signals.iter().any(|s| std::ptr::eq(s, signal)) => {
+                if dir == ast::PortDir::Input {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "cannot drive `{}`, which is an input of the clocking block",
+                            lhs.span.extract()
+                        ))
+                        .span(lhs.span)
+                        .add_note(format!("`{}` is declared here:", signal.name))
+                        .span(signal.span()),
+                    );
+                    return Err(());
+                }
+                skew = output.as_ref();
+            }
+            _ => (),
+        }
+    }
+    match skew.or(default_skew).and_then(|skew| skew.delay.as_ref()) {
+        Some(ast::ClockingDelay::Expr(expr)) => Ok(Some(expr)),
+        _ => Ok(None),
+    }
+}
+
+/// Check the uses of a subroutine's locals against their lifetime.
+///
+/// Locals of an `automatic` subroutine are allocated anew for every call, while
+/// those of a `static` subroutine are shared by all calls. A `static`
+/// subroutine that calls itself therefore clobbers the locals of the pending
+/// calls, which is flagged. Automatic variables may not be the target of a
+/// nonblocking assignment, since they may no longer exist once the assignment
+/// takes effect.
+fn check_subroutine_lifetime<'gcx>(cx: &impl Context<'gcx>, decl: &'gcx ast::SubroutineDecl<'gcx>) {
+    let lifetime = subroutine_lifetime(decl);
+    let mut finder = LifetimeUses {
+        automatic: vec![],
+        calls: vec![],
+        nonblocking: vec![],
+        lifetime,
+    };
+    for item in &decl.items {
+        if let ast::SubroutineItem::Stmt(ref stmt) = *item {
+            stmt.accept(&mut finder);
+        }
+    }
+    if lifetime == ast::Lifetime::Automatic {
+        finder
+            .automatic
+            .extend(decl.prototype.args.iter().map(|arg| arg.id()));
+    }
+
+    // Calls to the subroutine itself from within a static subroutine.
+    if lifetime == ast::Lifetime::Static {
+        let recursive = finder.calls.iter().find(|callee| match callee.data {
+            ast::IdentExpr(name) => {
+                match cx.resolve_local(name.value, cx.scope_location(**callee), false) {
+                    Ok(Some(def)) => def.node.id() == decl.id(),
+                    _ => false,
+                }
+            }
+            _ => false,
+        });
+        if let Some(callee) = recursive {
+            let kind = match decl.prototype.kind {
+                ast::SubroutineKind::Func => "function",
+                ast::SubroutineKind::Task => "task",
+            };
+            cx.emit(
+                DiagBuilder2::warning(format!(
+                    "recursive call to static {} `{}`",
+                    kind, decl.prototype.name
+                ))
+                .span(callee.span)
+                .add_note(format!(
+                    "All calls share the same local variables; declare the {} `automatic` to \
+                     allocate them for each call",
+                    kind
+                ))
+                .span(decl.prototype.name.span),
+            );
+        }
+    }
+
+    // Nonblocking assignments to automatic variables.
+    for lhs in finder.nonblocking {
+        let name = match lhs.data {
+            ast::IdentExpr(name) => name,
+            _ => continue,
+        };
+        let def = match cx.resolve_local(name.value, cx.scope_location(lhs), false) {
+            Ok(Some(def)) => def,
+            _ => continue,
+        };
+        if finder.automatic.contains(&def.node.id()) {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "automatic variable `{}` cannot be assigned with a nonblocking assignment",
+                    name
+                ))
+                .span(lhs.span)
+                .add_note(format!("`{}` declared here:", name))
+                .span(def.name.span),
+            );
+        }
+    }
+}
+
+/// A visitor that collects the automatic variables, calls, and nonblocking
+/// assignments in the body of a subroutine.
+struct LifetimeUses<'a> {
+    /// The IDs of the automatic variables.
+    automatic: Vec<NodeId>,
+    /// The callees of subroutine calls.
+    calls: Vec<&'a ast::Expr<'a>>,
+    /// The targets of nonblocking assignments.
+    nonblocking: Vec<&'a ast::Expr<'a>>,
+    /// The default lifetime of variables in the subroutine.
+    lifetime: ast::Lifetime,
+}
+
+impl<'a> ast::Visitor<'a> for LifetimeUses<'a> {
+    fn pre_visit_var_decl(&mut self, node: &'a ast::VarDecl<'a>) -> bool {
+        if node.lifetime.unwrap_or(self.lifetime) == ast::Lifetime::Automatic {
+            self.automatic
+                .extend(node.names.iter().map(|name| name.id()));
+        }
+        true
+    }
+
+    fn pre_visit_stmt(&mut self, node: &'a ast::Stmt<'a>) -> bool {
+        match node.kind {
+            ast::NonblockingAssignStmt { ref lhs, .. } => self.nonblocking.push(lhs),
+            // Tasks without arguments may be called by their bare name.
+            ast::ExprStmt(ref expr) => {
+                if let ast::IdentExpr(..) = expr.data {
+                    self.calls.push(expr);
+                }
+            }
+            _ => (),
+        }
+        true
+    }
+
+    fn pre_visit_expr(&mut self, node: &'a ast::Expr<'a>) -> bool {
+        if let ast::CallExpr(ref callee, _) = node.data {
+            self.calls.push(callee);
+        }
+        true
+    }
+}
+
+/// Find the constructor (`function new`) of a class, if it has one.
+fn find_class_ctor<'a>(decl: &'a ast::ClassDecl<'a>) -> Option<&'a ast::SubroutineDecl<'a>> {
+    decl.items.iter().find_map(|item| match item.data {
+        ast::ClassItemData::SubroutineDecl(ref sub)
+            if &*sub.prototype.name.value.as_str() == "new" =>
+        {
+            Some(sub)
+        }
+        _ => None,
+    })
+}
+
+/// Check whether a statement is an explicit `super.new(...)` call.
+fn is_super_new_call(stmt: &ast::Stmt) -> bool {
+    match stmt.kind {
+        ast::ExprStmt(ref expr) => match expr.data {
+            ast::CallExpr(ref callee, _) => match callee.data {
+                ast::MemberExpr { ref expr, name } => {
+                    expr.data == ast::SuperExpr && &*name.value.as_str() == "new"
+                }
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Check that `rand` and `randc` class properties have a randomizable type.
+fn check_class_random_props<'gcx>(cx: &impl Context<'gcx>, decl: &'gcx ast::ClassDecl<'gcx>) {
+    for item in &decl.items {
+        let (ty, names) = match item.data {
+            ast::ClassItemData::Property(ref ty, ref names) => (ty, names),
+            _ => continue,
+        };
+        for &(qualifier, _) in &item.qualifiers {
+            let qualifier = match qualifier {
+                ast::ClassItemQualifier::Rand => ast::RandomQualifier::Rand,
+                ast::ClassItemQualifier::Randc => ast::RandomQualifier::Randc,
+                _ => continue,
+            };
+            for name in names {
+                check_random_qualifier(cx, qualifier, ty, name);
+            }
+        }
+    }
+}
+
+/// Check that a `rand` or `randc` qualifier is applied to a randomizable type.
+///
+/// `rand` admits integral types, unpacked structs, and arrays of these. `randc`
+/// only admits integral types and arrays of them. Since classes are not
+/// elaborated yet, the type is checked as written, such that only the built-in
+/// types that can never be randomized are rejected.
+pub(crate) fn check_random_qualifier<'gcx>(
+    cx: &impl Context<'gcx>,
+    qualifier: ast::RandomQualifier,
+    ty: &ast::Type,
+    name: &ast::VarDeclName,
+) {
+    let ok = match ty.kind.data {
+        ast::ShortRealType
+        | ast::RealType
+        | ast::RealtimeType
+        | ast::StringType
+        | ast::ChandleType
+        | ast::EventType
+        | ast::MailboxType => false,
+        ast::StructType(ref strukt) => qualifier == ast::RandomQualifier::Rand || strukt.packed,
+        _ => true,
+    };
+    if !ok {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "`{}` cannot be applied to `{}` of type `{}`",
+                qualifier,
+                name.name,
+                ty.span.extract()
+            ))
+            .span(name.span)
+            .add_note(match qualifier {
+                ast::RandomQualifier::Rand => {
+                    "Only integral types, unpacked structs, and arrays of these can be `rand`."
+                }
+                ast::RandomQualifier::Randc => {
+                    "Only integral types and arrays of them can be `randc`."
+                }
+            }),
+        );
+    }
+}
+
+/// A property declared in a class.
+struct ClassProp<'a> {
+    name: &'a ast::VarDeclName<'a>,
+    ty: &'a ast::Type<'a>,
+    /// Whether the property is `rand` or `randc`.
+    rand: bool,
+    /// Whether the property is `static`.
+    statik: bool,
+    /// Whether the property is `const`.
+    konst: bool,
+}
+
+/// Collect the properties declared in a class, by the ID of their name.
+fn class_props<'a>(decl: &'a ast::ClassDecl<'a>) -> HashMap<NodeId, ClassProp<'a>> {
+    let mut props = HashMap::new();
+    for item in &decl.items {
+        if let ast::ClassItemData::Property(ref ty, ref names) = item.data {
+            let has = |qualifier| item.qualifiers.iter().any(|&(q, _)| q == qualifier);
+            for name in names {
+                props.insert(
+                    name.id(),
+                    ClassProp {
+                        name,
+                        ty,
+                        rand: has(ast::ClassItemQualifier::Rand)
+                            || has(ast::ClassItemQualifier::Randc),
+                        statik: has(ast::ClassItemQualifier::Static),
+                        konst: has(ast::ClassItemQualifier::Const),
+                    },
+                );
+            }
+        }
+    }
+    props
+}
+
+/// Check the properties of a class.
+///
+/// `static const` properties need an initializer, and other `const` properties
+/// need either an initializer or an assignment in the constructor. Initializers
+/// of `static` properties may not refer to non-static ones. Properties that
+/// shadow a property of a base class are flagged.
+fn check_class_props<'gcx>(cx: &impl Context<'gcx>, decl: &'gcx ast::ClassDecl<'gcx>) {
+    let props = class_props(decl);
+    let mut props: Vec<_> = props.values().collect();
+    props.sort_by_key(|prop| prop.name.name_span);
+
+    // Find the properties assigned in the constructor.
+    let mut assigned = AssignedNames { names: vec![] };
+    if let Some(ctor) = find_class_ctor(decl) {
+        ctor.accept(&mut assigned);
+    }
+
+    for prop in &props {
+        let name = prop.name;
+        if prop.konst && name.init.is_none() {
+            if prop.statik {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "static const property `{}` must have an initializer",
+                        name.name
+                    ))
+                    .span(name.name_span),
+                );
+            } else if !assigned.names.contains(&name.name) {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "const property `{}` is never initialized",
+                        name.name
+                    ))
+                    .span(name.name_span)
+                    .add_note(
+                        "Initialize the property in its declaration or assign it in the \
+                         constructor",
+                    ),
+                );
+            }
+        }
+
+        // Initializers of static properties are evaluated at class scope, and
+        // cannot access the properties of an instance.
+        if let (true, Some(init)) = (prop.statik, name.init.as_ref()) {
+            let mut finder = IdentFinder { idents: vec![] };
+            init.accept(&mut finder);
+            for ident in finder.idents {
+                let ident_name = match ident.data {
+                    ast::IdentExpr(name) => name,
+                    _ => continue,
+                };
+                let target =
+                    match cx.resolve_local(ident_name.value, cx.scope_location(ident), false) {
+                        Ok(Some(def)) => props.iter().find(|p| p.name.id() == def.node.id()),
+                        _ => None,
+                    };
+                if let Some(target) = target.filter(|p| !p.statik) {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "static property `{}` cannot refer to non-static property `{}`",
+                            name.name, ident_name
+                        ))
+                        .span(ident.span)
+                        .add_note(format!("`{}` declared here:", ident_name))
+                        .span(target.name.name_span),
+                    );
+                }
+            }
+        }
+    }
+
+    // Check for properties that shadow properties of a base class.
+    let mut base = base_class_of(cx, decl);
+    let mut visited = vec![decl as *const _];
+    while let Some(b) = base {
+        if visited.contains(&(b as *const _)) {
+            break;
+        }
+        visited.push(b);
+        let base_props = class_props(b);
+        for prop in &props {
+            let shadowed = base_props.values().find(|p| p.name.name == prop.name.name);
+            if let Some(shadowed) = shadowed {
+                cx.emit(
+                    DiagBuilder2::warning(format!(
+                        "property `{}` shadows a property of base {}",
+                        prop.name.name, b
+                    ))
+                    .span(prop.name.name_span)
+                    .add_note("Shadowed property declared here:")
+                    .span(shadowed.name.name_span),
+                );
+            }
+        }
+        base = base_class_of(cx, b);
+    }
+}
+
+/// Resolve the base class of a class, if it has one.
+///
+/// Emits no diagnostics, since `check_class_ctor` already reports base classes
+/// that cannot be resolved.
+fn base_class_of<'gcx>(
+    cx: &impl Context<'gcx>,
+    decl: &'gcx ast::ClassDecl<'gcx>,
+) -> Option<&'gcx ast::ClassDecl<'gcx>> {
+    let name = match decl.extends {
+        Some((ref ty, _)) => match ty.kind.data {
+            ast::NamedType(name) => name,
+            _ => return None,
+        },
+        None => return None,
+    };
+    match cx.resolve_local(name.value, cx.scope_location(decl), false) {
+        Ok(Some(def)) => match def.node {
+            resolver::DefNode::Ast(node) => node.as_all().get_class_decl(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A visitor that finds the names assigned to, either directly or through
+/// `this`.
+struct AssignedNames {
+    names: Vec<Name>,
+}
+
+impl<'a> ast::Visitor<'a> for AssignedNames {
+    fn pre_visit_stmt(&mut self, node: &'a ast::Stmt<'a>) -> bool {
+        match node.kind {
+            ast::BlockingAssignStmt { ref lhs, .. }
+            | ast::NonblockingAssignStmt { ref lhs, .. } => self.names.extend(assigned_name(lhs)),
+            _ => (),
+        }
+        true
+    }
+
+    fn pre_visit_expr(&mut self, node: &'a ast::Expr<'a>) -> bool {
+        if let ast::AssignExpr { ref lhs, .. } = node.data {
+            self.names.extend(assigned_name(lhs));
+        }
+        true
+    }
+}
+
+/// Determine the name assigned to by an assignment target such as `x` or
+/// `this.x`.
+fn assigned_name(lhs: &ast::Expr) -> Option<Name> {
+    match lhs.data {
+        ast::IdentExpr(name) => Some(name.value),
+        ast::MemberExpr { ref expr, name } => match expr.data {
+            ast::ThisExpr => Some(name.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Check the constraints of a class against its properties.
+///
+/// Classes are not elaborated yet, so only the properties declared in the
+/// class itself are considered. Constraints may not refer to properties of a
+/// type that cannot be constrained, such as `real` or `string`. Constraint
+/// expressions that only refer to non-random properties are flagged, since
+/// randomization cannot affect them.
+fn check_class_constraints<'gcx>(cx: &impl Context<'gcx>, decl: &'gcx ast::ClassDecl<'gcx>) {
+    let props = class_props(decl);
+    for item in &decl.items {
+        if let ast::ClassItemData::Constraint(ref constraint) = item.data {
+            for item in &constraint.items {
+                check_constraint_item(cx, item, &props);
+            }
+        }
+    }
+}
+
+/// Check a constraint item against the properties of its class.
+fn check_constraint_item<'gcx>(
+    cx: &impl Context<'gcx>,
+    item: &'gcx ast::ConstraintItem<'gcx>,
+    props: &HashMap<NodeId, ClassProp<'gcx>>,
+) {
+    match item.data {
+        ast::ConstraintItemData::If {
+            ref cond,
+            ref main,
+            ref alt,
+        } => {
+            constraint_props(cx, cond, props);
+            for item in main.iter().chain(alt.iter().flatten()) {
+                check_constraint_item(cx, item, props);
+            }
+        }
+        ast::ConstraintItemData::Foreach(ref foreach) => {
+            constraint_props(cx, &foreach.array, props);
+            for item in &foreach.items {
+                check_constraint_item(cx, item, props);
+            }
+        }
+        ast::ConstraintItemData::Expr(ref expr) => {
+            let refs = match constraint_props(cx, expr, props) {
+                Some(refs) => refs,
+                None => return,
+            };
+            if let Some(prop) = refs.first().filter(|_| refs.iter().all(|p| !p.rand)) {
+                cx.emit(
+                    DiagBuilder2::warning("constraint does not involve any random property")
+                        .span(expr.span)
+                        .add_note(format!(
+                            "`{}` is not declared `rand` or `randc`:",
+                            prop.name.name
+                        ))
+                        .span(prop.name.span),
+                );
+            }
+        }
+    }
+}
+
+/// Find the class properties an expression in a constraint refers to.
+///
+/// Emits diagnostics for properties that cannot be constrained. Returns `None`
+/// if the expression refers to names other than the properties declared in the
+/// class.
+fn constraint_props<'gcx, 'p>(
+    cx: &impl Context<'gcx>,
+    expr: &'gcx ast::Expr<'gcx>,
+    props: &'p HashMap<NodeId, ClassProp<'gcx>>,
+) -> Option<Vec<&'p ClassProp<'gcx>>> {
+    let mut finder = IdentFinder { idents: vec![] };
+    expr.accept(&mut finder);
+    let mut refs = vec![];
+    let mut complete = true;
+    for ident in finder.idents {
+        let name = match ident.data {
+            ast::IdentExpr(name) => name,
+            _ => continue,
+        };
+        let prop = match cx.resolve_local(name.value, cx.scope_location(ident), false) {
+            Ok(Some(def)) => props.get(&def.node.id()),
+            _ => None,
+        };
+        let prop = match prop {
+            Some(prop) => prop,
+            None => {
+                complete = false;
+                continue;
+            }
+        };
+        match prop.ty.kind.data {
+            ast::ShortRealType
+            | ast::RealType
+            | ast::RealtimeType
+            | ast::StringType
+            | ast::ChandleType
+            | ast::EventType => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` of type `{}` cannot be used in a constraint",
+                        name.value,
+                        prop.ty.span.extract()
+                    ))
+                    .span(ident.span),
+                );
+            }
+            _ => (),
+        }
+        refs.push(prop);
+    }
+    if complete {
+        Some(refs)
+    } else {
+        None
+    }
+}
+
+/// A visitor that finds identifier expressions.
+struct IdentFinder<'a> {
+    idents: Vec<&'a ast::Expr<'a>>,
+}
+
+impl<'a> ast::Visitor<'a> for IdentFinder<'a> {
+    fn pre_visit_expr(&mut self, node: &'a ast::Expr<'a>) -> bool {
+        match node.data {
+            ast::IdentExpr(..) => {
+                self.idents.push(node);
+                false
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Check the constructor chaining of a class that extends a base class.
+///
+/// The base class constructor is called with the arguments given in the
+/// `extends` clause, or explicitly via `super.new(...)` as the first statement
+/// of the derived constructor. Only one of the two may be present, and if the
+/// base constructor has arguments without defaults, one of the two must be.
+fn check_class_ctor<'gcx>(cx: &impl Context<'gcx>, decl: &'gcx ast::ClassDecl<'gcx>) {
+    // Collect the statements of the constructor. Declarations at the top of
+    // the body may precede `super.new`, so they are skipped.
+    let ctor = find_class_ctor(decl);
+    let stmts: Vec<_> = ctor
+        .iter()
+        .flat_map(|ctor| ctor.items.iter())
+        .flat_map(|item| match item {
+            ast::SubroutineItem::Stmt(stmt) => Some(stmt),
+            _ => None,
+        })
+        .filter(|stmt| match stmt.kind {
+            ast::VarDeclStmt(..) | ast::ImportStmt(..) => false,
+            _ => true,
+        })
+        .collect();
+
+    // Make sure `super.new` only appears as the first statement.
+    for stmt in stmts.iter().skip(1).filter(|stmt| is_super_new_call(stmt)) {
+        cx.emit(
+            DiagBuilder2::error("`super.new` must be the first statement in a constructor")
+                .span(stmt.span),
+        );
+    }
+    let explicit = stmts.first().filter(|stmt| is_super_new_call(stmt));
+
+    // Classes without a base class cannot chain constructors.
+    let (base_ty, extends_args) = match decl.extends {
+        Some((ref ty, ref args)) => (ty, args),
+        None => {
+            if let Some(stmt) = explicit {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "{} has no base class whose constructor could be called",
+                        decl
+                    ))
+                    .span(stmt.span),
+                );
+            }
+            return;
+        }
+    };
+    if let (Some(stmt), false) = (explicit, extends_args.is_empty()) {
+        cx.emit(
+            DiagBuilder2::error("base class constructor called twice")
+                .span(stmt.span)
+                .add_note("Arguments are already passed to the base class here:")
+                .span(base_ty.span),
+        );
+        return;
+    }
+
+    // Resolve the base class.
+    let name = match base_ty.kind.data {
+        ast::NamedType(name) => name,
+        _ => return,
+    };
+    let loc = cx.scope_location(decl);
+    let base = match cx.resolve_local_or_error(name, loc, false) {
+        Ok(def) => match def.node {
+            resolver::DefNode::Ast(node) => node.as_all().get_class_decl(),
+            _ => None,
+        },
+        Err(()) => return,
+    };
+    let base = match base {
+        Some(base) => base,
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!("`{}` is not a class", name))
+                    .span(name.span)
+                    .add_note(format!("{} can only extend another class", decl)),
+            );
+            return;
+        }
+    };
+
+    // Check that the base constructor receives all arguments it requires.
+    let required = find_class_ctor(base)
+        .map(|ctor| {
+            ctor.prototype
+                .args
+                .iter()
+                .filter(|arg| arg.name.as_ref().map(|n| n.expr.is_none()).unwrap_or(true))
+                .count()
+        })
+        .unwrap_or(0);
+    if required > 0 && extends_args.is_empty() && explicit.is_none() {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "constructor of base {} requires {} argument(s)",
+                base, required
+            ))
+            .span(base_ty.span)
+            .add_note(format!(
+                "Pass the arguments as `extends {}(...)`, or call `super.new(...)` as the \
+                 first statement in the constructor of {}",
+                name, decl
+            )),
+        );
+    }
 }
 
 fn lower_type<'gcx>(
@@ -763,6 +1968,11 @@ fn lower_type<'gcx>(
             }
             hir::TypeKind::Struct(fields)
         }
+        ast::ScopedType {
+            ref ty,
+            member: false,
+            name,
+        } if ty.kind.data == ast::UnitType => hir::TypeKind::UnitScope(name),
         ast::ScopedType {
             ref ty,
             member: false,
@@ -773,7 +1983,6 @@ fn lower_type<'gcx>(
         ),
         ast::EnumType(ref enm) => {
             let repr_ty = &enm.base_type;
-            let names = &enm.variants;
             let mut next_rib = node_id;
             let ty = match repr_ty {
                 Some(ref ty) => {
@@ -783,10 +1992,10 @@ fn lower_type<'gcx>(
                 None => None,
             };
             let mut variants = vec![];
-            for (index, name) in names.iter().enumerate() {
-                next_rib =
-                    cx.map_ast_with_parent(AstNode::EnumVariant(name, node_id, index), next_rib);
-                variants.push((name.name, next_rib));
+            for &(name, id, _) in cx.enum_variants(Ref(enm))?.iter() {
+                cx.set_parent(id, next_rib);
+                next_rib = id;
+                variants.push((name, id));
             }
             hir::TypeKind::Enum(variants, ty)
         }
@@ -837,6 +2046,7 @@ fn lower_type<'gcx>(
         | ast::RealtimeType
         | ast::SpecializedType(..)
         | ast::ForwardType { .. }
+        | ast::UnitType
         | ast::ScopedType { .. } => {
             error!("{:#?}", ty);
             bug_span!(
@@ -877,6 +2087,95 @@ fn lower_type<'gcx>(
     Ok(HirNode::Type(cx.arena().alloc_hir(hir)))
 }
 
+/// Determine the variants declared by an enum type.
+///
+/// A name with a range, such as `S[3]` or `S[2:4]`, is expanded into one
+/// variant per index of the range, such as `S0, S1, S2` or `S2, S3, S4`. The
+/// first of these keeps the node ID of the name, and the others are assigned
+/// new IDs. See IEEE 1800-2017 §6.19.
+#[moore_derive::query]
+pub(crate) fn enum_variants<'a>(
+    cx: &impl Context<'a>,
+    Ref(enm): Ref<'a, ast::Enum<'a>>,
+) -> Result<Arc<Vec<(Spanned<Name>, NodeId, &'a ast::EnumName<'a>)>>> {
+    let decl = enm.get_parent().unwrap().get_parent().unwrap().id();
+    let mut variants = vec![];
+    for name in &enm.variants {
+        let (lo, hi) = match name.range {
+            None => {
+                let id = cx.map_ast(AstNode::EnumVariant(name, decl, variants.len()));
+                variants.push((name.name, id, name));
+                continue;
+            }
+            Some(ast::TypeDim::Expr(ref count)) => match lower_enum_name_index(cx, count)? {
+                0 => {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "enum name `{}` has an empty range",
+                            name.name.value
+                        ))
+                        .span(count.span),
+                    );
+                    return Err(());
+                }
+                count => (0, count - 1),
+            },
+            Some(ast::TypeDim::Range(ref lo, ref hi)) => (
+                lower_enum_name_index(cx, lo)?,
+                lower_enum_name_index(cx, hi)?,
+            ),
+            Some(_) => bug_span!(name.span, cx, "invalid enum name range {:?}", name.range),
+        };
+        let indices: Vec<u64> = if lo <= hi {
+            (lo..=hi).collect()
+        } else {
+            (hi..=lo).rev().collect()
+        };
+        for index in indices {
+            let expanded = format!("{}{}", name.name.value, index);
+            let expanded = Spanned::new(
+                crate::common::name::get_name_table().intern(&expanded, true),
+                name.name.span,
+            );
+            let ast = AstNode::EnumVariant(name, decl, variants.len());
+            let id = if index == lo {
+                cx.map_ast(ast)
+            } else {
+                let id = cx.alloc_id(name.name.span);
+                cx.set_ast(id, ast);
+                id
+            };
+            variants.push((expanded, id, name));
+        }
+    }
+    Ok(Arc::new(variants))
+}
+
+/// Evaluate an index in the range of an enum name.
+///
+/// The index must be an integer literal.
+fn lower_enum_name_index<'a>(cx: &impl Context<'a>, expr: &'a ast::Expr<'a>) -> Result<u64> {
+    use crate::syntax::token::Lit;
+    match expr.data {
+        ast::LiteralExpr(Lit::Number(v, None)) => v.as_str().parse().map_err(|_| {
+            cx.emit(
+                DiagBuilder2::error(format!("enum name index `{}` is too large", v))
+                    .span(expr.span),
+            );
+        }),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "enum name index `{}` is not an integer literal",
+                    expr.span.extract()
+                ))
+                .span(expr.span),
+            );
+            Err(())
+        }
+    }
+}
+
 /// Lower an AST expression to HIR.
 #[moore_derive::query]
 pub(crate) fn hir_of_expr<'a>(
@@ -1016,6 +2315,10 @@ fn lower_expr_inner<'gcx>(
             }
         }
 
+        ast::LiteralExpr(Lit::Number(int, Some(frac))) => {
+            hir::ExprKind::RealConst(parse_fixed_point_number(cx, expr.span, int, Some(frac))?)
+        }
+
         ast::LiteralExpr(Lit::Time(int, frac, unit)) => {
             use syntax::token::TimeUnit;
             let mut value = parse_fixed_point_number(cx, expr.span, int, frac)?;
@@ -1038,6 +2341,7 @@ fn lower_expr_inner<'gcx>(
         }
 
         ast::IdentExpr(ident) => hir::ExprKind::Ident(ident),
+        ast::DollarExpr => hir::ExprKind::Unbounded,
         ast::UnaryExpr {
             op,
             expr: ref arg,
@@ -1149,75 +2453,7 @@ fn lower_expr_inner<'gcx>(
             hir::ExprKind::Index(indexee, mode)
         }
         ast::CallExpr(ref callee, ref args) => match callee.data {
-            ast::SysIdentExpr(ident) => {
-                let map_unary = || match args.as_slice() {
-                    [ast::CallArg {
-                        expr: Some(ref arg),
-                        ..
-                    }] => Ok(arg),
-                    _ => {
-                        cx.emit(
-                            DiagBuilder2::error(format!("`{}` takes one argument", ident))
-                                .span(expr.human_span()),
-                        );
-                        Err(())
-                    }
-                };
-                let map_unary_id =
-                    || Ok(cx.map_ast_with_parent(AstNode::Expr(map_unary()?), node_id));
-                let map_array_dim = |func| match args.as_slice() {
-                    [ast::CallArg {
-                        expr: Some(ref arg),
-                        ..
-                    }] => Ok(hir::BuiltinCall::ArrayDim(func, arg, None)),
-                    [ast::CallArg {
-                        expr: Some(ref arg),
-                        ..
-                    }, ast::CallArg {
-                        expr: Some(ref dim),
-                        ..
-                    }] => Ok(hir::BuiltinCall::ArrayDim(func, arg, Some(dim))),
-                    _ => {
-                        cx.emit(
-                            DiagBuilder2::error(format!("`{}` takes one or two arguments", ident))
-                                .span(expr.human_span()),
-                        );
-                        Err(())
-                    }
-                };
-                hir::ExprKind::Builtin(match &*ident.value.as_str() {
-                    "clog2" => hir::BuiltinCall::Clog2(map_unary_id()?),
-                    "signed" => hir::BuiltinCall::Signed(map_unary_id()?),
-                    "unsigned" => hir::BuiltinCall::Unsigned(map_unary_id()?),
-                    "countones" => hir::BuiltinCall::CountOnes(map_unary()?),
-                    "onehot" => hir::BuiltinCall::OneHot(map_unary()?),
-                    "onehot0" => hir::BuiltinCall::OneHot0(map_unary()?),
-                    "isunknown" => hir::BuiltinCall::IsUnknown(map_unary()?),
-                    "left" => map_array_dim(hir::ArrayDim::Left)?,
-                    "right" => map_array_dim(hir::ArrayDim::Right)?,
-                    "low" => map_array_dim(hir::ArrayDim::Low)?,
-                    "high" => map_array_dim(hir::ArrayDim::High)?,
-                    "increment" => map_array_dim(hir::ArrayDim::Increment)?,
-                    "size" => map_array_dim(hir::ArrayDim::Size)?,
-                    "display" | "info" | "warning" | "error" | "fatal" => {
-                        cx.emit(
-                            DiagBuilder2::warning(format!(
-                                "unsupported: system task `${}`; ignored",
-                                ident
-                            ))
-                            .span(expr.human_span()),
-                        );
-                        hir::BuiltinCall::Unsupported
-                    }
-                    _ => {
-                        cx.emit(
-                            DiagBuilder2::error(format!("unknown system task `${}`", ident))
-                                .span(expr.human_span()),
-                        );
-                        hir::BuiltinCall::Unsupported
-                    }
-                })
-            }
+            ast::SysIdentExpr(ident) => lower_system_call(cx, node_id, expr, ident, args)?,
             ast::IdentExpr(name) => {
                 let target =
                     cx.resolve_upwards_or_error(name, cx.parent_node_id(node_id).unwrap())?;
@@ -1228,6 +2464,13 @@ fn lower_expr_inner<'gcx>(
                         .collect(),
                 )
             }
+            ast::MemberExpr { ref expr, name } => hir::ExprKind::MethodCall(
+                cx.map_ast_with_parent(AstNode::Expr(expr), node_id),
+                name,
+                args.iter()
+                    .map(|arg| lower_call_arg(cx, arg, node_id))
+                    .collect(),
+            ),
             _ => {
                 error!("{:#?}", callee);
                 cx.emit(
@@ -1240,6 +2483,7 @@ fn lower_expr_inner<'gcx>(
                 return Err(());
             }
         },
+        ast::SysIdentExpr(ident) => lower_system_call(cx, node_id, expr, ident, &[])?,
         ast::TernaryExpr {
             ref cond,
             ref true_expr,
@@ -1249,6 +2493,9 @@ fn lower_expr_inner<'gcx>(
             cx.map_ast_with_parent(AstNode::Expr(true_expr), node_id),
             cx.map_ast_with_parent(AstNode::Expr(false_expr), node_id),
         ),
+        ast::ScopeExpr(ref expr, name) if is_unit_scope(expr) => hir::ExprKind::UnitScope(name),
+        // Misplaced `local::` qualifiers are diagnosed during name checking.
+        ast::ScopeExpr(ref expr, _) if expr.data == ast::LocalExpr => return Err(()),
         ast::ScopeExpr(ref expr, name) => hir::ExprKind::Scope(
             cx.map_ast_with_parent(AstNode::Expr(expr.as_ref()), node_id),
             name,
@@ -1360,6 +2607,36 @@ fn lower_expr_inner<'gcx>(
                 .map(|expr| cx.map_ast_with_parent(AstNode::Expr(expr), node_id))
                 .collect(),
         ),
+        ast::StreamConcatExpr {
+            dir,
+            ref slice,
+            ref exprs,
+        } => {
+            for expr in exprs {
+                if let Some(ref range) = expr.range {
+                    cx.emit(
+                        DiagBuilder2::error("unsupported: `with` in streaming concatenation")
+                            .span(range.span),
+                    );
+                    return Err(());
+                }
+            }
+            hir::ExprKind::StreamConcat(
+                dir,
+                slice.as_ref().map(|slice| match slice {
+                    ast::StreamConcatSlice::Expr(expr) => {
+                        cx.map_ast_with_parent(AstNode::Expr(expr), node_id)
+                    }
+                    ast::StreamConcatSlice::Type(ty) => {
+                        cx.map_ast_with_parent(AstNode::Type(ty), node_id)
+                    }
+                }),
+                exprs
+                    .iter()
+                    .map(|expr| cx.map_ast_with_parent(AstNode::Expr(&expr.expr), node_id))
+                    .collect(),
+            )
+        }
         ast::CastExpr(ref ty, ref expr) => {
             // Catch the corner case where a size cast looks like a type cast.
             if let ast::NamedType(n) = ty.kind.data {
@@ -1407,23 +2684,13 @@ fn lower_expr_inner<'gcx>(
             cx.map_ast_with_parent(AstNode::Expr(expr), node_id),
             ranges
                 .iter()
-                .map(|vr| match vr {
-                    ast::ValueRange::Single(expr) => Spanned::new(
-                        hir::InsideRange::Single(
-                            cx.map_ast_with_parent(AstNode::Expr(expr), node_id),
-                        ),
-                        expr.span,
-                    ),
-                    ast::ValueRange::Range { lo, hi, span } => Spanned::new(
-                        hir::InsideRange::Range(
-                            cx.map_ast_with_parent(AstNode::Expr(lo), node_id),
-                            cx.map_ast_with_parent(AstNode::Expr(hi), node_id),
-                        ),
-                        *span,
-                    ),
-                })
+                .map(|vr| lower_value_range(cx, vr, node_id))
                 .collect(),
         ),
+        ast::DistExpr(..) => {
+            cx.emit(DiagBuilder2::error("`dist` can only be used in a constraint").span(expr.span));
+            return Err(());
+        }
         ast::BitsExpr { ref arg, .. } => hir::ExprKind::Builtin(hir::BuiltinCall::Bits(arg)),
         ast::AssignExpr {
             op,
@@ -1446,6 +2713,257 @@ fn lower_expr_inner<'gcx>(
     })
 }
 
+/// Lower a call to a system function or task, such as `$clog2(x)`.
+///
+/// System tasks that are called without parentheses, such as `$finish`, are
+/// lowered as a call without arguments.
+fn lower_system_call<'gcx>(
+    cx: &impl Context<'gcx>,
+    node_id: NodeId,
+    expr: &'gcx ast::Expr<'gcx>,
+    ident: Spanned<Name>,
+    args: &'gcx [ast::CallArg<'gcx>],
+) -> Result<hir::ExprKind<'gcx>> {
+    let map_unary = || match args {
+        [ast::CallArg {
+            expr: Some(ref arg),
+            ..
+        }] => Ok(arg),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!("`{}` takes one argument", ident))
+                    .span(expr.human_span()),
+            );
+            Err(())
+        }
+    };
+    let map_unary_id = || Ok(cx.map_ast_with_parent(AstNode::Expr(map_unary()?), node_id));
+    // The first argument of the array query functions may also
+    // name a type, which is disambiguated later on.
+    let map_array_arg = |arg| &*cx.arena().alloc(ast::TypeOrExpr::Expr(arg));
+    let map_array_dim = |func| match args {
+        [ast::CallArg {
+            expr: Some(ref arg),
+            ..
+        }] => Ok(hir::BuiltinCall::ArrayDim(func, map_array_arg(arg), None)),
+        [ast::CallArg {
+            expr: Some(ref arg),
+            ..
+        }, ast::CallArg {
+            expr: Some(ref dim),
+            ..
+        }] => Ok(hir::BuiltinCall::ArrayDim(
+            func,
+            map_array_arg(arg),
+            Some(dim),
+        )),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!("`{}` takes one or two arguments", ident))
+                    .span(expr.human_span()),
+            );
+            Err(())
+        }
+    };
+    let map_display = |task| hir::BuiltinCall::Display(task, args);
+    let map_args = || -> Option<Vec<NodeId>> {
+        args.iter()
+            .map(|arg| {
+                arg.expr
+                    .as_ref()
+                    .map(|x| cx.map_ast_with_parent(AstNode::Expr(x), node_id))
+            })
+            .collect()
+    };
+    let map_read_mem = |radix| {
+        let (file, mem, start, end) = match map_args().as_ref().map(Vec::as_slice) {
+            Some(&[file, mem]) => (file, mem, None, None),
+            Some(&[file, mem, start]) => (file, mem, Some(start), None),
+            Some(&[file, mem, start, end]) => (file, mem, Some(start), Some(end)),
+            _ => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`${}` takes a file name, a memory, and an optional start and end \
+                         address",
+                        ident
+                    ))
+                    .span(expr.human_span()),
+                );
+                return Err(());
+            }
+        };
+        Ok(hir::BuiltinCall::ReadMem {
+            radix,
+            file,
+            mem,
+            start,
+            end,
+        })
+    };
+    let map_seed = || match args {
+        [] => Ok(None),
+        [ast::CallArg {
+            expr: Some(ref arg),
+            ..
+        }] => Ok(Some(cx.map_ast_with_parent(AstNode::Expr(arg), node_id))),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!("`${}` takes at most one argument", ident))
+                    .span(expr.human_span()),
+            );
+            Err(())
+        }
+    };
+    let map_finish = |task| {
+        if task != hir::FinishTask::Fatal && args.len() > 1 {
+            cx.emit(
+                DiagBuilder2::error(format!("`${}` takes at most one argument", ident))
+                    .span(expr.human_span()),
+            );
+            return Err(());
+        }
+        Ok(hir::BuiltinCall::Finish(task, args))
+    };
+    Ok(hir::ExprKind::Builtin(match &*ident.value.as_str() {
+        "clog2" => hir::BuiltinCall::Clog2(map_unary_id()?),
+        "signed" => hir::BuiltinCall::Signed(map_unary_id()?),
+        "unsigned" => hir::BuiltinCall::Unsigned(map_unary_id()?),
+        "countones" => hir::BuiltinCall::CountOnes(map_unary_id()?),
+        "onehot" => hir::BuiltinCall::OneHot(map_unary_id()?),
+        "onehot0" => hir::BuiltinCall::OneHot0(map_unary_id()?),
+        "countbits" => match args.split_first() {
+            Some((
+                ast::CallArg {
+                    expr: Some(ref arg),
+                    ..
+                },
+                control,
+            )) if !control.is_empty() => hir::BuiltinCall::CountBits(
+                cx.map_ast_with_parent(AstNode::Expr(arg), node_id),
+                control,
+            ),
+            _ => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`${}` requires an expression and at least one control bit",
+                        ident
+                    ))
+                    .span(expr.human_span()),
+                );
+                return Err(());
+            }
+        },
+        "isunknown" => hir::BuiltinCall::IsUnknown(map_unary()?),
+        "isunbounded" => hir::BuiltinCall::IsUnbounded(map_unary_id()?),
+        "left" => map_array_dim(hir::ArrayDim::Left)?,
+        "right" => map_array_dim(hir::ArrayDim::Right)?,
+        "low" => map_array_dim(hir::ArrayDim::Low)?,
+        "high" => map_array_dim(hir::ArrayDim::High)?,
+        "increment" => map_array_dim(hir::ArrayDim::Increment)?,
+        "size" => map_array_dim(hir::ArrayDim::Size)?,
+        "sformatf" => match args.first() {
+            Some(ast::CallArg { expr: Some(_), .. }) => hir::BuiltinCall::Sformatf(args),
+            _ => {
+                cx.emit(
+                    DiagBuilder2::error(format!("`{}` requires a format string argument", ident))
+                        .span(expr.human_span()),
+                );
+                return Err(());
+            }
+        },
+        "display" => map_display(hir::DisplayTask::Display),
+        "write" => map_display(hir::DisplayTask::Write),
+        "monitor" => map_display(hir::DisplayTask::Monitor),
+        "finish" => map_finish(hir::FinishTask::Finish)?,
+        "stop" => map_finish(hir::FinishTask::Stop)?,
+        "fatal" => map_finish(hir::FinishTask::Fatal)?,
+        "readmemh" => map_read_mem(16)?,
+        "readmemb" => map_read_mem(2)?,
+        "fopen" => match map_args().as_ref().map(Vec::as_slice) {
+            Some(&[name]) => hir::BuiltinCall::FileOpen(name, None),
+            Some(&[name, mode]) => hir::BuiltinCall::FileOpen(name, Some(mode)),
+            _ => {
+                cx.emit(
+                    DiagBuilder2::error(format!("`${}` takes one or two arguments", ident))
+                        .span(expr.human_span()),
+                );
+                return Err(());
+            }
+        },
+        "fclose" => hir::BuiltinCall::FileClose(map_unary_id()?),
+        "dumpfile" => hir::BuiltinCall::DumpFile(map_seed()?),
+        "dumpvars" => hir::BuiltinCall::DumpVars(args),
+        "fdisplay" => map_display(hir::DisplayTask::FileDisplay),
+        "fwrite" => map_display(hir::DisplayTask::FileWrite),
+        "random" => hir::BuiltinCall::Random(map_seed()?),
+        "urandom" => hir::BuiltinCall::Urandom(map_seed()?),
+        "urandom_range" => match args {
+            [ast::CallArg {
+                expr: Some(ref max),
+                ..
+            }] => hir::BuiltinCall::UrandomRange(
+                cx.map_ast_with_parent(AstNode::Expr(max), node_id),
+                None,
+            ),
+            [ast::CallArg {
+                expr: Some(ref max),
+                ..
+            }, ast::CallArg {
+                expr: Some(ref min),
+                ..
+            }] => hir::BuiltinCall::UrandomRange(
+                cx.map_ast_with_parent(AstNode::Expr(max), node_id),
+                Some(cx.map_ast_with_parent(AstNode::Expr(min), node_id)),
+            ),
+            _ => {
+                cx.emit(
+                    DiagBuilder2::error(format!("`${}` takes one or two arguments", ident))
+                        .span(expr.human_span()),
+                );
+                return Err(());
+            }
+        },
+        "info" | "warning" | "error" => {
+            cx.emit(
+                DiagBuilder2::warning(format!("unsupported: system task `${}`; ignored", ident))
+                    .span(expr.human_span()),
+            );
+            hir::BuiltinCall::Unsupported
+        }
+        "asserton" | "assertoff" | "assertkill" | "assertcontrol" | "assertpasson"
+        | "assertpassoff" | "assertfailon" | "assertfailoff" | "assertnonvacuouson"
+        | "assertvacuousoff" => {
+            cx.emit(
+                DiagBuilder2::warning(format!(
+                    "unsupported: assertion control task `${}`; ignored",
+                    ident
+                ))
+                .span(expr.human_span())
+                .add_note(
+                    "Assertions are not lowered yet, so no pass, failure, or cover statistics \
+                     are collected",
+                ),
+            );
+            hir::BuiltinCall::Unsupported
+        }
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!("unknown system task `${}`", ident))
+                    .span(expr.human_span()),
+            );
+            hir::BuiltinCall::Unsupported
+        }
+    }))
+}
+
+/// Check whether an expression is the compilation unit scope `$unit`.
+pub(crate) fn is_unit_scope(expr: &ast::Expr) -> bool {
+    match expr.data {
+        ast::SysIdentExpr(name) => &*name.value.as_str() == "unit",
+        _ => false,
+    }
+}
+
 /// Parse a fixed point number into a [`BigRational`].
 ///
 /// The fractional part of the number is optional, such that this function may
@@ -1480,6 +2998,153 @@ fn parse_fixed_point_number<'gcx>(
     }
 }
 
+/// Check that a `fork` block can be lowered.
+///
+/// Processes cannot be spawned dynamically at the moment. Instead, the branches
+/// of a `fork ... join` or `fork ... join_any` are executed one after another,
+/// which is a valid scheduling of the branches as long as none of them
+/// suspends. Since all branches then complete in the same time step, `join` and
+/// `join_any` behave the same. A `fork ... join_none` requires the branches to
+/// run only once the parent process suspends, which cannot be modeled this
+/// way.
+fn check_fork_join<'gcx>(
+    cx: &impl Context<'gcx>,
+    stmt: &'gcx ast::Stmt<'gcx>,
+    branches: &'gcx [ast::Stmt<'gcx>],
+    join: ast::JoinKind,
+) -> Result<()> {
+    if join == ast::JoinKind::None {
+        cx.emit(
+            DiagBuilder2::error("unsupported: `fork ... join_none`")
+                .span(stmt.human_span())
+                .add_note("Processes cannot be spawned dynamically at the moment"),
+        );
+        return Err(());
+    }
+    let mut finder = SuspendFinder { found: None };
+    for branch in branches {
+        branch.accept(&mut finder);
+    }
+    if let Some(span) = finder.found {
+        cx.emit(
+            DiagBuilder2::error("unsupported: `fork` with branches that suspend")
+                .span(stmt.human_span())
+                .add_note(
+                    "Branches of a `fork` are executed one after another, which requires them \
+                     to complete without waiting:",
+                )
+                .span(span),
+        );
+        return Err(());
+    }
+    Ok(())
+}
+
+/// A visitor that finds statements which suspend the executing process.
+struct SuspendFinder {
+    found: Option<Span>,
+}
+
+impl<'a> ast::Visitor<'a> for SuspendFinder {
+    fn pre_visit_stmt(&mut self, node: &'a ast::Stmt<'a>) -> bool {
+        match node.kind {
+            ast::TimedStmt(..) | ast::WaitExprStmt(..) | ast::WaitForkStmt => {
+                self.found.get_or_insert(node.human_span());
+                false
+            }
+            _ => self.found.is_none(),
+        }
+    }
+}
+
+/// Lower a value or value range of an `inside` set.
+fn lower_value_range<'gcx>(
+    cx: &impl Context<'gcx>,
+    range: &'gcx ast::ValueRange<'gcx>,
+    parent_id: NodeId,
+) -> Spanned<hir::InsideRange> {
+    match *range {
+        ast::ValueRange::Single(ref expr) => Spanned::new(
+            hir::InsideRange::Single(cx.map_ast_with_parent(AstNode::Expr(expr), parent_id)),
+            expr.span,
+        ),
+        ast::ValueRange::Range {
+            ref lo,
+            ref hi,
+            span,
+        } => Spanned::new(
+            hir::InsideRange::Range(
+                cx.map_ast_with_parent(AstNode::Expr(lo), parent_id),
+                cx.map_ast_with_parent(AstNode::Expr(hi), parent_id),
+            ),
+            span,
+        ),
+    }
+}
+
+/// Lower the default item of a case statement.
+///
+/// Emits an error if the case statement has multiple default items.
+fn lower_case_default<'gcx>(
+    cx: &impl Context<'gcx>,
+    items: &'gcx [ast::CaseItem<'gcx>],
+    parent_id: NodeId,
+) -> Option<NodeId> {
+    let mut default = None;
+    for item in items {
+        if let ast::CaseItem::Default(ref stmt) = *item {
+            if default.is_none() {
+                default = Some(cx.map_ast_with_parent(AstNode::Stmt(stmt), parent_id));
+            } else {
+                cx.emit(DiagBuilder2::error("multiple default cases").span(stmt.human_span()));
+            }
+        }
+    }
+    default
+}
+
+/// Iterate over the statements enclosing a statement, innermost first.
+///
+/// The iteration stops at the first ancestor that is not a statement, such that
+/// statements outside the enclosing procedure or subroutine are not considered.
+fn enclosing_stmts<'a>(stmt: &'a ast::Stmt<'a>) -> impl Iterator<Item = &'a ast::Stmt<'a>> {
+    std::iter::successors(Some(stmt), |stmt| {
+        match stmt.get_parent().map(|p| p.as_all()) {
+            Some(ast::AllNode::Stmt(parent)) => Some(parent),
+            _ => None,
+        }
+    })
+    .skip(1)
+}
+
+/// Check whether a statement is nested within a loop.
+fn is_within_loop<'a>(stmt: &'a ast::Stmt<'a>) -> bool {
+    enclosing_stmts(stmt).any(|stmt| match stmt.kind {
+        ast::ForeverStmt(..)
+        | ast::RepeatStmt(..)
+        | ast::WhileStmt(..)
+        | ast::DoStmt(..)
+        | ast::ForStmt(..)
+        | ast::ForeachStmt(..) => true,
+        _ => false,
+    })
+}
+
+/// Lower the rise, fall, and turn-off delays of a net or continuous
+/// assignment.
+fn lower_delay3<'gcx>(
+    cx: &impl Context<'gcx>,
+    delay: &'gcx ast::Delay3<'gcx>,
+    parent: NodeId,
+) -> hir::Delay3 {
+    let map_expr = |expr| cx.map_ast_with_parent(AstNode::Expr(expr), parent);
+    hir::Delay3 {
+        rise: map_expr(&delay.rise),
+        fall: delay.fall.as_ref().map(map_expr),
+        turn_off: delay.turn_off.as_ref().map(map_expr),
+    }
+}
+
 fn lower_event_expr<'gcx>(
     cx: &impl Context<'gcx>,
     expr: &'gcx ast::EventExpr<'gcx>,
@@ -1493,6 +3158,17 @@ fn lower_event_expr<'gcx>(
             edge,
             ref value,
         } => {
+            // Waiting for a clocking block, as in `@(cb)`, waits for its
+            // clocking event.
+            let clocking = match (edge, &value.data) {
+                (ast::EdgeIdent::Implicit, &ast::IdentExpr(ident)) => cx
+                    .resolve_local(ident.value, cx.scope_location(value), false)?
+                    .and_then(|def| def.node.as_all().get_clocking_decl()),
+                _ => None,
+            };
+            if let Some(decl) = clocking {
+                return lower_event_expr(cx, &decl.event, parent_id, into, cond_stack);
+            }
             into.push(hir::Event {
                 span,
                 edge,
@@ -1603,6 +3279,14 @@ fn alloc_net_decl<'gcx>(
     mut next_rib: NodeId,
     into: &mut Vec<NodeId>,
 ) -> NodeId {
+    match decl.strength {
+        Some(ast::NetStrength::Drive(a, b)) => check_drive_strength(cx, a, b, decl.span),
+        Some(ast::NetStrength::Charge(_)) if decl.net_type != ast::NetType::TriReg => cx.emit(
+            DiagBuilder2::error("charge strength can only be given for `trireg` nets")
+                .span(decl.span),
+        ),
+        _ => (),
+    }
     let type_id = cx.map_ast_with_parent(AstNode::Type(&decl.ty), next_rib);
     next_rib = type_id;
     for name in &decl.names {
@@ -1613,6 +3297,28 @@ fn alloc_net_decl<'gcx>(
     next_rib
 }
 
+/// Check that a pair of drive strengths is valid.
+///
+/// The pair must consist of one strength for 0 and one for 1, which must not
+/// both be `highz`.
+fn check_drive_strength<'gcx>(
+    cx: &impl Context<'gcx>,
+    a: ast::DriveStrength,
+    b: ast::DriveStrength,
+    span: Span,
+) {
+    if hir::DriveStrength::new(a, b).is_none() {
+        cx.emit(
+            DiagBuilder2::error(format!("invalid drive strength `({}, {})`", a, b))
+                .span(span)
+                .add_note(
+                    "A drive strength must give one strength for 0 and one for 1, which \
+                     cannot both be `highz`.",
+                ),
+        );
+    }
+}
+
 /// Allocate node IDs for a struct member.
 fn alloc_struct_member<'gcx>(
     cx: &impl Context<'gcx>,
@@ -6,15 +6,15 @@
 //! of syntactic sugar and resolving any syntactic ambiguities.
 
 use crate::crate_prelude::*;
-use crate::mir::WalkVisitor as _;
+use crate::{ast_map::AstNode, mir::WalkVisitor as _};
 use std::{collections::BTreeSet, sync::Arc};
 
 pub(crate) mod lowering;
 mod nodes;
 mod visit;
 
-pub(crate) use self::lowering::hir_of;
 pub use self::lowering::Hint;
+pub(crate) use self::lowering::{hir_of, is_unit_scope};
 pub use self::nodes::*;
 pub use self::visit::*;
 
@@ -36,6 +36,7 @@ make_arenas!(
         event_exprs: EventExpr,
         gens: Gen,
         genvar_decls: GenvarDecl,
+        foreach_indices: ForeachIndex,
         typedefs: Typedef,
         assigns: Assign,
         packages: Package,
@@ -64,6 +65,21 @@ pub(crate) fn accessed_nodes<'a>(
     Ok(Arc::new(k.table))
 }
 
+/// Determine the implicit sensitivity list of a statement, as given by `@*`.
+///
+/// This is the set of signals read by the statement, like the inputs of an
+/// `always_comb` procedure. Signals that are only written by the statement are
+/// not included.
+#[moore_derive::query]
+pub(crate) fn implicit_sensitivity<'a>(
+    cx: &impl Context<'a>,
+    stmt_id: NodeId,
+    env: ParamEnv,
+) -> Result<Vec<AccessedNode>> {
+    let acc = cx.accessed_nodes(stmt_id, env)?;
+    Ok(acc.read.iter().cloned().collect())
+}
+
 /// A table of accessed nodes.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AccessTable {
@@ -126,6 +142,19 @@ where
             self.cx.mir_rvalue(expr.id, self.env).walk(self);
         }
     }
+
+    fn visit_timing_control(&mut self, ctrl: &'gcx TimingControl) {
+        // Real delays such as `#2.5` read no signals, and cannot be lowered as
+        // rvalues.
+        if let TimingControl::Delay(id) = *ctrl {
+            if let Ok(HirNode::Expr(expr)) = self.cx.hir_of(id) {
+                if let ExprKind::RealConst(_) = expr.kind {
+                    return;
+                }
+            }
+        }
+        walk_timing_control(self, ctrl);
+    }
 }
 
 impl<'a, 'gcx: 'a, C> mir::Visitor<'gcx> for AccessTableCollector<'a, C>
@@ -181,3 +210,108 @@ where
         !self.cx.is_parent_of(self.table.node_id, binding)
     }
 }
+
+/// Determine the lvalues that drive a signal.
+///
+/// Collects every assignment target in the procedures and continuous
+/// assignments of the module enclosing the signal whose root is the signal.
+/// Bit- and part-selects, struct field accesses, concatenations, and interface
+/// signal accesses are looked through to find the root.
+#[moore_derive::query]
+pub(crate) fn drivers_of<'a>(
+    cx: &impl Context<'a>,
+    signal_id: NodeId,
+    env: ParamEnv,
+) -> Vec<&'a mir::Lvalue<'a>> {
+    // Find the module that encloses the signal.
+    let mut next = Some(signal_id);
+    let module = loop {
+        let id = match next {
+            Some(id) => id,
+            None => return vec![],
+        };
+        if let Ok(AstNode::Module(_)) = cx.ast_of(id) {
+            match cx.hir_of(id) {
+                Ok(HirNode::Module(x)) => break x,
+                _ => return vec![],
+            }
+        }
+        next = cx.parent_node_id(id);
+    };
+
+    let mut k = DriverCollector {
+        cx,
+        env,
+        signal_id,
+        drivers: vec![],
+    };
+    for &id in module.block.procs.iter().chain(module.block.assigns.iter()) {
+        k.visit_node_with_id(id, false);
+    }
+    k.drivers
+}
+
+/// A visitor for the HIR that collects the drivers of a signal.
+struct DriverCollector<'a, 'gcx, C> {
+    cx: &'a C,
+    env: ParamEnv,
+    signal_id: NodeId,
+    drivers: Vec<&'gcx mir::Lvalue<'gcx>>,
+}
+
+impl<'a, 'gcx: 'a, C> Visitor<'gcx> for DriverCollector<'a, 'gcx, C>
+where
+    C: Context<'gcx>,
+{
+    type Context = C;
+    fn context(&self) -> &C {
+        self.cx
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx Expr, lvalue: bool) {
+        if lvalue {
+            let lv = self.cx.mir_lvalue(expr.id, self.env);
+            self.add_driver(lv);
+        } else {
+            // Assignment expressions such as `x++` may be nested in rvalues.
+            self.cx.mir_rvalue(expr.id, self.env).walk(self);
+        }
+    }
+
+    // Timing controls and delays cannot contain assignments. They are skipped
+    // since delays such as `#2.5` cannot be lowered as rvalues.
+    fn visit_timing_control(&mut self, _ctrl: &'gcx TimingControl) {}
+
+    fn visit_assign(&mut self, assign: &'gcx Assign) {
+        self.visit_node_with_id(assign.lhs, true);
+        self.visit_node_with_id(assign.rhs, false);
+    }
+}
+
+impl<'a, 'gcx: 'a, C> mir::Visitor<'gcx> for DriverCollector<'a, 'gcx, C>
+where
+    C: Context<'gcx>,
+{
+    fn pre_visit_rvalue(&mut self, mir: &'gcx mir::Rvalue<'gcx>) -> bool {
+        if let mir::RvalueKind::Assignment { lvalue, .. } = mir.kind {
+            self.add_driver(lvalue);
+        }
+        true
+    }
+}
+
+impl<'a, 'gcx: 'a, C> DriverCollector<'a, 'gcx, C>
+where
+    C: Context<'gcx>,
+{
+    /// Add an lvalue, or the parts of it, that drive the signal.
+    fn add_driver(&mut self, lv: &'gcx mir::Lvalue<'gcx>) {
+        let signal_id = self.signal_id;
+        let drivers = &mut self.drivers;
+        lv.for_each_part(&mut |part| {
+            if part.root_id() == Some(signal_id) {
+                drivers.push(part);
+            }
+        });
+    }
+}
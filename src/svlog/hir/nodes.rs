@@ -29,6 +29,7 @@ pub enum HirNode<'a> {
     EventExpr(&'a EventExpr),
     Gen(&'a Gen),
     GenvarDecl(&'a GenvarDecl),
+    ForeachIndex(&'a ForeachIndex),
     Typedef(&'a Typedef),
     Assign(&'a Assign),
     Package(&'a Package),
@@ -55,6 +56,7 @@ impl<'hir> HasSpan for HirNode<'hir> {
             HirNode::EventExpr(x) => x.span(),
             HirNode::Gen(x) => x.span(),
             HirNode::GenvarDecl(x) => x.span(),
+            HirNode::ForeachIndex(x) => x.span(),
             HirNode::Typedef(x) => x.span(),
             HirNode::Assign(x) => x.span(),
             HirNode::Package(x) => x.span(),
@@ -81,6 +83,7 @@ impl<'hir> HasSpan for HirNode<'hir> {
             HirNode::EventExpr(x) => x.human_span(),
             HirNode::Gen(x) => x.human_span(),
             HirNode::GenvarDecl(x) => x.human_span(),
+            HirNode::ForeachIndex(x) => x.human_span(),
             HirNode::Typedef(x) => x.human_span(),
             HirNode::Assign(x) => x.human_span(),
             HirNode::Package(x) => x.human_span(),
@@ -109,6 +112,7 @@ impl<'hir> HasDesc for HirNode<'hir> {
             HirNode::EventExpr(x) => x.desc(),
             HirNode::Gen(x) => x.desc(),
             HirNode::GenvarDecl(x) => x.desc(),
+            HirNode::ForeachIndex(x) => x.desc(),
             HirNode::Typedef(x) => x.desc(),
             HirNode::Assign(x) => x.desc(),
             HirNode::Package(x) => x.desc(),
@@ -135,6 +139,7 @@ impl<'hir> HasDesc for HirNode<'hir> {
             HirNode::EventExpr(x) => x.desc_full(),
             HirNode::Gen(x) => x.desc_full(),
             HirNode::GenvarDecl(x) => x.desc_full(),
+            HirNode::ForeachIndex(x) => x.desc_full(),
             HirNode::Typedef(x) => x.desc_full(),
             HirNode::Assign(x) => x.desc_full(),
             HirNode::Package(x) => x.desc_full(),
@@ -516,6 +521,8 @@ pub enum TypeKind {
     PackedArray(Box<TypeKind>, NodeId, NodeId),
     /// A scope access such as `foo::bar`.
     Scope(NodeId, Spanned<Name>),
+    /// A compilation unit scope access such as `$unit::bar`.
+    UnitScope(Spanned<Name>),
     /// An enum type.
     ///
     /// Each element in the vector refers to a `EnumVariant`. The optional field
@@ -602,6 +609,7 @@ impl HasDesc for Expr<'_> {
         match self.kind {
             ExprKind::IntConst { .. } => "integer constant",
             ExprKind::TimeConst(_) => "time constant",
+            ExprKind::RealConst(_) => "real constant",
             ExprKind::Ident(_) => "identifier",
             _ => "expression",
         }
@@ -611,7 +619,9 @@ impl HasDesc for Expr<'_> {
         #[allow(unreachable_patterns)]
         match self.kind {
             ExprKind::IntConst { value: ref k, .. } => format!("{} `{}`", self.desc(), k),
-            ExprKind::TimeConst(ref k) => format!("{} `{}`", self.desc(), k),
+            ExprKind::TimeConst(ref k) | ExprKind::RealConst(ref k) => {
+                format!("{} `{}`", self.desc(), k)
+            }
             ExprKind::Ident(n) => format!("`{}`", n.value),
             ExprKind::PositionalPattern(..) => format!("positional pattern"),
             ExprKind::NamedPattern(..) => format!("named pattern"),
@@ -639,8 +649,12 @@ pub enum ExprKind<'a> {
     UnsizedConst(char),
     /// A time constant literal.
     TimeConst(BigRational),
+    /// A real constant literal, such as `2.5`.
+    RealConst(BigRational),
     /// A string constant literal.
     StringConst(Spanned<Name>),
+    /// The unbounded value `$`, as in `parameter int X = $` or `[$:X]`.
+    Unbounded,
     /// An identifier.
     Ident(Spanned<Name>),
     /// A unary operator.
@@ -657,6 +671,8 @@ pub enum ExprKind<'a> {
     Ternary(NodeId, NodeId, NodeId),
     /// A scope expression such as `foo::bar`.
     Scope(NodeId, Spanned<Name>),
+    /// A compilation unit scope expression such as `$unit::bar`.
+    UnitScope(Spanned<Name>),
     /// A positional pattern such as `'{a, b, c}`.
     PositionalPattern(Vec<NodeId>),
     /// A named pattern such as `'{logic: a, foo: b, 31: c, default: d}`.
@@ -665,6 +681,11 @@ pub enum ExprKind<'a> {
     RepeatPattern(NodeId, Vec<NodeId>),
     /// A concatenation such as `{a,b}` or `{4{a,b}}`.
     Concat(Option<NodeId>, Vec<NodeId>),
+    /// A streaming concatenation `(dir, slice, exprs)` such as `{<< 8 {a, b}}`.
+    ///
+    /// The slice is either an expression or a type that determines the size
+    /// of the blocks that are streamed.
+    StreamConcat(ast::StreamDir, Option<NodeId>, Vec<NodeId>),
     /// A cast `(ty, expr)` such as `foo'(bar)`.
     Cast(NodeId, NodeId),
     /// A sign cast such as `unsigned'(foo)`.
@@ -675,6 +696,8 @@ pub enum ExprKind<'a> {
     Inside(NodeId, Vec<Spanned<InsideRange>>),
     /// A function call such as `foo(a, b, c)`.
     FunctionCall(NodeId, Vec<CallArg>),
+    /// A method call through an interface such as `intf.foo(a, b, c)`.
+    MethodCall(NodeId, Spanned<Name>, Vec<CallArg>),
     /// An assignment.
     Assign {
         op: ast::AssignOp,
@@ -844,15 +867,48 @@ pub enum BuiltinCall<'a> {
     /// A call to the convert-to-unsigned function `$unsigned(x)`.
     Unsigned(NodeId),
     /// A call to the `$countones(x)` function.
-    CountOnes(&'a ast::Expr<'a>),
+    CountOnes(NodeId),
+    /// A call to the `$countbits(x, control_bits...)` function.
+    CountBits(NodeId, &'a [ast::CallArg<'a>]),
     /// A call to the `$onehot(x)` function.
-    OneHot(&'a ast::Expr<'a>),
+    OneHot(NodeId),
     /// A call to the `$onehot0(x)` function.
-    OneHot0(&'a ast::Expr<'a>),
+    OneHot0(NodeId),
     /// A call to the `$isunknown(x)` function.
     IsUnknown(&'a ast::Expr<'a>),
+    /// A call to the `$isunbounded(x)` function.
+    IsUnbounded(NodeId),
     /// A call to one of the array dimension functions.
-    ArrayDim(ArrayDim, &'a ast::Expr<'a>, Option<&'a ast::Expr<'a>>),
+    ArrayDim(ArrayDim, &'a ast::TypeOrExpr<'a>, Option<&'a ast::Expr<'a>>),
+    /// A call to the string formatting function `$sformatf(fmt, args...)`.
+    Sformatf(&'a [ast::CallArg<'a>]),
+    /// A call to one of the display tasks, such as `$display(args...)`.
+    Display(DisplayTask, &'a [ast::CallArg<'a>]),
+    /// A call to one of the simulation control tasks, such as `$finish(n)`.
+    Finish(FinishTask, &'a [ast::CallArg<'a>]),
+    /// A call to the `$random(seed)` function.
+    Random(Option<NodeId>),
+    /// A call to the `$urandom(seed)` function.
+    Urandom(Option<NodeId>),
+    /// A call to the `$urandom_range(max, min)` function.
+    UrandomRange(NodeId, Option<NodeId>),
+    /// A call to `$readmemh` or `$readmemb`, which load the hexadecimal or
+    /// binary numbers in a file into a memory.
+    ReadMem {
+        radix: u32,
+        file: NodeId,
+        mem: NodeId,
+        start: Option<NodeId>,
+        end: Option<NodeId>,
+    },
+    /// A call to the `$fopen(name, mode)` function.
+    FileOpen(NodeId, Option<NodeId>),
+    /// A call to the `$fclose(fd)` task.
+    FileClose(NodeId),
+    /// A call to the `$dumpfile(name)` task.
+    DumpFile(Option<NodeId>),
+    /// A call to the `$dumpvars(levels, scopes...)` task.
+    DumpVars(&'a [ast::CallArg<'a>]),
 }
 
 /// The different builtin array dimension function calls that are supported.
@@ -872,6 +928,33 @@ pub enum ArrayDim {
     Size,
 }
 
+/// The different builtin display tasks that are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayTask {
+    /// The `$display` task, which terminates the output with a newline.
+    Display,
+    /// The `$write` task, which does not add a newline.
+    Write,
+    /// The `$monitor` task, which prints whenever one of its arguments changes.
+    Monitor,
+    /// The `$fdisplay` task, which is `$display` into a file.
+    FileDisplay,
+    /// The `$fwrite` task, which is `$write` into a file.
+    FileWrite,
+}
+
+/// The different builtin simulation control tasks that are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinishTask {
+    /// The `$finish` task, which ends the simulation.
+    Finish,
+    /// The `$stop` task, which suspends the simulation.
+    Stop,
+    /// The `$fatal` task, which prints a message and ends the simulation with
+    /// a failure.
+    Fatal,
+}
+
 /// A variable or net declaration.
 #[derive(Debug, PartialEq, Eq)]
 pub struct VarDecl {
@@ -884,6 +967,14 @@ pub struct VarDecl {
     pub init: Option<NodeId>,
     /// Variable or net-specific data
     pub kind: ast::VarKind,
+    /// The drive strength of a net declaration.
+    pub drive: Option<DriveStrength>,
+    /// The charge strength of a `trireg` net declaration.
+    pub charge: Option<ast::ChargeStrength>,
+    /// Whether the variable is allocated per activation or once.
+    pub lifetime: ast::Lifetime,
+    /// The delay of a net declaration, such as `wire #5 w`.
+    pub delay: Option<Delay3>,
 }
 
 impl HasSpan for VarDecl {
@@ -1016,7 +1107,53 @@ pub enum StmtKind {
         ways: Vec<(Vec<NodeId>, NodeId)>,
         default: Option<NodeId>,
         kind: ast::CaseKind,
+        /// The optional `unique`, `unique0`, or `priority` qualifier.
+        up: Option<ast::UniquePriority>,
+    },
+    /// A set membership case statement.
+    ///
+    /// ```text
+    /// case (<expr>) inside <ranges>: <stmt> ... endcase
+    /// ```
+    CaseInside {
+        expr: NodeId,
+        ways: Vec<(Vec<Spanned<InsideRange>>, NodeId)>,
+        default: Option<NodeId>,
     },
+    /// A foreach loop.
+    ///
+    /// ```text
+    /// foreach (<array>[<indices>]) <body>
+    /// ```
+    Foreach {
+        array: NodeId,
+        indices: Vec<NodeId>,
+        body: NodeId,
+    },
+    /// A `break` statement, which exits the innermost enclosing loop.
+    Break,
+    /// A `continue` statement, which advances the innermost enclosing loop to
+    /// its next iteration.
+    Continue,
+    /// A `disable` statement, which terminates the execution of an enclosing
+    /// labeled statement.
+    Disable(NodeId),
+    /// A `deassign` or `release` statement, which ends a procedural continuous
+    /// assignment to the target lvalue.
+    Release { target: NodeId, kind: OverrideKind },
+    /// A level-sensitive wait statement, which suspends execution until the
+    /// condition becomes true.
+    ///
+    /// ```text
+    /// wait (<cond>) <stmt>
+    /// ```
+    Wait { cond: NodeId, stmt: NodeId },
+    /// An event trigger statement, which triggers the named event.
+    ///
+    /// ```text
+    /// -> <target>;
+    /// ```
+    Trigger(NodeId),
 }
 
 /// The different forms an assignment can take.
@@ -1028,6 +1165,36 @@ pub enum AssignKind {
     Nonblock,
     /// A non-blocking assignment with delay.
     NonblockDelay(NodeId),
+    /// A procedural continuous assignment, which overrides all other
+    /// assignments to the target until it is released.
+    Override(OverrideKind),
+}
+
+/// The different forms of procedural continuous assignments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverrideKind {
+    /// An `assign` and `deassign` pair, which applies to variables.
+    Assign,
+    /// A `force` and `release` pair, which applies to variables and nets.
+    Force,
+}
+
+impl OverrideKind {
+    /// The keyword that establishes the override.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            OverrideKind::Assign => "assign",
+            OverrideKind::Force => "force",
+        }
+    }
+
+    /// The keyword that ends the override.
+    pub fn release_keyword(self) -> &'static str {
+        match self {
+            OverrideKind::Assign => "deassign",
+            OverrideKind::Force => "release",
+        }
+    }
 }
 
 /// The different forms a loop can take.
@@ -1054,6 +1221,9 @@ pub enum TimingControl {
     ImplicitEvent,
     /// A statement triggered by an explicit event expression.
     ExplicitEvent(NodeId),
+    /// A statement delayed by a number of cycles of the default clocking
+    /// block. Contains the number of cycles and the clocking event.
+    Cycle { count: NodeId, event: NodeId },
 }
 
 /// An event expression.
@@ -1123,6 +1293,12 @@ pub enum GenKind {
         step: NodeId,
         body: ModuleBlock,
     },
+    /// A case-generate statement.
+    Case {
+        expr: NodeId,
+        ways: Vec<(Vec<NodeId>, ModuleBlock)>,
+        default: Option<ModuleBlock>,
+    },
 }
 
 /// A genvar declaration.
@@ -1154,6 +1330,38 @@ impl HasDesc for GenvarDecl {
     }
 }
 
+/// A foreach-loop index variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeachIndex {
+    pub id: NodeId,
+    pub name: Spanned<Name>,
+    pub span: Span,
+    /// The array being iterated over.
+    pub array: NodeId,
+    /// The dimension of the array this index iterates over.
+    pub index: usize,
+}
+
+impl HasSpan for ForeachIndex {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn human_span(&self) -> Span {
+        self.name.span
+    }
+}
+
+impl HasDesc for ForeachIndex {
+    fn desc(&self) -> &'static str {
+        "index variable"
+    }
+
+    fn desc_full(&self) -> String {
+        format!("index variable `{}`", self.name.value)
+    }
+}
+
 /// A typedef.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Typedef {
@@ -1190,6 +1398,58 @@ pub struct Assign {
     pub span: Span,
     pub lhs: NodeId,
     pub rhs: NodeId,
+    /// The delay after which the assigned value is updated.
+    pub delay: Option<Delay3>,
+    /// The strength with which the assignment drives its target.
+    pub drive: Option<DriveStrength>,
+}
+
+/// The strengths with which a net or continuous assignment drives 0 and 1,
+/// such as `(weak0, strong1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriveStrength {
+    /// The strength with which a 0 is driven.
+    pub zero: ast::DriveStrength,
+    /// The strength with which a 1 is driven.
+    pub one: ast::DriveStrength,
+}
+
+impl DriveStrength {
+    /// The default `(strong0, strong1)` drive strength.
+    pub const STRONG: DriveStrength = DriveStrength {
+        zero: ast::DriveStrength::Strong0,
+        one: ast::DriveStrength::Strong1,
+    };
+
+    /// Create a drive strength from a pair of strengths given in any order.
+    ///
+    /// Returns `None` if the pair does not consist of one strength for 0 and
+    /// one for 1, or if both are `highz`.
+    pub fn new(a: ast::DriveStrength, b: ast::DriveStrength) -> Option<Self> {
+        let (zero, one) = if a.is_one() { (b, a) } else { (a, b) };
+        if zero.is_one() || !one.is_one() || (zero.level() == 0 && one.level() == 0) {
+            return None;
+        }
+        Some(DriveStrength { zero, one })
+    }
+
+    /// Get the strength with which a bit value is driven.
+    pub fn of_bit(&self, bit: bool) -> ast::DriveStrength {
+        if bit {
+            self.one
+        } else {
+            self.zero
+        }
+    }
+}
+
+/// A delay with separate values for rising, falling, and turn-off
+/// transitions, such as `#(1,2,3)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delay3 {
+    pub rise: NodeId,
+    pub fall: Option<NodeId>,
+    pub turn_off: Option<NodeId>,
 }
 
 impl HasSpan for Assign {
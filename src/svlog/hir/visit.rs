@@ -151,7 +151,7 @@ pub fn walk_proc<'a>(visitor: &mut impl Visitor<'a>, prok: &'a Proc) {
 pub fn walk_stmt<'a>(visitor: &mut impl Visitor<'a>, stmt: &'a Stmt) {
     #[allow(unreachable_patterns)]
     match stmt.kind {
-        StmtKind::Null => (),
+        StmtKind::Null | StmtKind::Break | StmtKind::Continue | StmtKind::Disable(_) => (),
         StmtKind::Block(ref stmts) => {
             for &id in stmts {
                 visitor.visit_node_with_id(id, false);
@@ -161,6 +161,16 @@ pub fn walk_stmt<'a>(visitor: &mut impl Visitor<'a>, stmt: &'a Stmt) {
             visitor.visit_node_with_id(lhs, true);
             visitor.visit_node_with_id(rhs, false);
         }
+        StmtKind::Release { target, .. } => {
+            visitor.visit_node_with_id(target, false);
+        }
+        StmtKind::Trigger(target) => {
+            visitor.visit_node_with_id(target, true);
+        }
+        StmtKind::Wait { cond, stmt } => {
+            visitor.visit_node_with_id(cond, false);
+            visitor.visit_node_with_id(stmt, false);
+        }
         StmtKind::Timed { ref control, stmt } => {
             visitor.visit_timing_control(control);
             visitor.visit_node_with_id(stmt, false);
@@ -213,6 +223,32 @@ pub fn walk_stmt<'a>(visitor: &mut impl Visitor<'a>, stmt: &'a Stmt) {
                 visitor.visit_node_with_id(default, false);
             }
         }
+        StmtKind::CaseInside {
+            expr,
+            ref ways,
+            default,
+        } => {
+            visitor.visit_node_with_id(expr, false);
+            for &(ref ranges, stmt) in ways {
+                for range in ranges {
+                    match range.value {
+                        InsideRange::Single(expr) => visitor.visit_node_with_id(expr, false),
+                        InsideRange::Range(lo, hi) => {
+                            visitor.visit_node_with_id(lo, false);
+                            visitor.visit_node_with_id(hi, false);
+                        }
+                    }
+                }
+                visitor.visit_node_with_id(stmt, false);
+            }
+            if let Some(default) = default {
+                visitor.visit_node_with_id(default, false);
+            }
+        }
+        StmtKind::Foreach { array, body, .. } => {
+            visitor.visit_node_with_id(array, false);
+            visitor.visit_node_with_id(body, false);
+        }
     }
 }
 
@@ -223,7 +259,10 @@ pub fn walk_expr<'a>(visitor: &mut impl Visitor<'a>, expr: &'a Expr, lvalue: boo
         | ExprKind::IntConst { .. }
         | ExprKind::UnsizedConst(_)
         | ExprKind::TimeConst(_)
-        | ExprKind::StringConst(_) => (),
+        | ExprKind::RealConst(_)
+        | ExprKind::StringConst(_)
+        | ExprKind::UnitScope(_)
+        | ExprKind::Unbounded => (),
         ExprKind::Ident(x) => {
             visitor.visit_ident(x);
         }
@@ -251,15 +290,76 @@ pub fn walk_expr<'a>(visitor: &mut impl Visitor<'a>, expr: &'a Expr, lvalue: boo
         }
         ExprKind::Builtin(BuiltinCall::Clog2(arg))
         | ExprKind::Builtin(BuiltinCall::Signed(arg))
-        | ExprKind::Builtin(BuiltinCall::Unsigned(arg)) => {
-            visitor.visit_node_with_id(arg, false);
-        }
-        ExprKind::Builtin(BuiltinCall::CountOnes(arg))
+        | ExprKind::Builtin(BuiltinCall::Unsigned(arg))
+        | ExprKind::Builtin(BuiltinCall::IsUnbounded(arg))
+        | ExprKind::Builtin(BuiltinCall::CountOnes(arg))
         | ExprKind::Builtin(BuiltinCall::OneHot(arg))
         | ExprKind::Builtin(BuiltinCall::OneHot0(arg))
-        | ExprKind::Builtin(BuiltinCall::IsUnknown(arg)) => {
+        | ExprKind::Builtin(BuiltinCall::FileClose(arg)) => {
+            visitor.visit_node_with_id(arg, false);
+        }
+        ExprKind::Builtin(BuiltinCall::Random(seed))
+        | ExprKind::Builtin(BuiltinCall::Urandom(seed))
+        | ExprKind::Builtin(BuiltinCall::DumpFile(seed)) => {
+            if let Some(seed) = seed {
+                visitor.visit_node_with_id(seed, false);
+            }
+        }
+        ExprKind::Builtin(BuiltinCall::DumpVars(args)) => {
+            // Only the number of levels is an expression. The scopes name
+            // instances and variables, which are not evaluated.
+            if let Some(levels) = args.first().and_then(|arg| arg.expr.as_ref()) {
+                visitor.visit_node_with_id(levels.id(), false);
+            }
+        }
+        ExprKind::Builtin(BuiltinCall::UrandomRange(max, min)) => {
+            visitor.visit_node_with_id(max, false);
+            if let Some(min) = min {
+                visitor.visit_node_with_id(min, false);
+            }
+        }
+        ExprKind::Builtin(BuiltinCall::ReadMem {
+            file,
+            mem,
+            start,
+            end,
+            ..
+        }) => {
+            visitor.visit_node_with_id(file, false);
+            visitor.visit_node_with_id(mem, true);
+            if let Some(start) = start {
+                visitor.visit_node_with_id(start, false);
+            }
+            if let Some(end) = end {
+                visitor.visit_node_with_id(end, false);
+            }
+        }
+        ExprKind::Builtin(BuiltinCall::FileOpen(name, mode)) => {
+            visitor.visit_node_with_id(name, false);
+            if let Some(mode) = mode {
+                visitor.visit_node_with_id(mode, false);
+            }
+        }
+        ExprKind::Builtin(BuiltinCall::IsUnknown(arg)) => {
             visitor.visit_node_with_id(arg.id(), false);
         }
+        ExprKind::Builtin(BuiltinCall::CountBits(arg, control)) => {
+            visitor.visit_node_with_id(arg, false);
+            for bit in control {
+                if let Some(ref expr) = bit.expr {
+                    visitor.visit_node_with_id(expr.id(), false);
+                }
+            }
+        }
+        ExprKind::Builtin(BuiltinCall::Sformatf(args))
+        | ExprKind::Builtin(BuiltinCall::Display(_, args))
+        | ExprKind::Builtin(BuiltinCall::Finish(_, args)) => {
+            for arg in args {
+                if let Some(ref expr) = arg.expr {
+                    visitor.visit_node_with_id(expr.id(), false);
+                }
+            }
+        }
         ExprKind::Builtin(BuiltinCall::ArrayDim(_, arg, dim)) => {
             visitor.visit_node_with_id(arg.id(), false);
             if let Some(dim) = dim {
@@ -306,6 +406,14 @@ pub fn walk_expr<'a>(visitor: &mut impl Visitor<'a>, expr: &'a Expr, lvalue: boo
                 visitor.visit_node_with_id(expr, lvalue);
             }
         }
+        ExprKind::StreamConcat(_, slice, ref exprs) => {
+            if let Some(slice) = slice {
+                visitor.visit_node_with_id(slice, false);
+            }
+            for &expr in exprs {
+                visitor.visit_node_with_id(expr, lvalue);
+            }
+        }
         ExprKind::Cast(ty, expr) => {
             visitor.visit_node_with_id(ty, false);
             visitor.visit_node_with_id(expr, false);
@@ -336,6 +444,14 @@ pub fn walk_expr<'a>(visitor: &mut impl Visitor<'a>, expr: &'a Expr, lvalue: boo
                 }
             }
         }
+        ExprKind::MethodCall(target, _, ref args) => {
+            visitor.visit_node_with_id(target, false);
+            for &arg in args {
+                if let Some(expr) = arg.expr {
+                    visitor.visit_node_with_id(expr, false);
+                }
+            }
+        }
         ExprKind::Assign { lhs, rhs, .. } => {
             visitor.visit_node_with_id(lhs.id, true);
             visitor.visit_node_with_id(rhs.id, false);
@@ -349,6 +465,10 @@ pub fn walk_timing_control<'a>(visitor: &mut impl Visitor<'a>, ctrl: &'a TimingC
         TimingControl::Delay(id) => visitor.visit_node_with_id(id, false),
         TimingControl::ImplicitEvent => (),
         TimingControl::ExplicitEvent(id) => visitor.visit_node_with_id(id, false),
+        TimingControl::Cycle { count, event } => {
+            visitor.visit_node_with_id(count, false);
+            visitor.visit_node_with_id(event, false);
+        }
     }
 }
 
@@ -378,12 +498,26 @@ pub fn walk_var_decl<'a>(visitor: &mut impl Visitor<'a>, decl: &'a VarDecl) {
     if let Some(init) = decl.init {
         visitor.visit_node_with_id(init, false);
     }
+    if let Some(delay) = decl.delay {
+        walk_delay3(visitor, &delay);
+    }
 }
 
 /// Walk the contents of an assignment.
 pub fn walk_assign<'a>(visitor: &mut impl Visitor<'a>, assign: &'a Assign) {
     visitor.visit_node_with_id(assign.lhs, true);
     visitor.visit_node_with_id(assign.rhs, false);
+    if let Some(delay) = assign.delay {
+        walk_delay3(visitor, &delay);
+    }
+}
+
+/// Walk the contents of a rise, fall, and turn-off delay.
+pub fn walk_delay3<'a>(visitor: &mut impl Visitor<'a>, delay: &Delay3) {
+    visitor.visit_node_with_id(delay.rise, false);
+    for &id in delay.fall.iter().chain(delay.turn_off.iter()) {
+        visitor.visit_node_with_id(id, false);
+    }
 }
 
 /// Walk the contents of an internal port.
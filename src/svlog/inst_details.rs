@@ -4,12 +4,17 @@
 
 #[warn(missing_docs)]
 use crate::{
+    ast_map::AstNode,
+    codegen,
     crate_prelude::*,
     hir::{self, HirNode},
-    port_list::AsPortedNode,
-    resolver::InstTarget,
+    port_list::{AsPortedNode, IntPort},
+    resolver::{HierarchicalKind, HierarchicalTarget, InstTarget, ScopedNode},
+    ty::{IntAtomType, PackedCore, RealType, UnpackedCore, UnpackedType},
+    value::Value,
     Context, ParamEnv, ParamEnvData, ParamEnvSource, PortMapping,
 };
+use num::BigInt;
 use std::sync::Arc;
 
 /// Instantiation details
@@ -67,13 +72,31 @@ pub(crate) fn inst_details<'a>(
     // Determine the details of the instantiation target.
     let target = cx.inst_target_details(Ref(inst_target), env)?;
 
+    // Apply the defparams that override parameters of this particular
+    // instance. These take precedence over the parameter assignments of the
+    // instantiation (see §23.10).
+    let defparams = match target.kind {
+        InstTarget::Module(module) => defparams_into_inst(cx, inst, module, env),
+        InstTarget::Interface(..) => vec![],
+    };
+    let param_env = if !defparams.is_empty() {
+        let mut params = target.params.clone();
+        for (param_id, value) in defparams {
+            params.set_indirect_value(param_id, value);
+        }
+        trace!("Overrode parametrization with defparams: {:?}", params);
+        cx.intern_param_env(params)
+    } else {
+        target.inner_env
+    };
+
     // Determine the port connections of the instantiations. Connections
     // are made to the module's external ports, and must later be mapped
     // to the actual internal ports in a second step.
     let port_mapping = cx.port_mapping(
         target.kind.as_any().as_all().get_ported().unwrap(),
         target.outer_env,
-        target.inner_env,
+        param_env,
         Ref(inst),
         &inst.pos_ports,
         &inst.named_ports,
@@ -98,7 +121,7 @@ pub(crate) fn inst_details<'a>(
         };
 
         // Check if the port is actually an interface.
-        let ty = cx.packed_type_from_ast(Ref(data.ty), target.inner_env, None);
+        let ty = cx.packed_type_from_ast(Ref(data.ty), param_env, None);
         if ty.get_interface().is_none() {
             continue;
         }
@@ -113,18 +136,26 @@ pub(crate) fn inst_details<'a>(
         intf_params.push((int_port.id, assigned));
     }
 
+    // Mark the bind directives that insert an instance into this particular
+    // instance, rather than into every instance of the module.
+    let binds = match target.kind {
+        InstTarget::Module(module) => binds_into_inst(cx, inst, module, env),
+        InstTarget::Interface(..) => vec![],
+    };
+
     // If we have found any additional parametrization, create an extended
     // parameter environment for this instance.
-    let inner_env = if !intf_params.is_empty() {
-        let mut params = target.params.clone();
+    let inner_env = if !intf_params.is_empty() || !binds.is_empty() {
+        let mut params = cx.param_env_data(param_env).clone();
         params.add_interfaces(intf_params);
+        params.add_binds(binds);
         trace!(
-            "Extended parametrization with implicit interface parameters: {:?}",
+            "Extended parametrization with implicit interface parameters and binds: {:?}",
             params
         );
         cx.intern_param_env(params)
     } else {
-        target.inner_env
+        param_env
     };
 
     // Wrap everything up.
@@ -136,6 +167,130 @@ pub(crate) fn inst_details<'a>(
     }))
 }
 
+/// Find the bind directives that name an instance by its hierarchical path.
+fn binds_into_inst<'a>(
+    cx: &impl Context<'a>,
+    inst: &'a hir::Inst<'a>,
+    module: &'a ast::Module<'a>,
+    env: ParamEnv,
+) -> Vec<NodeId> {
+    resolver::binds_into(cx, module)
+        .into_iter()
+        .filter(|&bind| match cx.bind_target(bind) {
+            Ok(target) => target
+                .instances
+                .iter()
+                .any(|path| is_instance_path_of(cx, path, inst, env)),
+            Err(()) => false,
+        })
+        .map(|bind| bind.id())
+        .collect()
+}
+
+/// Find the defparams that override a parameter of an instance.
+///
+/// Returns the overridden parameters and the values assigned to them. If a
+/// parameter is overridden multiple times, the last defparam in source order
+/// takes effect.
+fn defparams_into_inst<'a>(
+    cx: &impl Context<'a>,
+    inst: &'a hir::Inst<'a>,
+    module: &'a ast::Module<'a>,
+    env: ParamEnv,
+) -> Vec<(NodeId, NodeEnvId)> {
+    let mut overrides: Vec<(&ast::Expr, NodeId, NodeEnvId)> = vec![];
+    for (path, target, value) in resolver::defparams_into(cx, module) {
+        if !is_instance_path_of(cx, target.instance, inst, env) {
+            continue;
+        }
+        let param_id = target.param.id();
+        if let Some(prev) = overrides.iter().position(|&(_, id, _)| id == param_id) {
+            let (prev_path, ..) = overrides.remove(prev);
+            cx.emit(
+                DiagBuilder2::warning(format!(
+                    "parameter `{}` of instance `{}` overridden by multiple defparams",
+                    target.param.name, inst.name
+                ))
+                .span(path.span)
+                .add_note("The last defparam takes effect; the previous one was here:")
+                .span(prev_path.span),
+            );
+        }
+        // Values of defparams naming a local instance are evaluated in the
+        // same environment as the instance. Longer paths are resolved from the
+        // top of the hierarchy.
+        let value_env = match target.instance.data {
+            ast::IdentExpr(..) => env,
+            _ => cx.default_param_env(),
+        };
+        let value_id = cx.map_ast_with_parent(AstNode::Expr(value), param_id);
+        overrides.push((path, param_id, value_id.env(value_env)));
+    }
+    overrides
+        .into_iter()
+        .map(|(_, param_id, value)| (param_id, value))
+        .collect()
+}
+
+/// Check whether an instance path of a bind directive or defparam names an
+/// instance in a parameter environment.
+fn is_instance_path_of<'a>(
+    cx: &impl Context<'a>,
+    path: &'a ast::Expr<'a>,
+    inst: &'a hir::Inst<'a>,
+    env: ParamEnv,
+) -> bool {
+    let def = match path.data {
+        // A plain name refers to an instance in the scope of the directive.
+        ast::IdentExpr(name) if name.value == inst.name.value => cx
+            .resolve_local(name.value, cx.scope_location(path), false)
+            .ok()
+            .flatten(),
+        // A path names the instance in the scope its prefix resolves to.
+        ast::MemberExpr { ref expr, name } if name.value == inst.name.value => {
+            match cx.resolve_hierarchical_name(expr.id(), cx.default_param_env()) {
+                Ok(Some(HierarchicalTarget {
+                    kind: HierarchicalKind::Scope(scope),
+                    env: scope_env,
+                    ..
+                })) if scope_env == env => resolver::find_hierarchical_def(cx, name.value, scope),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    match def {
+        Some(def) => def.node.id() == inst.ast.id(),
+        None => false,
+    }
+}
+
+/// Check whether an instance in a module block is elaborated in a parameter
+/// environment.
+///
+/// Instances inserted by a bind directive that lists instance paths only exist
+/// in the instances the directive names.
+pub(crate) fn is_inst_elaborated<'a>(
+    cx: &impl Context<'a>,
+    inst: &'a hir::Inst<'a>,
+    env: ParamEnv,
+) -> bool {
+    let bind = match inst
+        .ast
+        .inst()
+        .get_parent()
+        .and_then(|p| p.as_all().get_bind())
+    {
+        Some(x) => x,
+        None => return true,
+    };
+    match cx.bind_target(bind) {
+        Ok(target) if target.instances.is_empty() => true,
+        Ok(_) => cx.param_env_data(env).has_bind(bind.id()),
+        Err(()) => false,
+    }
+}
+
 /// Compute the details of an instantiated module or interface.
 #[moore_derive::query]
 pub(crate) fn inst_target_details<'a>(
@@ -214,3 +369,641 @@ impl<'a, 'gcx> hir::Visitor<'gcx> for InstVerbosityVisitor<'a, 'gcx> {
         .visit_node_with_id(details.target.kind.as_any().id(), false);
     }
 }
+
+/// An elaborated instance in the design hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElabInstance<'a> {
+    /// The hierarchical path of the instance, such as `top.gen[2].u0`.
+    pub path: String,
+    /// The HIR instantiation.
+    pub inst: NodeId,
+    /// The name of the instantiated module or interface.
+    pub target: Name,
+    /// The parameter environment of the instantiated module or interface.
+    pub env: ParamEnv,
+    /// The values of the non-local value parameters of an instantiated module.
+    pub params: Vec<(Name, Value<'a>)>,
+    /// The ports of the instantiated module or interface.
+    pub ports: Vec<ElabPort<'a>>,
+}
+
+/// A port of an elaborated instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElabPort<'a> {
+    /// The name of the port.
+    pub name: Name,
+    /// The direction of the port.
+    pub dir: ast::PortDir,
+    /// The type of the port within the instance's parameter environment.
+    pub ty: &'a UnpackedType<'a>,
+}
+
+/// List all instances elaborated underneath a top-level module.
+///
+/// This follows the instantiations and generate blocks of the design the same
+/// way code generation does. The result is sorted by hierarchical path, with
+/// generate loop indices ordered numerically.
+#[moore_derive::query]
+pub(crate) fn instances<'a>(
+    cx: &impl Context<'a>,
+    top: NodeId,
+) -> Result<Arc<Vec<ElabInstance<'a>>>> {
+    let module = match cx.hir_of(top)? {
+        HirNode::Module(x) => x,
+        x => bug_span!(cx.span(top), cx, "instances of non-module {:?}", x),
+    };
+    let mut out = vec![];
+    collect_instances(
+        cx,
+        &module.block,
+        cx.default_param_env(),
+        &module.name.value.to_string(),
+        &mut out,
+    )?;
+    out.sort_by(|a, b| path_sort_key(&a.path).cmp(&path_sort_key(&b.path)));
+    Ok(Arc::new(out))
+}
+
+/// A part of a hierarchical path, as used for sorting.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PathPart<'a> {
+    Number(BigInt),
+    Text(&'a str),
+}
+
+/// Split a hierarchical path into text and numbers.
+///
+/// This allows paths such as `top.g[10]` to sort after `top.g[9]`.
+fn path_sort_key(path: &str) -> Vec<PathPart> {
+    let mut parts = vec![];
+    let mut rest = path;
+    while let Some(c) = rest.chars().next() {
+        let is_digit = c.is_ascii_digit();
+        let len = rest
+            .find(|c: char| c.is_ascii_digit() != is_digit)
+            .unwrap_or(rest.len());
+        let (part, tail) = rest.split_at(len);
+        parts.push(if is_digit {
+            PathPart::Number(part.parse().unwrap())
+        } else {
+            PathPart::Text(part)
+        });
+        rest = tail;
+    }
+    parts
+}
+
+/// Collect the instances within a module block.
+fn collect_instances<'a>(
+    cx: &impl Context<'a>,
+    block: &hir::ModuleBlock,
+    env: ParamEnv,
+    prefix: &str,
+    out: &mut Vec<ElabInstance<'a>>,
+) -> Result<()> {
+    for &inst_id in &block.insts {
+        let inst = match cx.hir_of(inst_id)? {
+            HirNode::Inst(x) => x,
+            _ => unreachable!(),
+        };
+        if !is_inst_elaborated(cx, inst, env) {
+            continue;
+        }
+        let details = cx.inst_details(Ref(inst), env)?;
+        let inner_env = details.inner_env;
+        let path = format!("{}.{}", prefix, inst.name);
+        let (target, inner_block, params, ports) = match details.target.kind {
+            InstTarget::Module(x) => {
+                let module = cx.hir_of_module(x)?;
+                (
+                    x.name.value,
+                    &module.block,
+                    module_params(cx, module, inner_env)?,
+                    elab_ports(cx, &module.ports_new.int, inner_env),
+                )
+            }
+            InstTarget::Interface(x) => {
+                let intf = cx.hir_of_interface(x)?;
+                (
+                    x.name.value,
+                    &intf.block,
+                    vec![],
+                    elab_ports(cx, &intf.ports.int, inner_env),
+                )
+            }
+        };
+        collect_instances(cx, inner_block, inner_env, &path, out)?;
+        out.push(ElabInstance {
+            path,
+            inst: inst_id,
+            target,
+            env: inner_env,
+            params,
+            ports,
+        });
+    }
+
+    for (path, body, env) in generate_bodies(cx, block, env, prefix)? {
+        collect_instances(cx, body, env, &path, out)?;
+    }
+    Ok(())
+}
+
+/// Elaborate the generate constructs within a module block.
+///
+/// Returns the hierarchical path, body, and parameter environment of every
+/// generate block selected by a condition or produced by a loop iteration.
+fn generate_bodies<'a>(
+    cx: &impl Context<'a>,
+    block: &hir::ModuleBlock,
+    env: ParamEnv,
+    prefix: &str,
+) -> Result<Vec<(String, &'a hir::ModuleBlock, ParamEnv)>> {
+    let mut out = vec![];
+    for (index, &gen_id) in block.gens.iter().enumerate() {
+        let gen = match cx.hir_of(gen_id)? {
+            HirNode::Gen(x) => x,
+            _ => unreachable!(),
+        };
+        // Unlabeled generate blocks are named `genblk<n>` after their position
+        // in the enclosing scope.
+        let label = |label: Option<Spanned<Name>>| match label {
+            Some(x) => x.value.to_string(),
+            None => format!("genblk{}", index + 1),
+        };
+        match (&gen.kind, cx.ast_of(gen_id)?) {
+            (
+                hir::GenKind::If {
+                    cond,
+                    main_body,
+                    else_body,
+                },
+                AstNode::GenIf(ast),
+            ) => {
+                if !cx.constant_value_of(*cond, env).is_false() {
+                    let path = child_path(prefix, &label(ast.main_block.label));
+                    out.push((path, main_body, env));
+                } else if let (Some(body), Some(ast_body)) = (else_body, &ast.else_block) {
+                    let path = child_path(prefix, &label(ast_body.label));
+                    out.push((path, body, env));
+                }
+            }
+            (hir::GenKind::For { body, .. }, AstNode::GenFor(ast)) => {
+                let label = label(ast.block.label);
+                for (value, local_env) in generate_loop_iterations(cx, gen, env)? {
+                    let path = child_path(prefix, &format!("{}[{}]", label, value.kind));
+                    out.push((path, body, local_env));
+                }
+            }
+            (
+                hir::GenKind::Case {
+                    expr,
+                    ways,
+                    default,
+                },
+                AstNode::GenCase(ast),
+            ) => {
+                let selected = match codegen::select_generate_case(cx, *expr, ways, env) {
+                    Some(index) => ast
+                        .items
+                        .iter()
+                        .filter_map(|item| match item {
+                            ast::GenerateCaseItem::Expr(_, block) => Some(block),
+                            _ => None,
+                        })
+                        .nth(index)
+                        .map(|block| (&ways[index].1, block)),
+                    None => default.as_ref().and_then(|body| {
+                        ast.items.iter().find_map(|item| match item {
+                            ast::GenerateCaseItem::Default(block) => Some((body, block)),
+                            _ => None,
+                        })
+                    }),
+                };
+                if let Some((body, block)) = selected {
+                    let path = child_path(prefix, &label(block.label));
+                    out.push((path, body, env));
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    Ok(out)
+}
+
+/// Append a name to a hierarchical path, which may be empty.
+fn child_path(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// A variable selected for dumping into a VCD file by `$dumpvars`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpVar {
+    /// The hierarchical path of the variable, relative to the instance that
+    /// calls `$dumpvars`.
+    pub path: String,
+    /// The VCD variable type, such as `wire`, `reg`, or `integer`.
+    pub kind: &'static str,
+    /// The width of the variable in bits.
+    pub width: usize,
+}
+
+/// Collect the variables within a hierarchical scope for `$dumpvars`.
+///
+/// The levels count the instances to descend into, where zero selects all
+/// levels and one only the variables of the scope itself. Generate blocks
+/// belong to the level of the instance that contains them.
+pub(crate) fn collect_dump_vars<'a>(
+    cx: &impl Context<'a>,
+    scope: &'a dyn ScopedNode<'a>,
+    env: ParamEnv,
+    prefix: &str,
+    levels: u32,
+    out: &mut Vec<DumpVar>,
+) -> Result<()> {
+    let (ports, block) = match scope.as_all() {
+        ast::AllNode::Module(x) => {
+            let module = cx.hir_of_module(x)?;
+            (&module.ports_new.int, &module.block)
+        }
+        ast::AllNode::Interface(x) => {
+            let intf = cx.hir_of_interface(x)?;
+            (&intf.ports.int, &intf.block)
+        }
+        ast::AllNode::GenerateBlock(x) => {
+            return collect_dump_block(cx, generate_block_body(cx, x)?, env, prefix, levels, out)
+        }
+        _ => bug_span!(scope.span(), cx, "dump variables of {}", scope),
+    };
+    for port in ports {
+        let path = child_path(prefix, &port.name.value.to_string());
+        push_dump_var(out, path, port.kind, cx.type_of_int_port(Ref(port), env));
+    }
+    collect_dump_block(cx, block, env, prefix, levels, out)
+}
+
+/// Collect the variables within a module block for `$dumpvars`.
+fn collect_dump_block<'a>(
+    cx: &impl Context<'a>,
+    block: &hir::ModuleBlock,
+    env: ParamEnv,
+    prefix: &str,
+    levels: u32,
+    out: &mut Vec<DumpVar>,
+) -> Result<()> {
+    for &decl_id in &block.decls {
+        if let HirNode::VarDecl(decl) = cx.hir_of(decl_id)? {
+            let path = child_path(prefix, &decl.name.value.to_string());
+            push_dump_var(out, path, decl.kind, cx.type_of(decl_id, env)?);
+        }
+    }
+    for (path, body, env) in generate_bodies(cx, block, env, prefix)? {
+        collect_dump_block(cx, body, env, &path, levels, out)?;
+    }
+    if levels == 1 {
+        return Ok(());
+    }
+    for &inst_id in &block.insts {
+        let inst = match cx.hir_of(inst_id)? {
+            HirNode::Inst(x) => x,
+            _ => unreachable!(),
+        };
+        if !is_inst_elaborated(cx, inst, env) {
+            continue;
+        }
+        let details = cx.inst_details(Ref(inst), env)?;
+        let scope: &'a dyn ScopedNode<'a> = match details.target.kind {
+            InstTarget::Module(x) => x,
+            InstTarget::Interface(x) => x,
+        };
+        let path = child_path(prefix, &inst.name.to_string());
+        collect_dump_vars(
+            cx,
+            scope,
+            details.inner_env,
+            &path,
+            levels.saturating_sub(1),
+            out,
+        )?;
+    }
+    Ok(())
+}
+
+/// Add a variable to the list of dumped variables.
+///
+/// Variables that have no fixed width, such as strings and unpacked arrays,
+/// cannot be represented in a VCD file. Returns `false` for them, such that
+/// they are skipped. Variables that have been added already, such as ports
+/// and their declarations, are added only once.
+pub(crate) fn push_dump_var(
+    out: &mut Vec<DumpVar>,
+    path: String,
+    kind: ast::VarKind,
+    ty: &UnpackedType,
+) -> bool {
+    let width = match ty.get_bit_size() {
+        Some(x) if ty.dims.is_empty() => x,
+        _ => return false,
+    };
+    if !out.iter().any(|var| var.path == path) {
+        out.push(DumpVar {
+            path,
+            kind: vcd_var_kind(kind, ty),
+            width,
+        });
+    }
+    true
+}
+
+/// Determine the VCD variable type of a variable or net.
+fn vcd_var_kind(kind: ast::VarKind, ty: &UnpackedType) -> &'static str {
+    match kind {
+        ast::VarKind::Net { ty: net, .. } => match net {
+            ast::NetType::Supply0 => "supply0",
+            ast::NetType::Supply1 => "supply1",
+            ast::NetType::Tri => "tri",
+            ast::NetType::TriAnd => "triand",
+            ast::NetType::TriOr => "trior",
+            ast::NetType::TriReg => "trireg",
+            ast::NetType::Tri0 => "tri0",
+            ast::NetType::Tri1 => "tri1",
+            ast::NetType::WireAnd => "wand",
+            ast::NetType::WireOr => "wor",
+            ast::NetType::Uwire | ast::NetType::Wire | ast::NetType::Interconnect => "wire",
+        },
+        ast::VarKind::Var => match ty.resolve_full().core {
+            UnpackedCore::Real(RealType::RealTime) => "realtime",
+            UnpackedCore::Real(_) => "real",
+            _ if ty.is_integer_atom() => match ty.get_packed().map(|x| &x.resolve_full().core) {
+                Some(PackedCore::IntAtom(IntAtomType::Time)) => "time",
+                _ => "integer",
+            },
+            _ => "reg",
+        },
+    }
+}
+
+/// Find the body of an elaborated generate block.
+fn generate_block_body<'a>(
+    cx: &impl Context<'a>,
+    block: &'a ast::GenerateBlock<'a>,
+) -> Result<&'a hir::ModuleBlock> {
+    let parent = block.get_parent().unwrap();
+    let gen = match cx.hir_of(parent.id())? {
+        HirNode::Gen(x) => x,
+        _ => unreachable!(),
+    };
+    match (&gen.kind, parent.as_all()) {
+        (hir::GenKind::For { body, .. }, _) => Ok(body),
+        (
+            hir::GenKind::If {
+                main_body,
+                else_body,
+                ..
+            },
+            ast::AllNode::GenerateIf(ast),
+        ) => match else_body {
+            Some(body) if !std::ptr::eq(&ast.main_block, block) => Ok(body),
+            _ => Ok(main_body),
+        },
+        (hir::GenKind::Case { ways, default, .. }, ast::AllNode::GenerateCase(ast)) => {
+            let index = ast
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    ast::GenerateCaseItem::Expr(_, block) => Some(block),
+                    _ => None,
+                })
+                .position(|x| std::ptr::eq(x, block));
+            match (index, default) {
+                (Some(index), _) => Ok(&ways[index].1),
+                (None, Some(body)) => Ok(body),
+                (None, None) => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Determine the values of the non-local value parameters of a module.
+fn module_params<'a>(
+    cx: &impl Context<'a>,
+    module: &hir::Module<'a>,
+    env: ParamEnv,
+) -> Result<Vec<(Name, Value<'a>)>> {
+    let mut params = vec![];
+    for &param_id in module.params {
+        match cx.hir_of(param_id)? {
+            HirNode::ValueParam(p) if !p.local => {
+                params.push((p.name.value, cx.constant_value_of(param_id, env)))
+            }
+            _ => (),
+        }
+    }
+    Ok(params)
+}
+
+/// Determine the directions and types of a list of internal ports.
+fn elab_ports<'a>(
+    cx: &impl Context<'a>,
+    ports: &'a [IntPort<'a>],
+    env: ParamEnv,
+) -> Vec<ElabPort<'a>> {
+    ports
+        .iter()
+        .map(|port| ElabPort {
+            name: port.name.value,
+            dir: port.dir,
+            ty: cx.type_of_int_port(Ref(port), env),
+        })
+        .collect()
+}
+
+/// Describe the elaborated instance hierarchy underneath a top-level module
+/// as a JSON document.
+///
+/// Every instance is described by an object with its name, hierarchical path,
+/// instantiated module or interface, parameter values, ports, and child
+/// instances. Types and values are rendered in their SystemVerilog syntax.
+pub fn hierarchy_json<'a>(cx: &impl Context<'a>, top: NodeId) -> Result<String> {
+    let module = match cx.hir_of(top)? {
+        HirNode::Module(x) => x,
+        x => bug_span!(cx.span(top), cx, "hierarchy of non-module {:?}", x),
+    };
+    let env = cx.default_param_env();
+    let root = ElabInstance {
+        path: module.name.value.to_string(),
+        inst: top,
+        target: module.name.value,
+        env,
+        params: module_params(cx, module, env)?,
+        ports: elab_ports(cx, &module.ports_new.int, env),
+    };
+    let instances = cx.instances(top)?;
+    let mut out = String::new();
+    instance_json(&root, &instances).write(&mut out, 0);
+    Ok(out)
+}
+
+/// Describe an instance and the instances nested within it.
+///
+/// The child instances are found among the `instances` by their hierarchical
+/// path: they are the nested instances which are not nested within another
+/// nested instance. Generate blocks do not appear as separate levels.
+fn instance_json(inst: &ElabInstance, instances: &[ElabInstance]) -> Json {
+    let prefix = format!("{}.", inst.path);
+    let nested: Vec<&ElabInstance> = instances
+        .iter()
+        .filter(|x| x.path.starts_with(&prefix))
+        .collect();
+    let children = nested
+        .iter()
+        .filter(|x| {
+            !nested.iter().any(|y| {
+                x.path.len() > y.path.len()
+                    && x.path.starts_with(&y.path)
+                    && x.path[y.path.len()..].starts_with('.')
+            })
+        })
+        .map(|x| instance_json(x, instances))
+        .collect();
+    let name = inst.path.rsplit('.').next().unwrap_or(&inst.path);
+    Json::Object(vec![
+        ("name", Json::String(name.to_string())),
+        ("path", Json::String(inst.path.clone())),
+        ("target", Json::String(inst.target.to_string())),
+        (
+            "params",
+            Json::Array(
+                inst.params
+                    .iter()
+                    .map(|(name, value)| {
+                        Json::Object(vec![
+                            ("name", Json::String(name.to_string())),
+                            ("value", Json::String(value.kind.to_string())),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        (
+            "ports",
+            Json::Array(
+                inst.ports
+                    .iter()
+                    .map(|port| {
+                        Json::Object(vec![
+                            ("name", Json::String(port.name.to_string())),
+                            ("dir", Json::String(port.dir.to_string())),
+                            ("type", Json::String(port.ty.resolve_full().to_string())),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        ("children", Json::Array(children)),
+    ])
+}
+
+/// A minimal JSON document.
+enum Json {
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    /// Append the document to a string, indenting nested lines by `indent`
+    /// levels.
+    fn write(&self, out: &mut String, indent: usize) {
+        let pad = |out: &mut String, indent: usize| {
+            for _ in 0..indent {
+                out.push_str("  ");
+            }
+        };
+        match self {
+            Json::String(s) => write_json_string(out, s),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) => {
+                out.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    pad(out, indent + 1);
+                    item.write(out, indent + 1);
+                    out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                pad(out, indent);
+                out.push(']');
+            }
+            Json::Object(fields) => {
+                out.push_str("{\n");
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    pad(out, indent + 1);
+                    write_json_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                    out.push_str(if i + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+                pad(out, indent);
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// Append a string to a JSON document as a quoted and escaped string literal.
+fn write_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Elaborate the iterations of a generate loop.
+///
+/// Returns the value of the loop's genvar together with the parameter
+/// environment of the loop body for every iteration.
+pub(crate) fn generate_loop_iterations<'a>(
+    cx: &impl Context<'a>,
+    gen: &hir::Gen,
+    env: ParamEnv,
+) -> Result<Vec<(Value<'a>, ParamEnv)>> {
+    let (init, cond, step) = match gen.kind {
+        hir::GenKind::For {
+            ref init,
+            cond,
+            step,
+            ..
+        } => (init, cond, step),
+        _ => bug_span!(gen.span(), cx, "{:?} is not a generate loop", gen),
+    };
+    let genvar = match cx.hir_of(*init.last().unwrap())? {
+        HirNode::GenvarDecl(decl) => decl.id,
+        HirNode::Stmt(hir::Stmt {
+            kind: hir::StmtKind::Assign { lhs, .. },
+            ..
+        }) => cx.resolve_node(*lhs, env)?,
+        _ => unreachable!(),
+    };
+    let mut local_env = env;
+    for &i in init {
+        local_env = codegen::execute_genvar_init(cx, i, local_env)?;
+    }
+    let mut iterations = vec![];
+    while cx.constant_value_of(cond, local_env).is_true() {
+        iterations.push((cx.constant_value_of(genvar, local_env), local_env));
+        local_env = codegen::execute_genvar_step(cx, step, local_env)?;
+    }
+    Ok(iterations)
+}
@@ -114,6 +114,7 @@ macro_rules! assert_type {
 mod ast_map;
 mod codegen;
 mod context;
+mod dataflow;
 pub mod hir;
 mod inst_details;
 pub mod mir;
@@ -140,7 +141,10 @@ pub type Result<T> = std::result::Result<T, ()>;
 pub use crate::{
     codegen::CodeGenerator,
     context::*,
-    inst_details::{InstDetails, InstTargetDetails, InstVerbosityVisitor},
+    inst_details::{
+        hierarchy_json, DumpVar, ElabInstance, ElabPort, InstDetails, InstTargetDetails,
+        InstVerbosityVisitor,
+    },
     param_env::{
         IntoNodeEnvId, NodeEnvId, ParamEnv, ParamEnvBinding, ParamEnvData, ParamEnvSource,
     },
@@ -265,11 +269,12 @@ mod queries {
     #[allow(deprecated)]
     use crate::{
         hir::lowering::*,
-        hir::{accessed_nodes, AccessTable},
+        hir::{accessed_nodes, drivers_of, implicit_sensitivity, AccessTable, AccessedNode},
         inst_details::*,
         mir::lower::assign::{
             mir_assignment_from_concurrent, mir_assignment_from_procedural, mir_simplify_assignment,
         },
+        mir::lower::lvalue::mir_lvalue_packed,
         param_env::*,
         pattern_mapping::*,
         port_list::{self, *},
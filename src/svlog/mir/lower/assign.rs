@@ -4,6 +4,7 @@
 
 use crate::crate_prelude::*;
 use crate::{
+    hir::HirNode,
     mir::{
         assign::*,
         lower,
@@ -39,6 +40,40 @@ pub(crate) fn mir_assignment_from_procedural<'a>(
             lhs: lhs_mir_lv,
             rhs: rhs_mir,
         },
+        // `assign a = b` and `force a = b`
+        hir::AssignKind::Override(kind) => {
+            if !check_override_target(cx, kind, lhs_mir_lv, span) {
+                let builder = lower::rvalue::Builder {
+                    cx,
+                    span,
+                    expr: rhs_mir.id,
+                    env,
+                };
+                let lhs_builder = lower::lvalue::Builder {
+                    cx,
+                    span,
+                    expr: lhs,
+                    env,
+                };
+                let ty = ty::UnpackedType::make_error();
+                return cx.arena().alloc_mir_assignment(Assignment {
+                    id: origin,
+                    env,
+                    span,
+                    ty,
+                    lhs: lhs_builder.error(),
+                    rhs: builder.error(),
+                });
+            }
+            Assignment {
+                id: origin,
+                env,
+                span,
+                ty: lhs_mir_lv.ty,
+                lhs: lhs_mir_lv,
+                rhs: rhs_mir,
+            }
+        }
         // `a (+= -= *= /= %= &= |= ^= <<= >>= <<<= >>>=) b`
         hir::AssignKind::Block(op) => {
             let builder = lower::rvalue::Builder {
@@ -47,6 +82,23 @@ pub(crate) fn mir_assignment_from_procedural<'a>(
                 expr: rhs_mir.id,
                 env,
             };
+            if !check_compound_target(cx, op, lhs_mir_lv, span) {
+                let lhs_builder = lower::lvalue::Builder {
+                    cx,
+                    span,
+                    expr: lhs,
+                    env,
+                };
+                let ty = ty::UnpackedType::make_error();
+                return cx.arena().alloc_mir_assignment(Assignment {
+                    id: origin,
+                    env,
+                    span,
+                    ty,
+                    lhs: lhs_builder.error(),
+                    rhs: builder.error(),
+                });
+            }
             let lhs_mir_rv = cx.mir_rvalue(lhs, env);
             let value = match op {
                 ast::AssignOp::Identity => unreachable!(),
@@ -108,6 +160,135 @@ enum AssignOp {
     Shift(ShiftOp, bool),
 }
 
+/// Check that the target of a compound assignment can be operated on.
+///
+/// The target is read, combined with the right-hand side, and written back.
+/// The modulus, bitwise, and shift operators require an integral value. The
+/// arithmetic operators are also defined for reals, which are not supported
+/// at the moment.
+fn check_compound_target<'a>(
+    cx: &impl Context<'a>,
+    op: ast::AssignOp,
+    lhs: &'a Lvalue<'a>,
+    span: Span,
+) -> bool {
+    if lhs.is_error() || lhs.ty.get_simple_bit_vector().is_some() {
+        return true;
+    }
+    let integral_only = match op {
+        ast::AssignOp::Add | ast::AssignOp::Sub | ast::AssignOp::Mul | ast::AssignOp::Div => false,
+        _ => true,
+    };
+    if integral_only {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "`{}` cannot be applied to `{}` of type `{}`",
+                op,
+                lhs.span.extract(),
+                lhs.ty
+            ))
+            .span(span)
+            .add_note(
+                "The target of a compound assignment is read, combined with the right-hand \
+                 side, and written back, which requires an integral type",
+            ),
+        );
+    } else {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "unsupported: `{}` on `{}` of type `{}`",
+                op,
+                lhs.span.extract(),
+                lhs.ty
+            ))
+            .span(span)
+            .add_note("Compound assignments are only supported for integral types at the moment"),
+        );
+    }
+    false
+}
+
+/// Check that the target of a procedural continuous assignment is permitted.
+///
+/// An `assign` or `deassign` may only target variables and concatenations
+/// thereof. A `force` or `release` may additionally target nets and constant
+/// selects of nets, but not selects of variables. See IEEE 1800-2017 §10.6.
+pub(crate) fn check_override_target<'a>(
+    cx: &impl Context<'a>,
+    kind: hir::OverrideKind,
+    lhs: &'a Lvalue<'a>,
+    span: Span,
+) -> bool {
+    let (part, reason) = match invalid_override_part(cx, kind, lhs, false) {
+        Some(x) => x,
+        None => return true,
+    };
+    cx.emit(
+        DiagBuilder2::error(format!(
+            "`{}` cannot target {} `{}`",
+            kind.keyword(),
+            reason,
+            part.span.extract()
+        ))
+        .span(span)
+        .add_note(match kind {
+            hir::OverrideKind::Assign => {
+                "A procedural `assign` may only target entire variables or concatenations thereof"
+            }
+            hir::OverrideKind::Force => {
+                "A `force` may only target entire variables, nets, constant selects of nets, or \
+                 concatenations thereof"
+            }
+        }),
+    );
+    false
+}
+
+/// Find the part of an override target that is not permitted, together with a
+/// description of what it is.
+fn invalid_override_part<'a>(
+    cx: &impl Context<'a>,
+    kind: hir::OverrideKind,
+    lv: &'a Lvalue<'a>,
+    selected: bool,
+) -> Option<(&'a Lvalue<'a>, &'static str)> {
+    match lv.kind {
+        LvalueKind::Transmute(value)
+        | LvalueKind::Truncate(value)
+        | LvalueKind::Extend(value, _) => invalid_override_part(cx, kind, value, selected),
+        LvalueKind::Concat(ref values)
+        | LvalueKind::Overlay(ref values)
+        | LvalueKind::DestructArray(ref values)
+        | LvalueKind::DestructStruct(ref values) => values
+            .iter()
+            .flat_map(|value| invalid_override_part(cx, kind, value, selected))
+            .next(),
+        LvalueKind::Index { value, base, .. } => {
+            if kind == hir::OverrideKind::Force && !base.is_const() {
+                return Some((lv, "the non-constant select"));
+            }
+            invalid_override_part(cx, kind, value, true)
+        }
+        LvalueKind::Member { value, .. } => invalid_override_part(cx, kind, value, true),
+        LvalueKind::Var(id) | LvalueKind::Port(id) | LvalueKind::IntfSignal(_, id) => {
+            let is_net = match cx.hir_of(id) {
+                Ok(HirNode::VarDecl(x)) => x.kind.is_net(),
+                Ok(HirNode::IntPort(x)) => x.kind.is_net(),
+                _ => false,
+            };
+            match kind {
+                hir::OverrideKind::Assign if is_net => Some((lv, "the net")),
+                hir::OverrideKind::Assign if selected => Some((lv, "a select of the variable")),
+                hir::OverrideKind::Force if selected && !is_net => {
+                    Some((lv, "a select of the variable"))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Lower a concurrent assign statement.
 #[moore_derive::query]
 pub(crate) fn mir_assignment_from_concurrent<'a>(
@@ -153,6 +334,51 @@ fn simplify<'a>(
     trace!("Simplifying {:?}", root);
     match lhs.kind {
         LvalueKind::Transmute(value) => simplify(cx, root, value, rhs, into),
+        LvalueKind::Truncate(value) => {
+            // Only the lower bits of the RHS are assigned.
+            let sbvt = value.ty.simple_bit_vector(cx, value.span);
+            let builder = lower::rvalue::Builder {
+                cx,
+                span: rhs.span,
+                expr: rhs.id,
+                env: rhs.env,
+            };
+            let index = builder.build(
+                sbvt.to_unpacked(cx),
+                RvalueKind::Index {
+                    value: rhs,
+                    base: builder.constant_u32(0),
+                    length: sbvt.size,
+                },
+            );
+            let mut a = root.clone();
+            a.lhs = value;
+            a.rhs = index;
+            let a = cx.arena().alloc_mir_assignment(a);
+            simplify(cx, a, a.lhs, a.rhs, into);
+        }
+        LvalueKind::Extend(value, signed) => {
+            // The RHS is extended to the full width of the target.
+            let sbvt = value.ty.simple_bit_vector(cx, value.span);
+            let builder = lower::rvalue::Builder {
+                cx,
+                span: rhs.span,
+                expr: rhs.id,
+                env: rhs.env,
+            };
+            let extended = builder.build(
+                sbvt.to_unpacked(cx),
+                match signed {
+                    true => RvalueKind::SignExtend(sbvt.size, rhs),
+                    false => RvalueKind::ZeroExtend(sbvt.size, rhs),
+                },
+            );
+            let mut a = root.clone();
+            a.lhs = value;
+            a.rhs = extended;
+            let a = cx.arena().alloc_mir_assignment(a);
+            simplify(cx, a, a.lhs, a.rhs, into);
+        }
         LvalueKind::Concat(ref values) if values.len() == 1 => {
             let mut a = root.clone();
             a.lhs = values[0];
@@ -199,6 +425,62 @@ fn simplify<'a>(
                 base += sbvt.size;
             }
         }
+        LvalueKind::Overlay(ref values) => {
+            for &value in values {
+                // Formulate a new assignment of the same value.
+                let mut a = root.clone();
+                a.lhs = value;
+                a.rhs = rhs;
+                let a = cx.arena().alloc_mir_assignment(a);
+                simplify(cx, a, a.lhs, a.rhs, into);
+            }
+        }
+        LvalueKind::DestructArray(ref values) => {
+            for (index, &value) in values.iter().enumerate() {
+                // Extract the array element from the RHS.
+                let builder = lower::rvalue::Builder {
+                    cx,
+                    span: rhs.span,
+                    expr: rhs.id,
+                    env: rhs.env,
+                };
+                let base_rv = builder.constant_u32(index as u32);
+                let elem = builder.build(
+                    value.ty,
+                    RvalueKind::Index {
+                        value: rhs,
+                        base: base_rv,
+                        length: 0,
+                    },
+                );
+
+                // Formulate a new assignment.
+                let mut a = root.clone();
+                a.lhs = value;
+                a.rhs = elem;
+                let a = cx.arena().alloc_mir_assignment(a);
+                simplify(cx, a, a.lhs, a.rhs, into);
+            }
+        }
+        LvalueKind::DestructStruct(ref values) => {
+            for (field, &value) in values.iter().enumerate() {
+                // Extract the struct member from the RHS.
+                let builder = lower::rvalue::Builder {
+                    cx,
+                    span: rhs.span,
+                    expr: rhs.id,
+                    env: rhs.env,
+                };
+                let member = builder.build(value.ty, RvalueKind::Member { value: rhs, field });
+
+                // Formulate a new assignment.
+                let mut a = root.clone();
+                a.lhs = value;
+                a.rhs = member;
+                let a = cx.arena().alloc_mir_assignment(a);
+                simplify(cx, a, a.lhs, a.rhs, into);
+            }
+        }
         LvalueKind::Index {
             value,
             base,
@@ -260,6 +542,33 @@ fn simplify<'a>(
                     shift += sbvt.size;
                 }
             }
+            // If we index into overlaid values, we apply the same index to
+            // each of them, and emit an individual assignment for each.
+            LvalueKind::Overlay(ref values) => {
+                for &value in values {
+                    let lbuilder = lower::lvalue::Builder {
+                        cx,
+                        span: value.span,
+                        expr: value.id,
+                        env: value.env,
+                    };
+                    let index = lbuilder.build(
+                        lhs.ty,
+                        LvalueKind::Index {
+                            value,
+                            base,
+                            length,
+                        },
+                    );
+
+                    // Formulate a new assignment.
+                    let mut a = root.clone();
+                    a.lhs = index;
+                    a.rhs = rhs;
+                    let a = cx.arena().alloc_mir_assignment(a);
+                    simplify(cx, a, a.lhs, a.rhs, into);
+                }
+            }
             _ => {
                 into.push(root);
             }
@@ -7,10 +7,11 @@ use crate::{
     hir::HirNode,
     mir::{
         lower,
-        lower::rvalue::{adjust_indexing, compute_indexing},
+        lower::rvalue::{adjust_indexing, check_indexing_bounds, compute_indexing},
         lvalue::*,
         rvalue::RvalueKind,
     },
+    resolver::HierarchicalTarget,
     syntax::ast::BasicNode,
     ty::{SbvType, UnpackedType},
     typeck::{CastOp, CastType},
@@ -103,6 +104,29 @@ pub fn lower_expr<'a>(cx: &impl Context<'a>, expr_id: NodeId, env: ParamEnv) ->
     lower_cast(&builder, lvalue, cast)
 }
 
+/// Lower an expression to an lvalue in the MIR and pack it as a simple bit
+/// vector.
+///
+/// Values that already coalesce to an LLHD scalar are returned as they are.
+#[moore_derive::query]
+pub(crate) fn mir_lvalue_packed<'a>(
+    cx: &impl Context<'a>,
+    expr_id: NodeId,
+    env: ParamEnv,
+) -> &'a Lvalue<'a> {
+    let value = cx.mir_lvalue(expr_id, env);
+    if value.is_error() || value.ty.coalesces_to_llhd_scalar() {
+        return value;
+    }
+    let builder = Builder {
+        cx,
+        span: value.span,
+        expr: expr_id,
+        env,
+    };
+    pack_simple_bit_vector(&builder, value)
+}
+
 /// Lower an expression to an rvalue in the MIR.
 ///
 /// May return an error if any of the database queries break.
@@ -123,7 +147,7 @@ fn lower_expr_inner<'a>(
     match hir.kind {
         // Identifiers and scoped identifiers we simply resolve and try to lower
         // the resolved node to an MIR node.
-        hir::ExprKind::Ident(..) | hir::ExprKind::Scope(..) => {
+        hir::ExprKind::Ident(..) | hir::ExprKind::Scope(..) | hir::ExprKind::UnitScope(..) => {
             let binding = cx.resolve_node(expr_id, env)?;
             return match cx.hir_of(binding)? {
                 HirNode::GenvarDecl(decl) => Ok(builder.build(ty, LvalueKind::Genvar(decl.id))),
@@ -166,13 +190,38 @@ fn lower_expr_inner<'a>(
             // such as `x[1]` into `logic [2:1] x`, which essentially accesses
             // element 0.
             let target_dim = target.ty.dims().next().unwrap();
+            let dynamic = match target_dim {
+                ty::Dim::Unpacked(ty::UnpackedDim::Unsized) => Some("a dynamic array"),
+                ty::Dim::Unpacked(ty::UnpackedDim::Assoc(_)) => Some("an associative array"),
+                ty::Dim::Unpacked(ty::UnpackedDim::Queue(_)) => Some("a queue"),
+                _ => None,
+            };
+            if let Some(dynamic) = dynamic {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "unsupported: assignment to an element of {}",
+                        dynamic
+                    ))
+                    .span(span)
+                    .add_note(format!(
+                        "`{}` has type `{}`",
+                        target.span.extract(),
+                        target.ty
+                    ))
+                    .add_note(
+                        "Dynamically sized storage cannot be represented in LLHD at the moment",
+                    ),
+                );
+                return Err(());
+            }
+            check_indexing_bounds(cx, builder.expr, mode, base, length, target_dim);
             let rvalue_builder = lower::rvalue::Builder {
                 cx,
                 span: base.span,
                 expr: base.id,
                 env: base.env,
             };
-            let base = adjust_indexing(&rvalue_builder, base, target_dim);
+            let base = adjust_indexing(&rvalue_builder, base, length, target_dim);
 
             // Build the cast lvalue.
             return Ok(builder.build(
@@ -186,6 +235,9 @@ fn lower_expr_inner<'a>(
         }
 
         hir::ExprKind::Field(target, name) => {
+            if let Some(target) = cx.resolve_hierarchical_name(expr_id, env)? {
+                return lower_hierarchical_name(builder, ty, target);
+            }
             let target_ty = cx.self_determined_type(target, env);
             let value = cx.mir_lvalue(target, env);
             if let Some(intf) = target_ty.and_then(|ty| ty.get_interface()) {
@@ -194,6 +246,7 @@ fn lower_expr_inner<'a>(
                 if def.node.as_all().is_modport_name() {
                     return Ok(builder.build(ty, value.kind.clone()));
                 } else {
+                    check_modport_direction(builder, intf, name)?;
                     return Ok(builder.build(ty, LvalueKind::IntfSignal(value, def.node.id())));
                 }
             } else {
@@ -250,6 +303,70 @@ fn lower_expr_inner<'a>(
             return Ok(repeat);
         }
 
+        hir::ExprKind::StreamConcat(dir, slice, ref exprs) => {
+            // Determine the size of the streamed blocks.
+            let slice_size = match slice {
+                Some(slice) => stream_slice_size(cx, slice, env)?,
+                None => 1,
+            };
+
+            // Lower the arguments to a regular concatenation.
+            let values = exprs
+                .iter()
+                .map(|&expr| {
+                    let value = cx.mir_lvalue(expr, env);
+                    if value.is_error() {
+                        return Err(());
+                    }
+                    Ok(value)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let final_ty = cx.need_self_determined_type(hir.id, env);
+            if final_ty.is_error() {
+                return Err(());
+            }
+            let concat = builder.build(final_ty, LvalueKind::Concat(values));
+
+            // Streaming to the right preserves the order of the bits, such that
+            // the concatenation can be assigned to directly.
+            if dir == ast::StreamDir::In {
+                return Ok(concat);
+            }
+
+            // Streaming to the left cuts the concatenation into blocks of the
+            // slice size, starting at the LSB, and reverses their order. The
+            // block at the MSB may be shorter if the size is not a multiple of
+            // the slice size.
+            let domain = final_ty.domain();
+            let rvalue_builder = lower::rvalue::Builder {
+                cx,
+                span: builder.span,
+                expr: builder.expr,
+                env,
+            };
+            let mut blocks = vec![];
+            let size = final_ty.get_bit_size().unwrap();
+            let mut lo = 0;
+            while lo < size {
+                let hi = std::cmp::min(lo + slice_size, size);
+                let block_ty = SbvType::new(domain, ty::Sign::Unsigned, hi - lo).to_unpacked(cx);
+                blocks.push(builder.build(
+                    block_ty,
+                    LvalueKind::Index {
+                        value: concat,
+                        base: rvalue_builder.constant_u32(lo as u32),
+                        length: hi - lo,
+                    },
+                ));
+                lo = hi;
+            }
+            return Ok(builder.build(final_ty, LvalueKind::Concat(blocks)));
+        }
+
+        hir::ExprKind::PositionalPattern(..)
+        | hir::ExprKind::RepeatPattern(..)
+        | hir::ExprKind::NamedPattern(..) => return lower_pattern(builder, hir, ty),
+
         _ => (),
     }
 
@@ -260,6 +377,162 @@ fn lower_expr_inner<'a>(
     Err(())
 }
 
+/// Lower a `'{...}` pattern that is assigned to.
+///
+/// Each field of the pattern is lowered to an lvalue of the struct member or
+/// array element it maps to, such that the assignment can later be split into
+/// one assignment per field.
+fn lower_pattern<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    hir: &'a hir::Expr<'a>,
+    ty: &'a UnpackedType<'a>,
+) -> Result<&'a Lvalue<'a>> {
+    let cx = builder.cx;
+
+    // Only patterns that assign to every field exactly once can be used as the
+    // target of an assignment.
+    let invalid = match hir.kind {
+        hir::ExprKind::RepeatPattern(count, _) => Some(("replication", cx.span(count))),
+        hir::ExprKind::NamedPattern(ref mapping) => mapping
+            .iter()
+            .flat_map(|&(map, to)| match map {
+                hir::PatternMapping::Type(type_id) => Some(("type keys", cx.span(type_id))),
+                hir::PatternMapping::Default => Some(("`default:`", cx.span(to))),
+                hir::PatternMapping::Member(..) => None,
+            })
+            .next(),
+        _ => None,
+    };
+    if let Some((what, span)) = invalid {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "{} cannot be used in a pattern that is assigned to",
+                what
+            ))
+            .span(span)
+            .add_note(
+                "A pattern on the left-hand side of an assignment must list every struct \
+                 member or array element exactly once",
+            )
+            .span(hir.span),
+        );
+        return Err(());
+    }
+
+    // Lower each field to the lvalue it is assigned to.
+    let map = cx.map_pattern(Ref(hir), builder.env)?;
+    assert_type!(ty, map.ty, builder.span, cx);
+    let values = map
+        .fields
+        .iter()
+        .map(|&(field, expr)| {
+            let value = cx.mir_lvalue(expr.id, builder.env);
+            if value.is_error() {
+                return Err(());
+            }
+            assert_type!(value.ty, field.ty(cx), value.span, cx);
+            Ok(value)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Construct the correct destructuring lvalue.
+    if ty.coalesces_to_llhd_scalar() {
+        Ok(builder.build(ty, LvalueKind::Concat(values)))
+    } else if ty.outermost_dim().is_some() {
+        Ok(builder.build(ty, LvalueKind::DestructArray(values)))
+    } else if ty.get_struct().is_some() {
+        Ok(builder.build(ty, LvalueKind::DestructStruct(values)))
+    } else {
+        bug_span!(builder.span, cx, "pattern with invalid type `{}`", ty)
+    }
+}
+
+/// Ensure that an interface signal accessed through a modport may be assigned.
+///
+/// Signals which the modport lists as `input` are read-only.
+fn check_modport_direction<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    intf: &ty::InterfaceType<'a>,
+    name: Spanned<Name>,
+) -> Result<()> {
+    let modport = match intf.modport {
+        Some(x) => x,
+        None => return Ok(()),
+    };
+    for port in &modport.ports {
+        match port.data {
+            ast::ModportPortData::Simple { dir, ref port } => {
+                for port_name in port {
+                    if port_name.name.value != name.value || dir.value != ast::PortDir::Input {
+                        continue;
+                    }
+                    builder.cx.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` cannot be assigned through modport `{}`",
+                            name.value, modport.name.value
+                        ))
+                        .span(name.span)
+                        .add_note(format!(
+                            "`{}` is declared as `{}` in modport `{}`:",
+                            name.value, dir.value, modport.name.value
+                        ))
+                        .span(port_name.name.span),
+                    );
+                    return Err(());
+                }
+            }
+            ast::ModportPortData::Tf { .. } => (),
+        }
+    }
+    Ok(())
+}
+
+/// Lower a hierarchical name such as `top.gen_lanes[2].q` to an lvalue.
+///
+/// Only names that stay within the current module instance can be assigned
+/// at the moment.
+fn lower_hierarchical_name<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    target: HierarchicalTarget<'a>,
+) -> Result<&'a Lvalue<'a>> {
+    let cx = builder.cx;
+    let hir = cx.hir_of(target.node)?;
+    if !target.local {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "unsupported: assignment to `{}` in another instance or generate loop iteration",
+                builder.span.extract()
+            ))
+            .span(builder.span)
+            .add_note(format!(
+                "`{}` resolves to this {}:",
+                builder.span.extract(),
+                hir.desc()
+            ))
+            .span(hir.human_span()),
+        );
+        return Err(());
+    }
+    match hir {
+        HirNode::VarDecl(decl) => Ok(builder.build(ty, LvalueKind::Var(decl.id))),
+        HirNode::IntPort(port) if ty.resolve_full().core.get_interface().is_some() => {
+            Ok(builder.build(ty, LvalueKind::Intf(port.id)))
+        }
+        HirNode::IntPort(port) => Ok(builder.build(ty, LvalueKind::Port(port.id))),
+        x => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "{} cannot be used as the target of an assignment",
+                    x.desc_full()
+                ))
+                .span(builder.span),
+            );
+            Err(())
+        }
+    }
+}
+
 /// Generate the nodes necessary for a cast operation.
 fn lower_cast<'a>(
     builder: &Builder<'_, impl Context<'a>>,
@@ -290,6 +563,14 @@ fn lower_cast<'a>(
         value.span.begin().human_line()
     );
 
+    // Values assigned through a widening cast are extended according to the
+    // sign of the final type, which is the type of the assigned value.
+    let signed = to
+        .ty
+        .get_simple_bit_vector()
+        .map(|sbvt| sbvt.is_signed())
+        .unwrap_or(false);
+
     // Lower each cast individually.
     for &(op, to) in &to.casts {
         debug!("- {:?} from `{}` to `{}`", op, value.ty, to);
@@ -301,14 +582,41 @@ fn lower_cast<'a>(
             CastOp::PickModport => {
                 value = builder.build(to, value.kind.clone());
             }
-            _ => {
-                bug_span!(
-                    value.span,
-                    builder.cx,
-                    "lvalue lowering of cast to `{}` not yet supported: {:?}",
-                    to,
-                    op
+            // Sign and domain casts do not change the bits stored, such that
+            // the target can be assigned through them directly.
+            CastOp::Sign(_) | CastOp::Domain(_) => {
+                assert_span!(value.ty.is_simple_bit_vector(), value.span, builder.cx);
+                value = builder.build(to, LvalueKind::Transmute(value));
+            }
+            CastOp::Range(range, _) => {
+                assert_span!(value.ty.is_simple_bit_vector(), value.span, builder.cx);
+                assert_span!(to.is_simple_bit_vector(), value.span, builder.cx);
+                let size = value.ty.simple_bit_vector(builder.cx, value.span).size;
+                if size == range.size {
+                    value = builder.build(to, LvalueKind::Transmute(value));
+                } else if size > range.size {
+                    // Assigning a narrower value extends it to the full width.
+                    value = builder.build(to, LvalueKind::Extend(value, signed));
+                } else {
+                    // Assigning a wider value drops the upper bits.
+                    value = builder.build(to, LvalueKind::Truncate(value));
+                }
+            }
+            CastOp::Bool | CastOp::UnpackSBVT | CastOp::PackString | CastOp::UnpackString => {
+                builder.cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` cannot be assigned to as `{}`",
+                        value.span.extract(),
+                        to
+                    ))
+                    .span(value.span)
+                    .add_note(format!(
+                        "Assigning to `{}` would require a conversion from `{}`, which is not \
+                         supported for assignment targets",
+                        value.ty, to
+                    )),
                 );
+                return builder.error();
             }
         }
         if !value.ty.is_identical(to) {
@@ -351,14 +659,25 @@ fn pack_simple_bit_vector<'a>(
     } else if let Some(dim) = value.ty.outermost_dim() {
         pack_array(builder, value, dim, to)
     } else if let Some(strukt) = value.ty.get_struct() {
-        pack_struct(builder, value, strukt, to)
+        if strukt.is_union() {
+            pack_union(builder, value, strukt, to)
+        } else {
+            pack_struct(builder, value, strukt, to)
+        }
     } else {
-        bug_span!(
-            value.span,
-            builder.cx,
-            "cannot pack a `{}` as SBVT",
-            value.ty
+        builder.cx.emit(
+            DiagBuilder2::error(format!(
+                "`{}` cannot be used as an assignment target here",
+                value.ty
+            ))
+            .span(value.span)
+            .add_note(format!(
+                "The assignment requires `{}` to be packed into a bit vector, which is not \
+                 possible for this type",
+                value.ty
+            )),
         );
+        builder.error()
     }
 }
 
@@ -381,6 +700,29 @@ fn pack_struct<'a>(
     builder.build(to, LvalueKind::Concat(packed_fields))
 }
 
+/// Pack a union as a simple bit vector.
+///
+/// All members of a union overlay the same bits, so the union is packed by
+/// packing each of its members and assigning them all the same value. This
+/// requires all members to be of equal width.
+fn pack_union<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    value: &'a Lvalue<'a>,
+    strukt: &'a ty::StructType<'a>,
+    to: &'a UnpackedType<'a>,
+) -> &'a Lvalue<'a> {
+    if !lower::rvalue::check_union_widths(builder.cx, value.ty, strukt) {
+        return builder.error();
+    }
+    let mut packed_members = vec![];
+    for (i, member) in strukt.members.iter().enumerate() {
+        let member = builder.build(member.ty, LvalueKind::Member { value, field: i });
+        let member = pack_simple_bit_vector(builder, member);
+        packed_members.push(member);
+    }
+    builder.build(to, LvalueKind::Overlay(packed_members))
+}
+
 /// Pack an array as a simple bit vector.
 fn pack_array<'a>(
     builder: &Builder<'_, impl Context<'a>>,
@@ -444,3 +786,33 @@ fn pack_array<'a>(
     // Concatenate the elements.
     builder.build(to, LvalueKind::Concat(packed_elements))
 }
+
+/// Determine the size of the blocks in a streaming concatenation.
+///
+/// The slice is either a constant expression or a type whose bit size is used.
+fn stream_slice_size<'a>(cx: &impl Context<'a>, slice: NodeId, env: ParamEnv) -> Result<usize> {
+    let size = match cx.ast_for_id(slice).as_all().get_type() {
+        Some(ty) => {
+            let ty = cx.packed_type_from_ast(Ref(ty), env, None);
+            if ty.is_error() {
+                return Err(());
+            }
+            ty.get_bit_size()
+        }
+        None => cx.constant_int_value_of(slice, env)?.to_usize(),
+    };
+    match size {
+        Some(size) if size > 0 => Ok(size),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "slice size of streaming concatenation must be a positive constant, \
+                     but `{}` is not",
+                    cx.span(slice).extract()
+                ))
+                .span(cx.span(slice)),
+            );
+            Err(())
+        }
+    }
+}
@@ -5,15 +5,22 @@
 use crate::crate_prelude::*;
 use crate::{
     hir::HirNode,
+    inst_details::{collect_dump_vars, push_dump_var},
     mir::rvalue::*,
+    resolver::{HierarchicalKind, HierarchicalTarget},
     syntax::ast::BasicNode,
     ty::{SbvType, UnpackedType},
     typeck::{CastOp, CastType},
     value::{self, ValueData, ValueKind},
     ParamEnv,
 };
+use moore_common::source::{get_source_manager, Source};
 use num::{BigInt, One, Signed, ToPrimitive, Zero};
-use std::{cmp::max, collections::HashMap};
+use std::{
+    cmp::max,
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 /// An internal builder for rvalue lowering.
 pub struct Builder<'a, C> {
@@ -159,6 +166,28 @@ fn lower_expr_inner<'gcx>(
             bug_span!(span, cx, "unsized const with weird '{}' char", c)
         }
         hir::ExprKind::TimeConst(ref k) => Ok(builder.constant(value::make_time(k.clone()))),
+        hir::ExprKind::RealConst(_) => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "unsupported: real number `{}` used as a value",
+                    span.extract()
+                ))
+                .span(span)
+                .add_note("Real numbers are only supported as delays at the moment"),
+            );
+            Err(())
+        }
+        hir::ExprKind::Unbounded => {
+            cx.emit(
+                DiagBuilder2::error("`$` cannot be used as a value")
+                    .span(span)
+                    .add_note(
+                        "`$` denotes an unbounded parameter or range, and may only appear as \
+                         a parameter value, a range bound, or the argument of `$isunbounded`",
+                    ),
+            );
+            Err(())
+        }
         hir::ExprKind::StringConst(string) => Ok(builder.constant(value::make_int(
             // TODO: This could use `value::make_string` to build a string
             // value, and then resort to the conversion function there to map
@@ -187,18 +216,18 @@ fn lower_expr_inner<'gcx>(
             Ok(builder.constant(value::make_int(ty, num::zero())))
         }
         hir::ExprKind::Builtin(hir::BuiltinCall::Clog2(arg)) => {
-            let arg_val = cx.constant_value_of(arg, env);
-            let arg_int = match arg_val.kind {
-                ValueKind::Int(ref arg, ..) => arg,
-                ValueKind::Error => return Ok(builder.error()),
-                _ => unreachable!(),
-            };
-            let value = if arg_int <= &BigInt::one() {
-                BigInt::zero()
-            } else {
-                BigInt::from((arg_int - BigInt::one()).bits())
-            };
-            Ok(builder.constant(value::make_int(ty, value)))
+            let arg = lower_unsigned_arg(builder, "$clog2", arg)?;
+            if arg.is_const() {
+                let arg_int = cx.const_mir_rvalue_int(Ref(arg))?;
+                let value = if arg_int <= &BigInt::one() {
+                    BigInt::zero()
+                } else {
+                    BigInt::from((arg_int - BigInt::one()).bits())
+                };
+                return Ok(builder.constant(value::make_int(ty, value)));
+            }
+            let count = make_clog2(builder, arg);
+            Ok(builder.build(ty, RvalueKind::CastSign(ty::Sign::Signed, count)))
         }
         hir::ExprKind::Builtin(hir::BuiltinCall::Bits(arg)) => {
             let arg_ty = match cx.disamb_type_or_expr(Ref(arg))? {
@@ -219,36 +248,113 @@ fn lower_expr_inner<'gcx>(
                 }
             }
         }
-        hir::ExprKind::Builtin(hir::BuiltinCall::CountOnes(_))
-        | hir::ExprKind::Builtin(hir::BuiltinCall::OneHot(_))
-        | hir::ExprKind::Builtin(hir::BuiltinCall::OneHot0(_)) => {
-            bug_span!(span, cx, "unsupported system function {:?}", hir.kind)
+        hir::ExprKind::Builtin(hir::BuiltinCall::CountOnes(arg)) => {
+            let arg = lower_unsigned_arg(builder, "$countones", arg)?;
+            let count = make_count_bits(builder, arg, true, false)?;
+            Ok(builder.build(ty, RvalueKind::CastSign(ty::Sign::Signed, count)))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::CountBits(arg, control)) => {
+            let (ones, zeros) = lower_count_bits_control(builder, control)?;
+            let arg = lower_unsigned_arg(builder, "$countbits", arg)?;
+            let count = make_count_bits(builder, arg, ones, zeros)?;
+            Ok(builder.build(ty, RvalueKind::CastSign(ty::Sign::Signed, count)))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::OneHot(arg))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::OneHot0(arg)) => {
+            let (func, op) = match hir.kind {
+                hir::ExprKind::Builtin(hir::BuiltinCall::OneHot(_)) => ("$onehot", IntCompOp::Eq),
+                _ => ("$onehot0", IntCompOp::Leq),
+            };
+            let arg = lower_unsigned_arg(builder, func, arg)?;
+            let count = make_count_bits(builder, arg, true, false)?;
+            let one = builder.constant(value::make_int(count.ty, BigInt::one()));
+            Ok(make_int_comparison(builder, ty, count.ty, op, count, one))
         }
         hir::ExprKind::Builtin(hir::BuiltinCall::IsUnknown(_)) => {
             // Since we currently don't emit logic types, this is always zero.
             Ok(builder.constant(value::make_int(ty, num::zero())))
         }
+        hir::ExprKind::Builtin(hir::BuiltinCall::IsUnbounded(arg)) => {
+            let unbounded = value::is_unbounded(cx, arg, env);
+            Ok(builder.constant(value::make_int(ty, (unbounded as usize).into())))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::Sformatf(args)) => {
+            lower_sformatf(builder, ty, args)
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::Display(task, args)) => {
+            lower_print(builder, ty, task, args)
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::Finish(task, args)) => {
+            lower_finish(builder, ty, task, args)
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::Random(seed)) => {
+            let seed = lower_random_seed(builder, "$random", seed)?;
+            Ok(builder.build(ty, RvalueKind::Random(seed)))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::Urandom(seed)) => {
+            let seed = lower_random_seed(builder, "$urandom", seed)?;
+            Ok(builder.build(ty, RvalueKind::Random(seed)))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::UrandomRange(max, min)) => {
+            lower_urandom_range(builder, ty, max, min)
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::ReadMem {
+            radix,
+            file,
+            mem,
+            start,
+            end,
+        }) => lower_read_mem(builder, ty, radix, file, mem, start, end),
+        hir::ExprKind::Builtin(hir::BuiltinCall::FileOpen(name, mode)) => {
+            let name = lower_string_literal_arg(builder, "$fopen", name)?;
+            let mode = match mode {
+                Some(mode) => lower_string_literal_arg(builder, "$fopen", mode)?,
+                None => String::from("w"),
+            };
+            Ok(builder.build(ty, RvalueKind::FileOpen { name, mode }))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::FileClose(file)) => {
+            let file = lower_unsigned_arg(builder, "$fclose", file)?;
+            let file = make_unsigned_resize(builder, file, 32);
+            Ok(builder.build(ty, RvalueKind::FileClose(file)))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::DumpFile(name)) => {
+            let name = match name {
+                Some(name) => lower_string_literal_arg(builder, "$dumpfile", name)?,
+                None => String::from("dump.vcd"),
+            };
+            Ok(builder.build(ty, RvalueKind::DumpFile(name)))
+        }
+        hir::ExprKind::Builtin(hir::BuiltinCall::DumpVars(args)) => {
+            lower_dump_vars(builder, ty, args)
+        }
         hir::ExprKind::Builtin(hir::BuiltinCall::ArrayDim(func, arg, dim)) => {
             // Decide which dimension to inspect.
             let dim = match dim {
                 Some(dim) => match cx.constant_value_of(dim.id(), env).kind {
-                    ValueKind::Int(ref v, ..) => v.to_usize().unwrap(),
+                    ValueKind::Int(ref v, ..) => v.clone(),
                     ValueKind::Error => return Ok(builder.error()),
                     _ => unreachable!(),
                 },
-                None => 1,
+                None => BigInt::one(),
             };
 
-            // Get the fully resolved type of the argument.
-            let arg_ty = cx.type_of_expr(Ref(cx.hir_of_expr(Ref(arg))?), env);
+            // Get the fully resolved type of the argument, which may either be
+            // a type or an expression.
+            let arg_ty = match cx.disamb_type_or_expr(Ref(arg))? {
+                &ast::TypeOrExpr::Type(x) => cx.map_to_type_or_error(Ref(x), env),
+                &ast::TypeOrExpr::Expr(x) => cx.type_of_expr(Ref(cx.hir_of_expr(Ref(x))?), env),
+            };
             if arg_ty.is_error() {
                 return Err(());
             }
 
-            // Extract the dimension of interest.
-            let ty_dim = match arg_ty.dims().nth(dim - 1) {
-                Some(x) => x,
-                None => {
+            // Extract the dimension of interest. Dimensions are numbered
+            // starting at 1.
+            let dims = array_query_dims(arg_ty);
+            let ty_dim = match dim.to_usize().filter(|&d| d >= 1).map(|d| dims.get(d - 1)) {
+                Some(Some(&x)) => x,
+                _ => {
                     cx.emit(
                         DiagBuilder2::error(format!(
                             "value of type `{}` does not have a dimension {}",
@@ -258,7 +364,7 @@ fn lower_expr_inner<'gcx>(
                         .add_note(format!(
                             "Argument type `{}` has {} dimension(s)",
                             arg_ty.resolve_full(),
-                            arg_ty.dims().count()
+                            dims.len()
                         ))
                         .span(arg.span()),
                     );
@@ -287,10 +393,11 @@ fn lower_expr_inner<'gcx>(
             Ok(builder.constant(value::make_int(ty, value.into())))
         }
 
-        hir::ExprKind::Ident(..) | hir::ExprKind::Scope(..) => {
+        hir::ExprKind::Ident(..) | hir::ExprKind::Scope(..) | hir::ExprKind::UnitScope(..) => {
             let binding = builder.cx.resolve_node(expr_id, env)?;
             match builder.cx.hir_of(binding)? {
                 HirNode::VarDecl(decl) => Ok(builder.build(ty, RvalueKind::Var(decl.id))),
+                HirNode::ForeachIndex(index) => Ok(builder.build(ty, RvalueKind::Var(index.id))),
                 HirNode::IntPort(port) if ty.resolve_full().core.get_interface().is_some() => {
                     Ok(builder.build(ty, RvalueKind::Intf(port.id)))
                 }
@@ -298,6 +405,22 @@ fn lower_expr_inner<'gcx>(
                 HirNode::Inst(inst) if ty.resolve_full().core.get_interface().is_some() => {
                     Ok(builder.build(ty, RvalueKind::Intf(inst.id)))
                 }
+                HirNode::GenvarDecl(decl)
+                    if decl.init.is_none()
+                        && builder.cx.param_env_data(env).find_value(decl.id).is_none() =>
+                {
+                    builder.cx.emit(
+                        DiagBuilder2::error(format!(
+                            "{} used outside of a generate loop",
+                            decl.desc_full()
+                        ))
+                        .span(span)
+                        .add_note("Genvars only have a value inside the loop they control")
+                        .add_note("Genvar declared here:")
+                        .span(decl.human_span()),
+                    );
+                    Err(())
+                }
                 HirNode::EnumVariant(..) | HirNode::ValueParam(..) | HirNode::GenvarDecl(..) => {
                     let k = builder.cx.constant_value_of(binding, env);
                     Ok(builder.build(ty, RvalueKind::Const(k)))
@@ -399,7 +522,8 @@ fn lower_expr_inner<'gcx>(
             // such as `x[1]` into `logic [2:1] x`, which essentially accesses
             // element 0.
             let target_dim = target.ty.dims().next().unwrap();
-            let base = adjust_indexing(builder, base, target_dim);
+            check_indexing_bounds(cx, builder.expr, mode, base, length, target_dim);
+            let base = adjust_indexing(builder, base, length, target_dim);
 
             // Build the cast rvalue.
             Ok(builder.build(
@@ -413,6 +537,9 @@ fn lower_expr_inner<'gcx>(
         }
 
         hir::ExprKind::Field(target, name) => {
+            if let Some(target) = cx.resolve_hierarchical_name(expr_id, env)? {
+                return lower_hierarchical_name(builder, ty, target);
+            }
             let target_ty = cx.self_determined_type(target, env);
             let value = cx.mir_rvalue(target, env);
             if let Some(intf) = target_ty.and_then(|ty| ty.get_interface()) {
@@ -425,10 +552,33 @@ fn lower_expr_inner<'gcx>(
                 }
             } else {
                 let (field, _) = cx.resolve_field_access(expr_id, env)?;
+
+                // A packed struct unpacked from a simple bit vector consists
+                // of one bit-range extract `[offset +: width]` per field. Pick
+                // the field's extract directly, rather than reconstructing
+                // the entire struct only to access a single field.
+                let packed = value
+                    .ty
+                    .get_struct()
+                    .map(|s| s.ast.packed && s.kind == ast::StructKind::Struct)
+                    .unwrap_or(false);
+                if let (true, RvalueKind::ConstructStruct(fields)) = (packed, &value.kind) {
+                    return Ok(fields[field]);
+                }
                 Ok(builder.build(ty, RvalueKind::Member { value, field }))
             }
         }
 
+        // Casts to `void` evaluate the expression for its side effects and
+        // discard the result.
+        hir::ExprKind::Cast(_, expr) if ty.is_void() => {
+            let value = cx.mir_rvalue(expr, env);
+            if value.is_error() {
+                return Err(());
+            }
+            Ok(builder.build(ty, RvalueKind::Transmute(value)))
+        }
+
         // Casts are handled by the `cast_type` query, and the cast handling
         // that happens after the lowering to an MIR rvalue.
         hir::ExprKind::Cast(_, expr)
@@ -438,68 +588,39 @@ fn lower_expr_inner<'gcx>(
         | hir::ExprKind::Builtin(hir::BuiltinCall::Unsigned(expr)) => Ok(cx.mir_rvalue(expr, env)),
 
         hir::ExprKind::Inside(expr, ref ranges) => {
-            // By default nothing matches.
-            let mut check = builder.build(
-                ty,
-                RvalueKind::Const(cx.intern_value(value::make_int(ty, Zero::zero()))),
-            );
-
             // Determine the intermediate type for the comparisons.
             let comp_ty = cx.need_operation_type(expr_id, env);
 
             // Determine the result type for the comparison.
             let out_ty = cx.cast_type(expr_id, env).unwrap().init;
 
-            // Compare the LHS against all ranges.
             let lhs = cx.mir_rvalue(expr, env);
-            for r in ranges {
-                let arg = match r.value {
-                    hir::InsideRange::Single(expr) => {
-                        // Check if the value matches the LHS.
-                        let expr_rv = cx.mir_rvalue(expr, env);
-                        make_int_comparison(
-                            &builder.with(expr),
-                            out_ty,
-                            comp_ty,
-                            IntCompOp::Eq,
-                            lhs,
-                            expr_rv,
-                        )
-                    }
-                    hir::InsideRange::Range(lo, hi) => {
-                        // Check if the LHS is within [lo:hi], inclusive.
-                        let lo_rv = cx.mir_rvalue(lo, env);
-                        let hi_rv = cx.mir_rvalue(hi, env);
-                        let lo_chk = make_int_comparison(
-                            &builder.with(lo),
-                            out_ty,
-                            comp_ty,
-                            IntCompOp::Geq,
-                            lhs,
-                            lo_rv,
-                        );
-                        let hi_chk = make_int_comparison(
-                            &builder.with(hi),
-                            out_ty,
-                            comp_ty,
-                            IntCompOp::Leq,
-                            lhs,
-                            hi_rv,
-                        );
-                        make_binary_bitwise(
-                            builder,
-                            ty,
-                            BinaryBitwiseOp::And,
-                            false,
-                            lo_chk,
-                            hi_chk,
-                        )
-                    }
-                };
-                check = make_binary_bitwise(builder, ty, BinaryBitwiseOp::Or, false, check, arg);
-            }
+            Ok(lower_inside(builder, ty, out_ty, comp_ty, lhs, ranges))
+        }
+
+        hir::ExprKind::StreamConcat(..) => {
+            cx.emit(
+                DiagBuilder2::error(
+                    "unsupported: streaming concatenation outside of an assignment target",
+                )
+                .span(span),
+            );
+            Err(())
+        }
 
-            Ok(check)
+        // The method has been resolved during type checking, but calls to
+        // tasks and functions cannot be lowered yet.
+        hir::ExprKind::MethodCall(target, name, _) => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "unsupported: call to `{}` of interface `{}`",
+                    name,
+                    cx.span(target).extract()
+                ))
+                .span(span)
+                .add_note("Calls to tasks and functions are not supported yet"),
+            );
+            Err(())
         }
 
         hir::ExprKind::FunctionCall(..) => {
@@ -515,6 +636,141 @@ fn lower_expr_inner<'gcx>(
     }
 }
 
+/// Lower a check whether a value is contained in a set of values and ranges.
+///
+/// This implements the `inside` operator as well as the items of a
+/// `case ... inside` statement. See IEEE 1800-2017 §11.4.13.
+fn lower_inside<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    out_ty: &'a UnpackedType<'a>,
+    comp_ty: &'a UnpackedType<'a>,
+    lhs: &'a Rvalue<'a>,
+    ranges: &[Spanned<hir::InsideRange>],
+) -> &'a Rvalue<'a> {
+    let cx = builder.cx;
+    let env = builder.env;
+
+    // By default nothing matches.
+    let mut check = builder.build(
+        ty,
+        RvalueKind::Const(cx.intern_value(value::make_int(ty, Zero::zero()))),
+    );
+
+    // Compare the LHS against all ranges.
+    for r in ranges {
+        let arg = match r.value {
+            hir::InsideRange::Single(expr) => {
+                // Check if the value matches the LHS. The `x` and `z` bits of
+                // a constant value match anything, as for the `==?` operator.
+                let builder = builder.with(expr);
+                let expr_rv = cx.mir_rvalue(expr, env);
+                let (lhs, expr_rv) = match wildcard_mask(&builder, comp_ty, expr_rv) {
+                    Some(mask) => (
+                        make_binary_bitwise(
+                            &builder,
+                            comp_ty,
+                            BinaryBitwiseOp::And,
+                            false,
+                            lhs,
+                            mask,
+                        ),
+                        make_binary_bitwise(
+                            &builder,
+                            comp_ty,
+                            BinaryBitwiseOp::And,
+                            false,
+                            expr_rv,
+                            mask,
+                        ),
+                    ),
+                    None => (lhs, expr_rv),
+                };
+                make_int_comparison(&builder, out_ty, comp_ty, IntCompOp::Eq, lhs, expr_rv)
+            }
+            hir::InsideRange::Range(lo, hi) => {
+                // Check if the LHS is within [lo:hi], inclusive.
+                let lo_rv = cx.mir_rvalue(lo, env);
+                let hi_rv = cx.mir_rvalue(hi, env);
+                let lo_chk = make_int_comparison(
+                    &builder.with(lo),
+                    out_ty,
+                    comp_ty,
+                    IntCompOp::Geq,
+                    lhs,
+                    lo_rv,
+                );
+                let hi_chk = make_int_comparison(
+                    &builder.with(hi),
+                    out_ty,
+                    comp_ty,
+                    IntCompOp::Leq,
+                    lhs,
+                    hi_rv,
+                );
+                make_binary_bitwise(builder, ty, BinaryBitwiseOp::And, false, lo_chk, hi_chk)
+            }
+        };
+        check = make_binary_bitwise(builder, ty, BinaryBitwiseOp::Or, false, check, arg);
+    }
+    check
+}
+
+/// Compute the mask of the bits that a wildcard equality compares.
+///
+/// The `x` and `z` bits of a constant value are wildcards. Returns `None` if
+/// the value is not constant or has no such bits.
+fn wildcard_mask<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    value: &'a Rvalue<'a>,
+) -> Option<&'a Rvalue<'a>> {
+    if !value.is_const() || value.is_error() {
+        return None;
+    }
+    let special_bits = match builder.cx.const_mir_rvalue(value.into()).kind {
+        ValueKind::Int(_, ref special_bits, _) if special_bits.any() => special_bits.clone(),
+        _ => return None,
+    };
+
+    // The special bits are stored MSB first and may be fewer than the bits of
+    // the operation type, in which case they are aligned to the LSB.
+    let width = ty.simple_bit_vector(builder.cx, builder.span).size;
+    let len = special_bits.len();
+    let mut mask = BigInt::zero();
+    for i in (0..width).rev() {
+        mask <<= 1;
+        if i >= len || !special_bits[len - 1 - i] {
+            mask |= BigInt::one();
+        }
+    }
+    Some(builder.constant(value::make_int(ty, mask)))
+}
+
+/// Lower the check whether the expression of a `case ... inside` statement
+/// matches the values and ranges of one of its items.
+pub(crate) fn lower_case_inside_item<'a>(
+    cx: &impl Context<'a>,
+    stmt_id: NodeId,
+    expr: NodeId,
+    ranges: &[Spanned<hir::InsideRange>],
+    env: ParamEnv,
+) -> &'a Rvalue<'a> {
+    let builder = Builder {
+        cx,
+        span: cx.span(stmt_id),
+        expr: stmt_id,
+        env,
+    };
+    let comp_ty = cx.need_operation_type(stmt_id, env);
+    if comp_ty.is_error() {
+        return builder.error();
+    }
+    let ty = UnpackedType::make_logic();
+    let lhs = cx.mir_rvalue(expr, env);
+    lower_inside(&builder, ty, ty, comp_ty, lhs, ranges)
+}
+
 /// Compute the base and length of an indexing operation.
 ///
 /// Determine the index of the LSB and the width of the selection. Note that
@@ -542,24 +798,35 @@ pub(crate) fn compute_indexing<'gcx>(
                 .unwrap(),
         ),
         hir::IndexMode::Many(ast::RangeMode::RelativeDown, base, delta) => {
-            let base = cx.mir_rvalue(base, env);
-            let delta_rvalue = cx.mir_rvalue(delta, env);
-            assert_type!(delta_rvalue.ty, base.ty, delta_rvalue.span, builder.cx);
-            let base = builder.build(
-                base.ty,
-                RvalueKind::IntBinaryArith {
-                    op: IntBinaryArithOp::Sub,
-                    sign: base.ty.get_simple_bit_vector().unwrap().sign,
-                    domain: base.ty.domain(),
-                    lhs: base,
-                    rhs: delta_rvalue,
-                },
-            );
+            // The selection `x[b -: w]` covers `x[b]` down to `x[b-w+1]`.
             let length = builder
                 .cx
                 .constant_int_value_of(delta, env)?
                 .to_usize()
                 .unwrap();
+            let base = widen_index(&builder, cx.mir_rvalue(base, env));
+            let base = if length > 1 {
+                let shift = builder.build(
+                    base.ty,
+                    RvalueKind::Const(
+                        builder
+                            .cx
+                            .intern_value(value::make_int(base.ty, (length - 1).into())),
+                    ),
+                );
+                builder.build(
+                    base.ty,
+                    RvalueKind::IntBinaryArith {
+                        op: IntBinaryArithOp::Sub,
+                        sign: ty::Sign::Signed,
+                        domain: ty::Domain::TwoValued,
+                        lhs: base,
+                        rhs: shift,
+                    },
+                )
+            } else {
+                base
+            };
             (base, length)
         }
         hir::IndexMode::Many(ast::RangeMode::Absolute, lhs, rhs) => {
@@ -569,7 +836,7 @@ pub(crate) fn compute_indexing<'gcx>(
             let base_ty = SbvType::new(
                 ty::Domain::TwoValued,
                 ty::Sign::Signed,
-                max(base.bits() as usize, 1),
+                base.bits() as usize + 1,
             )
             .to_unpacked(builder.cx);
             let base = cx.intern_value(value::make_int(base_ty, base));
@@ -582,14 +849,79 @@ pub(crate) fn compute_indexing<'gcx>(
     })
 }
 
+/// Check that an indexed part-select with a constant base lies within the
+/// bounds of the indexed dimension.
+///
+/// Selecting bits outside the declared range is legal, but such bits read as
+/// `x` and writes to them are dropped. This is rarely intended, so a warning is
+/// emitted if the base of a `+:` or `-:` select is constant and the selection
+/// exceeds the dimension. Selections with a runtime base are not checked. The
+/// `base` and `length` are the ones determined by `compute_indexing`.
+pub(crate) fn check_indexing_bounds<'gcx>(
+    cx: &impl Context<'gcx>,
+    origin: NodeId,
+    mode: hir::IndexMode,
+    base: &'gcx Rvalue<'gcx>,
+    length: usize,
+    target_dim: ty::Dim<'gcx>,
+) {
+    match mode {
+        hir::IndexMode::Many(ast::RangeMode::RelativeUp, ..)
+        | hir::IndexMode::Many(ast::RangeMode::RelativeDown, ..) => (),
+        _ => return,
+    }
+    if length == 0 {
+        return;
+    }
+
+    // Determine the valid index range of the dimension.
+    let (lo, hi) = match (target_dim.get_range(), target_dim.get_size()) {
+        (Some(r), _) => (
+            BigInt::from(r.offset),
+            BigInt::from(r.offset) + BigInt::from(r.size) - BigInt::one(),
+        ),
+        (None, Some(size)) => (BigInt::zero(), BigInt::from(size) - BigInt::one()),
+        (None, None) => return,
+    };
+
+    // Only constant bases can be checked.
+    if base.is_error() || !base.is_const() {
+        return;
+    }
+    let sel_lo = match cx.const_mir_rvalue_int(base.into()) {
+        Ok(x) => x.clone(),
+        Err(()) => return,
+    };
+    let sel_hi = &sel_lo + BigInt::from(length) - BigInt::one();
+
+    // Compare the selected range against the dimension.
+    if sel_lo < lo || sel_hi > hi {
+        let span = cx.span(origin);
+        cx.emit(
+            DiagBuilder2::warning(format!("part-select `{}` is out of bounds", span.extract()))
+                .span(span)
+                .add_note(format!(
+                    "Selects bits [{}:{}], but the dimension is `{}`",
+                    sel_hi, sel_lo, target_dim
+                ))
+                .add_note("Out-of-range bits read as `x` and writes to them are dropped"),
+        );
+    }
+}
+
 /// Compute the index adjustment necessary to index into an array dimension that
 /// may start at a non-zero offset.
 ///
 /// This function accounts for indexes such as `x[1]` into `logic [8:1] x`,
-/// which essentially is accessing bit 0 of the 8-bit array `x`.
+/// which essentially is accessing bit 0 of the 8-bit array `x`. The `base` is
+/// the lowest index covered by a selection of `length` elements, or zero for a
+/// single element. In packed dimensions with an ascending range such as
+/// `logic [0:7] x`, the lowest index addresses the MSB, so the selection is
+/// mirrored within the dimension.
 pub(crate) fn adjust_indexing<'a>(
     builder: &Builder<'_, impl Context<'a>>,
     base: &'a Rvalue<'a>,
+    length: usize,
     target_dim: ty::Dim<'a>,
 ) -> &'a Rvalue<'a> {
     match target_dim {
@@ -600,6 +932,31 @@ pub(crate) fn adjust_indexing<'a>(
         | ty::Dim::Unpacked(ty::UnpackedDim::Assoc(_))
         | ty::Dim::Unpacked(ty::UnpackedDim::Queue(_)) => base,
 
+        // Ascending packed ranges count from the MSB, such that the selection
+        // needs to be mirrored.
+        ty::Dim::Packed(ty::PackedDim::Range(r)) if r.dir == ty::RangeDir::Up => {
+            let base = widen_index(builder, base);
+            let top = r.offset + r.size as isize - max(length, 1) as isize;
+            let top = builder.build(
+                base.ty,
+                RvalueKind::Const(
+                    builder
+                        .cx
+                        .intern_value(value::make_int(base.ty, top.into())),
+                ),
+            );
+            builder.build(
+                base.ty,
+                RvalueKind::IntBinaryArith {
+                    op: IntBinaryArithOp::Sub,
+                    sign: ty::Sign::Signed,
+                    domain: ty::Domain::TwoValued,
+                    lhs: top,
+                    rhs: base,
+                },
+            )
+        }
+
         // Part-selects with a zero offset don't need any shift.
         ty::Dim::Packed(ty::PackedDim::Range(r)) | ty::Dim::Unpacked(ty::UnpackedDim::Range(r))
             if r.offset.is_zero() =>
@@ -610,6 +967,7 @@ pub(crate) fn adjust_indexing<'a>(
         // Part-selects with a non-zero offset need the indexed location
         // to be shifted by that offset.
         ty::Dim::Packed(ty::PackedDim::Range(r)) | ty::Dim::Unpacked(ty::UnpackedDim::Range(r)) => {
+            let base = widen_index(builder, base);
             let offset = builder.build(
                 base.ty,
                 RvalueKind::Const(
@@ -632,6 +990,26 @@ pub(crate) fn adjust_indexing<'a>(
     }
 }
 
+/// Extend an index to at least 32 bits, such that shifting it by a dimension's
+/// offset does not overflow.
+fn widen_index<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    base: &'a Rvalue<'a>,
+) -> &'a Rvalue<'a> {
+    let sbvt = base.ty.simple_bit_vector(builder.cx, base.span);
+    if sbvt.size >= 32 {
+        return base;
+    }
+    let ty = SbvType::new(sbvt.domain, sbvt.sign, 32).to_unpacked(builder.cx);
+    builder.build(
+        ty,
+        match sbvt.sign {
+            ty::Sign::Signed => RvalueKind::SignExtend(32, base),
+            ty::Sign::Unsigned => RvalueKind::ZeroExtend(32, base),
+        },
+    )
+}
+
 /// Generate the nodes necessary for a cast operation.
 fn lower_cast<'gcx>(
     builder: &Builder<'_, impl Context<'gcx>>,
@@ -778,6 +1156,18 @@ fn pack_struct<'a>(
     strukt: &'a ty::StructType<'a>,
     to: &'a UnpackedType<'a>,
 ) -> &'a Rvalue<'a> {
+    // The members of a union overlay each other, so packing the first one
+    // suffices.
+    if strukt.is_union() && !strukt.members.is_empty() {
+        if !check_union_widths(builder.cx, value.ty, strukt) {
+            return builder.error();
+        }
+        let field_value =
+            builder.build(strukt.members[0].ty, RvalueKind::Member { value, field: 0 });
+        let field_value = pack_simple_bit_vector(builder, field_value);
+        return builder.build(to, RvalueKind::Transmute(field_value));
+    }
+
     // Pack each of the fields.
     let mut packed_fields = vec![];
     for (i, field) in strukt.members.iter().enumerate() {
@@ -790,6 +1180,42 @@ fn pack_struct<'a>(
     builder.build(to, RvalueKind::Concat(packed_fields))
 }
 
+/// Check that all members of a union are of equal width.
+///
+/// Packing a union into a bit vector, or assigning to it as one, relies on all
+/// members covering the same bits. Emits an error for each member that differs
+/// in width from the first one.
+pub(crate) fn check_union_widths<'a>(
+    cx: &impl Context<'a>,
+    ty: &'a UnpackedType<'a>,
+    strukt: &'a ty::StructType<'a>,
+) -> bool {
+    let first = match strukt.members.first() {
+        Some(x) => x,
+        None => return true,
+    };
+    let width = first.ty.get_bit_size();
+    let mut ok = true;
+    for member in &strukt.members[1..] {
+        if member.ty.get_bit_size() != width {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "members of `{}` must all have the same width to be packed",
+                    ty
+                ))
+                .span(member.name.span)
+                .add_note(format!(
+                    "`{}` is of type `{}`, but `{}` is of type `{}`",
+                    member.name, member.ty, first.name, first.ty
+                ))
+                .span(first.name.span),
+            );
+            ok = false;
+        }
+    }
+    ok
+}
+
 /// Pack an array as a simple bit vector.
 fn pack_array<'a>(
     builder: &Builder<'_, impl Context<'a>>,
@@ -872,14 +1298,26 @@ fn unpack_struct<'a>(
     to: &'a UnpackedType<'a>,
     strukt: &'a ty::StructType<'a>,
 ) -> &'a Rvalue<'a> {
-    // Unpack each of the fields.
-    let mut offset = 0;
+    // Unpack each of the fields. The first field occupies the most significant
+    // bits, and the members of a union all start at the LSB.
+    let sbvts: Vec<_> = strukt
+        .members
+        .iter()
+        .map(|field| field.ty.simple_bit_vector(builder.cx, value.span))
+        .collect();
+    let mut offset = if strukt.is_union() {
+        0
+    } else {
+        sbvts.iter().map(|sbvt| sbvt.size).sum()
+    };
     let mut unpacked_fields = vec![];
-    for field in &strukt.members {
-        let sbvt = field.ty.simple_bit_vector(builder.cx, value.span);
+    for (field, sbvt) in strukt.members.iter().zip(sbvts) {
         let ty =
             SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, 32).to_unpacked(builder.cx);
         let w = sbvt.size;
+        if !strukt.is_union() {
+            offset -= w;
+        }
         let i = builder.build(
             ty,
             RvalueKind::Const(builder.cx.intern_value(value::make_int(ty, offset.into()))),
@@ -894,7 +1332,6 @@ fn unpack_struct<'a>(
         );
         let value = unpack_simple_bit_vector(builder, value, field.ty);
         unpacked_fields.push(value);
-        offset += w;
     }
 
     // Construct the struct.
@@ -1003,6 +1440,74 @@ fn lower_pattern<'a>(
     }
 }
 
+/// Collect the dimensions of a type as seen by the array query functions.
+///
+/// Integer atom types such as `int` are treated as if they had an implicit
+/// `[N-1:0]` packed dimension, as per §20.7 of the standard.
+fn array_query_dims<'a>(arg_ty: &'a UnpackedType<'a>) -> Vec<ty::Dim<'a>> {
+    let mut dims: Vec<_> = arg_ty.dims().collect();
+    if let Some(packed) = arg_ty.resolve_full().core.get_packed() {
+        let packed = packed.resolve_full();
+        match packed.core {
+            ty::PackedCore::IntAtom(atom) if packed.dims.is_empty() => dims.push(ty::Dim::Packed(
+                ty::PackedDim::Range(ty::Range::with_size(atom.bit_size())),
+            )),
+            _ => (),
+        }
+    }
+    dims
+}
+
+/// Lower a hierarchical name such as `top.gen_lanes[2].q` to an rvalue.
+///
+/// Parameters may be referenced anywhere in the design. Variables and ports
+/// are only supported within the current module instance at the moment.
+fn lower_hierarchical_name<'gcx>(
+    builder: &Builder<'_, impl Context<'gcx>>,
+    ty: &'gcx UnpackedType<'gcx>,
+    target: HierarchicalTarget<'gcx>,
+) -> Result<&'gcx Rvalue<'gcx>> {
+    let cx = builder.cx;
+    let hir = cx.hir_of(target.node)?;
+    match hir {
+        HirNode::EnumVariant(..) | HirNode::ValueParam(..) => {
+            let k = cx.constant_value_of(target.node, target.env);
+            return Ok(builder.build(ty, RvalueKind::Const(k)));
+        }
+        _ => (),
+    }
+    if !target.local {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "unsupported: reference to `{}` in another instance or generate loop iteration",
+                builder.span.extract()
+            ))
+            .span(builder.span)
+            .add_note(format!(
+                "`{}` resolves to this {}:",
+                builder.span.extract(),
+                hir.desc()
+            ))
+            .span(hir.human_span()),
+        );
+        return Err(());
+    }
+    match hir {
+        HirNode::VarDecl(decl) => Ok(builder.build(ty, RvalueKind::Var(decl.id))),
+        HirNode::IntPort(port) if ty.resolve_full().core.get_interface().is_some() => {
+            Ok(builder.build(ty, RvalueKind::Intf(port.id)))
+        }
+        HirNode::IntPort(port) => Ok(builder.build(ty, RvalueKind::Port(port.id))),
+        x => {
+            cx.emit(
+                DiagBuilder2::error(format!("{} cannot be used in an expression", x.desc_full()))
+                    .span(builder.span),
+            );
+            Err(())
+        }
+    }
+}
+
 /// Map a unary operator to MIR.
 fn lower_unary<'gcx>(
     builder: &Builder<'_, impl Context<'gcx>>,
@@ -1275,6 +1780,1152 @@ fn lower_string_comparison<'a>(
     builder.build(result_ty, RvalueKind::StringComp { op, lhs, rhs })
 }
 
+/// Map a `$sformatf` call to MIR.
+///
+/// The call is evaluated at compile time and yields a constant string. All
+/// arguments must therefore be constant, and the format string must be a
+/// string literal.
+fn lower_sformatf<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    args: &'a [ast::CallArg<'a>],
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    let mut fmt = None;
+    let mut values = vec![];
+    for arg in args {
+        let expr = match arg.expr {
+            Some(ref x) => x,
+            None => {
+                cx.emit(DiagBuilder2::error("`$sformatf` argument missing").span(arg.span));
+                return Err(());
+            }
+        };
+        let hir = cx.hir_of_expr(Ref(expr))?;
+
+        // The first argument is the format string.
+        if fmt.is_none() {
+            match hir.kind {
+                hir::ExprKind::StringConst(x) => {
+                    fmt = Some(x);
+                    continue;
+                }
+                _ => {
+                    cx.emit(
+                        DiagBuilder2::error("format string must be a string literal")
+                            .span(expr.span()),
+                    );
+                    return Err(());
+                }
+            }
+        }
+
+        // All other arguments must be constant.
+        let mir = cx.mir_rvalue(hir.id, builder.env);
+        if mir.is_error() {
+            return Err(());
+        }
+        if !mir.is_const() {
+            cx.emit(
+                DiagBuilder2::error("unsupported: non-constant `$sformatf` argument")
+                    .span(expr.span())
+                    .add_note("`$sformatf` is evaluated at compile time"),
+            );
+            return Err(());
+        }
+        values.push((cx.const_mir_rvalue(mir.into()), expr.span()));
+    }
+    let bytes = value::format_values(cx, fmt.unwrap(), &values)?;
+    Ok(builder.constant(value::make_string(ty, bytes)))
+}
+
+/// Lower a display task such as `$display` to a print.
+fn lower_print<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    task: hir::DisplayTask,
+    args: &'a [ast::CallArg<'a>],
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+
+    // The file tasks take the file descriptor as their first argument.
+    let func = match task {
+        hir::DisplayTask::FileDisplay => Some("$fdisplay"),
+        hir::DisplayTask::FileWrite => Some("$fwrite"),
+        _ => None,
+    };
+    let (file, args) = match (func, args.split_first()) {
+        (None, _) => (None, args),
+        (
+            Some(func),
+            Some((
+                ast::CallArg {
+                    expr: Some(ref file),
+                    ..
+                },
+                rest,
+            )),
+        ) => {
+            let file = lower_unsigned_arg(builder, func, cx.hir_of_expr(Ref(file))?.id)?;
+            (Some(make_unsigned_resize(builder, file, 32)), rest)
+        }
+        (Some(func), _) => {
+            cx.emit(
+                DiagBuilder2::error(format!("`{}` requires a file descriptor argument", func))
+                    .span(builder.span),
+            );
+            return Err(());
+        }
+    };
+
+    let (mut format, args) = lower_print_format(builder, args)?;
+    match task {
+        hir::DisplayTask::Display | hir::DisplayTask::Monitor | hir::DisplayTask::FileDisplay => {
+            format.push('\n')
+        }
+        hir::DisplayTask::Write | hir::DisplayTask::FileWrite => (),
+    }
+    Ok(builder.build(ty, RvalueKind::Print { file, format, args }))
+}
+
+/// Lower the arguments of a display task to a format string and the values
+/// it formats.
+///
+/// Every string literal argument acts as the format string for the arguments
+/// that follow it. Arguments without a corresponding format specifier are
+/// printed in decimal, and empty arguments print as a single space, as per
+/// §21.2.1 "The display and write tasks".
+fn lower_print_format<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    args: &'a [ast::CallArg<'a>],
+) -> Result<(String, Vec<&'a Rvalue<'a>>)> {
+    let cx = builder.cx;
+    let mut format = String::new();
+    let mut values = vec![];
+    let mut args = args.iter();
+    let mut last_fmt = None;
+    while let Some(arg) = args.next() {
+        let expr = match arg.expr {
+            Some(ref x) => x,
+            None => {
+                format.push(' ');
+                continue;
+            }
+        };
+        let hir = cx.hir_of_expr(Ref(expr))?;
+
+        // Arguments that are not a format string are printed in decimal.
+        let fmt = match hir.kind {
+            hir::ExprKind::StringConst(x) => x,
+            _ => {
+                if let Some(fmt_span) = last_fmt {
+                    cx.emit(
+                        DiagBuilder2::warning(format!(
+                            "argument `{}` has no corresponding format specifier",
+                            expr.span().extract()
+                        ))
+                        .span(expr.span())
+                        .add_note("The argument is printed in decimal. Format string is:")
+                        .span(fmt_span),
+                    );
+                }
+                let value = lower_print_arg(builder, hir.id)?;
+                format.push('%');
+                format.push(print_conversion(value, 'd'));
+                values.push(value);
+                continue;
+            }
+        };
+
+        // Parse the format string and consume an argument for each format
+        // specifier.
+        last_fmt = None;
+        let mut chars = fmt.value.as_str().chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                format.push(c);
+                continue;
+            }
+            let fspec = value::parse_format_spec(&mut chars);
+            let spec = format!("%{}", fspec.text);
+            let conv = match fspec.conv {
+                Some(c) => c,
+                None => {
+                    cx.emit(
+                        DiagBuilder2::warning(
+                            "format string ends with an incomplete `%` specifier",
+                        )
+                        .span(fmt.span),
+                    );
+                    format.push_str("%%");
+                    break;
+                }
+            };
+            match conv {
+                '%' => format.push_str("%%"),
+                'm' => format.push_str(&print_scope_name(cx, builder.expr)),
+                'd' | 'h' | 'x' | 'o' | 'b' | 'c' | 's' | 't' | 'e' | 'f' | 'g' => {
+                    let arg_expr = match args.next() {
+                        Some(ast::CallArg {
+                            expr: Some(ref x), ..
+                        }) => x,
+                        _ => {
+                            cx.emit(
+                                DiagBuilder2::warning(format!(
+                                    "no argument given for format specifier `{}{}`",
+                                    spec, conv
+                                ))
+                                .span(fmt.span)
+                                .add_note("The specifier is printed verbatim"),
+                            );
+                            format.push('%');
+                            format.push_str(&spec);
+                            format.push(conv);
+                            continue;
+                        }
+                    };
+                    let value = lower_print_arg(builder, cx.hir_of_expr(Ref(arg_expr))?.id)?;
+                    format.push_str(&spec);
+                    format.push(print_conversion(value, conv));
+                    values.push(value);
+                    last_fmt = Some(fmt.span);
+                }
+                _ => {
+                    cx.emit(
+                        DiagBuilder2::warning(format!(
+                            "unsupported format specifier `{}{}`",
+                            spec, conv
+                        ))
+                        .span(fmt.span)
+                        .add_note("The specifier is printed verbatim"),
+                    );
+                    format.push('%');
+                    format.push_str(&spec);
+                    format.push(conv);
+                }
+            }
+        }
+    }
+    Ok((format, values))
+}
+
+/// Lower a simulation control task such as `$finish`.
+///
+/// The optional first argument is the finish number, which selects how much
+/// diagnostic information is printed when the simulation ends. The remaining
+/// arguments of `$fatal` form a message formatted as for `$display`, as per
+/// §20.10 "Severity tasks".
+fn lower_finish<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    task: hir::FinishTask,
+    args: &'a [ast::CallArg<'a>],
+) -> Result<&'a Rvalue<'a>> {
+    let (verbosity, rest) = match args.split_first() {
+        Some((
+            ast::CallArg {
+                expr: Some(ref arg),
+                ..
+            },
+            rest,
+        )) => (lower_finish_number(builder, arg)?, rest),
+        Some((_, rest)) => (1, rest),
+        None => (1, args),
+    };
+    let message = match task {
+        hir::FinishTask::Fatal => {
+            let (text, args) = lower_print_format(builder, rest)?;
+            let format = if text.is_empty() {
+                String::from("Fatal\n")
+            } else {
+                format!("Fatal: {}\n", text)
+            };
+            let void_ty = UnpackedType::make_void();
+            Some(builder.build(
+                void_ty,
+                RvalueKind::Print {
+                    file: None,
+                    format,
+                    args,
+                },
+            ))
+        }
+        hir::FinishTask::Finish | hir::FinishTask::Stop => None,
+    };
+    Ok(builder.build(
+        ty,
+        RvalueKind::Finish {
+            stop: task == hir::FinishTask::Stop,
+            verbosity,
+            status: (task == hir::FinishTask::Fatal) as u32,
+            message,
+        },
+    ))
+}
+
+/// Lower a call to the `$dumpvars` task.
+///
+/// The first argument gives the number of levels of the hierarchy to dump, and
+/// the remaining arguments name the instances, generate blocks, and variables
+/// to dump. See §21.7.1.2 "Specifying variables to be dumped ($dumpvars)".
+///
+/// The selected scopes are expanded into the variables they contain, such
+/// that the simulator is told the VCD type and width of every variable.
+fn lower_dump_vars<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    args: &'a [ast::CallArg<'a>],
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    let (levels, scopes) = match args.split_first() {
+        Some((
+            ast::CallArg {
+                expr: Some(ref arg),
+                ..
+            },
+            rest,
+        )) => (lower_dump_levels(builder, arg)?, rest),
+        Some((_, rest)) => (0, rest),
+        None => (0, args),
+    };
+    let mut vars = vec![];
+
+    // Without scopes, dump the instance that calls the task.
+    if scopes.is_empty() {
+        let mut current = builder.expr;
+        while let Some(parent) = cx.parent_node_id(current) {
+            if let Ok(HirNode::Module(module)) = cx.hir_of(parent) {
+                collect_dump_vars(cx, module.ast, builder.env, "", levels, &mut vars)?;
+                break;
+            }
+            current = parent;
+        }
+    }
+
+    for arg in scopes {
+        let arg = match arg.expr {
+            Some(ref x) => x,
+            None => continue,
+        };
+        let hir = cx.hir_of_expr(Ref(arg))?;
+        let target = cx.resolve_hierarchical_name(hir.id, builder.env)?;
+        let (node, env) = match target {
+            Some(HierarchicalTarget {
+                kind: HierarchicalKind::Scope(scope),
+                env,
+                ..
+            }) if !scope.as_all().is_clocking_decl() => {
+                let path = lower_dump_path(builder, hir)?;
+                collect_dump_vars(cx, scope, env, &path, levels, &mut vars)?;
+                continue;
+            }
+            Some(HierarchicalTarget {
+                kind: HierarchicalKind::Value,
+                node,
+                env,
+                ..
+            }) => (node, env),
+            Some(..) => (hir.id, builder.env),
+            // Variables of the calling scope are not hierarchical names.
+            None => (cx.resolve_node(hir.id, builder.env)?, builder.env),
+        };
+        let kind = match cx.hir_of(node) {
+            Ok(HirNode::VarDecl(decl)) => decl.kind,
+            Ok(HirNode::IntPort(port)) => port.kind,
+            _ => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`$dumpvars` requires an instance or a variable, but `{}` is neither",
+                        arg.span().extract()
+                    ))
+                    .span(arg.span()),
+                );
+                return Err(());
+            }
+        };
+        let path = lower_dump_path(builder, hir)?;
+        let ty = cx.type_of(node, env)?;
+        if !push_dump_var(&mut vars, path, kind, ty) {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` cannot be dumped into a VCD file",
+                    arg.span().extract()
+                ))
+                .span(arg.span())
+                .add_note(format!(
+                    "Only variables of a fixed width can be dumped, but `{}` is of type `{}`",
+                    arg.span().extract(),
+                    ty
+                )),
+            );
+            return Err(());
+        }
+    }
+    Ok(builder.build(ty, RvalueKind::DumpVars(vars)))
+}
+
+/// Determine the hierarchical path of a scope or variable passed to the
+/// `$dumpvars` task, relative to the instance that calls the task.
+///
+/// Indices into generate loops are evaluated, such that `g[i].x` within a
+/// generate loop names the block of the current iteration.
+fn lower_dump_path<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    expr: &'a hir::Expr<'a>,
+) -> Result<String> {
+    let cx = builder.cx;
+    let base = |id| match cx.hir_of(id)? {
+        HirNode::Expr(x) => lower_dump_path(builder, x),
+        x => bug_span!(cx.span(id), cx, "dump path of non-expression {:?}", x),
+    };
+    match expr.kind {
+        hir::ExprKind::Ident(name) => match cx.resolve_hierarchical_name(expr.id, builder.env)? {
+            // The calling module itself is the root of the path.
+            Some(HierarchicalTarget {
+                kind: HierarchicalKind::Scope(scope),
+                local,
+                ..
+            }) if scope.as_all().is_module() => {
+                if local {
+                    return Ok(String::new());
+                }
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "unsupported: `$dumpvars` of `{}` outside the calling module",
+                        name.value
+                    ))
+                    .span(expr.ast.span())
+                    .add_note("Scopes must be named relative to the calling module"),
+                );
+                Err(())
+            }
+            _ => Ok(name.value.to_string()),
+        },
+        hir::ExprKind::Field(target, name) => {
+            let target = base(target)?;
+            if target.is_empty() {
+                Ok(name.value.to_string())
+            } else {
+                Ok(format!("{}.{}", target, name.value))
+            }
+        }
+        hir::ExprKind::Index(target, hir::IndexMode::One(index)) => Ok(format!(
+            "{}[{}]",
+            base(target)?,
+            cx.constant_int_value_of(index, builder.env)?
+        )),
+        _ => bug_span!(expr.ast.span(), cx, "dump path of {:?}", expr.kind),
+    }
+}
+
+/// Lower the number of levels of a `$dumpvars` call.
+fn lower_dump_levels<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    arg: &'a ast::Expr<'a>,
+) -> Result<u32> {
+    let cx = builder.cx;
+    let value = cx.constant_value_of(cx.hir_of_expr(Ref(arg))?.id, builder.env);
+    let levels = match value.kind {
+        ValueKind::Int(ref v, ..) => v.to_u32(),
+        ValueKind::Error => return Err(()),
+        _ => None,
+    };
+    match levels {
+        Some(levels) => Ok(levels),
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`$dumpvars` levels `{}` must be a constant non-negative integer",
+                    arg.span().extract()
+                ))
+                .span(arg.span()),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Lower the finish number of a simulation control task such as `$finish`.
+fn lower_finish_number<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    arg: &'a ast::Expr<'a>,
+) -> Result<u32> {
+    let cx = builder.cx;
+    let value = cx.constant_value_of(cx.hir_of_expr(Ref(arg))?.id, builder.env);
+    let number = match value.kind {
+        ValueKind::Int(ref v, ..) => v.to_u32().filter(|&v| v <= 2),
+        ValueKind::Error => return Err(()),
+        _ => None,
+    };
+    match number {
+        Some(number) => Ok(number),
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "finish number `{}` must be 0, 1, or 2",
+                    arg.span().extract()
+                ))
+                .span(arg.span()),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Lower an argument of a display task such as `$display`.
+fn lower_print_arg<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    arg: NodeId,
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    let value = cx.mir_rvalue(arg, builder.env);
+    if value.is_error() {
+        return Err(());
+    }
+    if value.ty.get_simple_bit_vector().is_none() {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "unsupported: `{}` of type `{}` cannot be printed",
+                value.span.extract(),
+                value.ty
+            ))
+            .span(value.span)
+            .add_note("Only integral values can be printed at the moment"),
+        );
+        return Err(());
+    }
+    if value.ty.is_simple_bit_vector() {
+        Ok(value)
+    } else {
+        Ok(pack_simple_bit_vector(builder, value))
+    }
+}
+
+/// Determine the conversion character with which a value is printed.
+///
+/// Maps `%d` to `%i` for signed values, since LLHD integers carry no sign.
+fn print_conversion(value: &Rvalue, conv: char) -> char {
+    match conv {
+        'd' if value.ty.get_simple_bit_vector().map(|x| x.is_signed()) == Some(true) => 'i',
+        _ => conv,
+    }
+}
+
+/// Determine the name printed for the `%m` format specifier.
+///
+/// This is the name of the module that contains the display task. The
+/// hierarchical path of the instance is not known at this point.
+fn print_scope_name<'a>(cx: &impl Context<'a>, node_id: NodeId) -> String {
+    let mut current = node_id;
+    while let Some(parent) = cx.parent_node_id(current) {
+        if let Ok(HirNode::Module(module)) = cx.hir_of(parent) {
+            return module.ast.name.to_string();
+        }
+        current = parent;
+    }
+    String::new()
+}
+
+/// Lower the integral argument of a system function such as `$countones`.
+///
+/// The argument is packed into an unsigned two-valued simple bit vector, which
+/// is what the bit counting and random number logic operates on.
+fn lower_unsigned_arg<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    func: &str,
+    arg: NodeId,
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    let value = cx.mir_rvalue(arg, builder.env);
+    if value.is_error() {
+        return Err(());
+    }
+    let sbvt = match value.ty.get_simple_bit_vector() {
+        Some(x) => x,
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` requires an integral argument, but `{}` has type `{}`",
+                    func,
+                    value.span.extract(),
+                    value.ty
+                ))
+                .span(value.span),
+            );
+            return Err(());
+        }
+    };
+    let value = if value.ty.is_simple_bit_vector() {
+        value
+    } else {
+        pack_simple_bit_vector(builder, value)
+    };
+    let value = match sbvt.domain {
+        ty::Domain::TwoValued => value,
+        ty::Domain::FourValued => builder.build(
+            SbvType::new(ty::Domain::TwoValued, sbvt.sign, sbvt.size).to_unpacked(cx),
+            RvalueKind::CastValueDomain {
+                from: sbvt.domain,
+                to: ty::Domain::TwoValued,
+                value,
+            },
+        ),
+    };
+    let value = match sbvt.sign {
+        ty::Sign::Unsigned => value,
+        ty::Sign::Signed => builder.build(
+            SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, sbvt.size).to_unpacked(cx),
+            RvalueKind::CastSign(ty::Sign::Unsigned, value),
+        ),
+    };
+    Ok(value)
+}
+
+/// Resize an unsigned simple bit vector by truncation or zero extension.
+fn make_unsigned_resize<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    value: &'a Rvalue<'a>,
+    size: usize,
+) -> &'a Rvalue<'a> {
+    let sbvt = value.ty.simple_bit_vector(builder.cx, value.span);
+    let ty = SbvType::new(sbvt.domain, sbvt.sign, size).to_unpacked(builder.cx);
+    if sbvt.size < size {
+        builder.build(ty, RvalueKind::ZeroExtend(size, value))
+    } else if sbvt.size > size {
+        builder.build(ty, RvalueKind::Truncate(size, value))
+    } else {
+        value
+    }
+}
+
+/// Lower the optional seed argument of a random number function.
+///
+/// Without a seed, the generator is seeded from the global seed and the
+/// position of the enclosing procedure or subroutine in the design. This makes
+/// simulations reproducible, while different processes still produce different
+/// sequences of numbers. Note that the seed is only read, and not updated as
+/// required for `$random(seed)`.
+fn lower_random_seed<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    func: &str,
+    seed: Option<NodeId>,
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    if let Some(seed) = seed {
+        let seed = lower_unsigned_arg(builder, func, seed)?;
+        return Ok(make_unsigned_resize(builder, seed, 32));
+    }
+    let mut scope = builder.expr;
+    while let Some(parent) = cx.parent_node_id(scope) {
+        match cx.hir_of(scope) {
+            Ok(HirNode::Proc(_)) | Ok(HirNode::Subroutine(_)) => break,
+            _ => scope = parent,
+        }
+    }
+    let mut seed = cx.sess().opts.seed;
+    for &x in &[scope.as_usize() as u32, builder.env.0] {
+        seed = (seed ^ x).wrapping_mul(0x9e37_79b9).rotate_left(16);
+    }
+    Ok(builder.constant_u32(seed))
+}
+
+/// Lower a call to `$urandom_range(max, min)`.
+///
+/// Produces a random number in the inclusive range between the two bounds,
+/// which are swapped if `min` is greater than `max`, as per §18.13.3
+/// "$urandom_range()". The computation is carried out in 64 bits, such that
+/// the size of the full 32 bit range does not overflow.
+fn lower_urandom_range<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    max: NodeId,
+    min: Option<NodeId>,
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    let func = "$urandom_range";
+    let wide_ty = SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, 64).to_unpacked(cx);
+    let bit_ty = SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, 1).to_unpacked(cx);
+    let widen = |value| make_unsigned_resize(builder, make_unsigned_resize(builder, value, 32), 64);
+    let max = widen(lower_unsigned_arg(builder, func, max)?);
+    let min = match min {
+        Some(min) => widen(lower_unsigned_arg(builder, func, min)?),
+        None => widen(builder.constant_u32(0)),
+    };
+
+    // Order the bounds.
+    let swap = make_int_comparison(builder, bit_ty, wide_ty, IntCompOp::Gt, min, max);
+    let ternary = |true_value, false_value| {
+        builder.build(
+            wide_ty,
+            RvalueKind::Ternary {
+                cond: swap,
+                true_value,
+                false_value,
+            },
+        )
+    };
+    let lo = ternary(max, min);
+    let hi = ternary(min, max);
+
+    // Map a random number into the range.
+    let one = builder.constant(value::make_int(wide_ty, BigInt::one()));
+    let range = make_int_binary_arith(builder, wide_ty, IntBinaryArithOp::Sub, hi, lo);
+    let range = make_int_binary_arith(builder, wide_ty, IntBinaryArithOp::Add, range, one);
+    let seed = lower_random_seed(builder, func, None)?;
+    let value = builder.build(ty, RvalueKind::Random(seed));
+    let value = builder.build(wide_ty, RvalueKind::ZeroExtend(64, value));
+    let value = make_int_binary_arith(builder, wide_ty, IntBinaryArithOp::Mod, value, range);
+    let value = make_int_binary_arith(builder, wide_ty, IntBinaryArithOp::Add, value, lo);
+    Ok(builder.build(ty, RvalueKind::Truncate(32, value)))
+}
+
+/// Lower an argument of a system function that must be a string literal, such
+/// as the file name passed to `$fopen`.
+fn lower_string_literal_arg<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    func: &str,
+    arg: NodeId,
+) -> Result<String> {
+    let cx = builder.cx;
+    match cx.hir_of(arg)? {
+        HirNode::Expr(&hir::Expr {
+            kind: hir::ExprKind::StringConst(x),
+            ..
+        }) => Ok(x.value.to_string()),
+        _ => {
+            let span = cx.span(arg);
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "unsupported: `{}` requires a string literal, but got `{}`",
+                    func,
+                    span.extract()
+                ))
+                .span(span),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Lower a call to `$readmemh` or `$readmemb`.
+///
+/// The memory file is read and parsed during compilation, and the call is
+/// lowered to an assignment of the loaded words to the memory. Words that the
+/// file does not cover retain their previous value. See §21.4 "Loading memory
+/// array data from a file".
+fn lower_read_mem<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    ty: &'a UnpackedType<'a>,
+    radix: u32,
+    file: NodeId,
+    mem: NodeId,
+    start: Option<NodeId>,
+    end: Option<NodeId>,
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    let func = match radix {
+        16 => "$readmemh",
+        _ => "$readmemb",
+    };
+
+    // Determine the address range and word type of the memory.
+    let lvalue = cx.mir_lvalue(mem, builder.env);
+    let current = cx.mir_rvalue(mem, builder.env);
+    if lvalue.is_error() || current.is_error() {
+        return Err(());
+    }
+    let range = match current.ty.outermost_dim() {
+        Some(ty::Dim::Unpacked(ty::UnpackedDim::Range(r))) => r,
+        Some(ty::Dim::Unpacked(ty::UnpackedDim::Array(size))) => ty::Range {
+            size,
+            dir: ty::RangeDir::Up,
+            offset: 0,
+        },
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` requires an unpacked array as memory, but `{}` has type `{}`",
+                    func,
+                    current.span.extract(),
+                    current.ty
+                ))
+                .span(current.span),
+            );
+            return Err(());
+        }
+    };
+    let word_ty = current.ty.pop_dim(cx).unwrap();
+    let word_size = match word_ty.get_simple_bit_vector() {
+        Some(sbvt) => sbvt.size,
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "unsupported: `{}` cannot load words of type `{}`",
+                    func, word_ty
+                ))
+                .span(current.span)
+                .add_note("Only memories of integral words can be loaded at the moment"),
+            );
+            return Err(());
+        }
+    };
+
+    // Determine the addresses to be loaded. By default, words are loaded
+    // from the left towards the right address of the memory.
+    let start_addr = match start {
+        Some(start) => lower_mem_address(builder, func, start, range)?,
+        None => range.left(),
+    };
+    let end_addr = match end {
+        Some(end) => lower_mem_address(builder, func, end, range)?,
+        None => range.right(),
+    };
+    let step = if start_addr <= end_addr { 1 } else { -1 };
+
+    // Read and parse the memory file.
+    let name = lower_string_literal_arg(builder, func, file)?;
+    let content = match open_named_file(builder.span, &name) {
+        Ok(x) => x.get_content(),
+        Err(path) => {
+            cx.emit(
+                DiagBuilder2::error(format!("cannot find memory file `{}`", name))
+                    .span(cx.span(file))
+                    .add_note(format!("Looked for `{}`", path.display())),
+            );
+            return Err(());
+        }
+    };
+    let text = String::from_utf8_lossy(content.bytes());
+    let items = match parse_mem_file(&text, radix) {
+        Ok(x) => x,
+        Err(msg) => {
+            cx.emit(
+                DiagBuilder2::error(format!("{} in memory file `{}`", msg, name))
+                    .span(cx.span(file)),
+            );
+            return Err(());
+        }
+    };
+
+    // Assign the words to their addresses.
+    let mask = (BigInt::one() << word_size) - BigInt::one();
+    let mut words = HashMap::new();
+    let mut addr = BigInt::from(start_addr);
+    for item in items {
+        let word = match item {
+            MemFileItem::Address(x) => {
+                addr = x;
+                continue;
+            }
+            MemFileItem::Word(x) => x,
+        };
+        let index = addr
+            .to_isize()
+            .filter(|&a| a >= start_addr.min(end_addr) && a <= start_addr.max(end_addr));
+        let index = match index {
+            Some(x) => (x - range.offset) as usize,
+            None => {
+                cx.emit(
+                    DiagBuilder2::warning(format!(
+                        "memory file `{}` loads address {}, which is outside the range {} to {}",
+                        name, addr, start_addr, end_addr
+                    ))
+                    .span(cx.span(file))
+                    .add_note("This and all subsequent words are ignored"),
+                );
+                break;
+            }
+        };
+        let value = value::make_int(word_ty, word & &mask);
+        words.insert(index, builder.constant(value));
+        addr += step;
+    }
+
+    // Assign the loaded words to the memory, keeping the current value of the
+    // remaining words.
+    let elements = (0..range.size)
+        .map(|i| {
+            let value = words.remove(&i).unwrap_or_else(|| {
+                builder.build(
+                    word_ty,
+                    RvalueKind::Index {
+                        value: current,
+                        base: builder.constant_u32(i as u32),
+                        length: 0,
+                    },
+                )
+            });
+            (i, value)
+        })
+        .collect();
+    let rvalue = builder.build(current.ty, RvalueKind::ConstructArray(elements));
+    let assign = builder.build(
+        current.ty,
+        RvalueKind::Assignment {
+            lvalue,
+            rvalue,
+            result: rvalue,
+        },
+    );
+    Ok(builder.build(ty, RvalueKind::Transmute(assign)))
+}
+
+/// Lower the start or end address argument of `$readmemh` or `$readmemb`.
+fn lower_mem_address<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    func: &str,
+    arg: NodeId,
+    range: ty::Range,
+) -> Result<isize> {
+    let cx = builder.cx;
+    let value = cx.constant_value_of(arg, builder.env);
+    let addr = match value.kind {
+        ValueKind::Int(ref v, ..) => v.to_isize(),
+        ValueKind::Error => return Err(()),
+        _ => None,
+    };
+    match addr {
+        Some(addr) if addr >= range.low() && addr <= range.high() => Ok(addr),
+        _ => {
+            let span = cx.span(arg);
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` address `{}` is outside the memory range [{}:{}]",
+                    func,
+                    span.extract(),
+                    range.left(),
+                    range.right()
+                ))
+                .span(span),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Locate and open a file named in the source text, such as a memory file.
+///
+/// Relative paths are resolved against the directory of the source file that
+/// names them, such that the result does not depend on where moore is run.
+/// The file is opened through the source manager, which loads its contents
+/// once per session rather than once per query. Returns the path that was
+/// tried if the file does not exist.
+fn open_named_file(span: Span, name: &str) -> std::result::Result<Source, PathBuf> {
+    let mut path = PathBuf::from(name);
+    if path.is_relative() {
+        if let Some(dir) = Path::new(&*span.source.get_path()).parent() {
+            path = dir.join(path);
+        }
+    }
+    get_source_manager()
+        .open(&path.to_string_lossy())
+        .ok_or(path)
+}
+
+/// An item in a memory file read by `$readmemh` or `$readmemb`.
+enum MemFileItem {
+    /// An `@` directive, which sets the address of the next word.
+    Address(BigInt),
+    /// A word to be loaded at the current address.
+    Word(BigInt),
+}
+
+/// Parse the contents of a memory file.
+///
+/// Words are numbers in the given radix, and addresses are hexadecimal numbers
+/// prefixed with `@`. Both may contain underscores, and are separated by white
+/// space and comments.
+fn parse_mem_file(text: &str, radix: u32) -> std::result::Result<Vec<MemFileItem>, String> {
+    // Replace comments with white space.
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some(&'/')) => {
+                while chars.peek().map(|&c| c != '\n').unwrap_or(false) {
+                    chars.next();
+                }
+                stripped.push(' ');
+            }
+            ('/', Some(&'*')) => {
+                chars.next();
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => return Err(String::from("unterminated comment")),
+                    }
+                }
+                stripped.push(' ');
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    // Parse the addresses and words.
+    stripped
+        .split_whitespace()
+        .map(|token| {
+            let (digits, radix) = if token.starts_with('@') {
+                (&token[1..], 16)
+            } else {
+                (token, radix)
+            };
+            let digits: String = digits.chars().filter(|&c| c != '_').collect();
+            if digits.chars().any(|c| "xXzZ?".contains(c)) {
+                return Err(format!("unsupported: `x` or `z` digits in `{}`", token));
+            }
+            if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+                return Err(format!("invalid number `{}`", token));
+            }
+            let value = BigInt::parse_bytes(digits.as_bytes(), radix).unwrap();
+            Ok(if token.starts_with('@') {
+                MemFileItem::Address(value)
+            } else {
+                MemFileItem::Word(value)
+            })
+        })
+        .collect()
+}
+
+/// Determine which bit values the control bits of a `$countbits` call select.
+///
+/// Returns whether `1` and `0` bits are to be counted. Control bits `x` and `z`
+/// are accepted, but never match since the emitted logic is two-valued.
+fn lower_count_bits_control<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    control: &'a [ast::CallArg<'a>],
+) -> Result<(bool, bool)> {
+    let cx = builder.cx;
+    let mut ones = false;
+    let mut zeros = false;
+    for arg in control {
+        let expr = match arg.expr {
+            Some(ref x) => x,
+            None => {
+                cx.emit(DiagBuilder2::error("`$countbits` control bit missing").span(arg.span));
+                return Err(());
+            }
+        };
+        let mir = cx.mir_rvalue(cx.hir_of_expr(Ref(expr))?.id, builder.env);
+        if mir.is_error() {
+            return Err(());
+        }
+        if !mir.is_const() {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`$countbits` control bit `{}` is not constant",
+                    expr.span().extract()
+                ))
+                .span(expr.span()),
+            );
+            return Err(());
+        }
+        match cx.const_mir_rvalue(mir.into()).kind {
+            ValueKind::Int(_, ref special_bits, _) if special_bits.get(0) == Some(true) => (),
+            ValueKind::Int(ref v, ..) if (v & BigInt::one()).is_one() => ones = true,
+            ValueKind::Int(..) => zeros = true,
+            ValueKind::Error => return Err(()),
+            _ => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`$countbits` control bit `{}` is not a bit value",
+                        expr.span().extract()
+                    ))
+                    .span(expr.span()),
+                );
+                return Err(());
+            }
+        }
+    }
+    Ok((ones, zeros))
+}
+
+/// Count the bits of an unsigned two-valued simple bit vector.
+///
+/// Counts the `1` bits if `ones` is set, and the `0` bits if `zeros` is set.
+/// Constant values are folded. Otherwise the count is the sum of the
+/// individual bits. Produces a 32 bit unsigned count.
+fn make_count_bits<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    value: &'a Rvalue<'a>,
+    ones: bool,
+    zeros: bool,
+) -> Result<&'a Rvalue<'a>> {
+    let cx = builder.cx;
+    let size = value.ty.simple_bit_vector(cx, value.span).size;
+    let count_ty = SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, 32).to_unpacked(cx);
+    let make_count = |count: usize| builder.constant(value::make_int(count_ty, count.into()));
+
+    // Fold constant values right away.
+    if value.is_const() {
+        let int = cx.const_mir_rvalue_int(Ref(value))?;
+        let set = int.to_str_radix(2).bytes().filter(|&b| b == b'1').count();
+        let count = match (ones, zeros) {
+            (true, true) => size,
+            (true, false) => set,
+            (false, true) => size - set,
+            (false, false) => 0,
+        };
+        return Ok(make_count(count));
+    }
+
+    // Sum up the individual bits.
+    let set = match (ones, zeros) {
+        (true, true) => return Ok(make_count(size)),
+        (false, false) => return Ok(make_count(0)),
+        _ => {
+            let bit_ty = SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, 1).to_unpacked(cx);
+            (0..size).fold(make_count(0), |count, i| {
+                let bit = builder.build(
+                    bit_ty,
+                    RvalueKind::Index {
+                        value,
+                        base: builder.constant_u32(i as u32),
+                        length: 0,
+                    },
+                );
+                let bit = builder.build(count_ty, RvalueKind::ZeroExtend(32, bit));
+                make_int_binary_arith(builder, count_ty, IntBinaryArithOp::Add, count, bit)
+            })
+        }
+    };
+    Ok(match zeros {
+        true => make_int_binary_arith(
+            builder,
+            count_ty,
+            IntBinaryArithOp::Sub,
+            make_count(size),
+            set,
+        ),
+        false => set,
+    })
+}
+
+/// Compute the ceiling of the base-2 logarithm of a non-constant value.
+///
+/// The value is expected to be an unsigned two-valued simple bit vector. The
+/// result is the number of positions `i` for which `value > 2**i`, which is
+/// zero for the values 0 and 1, as required by §20.8.1. Produces a 32 bit
+/// unsigned result.
+fn make_clog2<'a>(
+    builder: &Builder<'_, impl Context<'a>>,
+    value: &'a Rvalue<'a>,
+) -> &'a Rvalue<'a> {
+    let cx = builder.cx;
+    let size = value.ty.simple_bit_vector(cx, value.span).size;
+    let count_ty = SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, 32).to_unpacked(cx);
+    let bit_ty = SbvType::new(ty::Domain::TwoValued, ty::Sign::Unsigned, 1).to_unpacked(cx);
+    let zero = builder.constant(value::make_int(count_ty, BigInt::zero()));
+    (0..size).fold(zero, |count, i| {
+        let pow = builder.constant(value::make_int(value.ty, BigInt::one() << i));
+        let cmp = make_int_comparison(builder, bit_ty, value.ty, IntCompOp::Gt, value, pow);
+        let cmp = builder.build(count_ty, RvalueKind::ZeroExtend(32, cmp));
+        make_int_binary_arith(builder, count_ty, IntBinaryArithOp::Add, count, cmp)
+    })
+}
+
 /// Map an integer shift operator to MIR.
 fn lower_shift<'a>(
     builder: &Builder<'_, impl Context<'a>>,
@@ -49,6 +49,57 @@ impl<'a> Lvalue<'a> {
             _ => None,
         }
     }
+
+    /// Call a function on each part of a composite lvalue.
+    ///
+    /// Concatenations, overlays, and destructuring assignments are split into
+    /// their parts recursively. Any other lvalue is passed to `f` as a whole.
+    pub fn for_each_part(&'a self, f: &mut impl FnMut(&'a Lvalue<'a>)) {
+        match self.kind {
+            LvalueKind::Concat(ref values)
+            | LvalueKind::Overlay(ref values)
+            | LvalueKind::DestructArray(ref values)
+            | LvalueKind::DestructStruct(ref values) => {
+                for value in values {
+                    value.for_each_part(f);
+                }
+            }
+            _ => f(self),
+        }
+    }
+
+    /// Find the variable, port, or interface signal at the root of an lvalue.
+    ///
+    /// Walks through member accesses, indexing, and casts, calling `f` on each
+    /// member access and indexing on the way, outermost first. Returns `None`
+    /// if the lvalue has no single root, such as a concatenation.
+    pub fn root(&'a self, mut f: impl FnMut(&'a Lvalue<'a>)) -> Option<&'a Lvalue<'a>> {
+        let mut current = self;
+        loop {
+            current = match current.kind {
+                LvalueKind::Member { value, .. } | LvalueKind::Index { value, .. } => {
+                    f(current);
+                    value
+                }
+                LvalueKind::Transmute(value)
+                | LvalueKind::Truncate(value)
+                | LvalueKind::Extend(value, _)
+                | LvalueKind::Repeat(_, value) => value,
+                LvalueKind::Var(_) | LvalueKind::Port(_) | LvalueKind::IntfSignal(..) => {
+                    return Some(current)
+                }
+                _ => return None,
+            };
+        }
+    }
+
+    /// Get the variable, port, or interface signal at the root of an lvalue.
+    pub fn root_id(&'a self) -> Option<NodeId> {
+        self.root(|_| ()).map(|root| match root.kind {
+            LvalueKind::Var(id) | LvalueKind::Port(id) | LvalueKind::IntfSignal(_, id) => id,
+            _ => unreachable!(),
+        })
+    }
 }
 
 impl<'a> std::fmt::Debug for Lvalue<'a> {
@@ -67,6 +118,13 @@ impl<'a> Print for Lvalue<'a> {
         write!(inner, "Lvalue ")?;
         match self.kind {
             LvalueKind::Transmute(v) => write!(inner, "Transmute({})", ctx.print(outer, v))?,
+            LvalueKind::Truncate(v) => write!(inner, "Truncate({})", ctx.print(outer, v))?,
+            LvalueKind::Extend(v, signed) => write!(
+                inner,
+                "Extend({}, {})",
+                ctx.print(outer, v),
+                if signed { "signed" } else { "unsigned" }
+            )?,
             LvalueKind::DestructArray(ref args) => write!(
                 inner,
                 "DestructArray({})",
@@ -115,6 +173,9 @@ impl<'a> Print for Lvalue<'a> {
             LvalueKind::Repeat(num, arg) => {
                 write!(inner, "Repeat({} x {})", num, ctx.print(outer, arg))?
             }
+            LvalueKind::Overlay(ref args) => {
+                write!(inner, "Overlay({})", ctx.print_comma_separated(outer, args))?
+            }
             LvalueKind::Error => write!(inner, "<error>")?,
         }
         write!(inner, " : {}", self.ty)?;
@@ -130,6 +191,12 @@ pub enum LvalueKind<'a> {
     /// A type cast which does not incur any operation. For example, going from
     /// `bit [31:0]` to `int`, or vice versa.
     Transmute(&'a Lvalue<'a>),
+    /// A narrowing type cast. Only the lower bits of the assigned value are
+    /// stored in the inner lvalue; the upper bits are discarded.
+    Truncate(&'a Lvalue<'a>),
+    /// A widening type cast. The assigned value is zero- or sign-extended to
+    /// the width of the inner lvalue, depending on the flag.
+    Extend(&'a Lvalue<'a>, bool),
     /// Destructor for an array.
     DestructArray(Vec<&'a Lvalue<'a>>),
     /// Destructor for a struct.
@@ -164,6 +231,12 @@ pub enum LvalueKind<'a> {
     /// The value is cast to and treated as a packed bit vector, and the result
     /// is yet another packed bit vector.
     Repeat(usize, &'a Lvalue<'a>),
+    /// Multiple values which overlay the same bits, such as the members of a
+    /// union.
+    ///
+    /// The values are cast to and treated as packed bit vectors of equal
+    /// width, and each is assigned the same value.
+    Overlay(Vec<&'a Lvalue<'a>>),
     /// An error occurred during lowering.
     Error,
 }
@@ -7,6 +7,7 @@
 
 use crate::crate_prelude::*;
 use crate::{
+    inst_details::DumpVar,
     mir::{
         lvalue::Lvalue,
         print::{Context, Print},
@@ -249,6 +250,47 @@ impl<'a> Print for Rvalue<'a> {
                 op,
                 ctx.print(outer, rhs)
             )?,
+            RvalueKind::Print {
+                file,
+                ref format,
+                ref args,
+            } => {
+                if let Some(file) = file {
+                    write!(inner, "FilePrint({}, ", ctx.print(outer, file))?;
+                } else {
+                    write!(inner, "Print(")?;
+                }
+                write!(
+                    inner,
+                    "{:?}, {})",
+                    format,
+                    ctx.print_comma_separated(outer, args)
+                )?
+            }
+            RvalueKind::Random(seed) => write!(inner, "Random({})", ctx.print(outer, seed))?,
+            RvalueKind::FileOpen { ref name, ref mode } => {
+                write!(inner, "FileOpen({:?}, {:?})", name, mode)?
+            }
+            RvalueKind::FileClose(file) => write!(inner, "FileClose({})", ctx.print(outer, file))?,
+            RvalueKind::DumpFile(ref name) => write!(inner, "DumpFile({:?})", name)?,
+            RvalueKind::DumpVars(ref vars) => write!(inner, "DumpVars({:?})", vars)?,
+            RvalueKind::Finish {
+                stop,
+                verbosity,
+                status,
+                message,
+            } => {
+                write!(
+                    inner,
+                    "{}({}, {})",
+                    if stop { "Stop" } else { "Finish" },
+                    verbosity,
+                    status
+                )?;
+                if let Some(message) = message {
+                    write!(inner, " after {}", ctx.print(outer, message))?;
+                }
+            }
             RvalueKind::Error => write!(inner, "<error>")?,
         }
         write!(inner, " : {}", self.ty)?;
@@ -407,6 +449,42 @@ pub enum RvalueKind<'a> {
         lhs: &'a Rvalue<'a>,
         rhs: &'a Rvalue<'a>,
     },
+    /// Print a formatted message, as done by the `$display` task.
+    ///
+    /// The format string carries exactly one specifier for each argument.
+    /// Since LLHD integers have no sign, signed values printed in decimal use
+    /// the `%i` specifier instead of `%d`. If a file descriptor is given, the
+    /// message is printed into that file, as done by the `$fdisplay` task.
+    Print {
+        file: Option<&'a Rvalue<'a>>,
+        format: String,
+        args: Vec<&'a Rvalue<'a>>,
+    },
+    /// Draw a 32 bit number from the random number generator of the current
+    /// process, which is seeded with the given value on first use.
+    Random(&'a Rvalue<'a>),
+    /// Open a file and produce its descriptor, as done by the `$fopen`
+    /// function.
+    FileOpen { name: String, mode: String },
+    /// Close the file with the given descriptor, as done by the `$fclose`
+    /// task.
+    FileClose(&'a Rvalue<'a>),
+    /// Set the name of the VCD file into which waveforms are dumped, as done
+    /// by the `$dumpfile` task.
+    DumpFile(String),
+    /// Dump the waveforms of the given variables into the VCD file, as done
+    /// by the `$dumpvars` task.
+    DumpVars(Vec<DumpVar>),
+    /// End the simulation, as done by the `$finish` task.
+    ///
+    /// The optional message is printed beforehand, as done by the `$fatal`
+    /// task. If `stop` is set, the simulation is suspended rather than ended.
+    Finish {
+        stop: bool,
+        verbosity: u32,
+        status: u32,
+        message: Option<&'a Rvalue<'a>>,
+    },
     /// An error occurred during lowering.
     Error,
 }
@@ -458,6 +536,13 @@ impl<'a> RvalueKind<'a> {
             } => cond.is_const() && true_value.is_const() && false_value.is_const(),
             RvalueKind::Shift { value, amount, .. } => value.is_const() && amount.is_const(),
             RvalueKind::Assignment { .. } => false,
+            RvalueKind::Print { .. } => false,
+            RvalueKind::Random(_) => false,
+            RvalueKind::FileOpen { .. } => false,
+            RvalueKind::FileClose(_) => false,
+            RvalueKind::DumpFile(_) => false,
+            RvalueKind::DumpVars(..) => false,
+            RvalueKind::Finish { .. } => false,
             RvalueKind::Error => true,
         }
     }
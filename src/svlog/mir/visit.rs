@@ -24,9 +24,11 @@ pub trait WalkVisitor<'a> {
 
 impl<'a> WalkVisitor<'a> for bool {}
 impl<'a> WalkVisitor<'a> for usize {}
+impl<'a> WalkVisitor<'a> for u32 {}
 impl<'a> WalkVisitor<'a> for NodeId {}
 impl<'a> WalkVisitor<'a> for ParamEnv {}
 impl<'a> WalkVisitor<'a> for Span {}
+impl<'a> WalkVisitor<'a> for String {}
 impl<'a> WalkVisitor<'a> for ty::UnpackedType<'a> {}
 impl<'a> WalkVisitor<'a> for ty::Sign {}
 impl<'a> WalkVisitor<'a> for ty::Domain {}
@@ -46,6 +48,14 @@ impl<'a, T: WalkVisitor<'a>> WalkVisitor<'a> for Vec<T> {
     }
 }
 
+impl<'a, T: WalkVisitor<'a>> WalkVisitor<'a> for Option<T> {
+    fn walk(&'a self, visitor: &mut dyn Visitor<'a>) {
+        if let Some(x) = self {
+            x.walk(visitor);
+        }
+    }
+}
+
 impl<'a, K, T: WalkVisitor<'a>> WalkVisitor<'a> for HashMap<K, T> {
     fn walk(&'a self, visitor: &mut dyn Visitor<'a>) {
         for x in self.values() {
@@ -81,6 +81,7 @@ pub struct ParamEnvData<'t> {
     values: Vec<(NodeId, ParamEnvBinding<Value<'t>>)>,
     types: Vec<(NodeId, ParamEnvBinding<&'t UnpackedType<'t>>)>,
     intfs: Vec<(NodeId, NodeEnvId)>,
+    binds: Vec<NodeId>,
 }
 
 impl<'t> ParamEnvData<'t> {
@@ -125,10 +126,29 @@ impl<'t> ParamEnvData<'t> {
         self.values.push((node_id, ParamEnvBinding::Direct(value)));
     }
 
+    /// Assign another node's value to a node.
+    pub fn set_indirect_value(&mut self, node_id: NodeId, value: NodeEnvId) {
+        self.values.retain(|&(n, _)| n != node_id);
+        self.values
+            .push((node_id, ParamEnvBinding::Indirect(value)));
+    }
+
     /// Add additional interface parametrizations.
     pub fn add_interfaces(&mut self, iter: impl IntoIterator<Item = (NodeId, NodeEnvId)>) {
         self.intfs.extend(iter);
     }
+
+    /// Check whether a bind directive inserts its instance into this
+    /// particular instance.
+    pub fn has_bind(&self, bind_id: NodeId) -> bool {
+        self.binds.contains(&bind_id)
+    }
+
+    /// Add bind directives that insert their instance into this particular
+    /// instance.
+    pub fn add_binds(&mut self, iter: impl IntoIterator<Item = NodeId>) {
+        self.binds.extend(iter);
+    }
 }
 
 /// A binding in a parameter environment.
@@ -170,12 +190,8 @@ pub(crate) fn compute<'gcx>(
         } => param_env_from_instance(
             cx,
             module.ast,
-            module
-                .params
-                .iter()
-                .cloned()
-                .chain(module.block.params.iter().cloned())
-                .collect(),
+            module.params,
+            &module.block.params,
             env,
             pos,
             named,
@@ -188,7 +204,7 @@ pub(crate) fn compute<'gcx>(
         } => param_env_from_instance(
             cx,
             interface.ast,
-            interface
+            &interface
                 .params
                 .iter()
                 .flat_map(|p| match &p.kind {
@@ -199,8 +215,8 @@ pub(crate) fn compute<'gcx>(
                         x.iter().map(|d| d.id()).collect::<Vec<_>>().into_iter()
                     }
                 })
-                .chain(interface.block.params.iter().cloned())
-                .collect(),
+                .collect::<Vec<_>>(),
+            &interface.block.params,
             env,
             pos,
             named,
@@ -211,70 +227,125 @@ pub(crate) fn compute<'gcx>(
 fn param_env_from_instance<'a>(
     cx: &impl Context<'a>,
     node: &'a dyn ast::AnyNode<'a>,
-    params: Vec<NodeId>,
+    header_params: &[NodeId],
+    body_params: &[NodeId],
     env: ParamEnv,
     pos: &[PosParam],
     named: &[NamedParam],
 ) -> Result<ParamEnv> {
+    // Gather the parameters and whether they can be overridden. Parameters
+    // declared in the body become local if there is a parameter port list
+    // (see §6.20.1).
+    let implicitly_local = !header_params.is_empty();
+    let mut params = vec![];
+    for (&param_id, in_body) in header_params
+        .iter()
+        .map(|id| (id, false))
+        .chain(body_params.iter().map(|id| (id, true)))
+    {
+        let (decl, name) = match cx.ast_of(param_id)? {
+            AstNode::TypeParam(decl, p) => (decl, p.name),
+            AstNode::ValueParam(decl, p) => (decl, p.name),
+            _ => unreachable!(),
+        };
+        let local = if decl.local {
+            Some(format!("`{}` is declared as `localparam` here:", name))
+        } else if in_body && implicitly_local {
+            Some(format!(
+                "`{}` is local since {} has a parameter port list:",
+                name, node
+            ))
+        } else {
+            None
+        };
+        params.push((param_id, name, local));
+    }
+    let overridable: Vec<_> = params
+        .iter()
+        .filter(|(_, _, local)| local.is_none())
+        .collect();
+
     // Associate the positional and named assignments with the actual
     // parameters of the module.
-    let param_iter = pos
-        .iter()
-        .enumerate()
-        .map(|(index, &(span, assign_id))| match params.get(index) {
-            Some(&param_id) => Ok((param_id, (assign_id, env))),
+    let mut failed = false;
+    let mut assigned: Vec<(NodeId, Option<NodeId>, Span)> = vec![];
+    for &(span, assign_id) in pos {
+        match overridable.get(assigned.len()) {
+            Some(&&(param_id, ..)) => assigned.push((param_id, assign_id, span)),
             None => {
                 cx.emit(
-                    DiagBuilder2::error(format!("{} only has {} parameter(s)", node, params.len()))
-                        .span(span),
+                    DiagBuilder2::error(format!(
+                        "{} only has {} parameter(s)",
+                        node,
+                        overridable.len()
+                    ))
+                    .span(span),
                 );
-                Err(())
+                failed = true;
+                break;
             }
-        })
-        .chain(named.iter().map(|&(_span, name, assign_id)| {
-            let names: Vec<_> = params
-                .iter()
-                .flat_map(|&id| match cx.ast_of(id) {
-                    Ok(AstNode::TypeParam(_, p)) => Some((p.name.value, id)),
-                    Ok(AstNode::ValueParam(_, p)) => Some((p.name.value, id)),
-                    Ok(_) => unreachable!(),
-                    Err(()) => None,
-                })
-                .collect();
-            match names
-                .iter()
-                .find(|&(param_name, _)| *param_name == name.value)
-            {
-                Some(&(_, param_id)) => Ok((param_id, (assign_id, env))),
-                None => {
+        }
+    }
+    for &(span, name, assign_id) in named {
+        let param = params
+            .iter()
+            .find(|(_, param_name, _)| param_name.value == name.value);
+        match param {
+            Some((_, param_name, Some(note))) => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "cannot override local parameter `{}` of {}",
+                        name, node
+                    ))
+                    .span(name.span)
+                    .add_note(note.as_str())
+                    .span(param_name.span),
+                );
+                failed = true;
+            }
+            Some(&(param_id, ..)) => {
+                if let Some(&(_, _, prev)) = assigned.iter().find(|&&(id, ..)| id == param_id) {
                     cx.emit(
-                        DiagBuilder2::error(format!("no parameter `{}` in {}", name, node,))
-                            .span(name.span)
-                            .add_note(format!(
-                                "declared parameters are {}",
-                                names
-                                    .iter()
-                                    .map(|&(n, _)| format!("`{}`", n))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            )),
+                        DiagBuilder2::error(format!(
+                            "parameter `{}` assigned more than once",
+                            name
+                        ))
+                        .span(span)
+                        .add_note("Previous assignment was here:")
+                        .span(prev),
                     );
-                    Err(())
+                    failed = true;
+                } else {
+                    assigned.push((param_id, assign_id, span));
                 }
             }
-        }));
-    let param_iter = param_iter
-        .collect::<Vec<_>>()
-        .into_iter()
-        .collect::<Result<Vec<_>>>()?
-        .into_iter();
+            None => {
+                cx.emit(
+                    DiagBuilder2::error(format!("no parameter `{}` in {}", name, node))
+                        .span(name.span)
+                        .add_note(format!(
+                            "declared parameters are {}",
+                            overridable
+                                .iter()
+                                .map(|(_, n, _)| format!("`{}`", n))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )),
+                );
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        return Err(());
+    }
 
     // Split up type and value parameters.
     let mut types = vec![];
     let mut values = vec![];
-    for (param_id, assign_id) in param_iter {
+    for (param_id, assign_id, _) in assigned {
         let assign_id = match assign_id {
-            (Some(i), n) => i.env(n),
+            Some(i) => i.env(env),
             _ => continue,
         };
         match cx.ast_of(param_id)? {
@@ -295,6 +366,7 @@ fn param_env_from_instance<'a>(
         types,
         values,
         intfs: Default::default(),
+        binds: Default::default(),
     });
     cx.add_param_env_context(env, node.id());
     Ok(env)
@@ -149,6 +149,7 @@ fn map_named_array_pattern<'a>(
     // Map things.
     let mut failed = false;
     let mut default: Option<&hir::Expr> = None;
+    let mut type_default: Option<&hir::Expr> = None;
     let mut values = HashMap::<usize, (PatternField, &hir::Expr)>::new();
 
     for &(map, to) in mapping {
@@ -161,10 +162,20 @@ fn map_named_array_pattern<'a>(
         };
         match map {
             hir::PatternMapping::Type(type_id) => {
-                cx.emit(
-                    DiagBuilder2::error("types cannot index into an array").span(cx.span(type_id)),
+                let ty = cx.packed_type_from_ast(
+                    Ref(cx.ast_for_id(type_id).as_all().get_type().unwrap()),
+                    env,
+                    None,
                 );
-                continue;
+                if ty.is_error() {
+                    failed = true;
+                    continue;
+                }
+                // Only the element type can be matched by a type key. Later
+                // keys for the same type take precedence.
+                if ty.resolve_full().is_identical(elem_ty.resolve_full()) {
+                    type_default = Some(to);
+                }
             }
             hir::PatternMapping::Member(member_id) => {
                 // Determine the index for the mapping.
@@ -235,9 +246,10 @@ fn map_named_array_pattern<'a>(
     }
 
     // In case the list of indices provided by the user is incomplete, use the
-    // default to fill in the other elements.
+    // type key matching the element type or the default to fill in the other
+    // elements.
     let values: Vec<_> = if values.len() != length {
-        let default = if let Some(default) = default {
+        let default = if let Some(default) = type_default.or(default) {
             default
         } else {
             cx.emit(
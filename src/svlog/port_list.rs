@@ -170,6 +170,9 @@ pub trait PortedNode<'a>: ast::AnyNode<'a> + resolver::ScopedNode<'a> {
 
     /// Get the items of the node.
     fn items(&self) -> &[ast::Item<'a>];
+
+    /// Get the net type of implicit nets in the node.
+    fn default_nettype(&self) -> Option<ast::NetType>;
 }
 
 impl<'a> PortedNode<'a> for ast::Module<'a> {
@@ -179,6 +182,9 @@ impl<'a> PortedNode<'a> for ast::Module<'a> {
     fn items(&self) -> &[ast::Item<'a>] {
         &self.items
     }
+    fn default_nettype(&self) -> Option<ast::NetType> {
+        self.default_nettype
+    }
 }
 
 impl<'a> PortedNode<'a> for ast::Interface<'a> {
@@ -188,6 +194,9 @@ impl<'a> PortedNode<'a> for ast::Interface<'a> {
     fn items(&self) -> &[ast::Item<'a>] {
         &self.items
     }
+    fn default_nettype(&self) -> Option<ast::NetType> {
+        self.default_nettype
+    }
 }
 
 // Compare and hash `PortedNode` by reference for use in the query system.
@@ -292,28 +301,44 @@ pub(crate) fn canonicalize_ports<'a>(
     // Extend the internal port with default sign, port kind, and data type
     // where necessary in order to arrive at a final internal port list.
     let mut ports = vec![];
-    let default_net_type = ast::NetType::Wire; // TODO: Make changeable by directive
+    let default_net_type = node.default_nettype();
 
     for port in partial_ports.int {
         let port_id = port.ast.id();
 
         // Determine the port kind.
-        let kind = port.kind.unwrap_or_else(|| match port.dir {
-            ast::PortDir::Input | ast::PortDir::Inout => ast::VarKind::Net {
-                ty: default_net_type,
-                kind: ast::NetKind::None,
-            },
-            ast::PortDir::Output => {
-                if port.ty.data == ast::ImplicitType {
+        let kind = port.kind.unwrap_or_else(|| {
+            let implicit_net = match port.dir {
+                ast::PortDir::Input | ast::PortDir::Inout => true,
+                ast::PortDir::Output => port.ty.data == ast::ImplicitType,
+                ast::PortDir::Ref => false,
+            };
+            if !implicit_net {
+                return ast::VarKind::Var;
+            }
+            match default_net_type {
+                Some(ty) => ast::VarKind::Net {
+                    ty,
+                    kind: ast::NetKind::None,
+                },
+                // Ports with an explicit data type become variables if
+                // implicit nets are disabled.
+                None if port.ty.data != ast::ImplicitType => ast::VarKind::Var,
+                None => {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "port `{}` has no net type, but implicit nets are disabled",
+                            port.name
+                        ))
+                        .span(port.name.span)
+                        .add_note("`default_nettype none is in effect; declare the port as `wire`"),
+                    );
                     ast::VarKind::Net {
-                        ty: default_net_type,
+                        ty: ast::NetType::Wire,
                         kind: ast::NetKind::None,
                     }
-                } else {
-                    ast::VarKind::Var
                 }
             }
-            ast::PortDir::Ref => ast::VarKind::Var,
         });
 
         // Verify that `inout` ports are of net kind, and `ref` ports are of var
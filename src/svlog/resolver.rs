@@ -7,10 +7,12 @@
 
 use crate::crate_prelude::*;
 use crate::{
-    ast::AnyNode,
+    ast::{AcceptVisitor, AnyNode, WalkVisitor},
     ast_map::AstNode,
+    codegen::select_generate_case,
     common::{SessionContext, Verbosity},
     hir::HirNode,
+    inst_details::generate_loop_iterations,
     port_list::{self, AsPortedNode},
     ParamEnv,
 };
@@ -307,6 +309,11 @@ pub(crate) fn resolve_node<'gcx>(
                 let within = cx.resolve_node(scope_id, env)?;
                 return cx.resolve_downwards_or_error(name, within);
             }
+            hir::ExprKind::UnitScope(name) => {
+                return cx
+                    .resolve_unit_or_error(name, expr.ast)
+                    .map(|def| def.node.id());
+            }
             _ => (),
         },
         HirNode::Type(ty) => match ty.kind {
@@ -315,6 +322,10 @@ pub(crate) fn resolve_node<'gcx>(
                 let within = cx.resolve_node(scope_id, env)?;
                 return cx.resolve_downwards_or_error(name, within);
             }
+            hir::TypeKind::UnitScope(name) => {
+                let ast = cx.ast_of(node_id)?.get_any().unwrap();
+                return cx.resolve_unit_or_error(name, ast).map(|def| def.node.id());
+            }
             _ => (),
         },
         HirNode::IntPort(port) if port.data.is_none() => {
@@ -400,6 +411,16 @@ pub(crate) fn resolve_field_access<'a>(
     let ty = cx.type_of(target_id, env)?;
     let strukt = if let Some(strukt) = ty.get_struct() {
         strukt
+    } else if &*name.value.as_str() == "triggered" || &*name.value.as_str() == "ended" {
+        // Sequence methods; sequences cannot be declared yet, so whatever we
+        // apply these to is not a sequence.
+        cx.emit(
+            DiagBuilder2::error(format!("`.{}` can only be applied to a sequence", name))
+                .span(hir.human_span())
+                .add_note(format!("Value of type `{}` is not a sequence", ty))
+                .add_note("Named sequences are not supported yet"),
+        );
+        return Err(());
     } else {
         let mut d = DiagBuilder2::error(format!("value of type `{}` is not a struct", ty))
             .span(hir.human_span());
@@ -447,6 +468,7 @@ impl<'a> ScopedNode<'a> for ast::Root<'a> {}
 impl<'a> ScopedNode<'a> for ast::SourceFile<'a> {}
 impl<'a> ScopedNode<'a> for ast::Module<'a> {}
 impl<'a> ScopedNode<'a> for ast::Interface<'a> {}
+impl<'a> ScopedNode<'a> for ast::CheckerDecl<'a> {}
 impl<'a> ScopedNode<'a> for ast::Package<'a> {}
 impl<'a> ScopedNode<'a> for ast::Stmt<'a> {}
 impl<'a> ScopedNode<'a> for ast::Procedure<'a> {}
@@ -456,6 +478,8 @@ impl<'a> ScopedNode<'a> for ast::GenerateFor<'a> {}
 impl<'a> ScopedNode<'a> for ast::GenerateIf<'a> {}
 impl<'a> ScopedNode<'a> for ast::GenerateCase<'a> {}
 impl<'a> ScopedNode<'a> for ast::GenerateBlock<'a> {}
+impl<'a> ScopedNode<'a> for ast::ConstraintForeach<'a> {}
+impl<'a> ScopedNode<'a> for ast::ClockingDecl<'a> {}
 
 // Compare and hash scoped nodes by reference for use in the query system.
 impl<'a> Eq for &'a dyn ScopedNode<'a> {}
@@ -489,6 +513,7 @@ impl<'a> AsScopedNode<'a> for ast::AllNode<'a> {
             ast::AllNode::SourceFile(x) => Some(x),
             ast::AllNode::Module(x) => Some(x),
             ast::AllNode::Interface(x) => Some(x),
+            ast::AllNode::CheckerDecl(x) => Some(x),
             ast::AllNode::Package(x) => Some(x),
             ast::AllNode::Stmt(x) => match x.kind {
                 ast::SequentialBlock(..)
@@ -510,6 +535,8 @@ impl<'a> AsScopedNode<'a> for ast::AllNode<'a> {
             ast::AllNode::GenerateIf(x) => Some(x),
             ast::AllNode::GenerateCase(x) => Some(x),
             ast::AllNode::GenerateBlock(x) => Some(x),
+            ast::AllNode::ConstraintForeach(x) => Some(x),
+            ast::AllNode::ClockingDecl(x) => Some(x),
             _ => None,
         }
     }
@@ -535,6 +562,7 @@ pub(crate) fn generated_scope<'a>(
             defs: Default::default(),
             wildcard_imports: Default::default(),
             subscopes: Default::default(),
+            default_clocking: None,
         },
     );
     debug!("Generating scope {:?}", node);
@@ -588,6 +616,8 @@ pub struct Scope<'a> {
     pub wildcard_imports: Vec<&'a ast::ImportItem<'a>>,
     /// The subscopes.
     pub subscopes: Vec<&'a dyn ScopedNode<'a>>,
+    /// The `default clocking` item in this scope, if any.
+    pub default_clocking: Option<&'a ast::Item<'a>>,
 }
 
 /// A definition in a scope.
@@ -630,6 +660,9 @@ pub enum DefNode<'a> {
     Ast(&'a dyn ast::AnyNode<'a>),
     /// An internal port of a module.
     IntPort(&'a port_list::IntPort<'a>),
+    /// An enum variant expanded from a name with a range, such as the `S1` in
+    /// `enum { S[2] }`, together with the variant's node ID.
+    EnumVariant(&'a ast::EnumName<'a>, NodeId),
 }
 
 impl<'a> std::fmt::Display for DefNode<'a> {
@@ -637,22 +670,27 @@ impl<'a> std::fmt::Display for DefNode<'a> {
         match *self {
             DefNode::Ast(node) => std::fmt::Display::fmt(node, f),
             DefNode::IntPort(node) => std::fmt::Display::fmt(node.ast, f),
+            DefNode::EnumVariant(node, _) => std::fmt::Display::fmt(node, f),
         }
     }
 }
 
-impl<'a> ast::AnyNode<'a> for DefNode<'a> {
-    fn id(&self) -> moore_common::NodeId {
+impl HasSpan for DefNode<'_> {
+    fn span(&self) -> Span {
         match *self {
-            DefNode::Ast(node) => node.id(),
-            DefNode::IntPort(node) => node.id,
+            DefNode::Ast(node) => node.span(),
+            DefNode::IntPort(node) => node.span,
+            DefNode::EnumVariant(node, _) => node.span(),
         }
     }
+}
 
-    fn span(&self) -> moore_common::source::Span {
+impl<'a> ast::AnyNode<'a> for DefNode<'a> {
+    fn id(&self) -> moore_common::NodeId {
         match *self {
-            DefNode::Ast(node) => node.span(),
-            DefNode::IntPort(node) => node.span,
+            DefNode::Ast(node) => node.id(),
+            DefNode::IntPort(node) => node.id,
+            DefNode::EnumVariant(_, id) => id,
         }
     }
 
@@ -660,6 +698,7 @@ impl<'a> ast::AnyNode<'a> for DefNode<'a> {
         match *self {
             DefNode::Ast(node) => node.order(),
             DefNode::IntPort(node) => node.ast.order(),
+            DefNode::EnumVariant(node, _) => node.order(),
         }
     }
 }
@@ -669,6 +708,7 @@ impl<'a> ast::AnyNodeData for DefNode<'a> {
         match *self {
             DefNode::Ast(node) => node.fmt_indefinite(f),
             DefNode::IntPort(node) => node.ast.fmt_indefinite(f),
+            DefNode::EnumVariant(node, _) => node.fmt_indefinite(f),
         }
     }
 }
@@ -678,6 +718,7 @@ impl<'a> ast::BasicNode<'a> for DefNode<'a> {
         match self {
             DefNode::Ast(node) => node.type_name(),
             DefNode::IntPort(node) => node.ast.type_name(),
+            DefNode::EnumVariant(node, _) => node.type_name(),
         }
     }
 
@@ -685,6 +726,7 @@ impl<'a> ast::BasicNode<'a> for DefNode<'a> {
         match self {
             DefNode::Ast(node) => node.as_all(),
             DefNode::IntPort(node) => node.ast.as_all(),
+            DefNode::EnumVariant(node, _) => node.as_all(),
         }
     }
 
@@ -692,6 +734,7 @@ impl<'a> ast::BasicNode<'a> for DefNode<'a> {
         match self {
             DefNode::Ast(node) => node.as_any(),
             DefNode::IntPort(node) => node.ast.as_any(),
+            DefNode::EnumVariant(node, _) => node.as_any(),
         }
     }
 }
@@ -736,6 +779,21 @@ impl<'a, 'c, C: Context<'a>> ScopeGenerator<'a, 'c, C> {
         self.scope.wildcard_imports.push(node);
     }
 
+    /// Register the default clocking block.
+    pub fn set_default_clocking(&mut self, item: &'a ast::Item<'a>) {
+        trace!(" - Setting default clocking {:?}", item);
+        if let Some(existing) = self.scope.default_clocking {
+            self.cx.emit(
+                DiagBuilder2::error("multiple default clocking blocks")
+                    .span(item.span())
+                    .add_note("Previous default clocking block was specified here:")
+                    .span(existing.span()),
+            );
+            return;
+        }
+        self.scope.default_clocking = Some(item);
+    }
+
     /// Register a definition.
     pub fn add_def(&mut self, mut def: Def<'a>) {
         trace!(" - Adding definition {:?}", def);
@@ -753,6 +811,7 @@ impl<'a, 'c, C: Context<'a>> ScopeGenerator<'a, 'c, C> {
                         }
                     }
                 }
+                DefNode::EnumVariant(..) => (),
             };
 
             // We can also have multiple forward declarations of the same name, must
@@ -820,6 +879,29 @@ impl<'a, C: Context<'a>> ast::Visitor<'a> for ScopeGenerator<'a, '_, C> {
         false
     }
 
+    fn pre_visit_checker_decl(&mut self, node: &'a ast::CheckerDecl<'a>) -> bool {
+        self.add_subscope(node);
+        self.add_def(Def {
+            node: DefNode::Ast(node),
+            name: node.name,
+            vis: DefVis::LOCAL | DefVis::GLOBAL,
+            may_override: true,
+            ordered: false,
+        });
+        false
+    }
+
+    fn pre_visit_checker_port(&mut self, node: &'a ast::CheckerPort<'a>) -> bool {
+        self.add_def(Def {
+            node: DefNode::Ast(node),
+            name: node.name,
+            vis: DefVis::LOCAL,
+            may_override: false,
+            ordered: false,
+        });
+        true
+    }
+
     fn pre_visit_modport_name(&mut self, node: &'a ast::ModportName<'a>) -> bool {
         self.add_def(Def {
             node: DefNode::Ast(node),
@@ -831,6 +913,44 @@ impl<'a, C: Context<'a>> ast::Visitor<'a> for ScopeGenerator<'a, '_, C> {
         false
     }
 
+    fn pre_visit_item(&mut self, node: &'a ast::Item<'a>) -> bool {
+        match node.data {
+            ast::ItemData::ClockingDecl(ref decl) if decl.default => {
+                self.set_default_clocking(node)
+            }
+            ast::ItemData::DefaultClocking(..) => self.set_default_clocking(node),
+            _ => (),
+        }
+        true
+    }
+
+    fn pre_visit_clocking_decl(&mut self, node: &'a ast::ClockingDecl<'a>) -> bool {
+        self.add_subscope(node);
+        if let Some(name) = node.name {
+            self.add_def(Def {
+                node: DefNode::Ast(node),
+                name,
+                vis: DefVis::LOCAL | DefVis::HIERARCHICAL,
+                may_override: false,
+                ordered: false,
+            });
+        }
+        false
+    }
+
+    fn pre_visit_clocking_signal(&mut self, node: &'a ast::ClockingSignal<'a>) -> bool {
+        // Clocking signals are only accessible as `cb.signal`. Inside the
+        // clocking block, names refer to the signals of the enclosing scope.
+        self.add_def(Def {
+            node: DefNode::Ast(node),
+            name: node.name,
+            vis: DefVis::HIERARCHICAL,
+            may_override: false,
+            ordered: false,
+        });
+        true
+    }
+
     fn pre_visit_package(&mut self, node: &'a ast::Package<'a>) -> bool {
         self.add_subscope(node);
         self.add_def(Def {
@@ -911,13 +1031,27 @@ impl<'a, C: Context<'a>> ast::Visitor<'a> for ScopeGenerator<'a, '_, C> {
     }
 
     fn pre_visit_enum_name(&mut self, node: &'a ast::EnumName<'a>) -> bool {
-        self.add_def(Def {
-            node: DefNode::Ast(node),
-            name: node.name,
-            vis: DefVis::LOCAL | DefVis::NAMESPACE | DefVis::HIERARCHICAL,
-            may_override: false,
-            ordered: true,
-        });
+        // A name with a range declares one variant per index.
+        let variants = node
+            .get_parent()
+            .and_then(|p| p.as_all().get_enum())
+            .and_then(|enm| self.cx.enum_variants(Ref(enm)).ok());
+        for &(name, id, ast) in variants.iter().flat_map(|v| v.iter()) {
+            if !std::ptr::eq(ast, node) {
+                continue;
+            }
+            self.add_def(Def {
+                node: if id == node.id() {
+                    DefNode::Ast(node)
+                } else {
+                    DefNode::EnumVariant(node, id)
+                },
+                name,
+                vis: DefVis::LOCAL | DefVis::NAMESPACE | DefVis::HIERARCHICAL,
+                may_override: false,
+                ordered: true,
+            });
+        }
         true
     }
 
@@ -985,6 +1119,12 @@ impl<'a, C: Context<'a>> ast::Visitor<'a> for ScopeGenerator<'a, '_, C> {
         true
     }
 
+    fn pre_visit_bind(&mut self, _: &'a ast::Bind<'a>) -> bool {
+        // Bound instances are inserted into the target module, not the scope
+        // that contains the directive.
+        false
+    }
+
     fn pre_visit_generate_for(&mut self, node: &'a ast::GenerateFor<'a>) -> bool {
         self.add_subscope(node);
         false
@@ -1034,6 +1174,11 @@ impl<'a, C: Context<'a>> ast::Visitor<'a> for ScopeGenerator<'a, '_, C> {
         }
     }
 
+    fn pre_visit_constraint_foreach(&mut self, node: &'a ast::ConstraintForeach<'a>) -> bool {
+        self.add_subscope(node);
+        false
+    }
+
     fn pre_visit_foreach_index(&mut self, node: &'a ast::ForeachIndex<'a>) -> bool {
         self.add_def(Def {
             node: DefNode::Ast(node),
@@ -1075,8 +1220,22 @@ pub(crate) fn scope_location<'a>(
 
     // Starting at the current node, check if it generates a scope, and if not,
     // advance to its parent.
+    let mut prev = node;
     let mut next: Option<&dyn ast::AnyNode> = node.get_parent();
     while let Some(node) = next {
+        // Names in the instantiation of a bind directive resolve in the
+        // module the instance is bound into.
+        if let Some(bind) = node.as_all().get_bind() {
+            if prev.id() == bind.inst.id() {
+                if let Ok(target) = cx.bind_target(bind) {
+                    trace!(" - Redirected by {:?}", bind);
+                    return ScopeLocation {
+                        scope: target.module,
+                        order: usize::MAX,
+                    };
+                }
+            }
+        }
         if let Some(scoped) = node.as_all().get_scoped_node() {
             trace!(" - Found {:?}", node);
             return ScopeLocation {
@@ -1085,6 +1244,7 @@ pub(crate) fn scope_location<'a>(
             };
         } else {
             trace!(" - Upwards to {:?}", node);
+            prev = node;
             next = node.get_parent();
         }
     }
@@ -1158,20 +1318,46 @@ pub(crate) fn resolve_local<'a>(
             }
         }
 
-        // Check the wildcard imports for any luck.
+        // Check the wildcard imports for any luck. These have lower priority
+        // than the definitions and explicit imports in the scope. If multiple
+        // wildcard imports provide the name, the name is ambiguous.
         if skip_imports {
             continue;
         }
-        for &import in scope.wildcard_imports.iter().rev() {
+        let mut found: Option<(&'a ast::ImportItem<'a>, &'a Def<'a>)> = None;
+        for &import in &scope.wildcard_imports {
             if import.order() > at.order {
                 continue;
             }
             let inside = cx.resolve_imported_scope(import)?;
-            let def = cx.resolve_namespace(name, inside);
-            if def.is_some() {
-                return Ok(def);
+            let def = match cx.resolve_namespace(name, inside) {
+                Some(def) => def,
+                None => continue,
+            };
+            match found {
+                Some((other, other_def)) if other_def.node.id() != def.node.id() => {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` is ambiguous; it is imported from both `{}` and `{}`",
+                            name, other.pkg, import.pkg
+                        ))
+                        .span(other.span())
+                        .add_note(format!("`{}` is also imported here:", name))
+                        .span(import.span())
+                        .add_note(format!(
+                            "Use an explicit import such as `import {}::{};` to disambiguate",
+                            other.pkg, name
+                        )),
+                    );
+                    return Err(());
+                }
+                Some(_) => (),
+                None => found = Some((import, def)),
             }
         }
+        if let Some((_, def)) = found {
+            return Ok(Some(def));
+        }
     }
     Ok(None)
 }
@@ -1313,121 +1499,1176 @@ pub(crate) fn resolve_hierarchical_or_error<'a>(
     }
 }
 
-/// Resolve an import to the scope it imports.
+/// Resolve the task or function called through an interface, such as the `f`
+/// in `intf.f(...)`.
 ///
-/// This function emits a diagnostic if the target of the import has no scope.
-/// Being a query, this ensures that the error is only produced once.
+/// Returns the subroutine together with the parameter environment of the
+/// interface. If the interface is accessed through a modport, the modport must
+/// import the subroutine (see §25.7 "Tasks and functions in interfaces").
 #[moore_derive::query]
-pub(crate) fn resolve_imported_scope<'a>(
+pub(crate) fn resolve_intf_method<'a>(
     cx: &impl Context<'a>,
-    node: &'a ast::ImportItem<'a>,
-) -> Result<&'a dyn ScopedNode<'a>> {
-    // Resolve the imported name, e.g. the `foo` in `import foo::*`.
-    let at = cx.scope_location(node);
-    let inside = cx.resolve_local_or_error(node.pkg, at, true)?;
+    expr_id: NodeId,
+    env: ParamEnv,
+) -> Result<(NodeId, ParamEnv)> {
+    let (target, name) = match cx.hir_of(expr_id)? {
+        HirNode::Expr(&hir::Expr {
+            kind: hir::ExprKind::MethodCall(target, name, _),
+            ..
+        }) => (target, name),
+        x => bug_span!(cx.span(expr_id), cx, "{:?} is not a method call", x),
+    };
 
-    // Ensure that what we have found is something we can actually perform a
-    // namespace lookup into.
-    let inside = match inside.node {
-        DefNode::Ast(node) => Some(node),
-        _ => None,
+    // Make sure we are calling a method of an interface.
+    let target_ty = cx.need_self_determined_type(target, env);
+    if target_ty.is_error() {
+        return Err(());
+    }
+    let intf = match target_ty.get_interface() {
+        Some(x) => x,
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "cannot call method `{}` on `{}`, which is not an interface",
+                    name,
+                    cx.span(target).extract()
+                ))
+                .span(cx.span(expr_id))
+                .add_note(format!(
+                    "`{}` has type `{}`",
+                    cx.span(target).extract(),
+                    target_ty
+                )),
+            );
+            return Err(());
+        }
     };
-    let inside = inside.and_then(|x| x.as_all().get_scoped_node());
-    match inside {
-        Some(x) => Ok(x),
+
+    // Find the subroutine in the interface.
+    let def = cx.resolve_hierarchical_or_error(name, intf.ast)?;
+    let decl = match def.node.as_all().get_subroutine_decl() {
+        Some(x) => x,
         None => {
             cx.emit(
-                DiagBuilder2::error(format!("name `{}` does not refer to a package", node.pkg))
-                    .span(node.pkg.span),
+                DiagBuilder2::error(format!(
+                    "`{}` is not a task or function of interface `{}`",
+                    name, intf.ast.name
+                ))
+                .span(name.span)
+                .add_note(format!("`{}` is defined here:", name))
+                .span(def.node.human_span()),
             );
-            Err(())
+            return Err(());
         }
-    }
-}
+    };
 
-/// Recursively ensures that all scopes have been constructed and potential
-/// diagnostics emitted.
-///
-/// This function helps in triggering naming conflicts at a defined point in the
-/// compilation.
-pub(crate) fn materialize_scope<'a>(cx: &impl Context<'a>, node: &'a dyn ScopedNode<'a>) {
-    debug!("Materializing scope {:?}", node);
-    let scope = cx.generated_scope(node);
-    for &subscope in &scope.subscopes {
-        materialize_scope(cx, subscope);
+    // Make sure the modport imports the subroutine, if one was specified.
+    if let Some(modport) = intf.modport {
+        let imported = modport.ports.iter().any(|port| match port.data {
+            ast::ModportPortData::Tf { dir, ref port } => {
+                dir.value == ast::ModportTfDir::Import
+                    && port.iter().any(|port| port.name.value == name.value)
+            }
+            _ => false,
+        });
+        if !imported {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is not imported by modport `{}`",
+                    name, modport.name
+                ))
+                .span(name.span)
+                .add_note(format!(
+                    "Add `import {}` to modport `{}` to call it through the modport:",
+                    name, modport.name
+                ))
+                .span(modport.span()),
+            );
+            return Err(());
+        }
     }
+    Ok((decl.id(), intf.env))
 }
 
-/// Recursively resolves names throughout the AST.
+/// Resolve a dotted hierarchical name such as `top.u_cpu.gen_lanes[2].reg_q`.
 ///
-/// Returns `true` if all names resolved successfully, `false` otherwise. This
-/// function helps in triggering name resolution errors at a defined point in
-/// the compilation.
+/// The name is walked segment by segment through the elaborated design:
+/// instance names descend into the instantiated module or interface, labels of
+/// generate blocks descend into the block, and generate loops are indexed to
+/// select one of their blocks. Blocks of generate constructs that are not
+/// elaborated in the given parameter environment cannot be referenced.
+///
+/// Returns `None` if the expression is not a hierarchical name, for example a
+/// field access into a struct variable. Emits a diagnostic naming the first
+/// segment that cannot be resolved otherwise.
 #[moore_derive::query]
-pub(crate) fn nameck<'a>(cx: &impl Context<'a>, node: &'a dyn ast::AnyNode<'a>) -> bool {
-    debug!("Checking name resolution on {:?}", node);
-    let mut rv = ResolutionVisitor::new(cx);
-    node.accept(&mut rv);
-    !rv.failed
+pub(crate) fn resolve_hierarchical_name<'a>(
+    cx: &impl Context<'a>,
+    expr_id: NodeId,
+    env: ParamEnv,
+) -> Result<Option<HierarchicalTarget<'a>>> {
+    let hir = match cx.hir_of(expr_id)? {
+        HirNode::Expr(x) => x,
+        _ => return Ok(None),
+    };
+    match hir.kind {
+        hir::ExprKind::Ident(name) => resolve_hierarchical_root(cx, hir, name, env),
+        hir::ExprKind::Field(target, name) => {
+            let (scope, inner_env, local) = match cx.resolve_hierarchical_name(target, env)? {
+                Some(HierarchicalTarget {
+                    kind: HierarchicalKind::Scope(scope),
+                    env,
+                    local,
+                    ..
+                }) => (scope, env, local),
+                Some(HierarchicalTarget {
+                    kind: HierarchicalKind::Loop(..),
+                    ..
+                }) => {
+                    let target = cx.span(target).extract();
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` is a generate loop; select one of its blocks with an index \
+                             such as `{}[0]`",
+                            target, target
+                        ))
+                        .span(cx.span(expr_id)),
+                    );
+                    return Err(());
+                }
+                _ => return Ok(None),
+            };
+            let def = match find_hierarchical_def(cx, name.value, scope) {
+                Some(def) => def,
+                None => {
+                    cx.emit(
+                        DiagBuilder2::error(format!("`{}` not found in {}", name.value, scope))
+                            .span(name.span)
+                            .add_note(format!(
+                                "Cannot resolve hierarchical name `{}`",
+                                cx.span(expr_id).extract()
+                            ))
+                            .add_note(format!("{} was defined here:", scope))
+                            .span(scope.human_span()),
+                    );
+                    return Err(());
+                }
+            };
+            enter_hierarchical_def(cx, def, cx.span(expr_id), inner_env, local).map(Some)
+        }
+        hir::ExprKind::Index(target, mode) => match cx.resolve_hierarchical_name(target, env)? {
+            Some(HierarchicalTarget {
+                kind: HierarchicalKind::Loop(block),
+                node,
+                env: inner_env,
+                ..
+            }) => {
+                let index = match mode {
+                    hir::IndexMode::One(index) => cx.constant_int_value_of(index, env)?,
+                    hir::IndexMode::Many(..) => {
+                        cx.emit(
+                            DiagBuilder2::error(format!(
+                                "cannot select a range of generate blocks in `{}`",
+                                cx.span(expr_id).extract()
+                            ))
+                            .span(cx.span(expr_id))
+                            .add_note("Select a single generate block with a constant index"),
+                        );
+                        return Err(());
+                    }
+                };
+                let gen = match cx.hir_of(block.get_parent().unwrap().id())? {
+                    HirNode::Gen(x) => x,
+                    _ => unreachable!(),
+                };
+                for (k, local_env) in generate_loop_iterations(cx, gen, inner_env)? {
+                    match k.kind {
+                        value::ValueKind::Int(ref v, ..) if v == index => {
+                            return Ok(Some(HierarchicalTarget {
+                                kind: HierarchicalKind::Scope(block),
+                                node,
+                                env: local_env,
+                                local: false,
+                            }));
+                        }
+                        _ => (),
+                    }
+                }
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` does not exist; generate loop `{}` has no block with index {}",
+                        cx.span(expr_id).extract(),
+                        cx.span(target).extract(),
+                        index
+                    ))
+                    .span(cx.span(expr_id))
+                    .add_note("Generate loop defined here:")
+                    .span(gen.span()),
+                );
+                Err(())
+            }
+            Some(HierarchicalTarget {
+                kind: HierarchicalKind::Scope(scope),
+                ..
+            }) => {
+                cx.emit(
+                    DiagBuilder2::error(format!("cannot index into {}", scope))
+                        .span(cx.span(expr_id))
+                        .add_note(format!(
+                            "`{}` is not a generate loop",
+                            cx.span(target).extract()
+                        )),
+                );
+                Err(())
+            }
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
 }
 
-/// A visitor that emits diagnostics for every resolved named.
-pub(crate) struct ResolutionVisitor<'cx, C> {
-    pub cx: &'cx C,
-    pub failed: bool,
+/// The target of a hierarchical name.
+#[derive(Debug, Clone, Copy)]
+pub struct HierarchicalTarget<'a> {
+    /// What the name refers to.
+    pub kind: HierarchicalKind<'a>,
+    /// The node the name resolves to.
+    pub node: NodeId,
+    /// The parameter environment of the target.
+    pub env: ParamEnv,
+    /// Whether the target lies within the module instance that contains the
+    /// name, rather than in another instance or a generate loop iteration.
+    pub local: bool,
 }
 
-impl<'cx, C> ResolutionVisitor<'cx, C> {
-    /// Create a new name resolution visitor.
-    pub fn new(cx: &'cx C) -> Self {
-        ResolutionVisitor { cx, failed: false }
-    }
+/// The different things a hierarchical name can refer to.
+#[derive(Debug, Clone, Copy)]
+pub enum HierarchicalKind<'a> {
+    /// A module, instance, or generate block that names can be looked up in.
+    Scope(&'a dyn ScopedNode<'a>),
+    /// A generate loop, which must be indexed to select one of its blocks.
+    Loop(&'a ast::GenerateBlock<'a>),
+    /// Anything else, such as a variable or a parameter.
+    Value,
 }
 
-impl<'a, 'cx, C> ast::Visitor<'a> for ResolutionVisitor<'cx, C>
-where
-    C: Context<'a>,
-    'a: 'cx,
-{
-    fn pre_visit_expr(&mut self, node: &'a ast::Expr<'a>) -> bool {
-        // Don't resolve the left-hand side of named pattern fields,
-        // since these refer to field names.
-        if let Some(patfield) = node
-            .get_parent()
-            .and_then(|p| p.as_all().get_pattern_field())
-        {
-            match patfield.data {
-                ast::PatternFieldData::Member(ref name_expr, ..) if name_expr.as_ref() == node => {
-                    return false;
+/// Resolve the first segment of a hierarchical name.
+fn resolve_hierarchical_root<'a>(
+    cx: &impl Context<'a>,
+    hir: &'a hir::Expr<'a>,
+    name: Spanned<Name>,
+    env: ParamEnv,
+) -> Result<Option<HierarchicalTarget<'a>>> {
+    let def = match find_hierarchical_root_def(cx, name.value, cx.scope_location(hir.ast))? {
+        Some(def) => def,
+        None => return Ok(None),
+    };
+
+    match def.node.as_all() {
+        // A module name starts a path at the top of the hierarchy, unless it
+        // refers to the module that contains the name.
+        ast::AllNode::Module(x) => {
+            cx.hir_of_module(x)?;
+            let mut parent = hir.ast.get_parent();
+            while let Some(node) = parent {
+                if node.as_all().is_module() {
+                    break;
                 }
-                _ => (),
+                parent = node.get_parent();
             }
+            let local = parent.map(|m| m.id() == x.id()).unwrap_or(false);
+            Ok(Some(HierarchicalTarget {
+                kind: HierarchicalKind::Scope(x),
+                node: x.id(),
+                env: if local { env } else { cx.default_param_env() },
+                local,
+            }))
         }
-
-        // Don't resolve the expression in implicit and explicit ports.
-        // These are meant to be analyzed in more detail by the port
-        // list ananlysis query.
-        if let Some(port) = node.get_parent().and_then(|p| p.as_all().get_port()) {
-            debug!("Checking {:?} in port {:?}", node, port);
-            match port.data {
-                ast::PortData::Explicit { .. } | ast::PortData::Implicit(..) => return false,
-                _ => (),
+        // Interface instances are accessed as values of interface type.
+        ast::AllNode::InstName(..) => {
+            let inst = match cx.hir_of(def.node.id())? {
+                HirNode::Inst(x) => x,
+                _ => unreachable!(),
+            };
+            match cx.inst_details(Ref(inst), env)?.target.kind {
+                InstTarget::Module(..) => {
+                    enter_hierarchical_def(cx, def, name.span, env, true).map(Some)
+                }
+                InstTarget::Interface(..) => Ok(None),
             }
         }
-
-        match node.data {
-            ast::IdentExpr(ident) => {
-                self.failed |= self
-                    .cx
-                    .resolve_local_or_error(ident, self.cx.scope_location(node), false)
-                    .is_err();
-                false
-            }
-            _ => true,
+        ast::AllNode::GenerateBlock(..) | ast::AllNode::ClockingDecl(..) => {
+            enter_hierarchical_def(cx, def, name.span, env, true).map(Some)
         }
+        _ => Ok(None),
     }
+}
 
-    fn pre_visit_type(&mut self, node: &'a ast::Type<'a>) -> bool {
+/// Look up the first segment of a hierarchical name.
+///
+/// The name is resolved locally. Labels of generate blocks are not visible
+/// outside the generate construct, so look for them in the generate constructs
+/// of the enclosing scopes as well.
+fn find_hierarchical_root_def<'a>(
+    cx: &impl Context<'a>,
+    name: Name,
+    at: ScopeLocation<'a>,
+) -> Result<Option<&'a Def<'a>>> {
+    if let Some(def) = cx.resolve_local(name, at, false)? {
+        return Ok(Some(def));
+    }
+    let mut next = Some(at.scope);
+    while let Some(scope) = next {
+        if let Some(def) = find_hierarchical_def(cx, name, scope) {
+            return Ok(Some(def));
+        }
+        next = cx.generated_scope(scope).parent;
+    }
+    Ok(None)
+}
+
+/// Look up a name among the hierarchical definitions of a scope.
+///
+/// In contrast to `resolve_hierarchical`, this also finds the labeled blocks of
+/// generate constructs in the scope, which define their labels in a scope of
+/// their own.
+pub(crate) fn find_hierarchical_def<'a>(
+    cx: &impl Context<'a>,
+    name: Name,
+    inside: &'a dyn ScopedNode<'a>,
+) -> Option<&'a Def<'a>> {
+    if let Some(def) = cx.resolve_hierarchical(name, inside) {
+        return Some(def);
+    }
+    cx.generated_scope(inside)
+        .subscopes
+        .iter()
+        .filter(|scope| match scope.as_all() {
+            ast::AllNode::GenerateFor(..)
+            | ast::AllNode::GenerateIf(..)
+            | ast::AllNode::GenerateCase(..) => true,
+            _ => false,
+        })
+        .find_map(|&scope| find_hierarchical_def(cx, name, scope))
+}
+
+/// Descend into the instance or generate block a segment of a hierarchical
+/// name resolved to, or stop at any other definition.
+fn enter_hierarchical_def<'a>(
+    cx: &impl Context<'a>,
+    def: &'a Def<'a>,
+    span: Span,
+    env: ParamEnv,
+    local: bool,
+) -> Result<HierarchicalTarget<'a>> {
+    let target = |kind, env, local| HierarchicalTarget {
+        kind,
+        node: def.node.id(),
+        env,
+        local,
+    };
+    match def.node.as_all() {
+        ast::AllNode::InstName(..) => {
+            let inst = match cx.hir_of(def.node.id())? {
+                HirNode::Inst(x) => x,
+                _ => unreachable!(),
+            };
+            let details = cx.inst_details(Ref(inst), env)?;
+            let scope: &'a dyn ScopedNode<'a> = match details.target.kind {
+                InstTarget::Module(x) => {
+                    cx.hir_of_module(x)?;
+                    x
+                }
+                InstTarget::Interface(x) => {
+                    cx.hir_of_interface(x)?;
+                    x
+                }
+            };
+            Ok(target(
+                HierarchicalKind::Scope(scope),
+                details.inner_env,
+                false,
+            ))
+        }
+        ast::AllNode::GenerateBlock(block) => {
+            let parent = block.get_parent().unwrap();
+            let gen = || match cx.hir_of(parent.id()) {
+                Ok(HirNode::Gen(x)) => Ok(x),
+                Ok(_) => unreachable!(),
+                Err(()) => Err(()),
+            };
+            let elaborated = match parent.as_all() {
+                ast::AllNode::GenerateFor(..) => {
+                    return Ok(target(HierarchicalKind::Loop(block), env, local));
+                }
+                ast::AllNode::GenerateIf(ast) => match gen()?.kind {
+                    hir::GenKind::If { cond, .. } => {
+                        let is_main = std::ptr::eq(&ast.main_block, block);
+                        cx.constant_value_of(cond, env).is_false() != is_main
+                    }
+                    _ => unreachable!(),
+                },
+                ast::AllNode::GenerateCase(ast) => match gen()?.kind {
+                    hir::GenKind::Case { expr, ref ways, .. } => {
+                        let selected = match select_generate_case(cx, expr, ways, env) {
+                            Some(index) => ast
+                                .items
+                                .iter()
+                                .filter_map(|item| match item {
+                                    ast::GenerateCaseItem::Expr(_, block) => Some(block),
+                                    _ => None,
+                                })
+                                .nth(index),
+                            None => ast.items.iter().find_map(|item| match item {
+                                ast::GenerateCaseItem::Default(block) => Some(block),
+                                _ => None,
+                            }),
+                        };
+                        selected.map(|b| std::ptr::eq(b, block)).unwrap_or(false)
+                    }
+                    _ => unreachable!(),
+                },
+                _ => true,
+            };
+            if !elaborated {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not elaborated in this design",
+                        span.extract()
+                    ))
+                    .span(span)
+                    .add_note(format!(
+                        "The condition of the {} does not select this block",
+                        parent
+                    ))
+                    .span(parent.human_span()),
+                );
+                return Err(());
+            }
+            Ok(target(HierarchicalKind::Scope(block), env, local))
+        }
+        ast::AllNode::ClockingDecl(decl) => Ok(target(HierarchicalKind::Scope(decl), env, local)),
+        // Signals of a clocking block refer to the signal of the same name in
+        // the scope that contains the block. Reads therefore see the current
+        // value of the signal, rather than the value sampled with the input
+        // skew before the clocking event (§14.13).
+        ast::AllNode::ClockingSignal(signal) => {
+            if let Some(ref expr) = signal.expr {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "unsupported: clocking signal `{}` assigned an expression",
+                        signal.name
+                    ))
+                    .span(span)
+                    .add_note(format!("`{}` is declared here:", signal.name))
+                    .span(expr.span()),
+                );
+                return Err(());
+            }
+            let decl = clocking_decl_of(signal);
+            let def = cx.resolve_local_or_error(signal.name, cx.scope_location(decl), false)?;
+            enter_hierarchical_def(cx, def, span, env, local)
+        }
+        _ => Ok(target(HierarchicalKind::Value, env, local)),
+    }
+}
+
+/// Find the clocking block that declares a signal.
+pub(crate) fn clocking_decl_of<'a>(
+    signal: &'a ast::ClockingSignal<'a>,
+) -> &'a ast::ClockingDecl<'a> {
+    let mut parent = signal.get_parent();
+    while let Some(node) = parent {
+        if let Some(decl) = node.as_all().get_clocking_decl() {
+            return decl;
+        }
+        parent = node.get_parent();
+    }
+    unreachable!("clocking signal outside of a clocking block")
+}
+
+/// Find the clocking block signal selected by an expression such as `cb.a`.
+///
+/// Returns `None` if the expression does not select a signal of a clocking
+/// block.
+pub(crate) fn resolve_clocking_signal<'a>(
+    cx: &impl Context<'a>,
+    expr: &'a ast::Expr<'a>,
+) -> Option<&'a ast::ClockingSignal<'a>> {
+    let (target, name) = match expr.data {
+        ast::MemberExpr {
+            expr: ref target,
+            name,
+        } => (target, name),
+        _ => return None,
+    };
+    let ident = match target.data {
+        ast::IdentExpr(ident) => ident,
+        _ => return None,
+    };
+    let def = cx
+        .resolve_local(ident.value, cx.scope_location(target.as_ref()), false)
+        .ok()??;
+    let decl = def.node.as_all().get_clocking_decl()?;
+    cx.resolve_hierarchical(name.value, decl)?
+        .node
+        .as_all()
+        .get_clocking_signal()
+}
+
+/// Find the default clocking block that applies at a location.
+///
+/// This is either a block declared with `default clocking`, or the block named
+/// by a `default clocking name;` item, in the innermost enclosing scope that
+/// has one. Returns `None` if there is no default clocking block.
+#[moore_derive::query]
+pub(crate) fn default_clocking<'a>(
+    cx: &impl Context<'a>,
+    at: ScopeLocation<'a>,
+) -> Result<Option<&'a ast::ClockingDecl<'a>>> {
+    let mut next = Some(at.scope);
+    while let Some(scope) = next {
+        let scope = cx.generated_scope(scope);
+        next = scope.parent;
+        let item = match scope.default_clocking {
+            Some(item) => item,
+            None => continue,
+        };
+        let name = match item.data {
+            ast::ItemData::ClockingDecl(ref decl) => return Ok(Some(decl)),
+            ast::ItemData::DefaultClocking(name) => name,
+            _ => unreachable!(),
+        };
+        let def = cx.resolve_local_or_error(name, cx.scope_location(item), false)?;
+        match def.node.as_all().get_clocking_decl() {
+            Some(decl) => return Ok(Some(decl)),
+            None => {
+                cx.emit(
+                    DiagBuilder2::error(format!("`{}` is not a clocking block", name))
+                        .span(name.span)
+                        .add_note(format!("`{}` is defined here:", name))
+                        .span(def.node.human_span()),
+                );
+                return Err(());
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Resolve the target of a bind directive.
+///
+/// The target is either the name of a module, optionally followed by a list of
+/// instances of that module, or the hierarchical path of a single module
+/// instance.
+#[moore_derive::query]
+pub(crate) fn bind_target<'a>(
+    cx: &impl Context<'a>,
+    bind: &'a ast::Bind<'a>,
+) -> Result<BindTarget<'a>> {
+    // A module name binds into all instances of the module, or the ones listed
+    // after the name.
+    if let ast::IdentExpr(name) = bind.target.data {
+        let def = instance_path_def(cx, &bind.target)?;
+        if let Some(module) = def.node.as_all().get_module() {
+            let mut failed = false;
+            for path in &bind.instances {
+                match instance_path_module(cx, path) {
+                    Ok(m) if m.id() == module.id() => (),
+                    Ok(m) => {
+                        cx.emit(
+                            DiagBuilder2::error(format!(
+                                "`{}` is not an instance of {}",
+                                path.span.extract(),
+                                module
+                            ))
+                            .span(path.span)
+                            .add_note(format!(
+                                "`{}` is an instance of {}",
+                                path.span.extract(),
+                                m
+                            )),
+                        );
+                        failed = true;
+                    }
+                    Err(()) => failed = true,
+                }
+            }
+            if failed {
+                return Err(());
+            }
+            trace!("Bind target `{}` is {:?}", name, module);
+            return Ok(BindTarget {
+                module,
+                instances: bind.instances.iter().collect(),
+            });
+        }
+    }
+
+    // Otherwise the target is the path of a single instance.
+    if let Some(first) = bind.instances.first() {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "`{}` is not a module name",
+                bind.target.span.extract()
+            ))
+            .span(bind.target.span)
+            .add_note("A list of instances can only follow the name of a module:")
+            .span(first.span),
+        );
+        return Err(());
+    }
+    Ok(BindTarget {
+        module: instance_path_module(cx, &bind.target)?,
+        instances: vec![&bind.target],
+    })
+}
+
+/// The target of a bind directive.
+#[derive(Debug, Clone)]
+pub struct BindTarget<'a> {
+    /// The module the instance is bound into.
+    pub module: &'a ast::Module<'a>,
+    /// The hierarchical paths of the instances of the module the instance is
+    /// bound into. Empty if it is bound into every instance.
+    pub instances: Vec<&'a ast::Expr<'a>>,
+}
+
+/// Resolve the instance path of a bind directive or defparam to the
+/// instantiated module.
+fn instance_path_module<'a>(
+    cx: &impl Context<'a>,
+    path: &'a ast::Expr<'a>,
+) -> Result<&'a ast::Module<'a>> {
+    let def = instance_path_def(cx, path)?;
+    let target = match (def.node.as_all(), &path.data) {
+        (_, ast::IndexExpr { .. }) => None,
+        (ast::AllNode::InstName(x), _) => match cx.resolve_inst_target(x.inst())? {
+            InstTarget::Module(x) => Some(x),
+            InstTarget::Interface(..) => None,
+        },
+        _ => None,
+    };
+    match target {
+        Some(x) => Ok(x),
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is not a module instance",
+                    path.span.extract()
+                ))
+                .span(path.span)
+                .add_note(format!("`{}` refers to {}", path.span.extract(), def.node))
+                .span(def.node.human_span()),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Resolve an instance path of a bind directive or defparam to the scope it
+/// names.
+fn instance_path_scope<'a>(
+    cx: &impl Context<'a>,
+    path: &'a ast::Expr<'a>,
+) -> Result<&'a dyn ScopedNode<'a>> {
+    let def = instance_path_def(cx, path)?;
+    let indexed = match path.data {
+        ast::IndexExpr { .. } => true,
+        _ => false,
+    };
+    let is_loop =
+        |block: &ast::GenerateBlock| block.get_parent().unwrap().as_all().is_generate_for();
+    match def.node.as_all() {
+        ast::AllNode::Module(x) if !indexed => Ok(x),
+        ast::AllNode::InstName(x) if !indexed => match cx.resolve_inst_target(x.inst())? {
+            InstTarget::Module(x) => Ok(x),
+            InstTarget::Interface(x) => Ok(x),
+        },
+        ast::AllNode::GenerateBlock(x) if is_loop(x) == indexed => Ok(x),
+        ast::AllNode::GenerateBlock(..) if !indexed => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is a generate loop; select one of its blocks with an index such as \
+                     `{}[0]`",
+                    path.span.extract(),
+                    path.span.extract()
+                ))
+                .span(path.span),
+            );
+            Err(())
+        }
+        _ if indexed => {
+            cx.emit(DiagBuilder2::error(format!("cannot index into {}", def.node)).span(path.span));
+            Err(())
+        }
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is not an instance or generate block",
+                    path.span.extract()
+                ))
+                .span(path.span)
+                .add_note(format!("`{}` refers to {}", path.span.extract(), def.node))
+                .span(def.node.human_span()),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Resolve a segment of an instance path of a bind directive or defparam.
+///
+/// Indexing into a generate loop resolves to the loop's block; the index is
+/// only evaluated when the design is elaborated.
+fn instance_path_def<'a>(cx: &impl Context<'a>, path: &'a ast::Expr<'a>) -> Result<&'a Def<'a>> {
+    match path.data {
+        ast::IdentExpr(name) => {
+            match find_hierarchical_root_def(cx, name.value, cx.scope_location(path))? {
+                Some(def) => Ok(def),
+                None => {
+                    cx.emit(
+                        DiagBuilder2::error(format!("`{}` not found", name.value)).span(name.span),
+                    );
+                    Err(())
+                }
+            }
+        }
+        ast::MemberExpr { ref expr, name } => {
+            let scope = instance_path_scope(cx, expr)?;
+            match find_hierarchical_def(cx, name.value, scope) {
+                Some(def) => Ok(def),
+                None => {
+                    cx.emit(
+                        DiagBuilder2::error(format!("`{}` not found in {}", name.value, scope))
+                            .span(name.span)
+                            .add_note(format!("{} was defined here:", scope))
+                            .span(scope.human_span()),
+                    );
+                    Err(())
+                }
+            }
+        }
+        ast::IndexExpr { ref indexee, .. } => instance_path_def(cx, indexee),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is not a hierarchical instance path",
+                    path.span.extract()
+                ))
+                .span(path.span),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Resolve the parameter overridden by an assignment in a defparam.
+///
+/// The target is the hierarchical path of a module instance followed by the
+/// name of one of the module's parameters, as in `top.u1.WIDTH`.
+#[moore_derive::query]
+pub(crate) fn defparam_target<'a>(
+    cx: &impl Context<'a>,
+    target: &'a ast::Expr<'a>,
+) -> Result<DefparamTarget<'a>> {
+    let (instance, name) = match target.data {
+        ast::MemberExpr { ref expr, name } => (expr.as_ref(), name),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is not a hierarchical parameter path",
+                    target.span.extract()
+                ))
+                .span(target.span)
+                .add_note(
+                    "A defparam must name the instance the parameter belongs to, as in \
+                     `top.u1.WIDTH`",
+                ),
+            );
+            return Err(());
+        }
+    };
+    let module = instance_path_module(cx, instance)?;
+    let def = match find_hierarchical_def(cx, name.value, module) {
+        Some(def) => def,
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!("no parameter `{}` in {}", name, module))
+                    .span(name.span)
+                    .add_note(format!("{} was defined here:", module))
+                    .span(module.human_span()),
+            );
+            return Err(());
+        }
+    };
+    let param = match def.node.as_all() {
+        ast::AllNode::ParamValueDecl(x) => x,
+        ast::AllNode::ParamTypeDecl(..) => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "defparam cannot override type parameter `{}`",
+                    name
+                ))
+                .span(name.span)
+                .add_note("Type parameters can only be overridden in the instantiation:")
+                .span(def.node.human_span()),
+            );
+            return Err(());
+        }
+        _ => {
+            cx.emit(
+                DiagBuilder2::error(format!("`{}` is not a parameter", target.span.extract()))
+                    .span(target.span)
+                    .add_note(format!("`{}` refers to {}", name, def.node))
+                    .span(def.node.human_span()),
+            );
+            return Err(());
+        }
+    };
+
+    // Parameters declared in the body become local if there is a parameter
+    // port list (see §6.20.1).
+    let decl = match param.get_parent().and_then(|p| p.as_all().get_param_decl()) {
+        Some(x) => x,
+        None => bug_span!(param.span, cx, "{:?} has no parameter declaration", param),
+    };
+    let local = if decl.local {
+        Some(format!("`{}` is declared as `localparam` here:", name))
+    } else if !module.params.is_empty() && module.params.iter().all(|p| p.id() != decl.id()) {
+        Some(format!(
+            "`{}` is local since {} has a parameter port list:",
+            name, module
+        ))
+    } else {
+        None
+    };
+    if let Some(note) = local {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "cannot override local parameter `{}` of {}",
+                name, module
+            ))
+            .span(name.span)
+            .add_note(note)
+            .span(param.name.span),
+        );
+        return Err(());
+    }
+    trace!("Defparam target `{}` is {:?}", target.span.extract(), param);
+    Ok(DefparamTarget {
+        module,
+        instance,
+        param,
+    })
+}
+
+/// The parameter overridden by an assignment in a defparam.
+#[derive(Debug, Clone)]
+pub struct DefparamTarget<'a> {
+    /// The module whose parameter is overridden.
+    pub module: &'a ast::Module<'a>,
+    /// The hierarchical path of the instance of the module.
+    pub instance: &'a ast::Expr<'a>,
+    /// The overridden parameter.
+    pub param: &'a ast::ParamValueDecl<'a>,
+}
+
+/// The directives of a design which modify instances elsewhere in the
+/// hierarchy.
+#[derive(Debug, Default)]
+pub struct Directives<'a> {
+    /// The bind directives, in source order.
+    pub binds: Vec<&'a ast::Bind<'a>>,
+    /// The defparams, in source order.
+    pub defparams: Vec<&'a ast::Defparam<'a>>,
+}
+
+/// Collect the bind directives and defparams of a design.
+#[moore_derive::query]
+pub(crate) fn directives<'a>(
+    cx: &impl Context<'a>,
+    root: &'a ast::Root<'a>,
+) -> Arc<Directives<'a>> {
+    struct Collector<'a>(Directives<'a>);
+    impl<'a> ast::Visitor<'a> for Collector<'a> {
+        fn pre_visit_bind(&mut self, node: &'a ast::Bind<'a>) -> bool {
+            self.0.binds.push(node);
+            false
+        }
+
+        fn pre_visit_defparam(&mut self, node: &'a ast::Defparam<'a>) -> bool {
+            self.0.defparams.push(node);
+            false
+        }
+    }
+    let mut collector = Collector(Default::default());
+    root.accept(&mut collector);
+    trace!(
+        "Found {} bind directives and {} defparams",
+        collector.0.binds.len(),
+        collector.0.defparams.len()
+    );
+    Arc::new(collector.0)
+}
+
+/// Find the root of the AST a module belongs to.
+fn root_of<'a>(cx: &impl Context<'a>, module: &'a ast::Module<'a>) -> &'a ast::Root<'a> {
+    let mut root: &dyn ast::AnyNode = module;
+    while let Some(parent) = root.get_parent() {
+        root = parent;
+    }
+    match root.as_all().get_root() {
+        Some(x) => x,
+        None => bug_span!(module.span, cx, "{:?} is not part of an AST root", module),
+    }
+}
+
+/// Find the bind directives that insert an instance into a module.
+pub(crate) fn binds_into<'a>(
+    cx: &impl Context<'a>,
+    module: &'a ast::Module<'a>,
+) -> Vec<&'a ast::Bind<'a>> {
+    cx.directives(root_of(cx, module))
+        .binds
+        .iter()
+        .cloned()
+        .filter(|&bind| match cx.bind_target(bind) {
+            Ok(target) => target.module.id() == module.id(),
+            Err(()) => false,
+        })
+        .collect()
+}
+
+/// Find the defparam assignments that override a parameter of a module.
+///
+/// Returns the target path, the resolved target, and the assigned value of each
+/// assignment, in source order.
+pub(crate) fn defparams_into<'a>(
+    cx: &impl Context<'a>,
+    module: &'a ast::Module<'a>,
+) -> Vec<(&'a ast::Expr<'a>, DefparamTarget<'a>, &'a ast::Expr<'a>)> {
+    cx.directives(root_of(cx, module))
+        .defparams
+        .iter()
+        .flat_map(|defparam| defparam.assignments.iter())
+        .filter_map(|(target, value)| match cx.defparam_target(target) {
+            Ok(resolved) if resolved.module.id() == module.id() => Some((target, resolved, value)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Resolve a name in the compilation unit scope `$unit`.
+///
+/// All files compiled together form a single compilation unit, so this looks
+/// up the name among the top-level declarations of every file.
+#[moore_derive::query]
+pub(crate) fn resolve_unit_or_error<'a>(
+    cx: &impl Context<'a>,
+    name: Spanned<Name>,
+    at: &'a dyn ast::AnyNode<'a>,
+) -> Result<&'a Def<'a>> {
+    let mut root = at;
+    while let Some(parent) = root.get_parent() {
+        root = parent;
+    }
+    let root = match root.as_all().get_root() {
+        Some(x) => x,
+        None => bug_span!(at.span(), cx, "{:?} is not part of an AST root", at),
+    };
+    for &file in &root.files {
+        if let Some(def) = cx.generated_scope(file).defs.get(&name.value) {
+            return Ok(def);
+        }
+    }
+    cx.emit(
+        DiagBuilder2::error(format!("`{}` not found in compilation unit `$unit`", name))
+            .span(name.span),
+    );
+    Err(())
+}
+
+/// Resolve an import to the scope it imports.
+///
+/// This function emits a diagnostic if the target of the import has no scope.
+/// Being a query, this ensures that the error is only produced once.
+#[moore_derive::query]
+pub(crate) fn resolve_imported_scope<'a>(
+    cx: &impl Context<'a>,
+    node: &'a ast::ImportItem<'a>,
+) -> Result<&'a dyn ScopedNode<'a>> {
+    // Resolve the imported name, e.g. the `foo` in `import foo::*`.
+    let at = cx.scope_location(node);
+    let inside = cx.resolve_local_or_error(node.pkg, at, true)?;
+
+    // Ensure that what we have found is something we can actually perform a
+    // namespace lookup into.
+    let inside = match inside.node {
+        DefNode::Ast(node) => Some(node),
+        _ => None,
+    };
+    let inside = inside.and_then(|x| x.as_all().get_scoped_node());
+    match inside {
+        Some(x) => Ok(x),
+        None => {
+            cx.emit(
+                DiagBuilder2::error(format!("name `{}` does not refer to a package", node.pkg))
+                    .span(node.pkg.span),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Recursively ensures that all scopes have been constructed and potential
+/// diagnostics emitted.
+///
+/// This function helps in triggering naming conflicts at a defined point in the
+/// compilation.
+pub(crate) fn materialize_scope<'a>(cx: &impl Context<'a>, node: &'a dyn ScopedNode<'a>) {
+    debug!("Materializing scope {:?}", node);
+    let scope = cx.generated_scope(node);
+    for &subscope in &scope.subscopes {
+        materialize_scope(cx, subscope);
+    }
+}
+
+/// Recursively resolves names throughout the AST.
+///
+/// Returns `true` if all names resolved successfully, `false` otherwise. This
+/// function helps in triggering name resolution errors at a defined point in
+/// the compilation.
+#[moore_derive::query]
+pub(crate) fn nameck<'a>(cx: &impl Context<'a>, node: &'a dyn ast::AnyNode<'a>) -> bool {
+    debug!("Checking name resolution on {:?}", node);
+    let mut rv = ResolutionVisitor::new(cx);
+    node.accept(&mut rv);
+    !rv.failed
+}
+
+/// A visitor that emits diagnostics for every resolved named.
+pub(crate) struct ResolutionVisitor<'cx, C> {
+    pub cx: &'cx C,
+    pub failed: bool,
+    /// The number of constraints the visitor is currently in.
+    constraints: usize,
+}
+
+impl<'cx, C> ResolutionVisitor<'cx, C> {
+    /// Create a new name resolution visitor.
+    pub fn new(cx: &'cx C) -> Self {
+        ResolutionVisitor {
+            cx,
+            failed: false,
+            constraints: 0,
+        }
+    }
+}
+
+impl<'a, 'cx, C> ast::Visitor<'a> for ResolutionVisitor<'cx, C>
+where
+    C: Context<'a>,
+    'a: 'cx,
+{
+    fn pre_visit_expr(&mut self, node: &'a ast::Expr<'a>) -> bool {
+        // Don't resolve the left-hand side of named pattern fields,
+        // since these refer to field names.
+        if let Some(patfield) = node
+            .get_parent()
+            .and_then(|p| p.as_all().get_pattern_field())
+        {
+            match patfield.data {
+                ast::PatternFieldData::Member(ref name_expr, ..) if name_expr.as_ref() == node => {
+                    return false;
+                }
+                _ => (),
+            }
+        }
+
+        // Don't resolve the expression in implicit and explicit ports.
+        // These are meant to be analyzed in more detail by the port
+        // list ananlysis query.
+        if let Some(port) = node.get_parent().and_then(|p| p.as_all().get_port()) {
+            debug!("Checking {:?} in port {:?}", node, port);
+            match port.data {
+                ast::PortData::Explicit { .. } | ast::PortData::Implicit(..) => return false,
+                _ => (),
+            }
+        }
+
+        match node.data {
+            ast::IdentExpr(ident) => {
+                self.failed |= self
+                    .cx
+                    .resolve_local_or_error(ident, self.cx.scope_location(node), false)
+                    .is_err();
+                false
+            }
+            ast::ScopeExpr(ref target, name) if crate::hir::is_unit_scope(target) => {
+                self.failed |= self.cx.resolve_unit_or_error(name, node).is_err();
+                false
+            }
+            ast::ScopeExpr(ref target, name) if target.data == ast::LocalExpr => {
+                self.failed |=
+                    resolve_local_qualifier(self.cx, node, name, self.constraints > 0).is_err();
+                false
+            }
+            _ => true,
+        }
+    }
+
+    fn pre_visit_clocking_signal(&mut self, node: &'a ast::ClockingSignal<'a>) -> bool {
+        // Signals without an expression refer to the signal of the same name
+        // in the enclosing scope.
+        if node.expr.is_none() {
+            self.failed |= self
+                .cx
+                .resolve_local_or_error(node.name, self.cx.scope_location(node), false)
+                .is_err();
+        }
+        true
+    }
+
+    fn pre_visit_constraint(&mut self, _: &'a ast::Constraint<'a>) -> bool {
+        self.constraints += 1;
+        true
+    }
+
+    fn post_visit_constraint(&mut self, _: &'a ast::Constraint<'a>) {
+        self.constraints -= 1;
+    }
+
+    fn pre_visit_bind(&mut self, node: &'a ast::Bind<'a>) -> bool {
+        // The target is resolved as a hierarchical path, and the names in the
+        // instantiation only resolve once the target is known.
+        match self.cx.bind_target(node) {
+            Ok(_) => node.inst.walk(self),
+            Err(()) => self.failed = true,
+        }
+        false
+    }
+
+    fn pre_visit_defparam(&mut self, node: &'a ast::Defparam<'a>) -> bool {
+        // The targets are resolved as hierarchical paths, and the values as
+        // regular expressions in the current scope.
+        for (target, value) in &node.assignments {
+            self.failed |= self.cx.defparam_target(target).is_err();
+            value.walk(self);
+        }
+        false
+    }
+
+    fn pre_visit_inst(&mut self, node: &'a ast::Inst<'a>) -> bool {
+        if let Some(checker) = resolve_checker_target(self.cx, node) {
+            for name in &node.names {
+                self.failed |= check_checker_inst(self.cx, checker, name).is_err();
+            }
+        }
+        true
+    }
+
+    fn pre_visit_type(&mut self, node: &'a ast::Type<'a>) -> bool {
         match node.kind.data {
             ast::NamedType(ident) => {
                 self.failed |= self
@@ -1436,6 +2677,14 @@ where
                     .is_err();
                 false
             }
+            ast::ScopedType {
+                ref ty,
+                member: false,
+                name,
+            } if ty.kind.data == ast::UnitType => {
+                self.failed |= self.cx.resolve_unit_or_error(name, node).is_err();
+                false
+            }
             _ => true,
         }
     }
@@ -1493,3 +2742,168 @@ pub(crate) fn resolve_inst_target<'a>(
         }
     }
 }
+
+/// Resolve the target of an instantiation to a checker, if it is one.
+pub(crate) fn resolve_checker_target<'a>(
+    cx: &impl Context<'a>,
+    inst: &'a ast::Inst<'a>,
+) -> Option<&'a ast::CheckerDecl<'a>> {
+    let loc = cx.scope_location(inst);
+    match cx.resolve_local(inst.target.value, loc, false) {
+        Ok(Some(Def {
+            node: DefNode::Ast(ast),
+            ..
+        })) => ast.as_all().get_checker_decl(),
+        _ => None,
+    }
+}
+
+/// Bind the actual arguments of a checker instance to the checker's formal
+/// ports, and check that each argument is of a kind the port accepts.
+pub(crate) fn check_checker_inst<'a>(
+    cx: &impl Context<'a>,
+    checker: &'a ast::CheckerDecl<'a>,
+    inst: &'a ast::InstName<'a>,
+) -> Result<()> {
+    let mut failed = false;
+    let mut bound: Vec<Option<&'a ast::Expr<'a>>> = vec![None; checker.ports.len()];
+    let mut auto = false;
+
+    // Associate the actuals with the formal ports.
+    let mut positional = 0;
+    for conn in &inst.conns {
+        match *conn {
+            ast::PortConn::Auto => auto = true,
+            ast::PortConn::Positional(ref expr) => {
+                if positional < bound.len() {
+                    bound[positional] = Some(expr);
+                } else {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "{} has {} ports, but {} were given",
+                            checker,
+                            checker.ports.len(),
+                            positional + 1
+                        ))
+                        .span(expr.span)
+                        .add_note(format!("{} was declared here:", checker))
+                        .span(checker.name.span),
+                    );
+                    failed = true;
+                }
+                positional += 1;
+            }
+            ast::PortConn::Named(name, ref mode) => {
+                match checker
+                    .ports
+                    .iter()
+                    .position(|p| p.name.value == name.value)
+                {
+                    Some(index) => {
+                        bound[index] = match *mode {
+                            ast::PortConnMode::Connected(ref expr) => Some(expr),
+                            _ => None,
+                        }
+                    }
+                    None => {
+                        cx.emit(
+                            DiagBuilder2::error(format!("{} has no port `{}`", checker, name))
+                                .span(name.span)
+                                .add_note(format!("{} was declared here:", checker))
+                                .span(checker.name.span),
+                        );
+                        failed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    // Check that every port is bound to an argument of the right kind.
+    for (port, actual) in checker.ports.iter().zip(bound) {
+        let actual = match actual {
+            Some(actual) => actual,
+            None => {
+                if port.default.is_none() && !auto {
+                    cx.emit(
+                        DiagBuilder2::error(format!("no argument given for {}", port))
+                            .span(inst.span)
+                            .add_note(format!("{} was declared here:", port))
+                            .span(port.span),
+                    );
+                    failed = true;
+                }
+                continue;
+            }
+        };
+        let actual_port = match actual.data {
+            ast::IdentExpr(ident) => {
+                match cx.resolve_local(ident.value, cx.scope_location(actual), false) {
+                    Ok(Some(Def {
+                        node: DefNode::Ast(ast),
+                        ..
+                    })) => ast.as_all().get_checker_port(),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let actual_kind = match actual_port {
+            Some(p) => &p.kind,
+            None => continue,
+        };
+        let compatible = match (&port.kind, actual_kind) {
+            (ast::CheckerPortKind::Untyped, _) | (_, ast::CheckerPortKind::Untyped) => true,
+            (ast::CheckerPortKind::Property, _) => true,
+            (ast::CheckerPortKind::Sequence, ast::CheckerPortKind::Property) => false,
+            (ast::CheckerPortKind::Sequence, _) => true,
+            (ast::CheckerPortKind::Data(..), ast::CheckerPortKind::Data(..)) => true,
+            (ast::CheckerPortKind::Data(..), _) => false,
+        };
+        if !compatible {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "cannot pass {} `{}` to {} port `{}`",
+                    actual_kind.desc(),
+                    actual.span.extract(),
+                    port.kind.desc(),
+                    port.name
+                ))
+                .span(actual.span)
+                .add_note(format!("{} was declared here:", port))
+                .span(port.span),
+            );
+            failed = true;
+        }
+    }
+
+    if failed {
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+/// Resolve a `local::name` qualified name.
+///
+/// The qualifier binds the name to a member of the object being randomized,
+/// which is the class enclosing the constraint.
+fn resolve_local_qualifier<'a>(
+    cx: &impl Context<'a>,
+    expr: &'a ast::Expr<'a>,
+    name: Spanned<Name>,
+    in_constraint: bool,
+) -> Result<&'a Def<'a>> {
+    let class = std::iter::successors(expr.get_parent(), |node| node.get_parent())
+        .find_map(|node| node.as_all().get_class_decl());
+    match class {
+        Some(class) if in_constraint => cx.resolve_hierarchical_or_error(name, class),
+        _ => {
+            cx.emit(
+                DiagBuilder2::error("`local::` can only be used inside a constraint")
+                    .span(expr.span),
+            );
+            Err(())
+        }
+    }
+}
@@ -131,6 +131,41 @@ pub(crate) fn disamb_type_or_expr<'a>(
                         }
                     }
                 }
+                _ if crate::hir::is_unit_scope(target) => {
+                    let def = cx.resolve_unit_or_error(name, expr)?;
+                    match cx.disamb_kind(Ref(&def.node)) {
+                        Kind::Value => Ok(ast),
+                        Kind::Type => {
+                            let target_ty = ast::Type::new(
+                                target.span,
+                                ast::TypeData {
+                                    kind: ast::TypeKind::new(target.span, ast::UnitType),
+                                    sign: ast::TypeSign::None,
+                                    dims: vec![],
+                                },
+                            );
+                            let ty = cx.arena().alloc(ast::Type::new(
+                                expr.span,
+                                ast::TypeData {
+                                    kind: ast::TypeKind::new(
+                                        expr.span,
+                                        ast::ScopedType {
+                                            ty: Box::new(target_ty),
+                                            member: false,
+                                            name,
+                                        },
+                                    ),
+                                    sign: ast::TypeSign::None,
+                                    dims: vec![],
+                                },
+                            ));
+                            ty.link_attach(expr, expr.order());
+                            cx.register_ast(ty);
+                            cx.map_ast_with_parent(AstNode::Type(ty), ty.id());
+                            Ok(cx.arena().alloc(ast::TypeOrExpr::Type(ty)))
+                        }
+                    }
+                }
                 _ => {
                     cx.emit(
                         DiagBuilder2::error(format!(
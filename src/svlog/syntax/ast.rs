@@ -4,7 +4,7 @@
 
 #![allow(unused_variables)]
 
-use crate::token::{Lit, Op};
+use crate::token::{Lit, Op, TimeUnit};
 use moore_common::{
     id::NodeId,
     name::Name,
@@ -18,18 +18,14 @@ use std::{
 };
 
 /// An AST node.
-pub trait AnyNode<'a>: BasicNode<'a> + AnyNodeData + std::fmt::Display + Send + Sync {
+///
+/// The node's span is provided by the `HasSpan` supertrait.
+pub trait AnyNode<'a>:
+    BasicNode<'a> + AnyNodeData + HasSpan + std::fmt::Display + Send + Sync
+{
     /// Get this node's unique ID.
     fn id(&self) -> NodeId;
 
-    /// Get this node's span in the input.
-    fn span(&self) -> Span;
-
-    /// Get a span that is terse and suitable to pinpoint the node for a human.
-    fn human_span(&self) -> Span {
-        self.span()
-    }
-
     /// Get this node's lexical order.
     fn order(&self) -> usize;
 
@@ -238,6 +234,7 @@ impl<'a> ForEachNode<'a> for Name {}
 impl<'a> ForEachNode<'a> for Identifier {}
 impl<'a> ForEachNode<'a> for Lit {}
 impl<'a> ForEachNode<'a> for Op {}
+impl<'a> ForEachNode<'a> for TimeUnit {}
 impl<'a> ForEachNode<'a> for bool {}
 impl<'a> ForEachNode<'a> for usize {}
 
@@ -269,6 +266,12 @@ impl<'a, T> Node<'a, T> {
     }
 }
 
+impl<'a, T> HasSpan for Node<'a, T> {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
 // The following are needed due to the `Cell`s in `Node`. It is safe to share
 // nodes between threads if we never change `parent` and `order` afterwards.
 // We only set these cells once immediately after constructing an AST, and never
@@ -287,10 +290,6 @@ where
         self.id
     }
 
-    fn span(&self) -> Span {
-        self.span
-    }
-
     fn order(&self) -> usize {
         self.order.get()
     }
@@ -551,6 +550,10 @@ impl<'a> WalkVisitor<'a> for Op {
     fn walk(&'a self, visitor: &mut dyn Visitor<'a>) {}
 }
 
+impl<'a> WalkVisitor<'a> for TimeUnit {
+    fn walk(&'a self, visitor: &mut dyn Visitor<'a>) {}
+}
+
 impl<'a> WalkVisitor<'a> for bool {
     fn walk(&'a self, visitor: &mut dyn Visitor<'a>) {}
 }
@@ -634,6 +637,7 @@ pub enum Item<'a> {
     Dummy,
     ModuleDecl(#[forward] Module<'a>),
     InterfaceDecl(#[forward] Interface<'a>),
+    CheckerDecl(#[forward] CheckerDecl<'a>),
     PackageDecl(#[forward] Package<'a>),
     ClassDecl(#[forward] ClassDecl<'a>),
     ProgramDecl(()),
@@ -641,6 +645,8 @@ pub enum Item<'a> {
     DpiDecl(#[forward] DpiDecl<'a>),
     ParamDecl(#[forward] ParamDecl<'a>),
     ModportDecl(#[forward] Modport<'a>),
+    ClockingDecl(#[forward] ClockingDecl<'a>),
+    DefaultClocking(Spanned<Name>),
     Typedef(#[forward] Typedef<'a>),
     PortDecl(#[forward] PortDecl<'a>),
     Procedure(#[forward] Procedure<'a>),
@@ -655,6 +661,8 @@ pub enum Item<'a> {
     NetDecl(NetDecl<'a>),
     VarDecl(#[forward] VarDecl<'a>),
     Inst(Inst<'a>),
+    Bind(Bind<'a>),
+    Defparam(Defparam<'a>),
 }
 
 /// A module.
@@ -664,8 +672,12 @@ pub enum Item<'a> {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Module<'a> {
     pub lifetime: Lifetime, // default static
+    /// The net type of implicit nets, as set by `` `default_nettype``. `None`
+    /// if implicit nets are disabled.
+    pub default_nettype: Option<NetType>,
     #[name]
     pub name: Spanned<Name>,
+    pub timeunits: Timeunit,
     pub imports: Vec<ImportDecl<'a>>,
     pub params: Vec<ParamDecl<'a>>,
     pub ports: Vec<Port<'a>>,
@@ -679,13 +691,71 @@ pub struct Module<'a> {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Interface<'a> {
     pub lifetime: Lifetime, // default static
+    /// The net type of implicit nets, as set by `` `default_nettype``. `None`
+    /// if implicit nets are disabled.
+    pub default_nettype: Option<NetType>,
     #[name]
     pub name: Spanned<Name>,
+    pub timeunits: Timeunit,
     pub params: Vec<ParamDecl<'a>>,
     pub ports: Vec<Port<'a>>,
     pub items: Vec<Item<'a>>,
 }
 
+/// A checker.
+///
+/// For example `checker foo(sequence s, logic a); ... endchecker`.
+#[moore_derive::node]
+#[indefinite("checker")]
+#[definite("checker `{}`", name)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckerDecl<'a> {
+    #[name]
+    pub name: Spanned<Name>,
+    pub ports: Vec<CheckerPort<'a>>,
+    pub items: Vec<Item<'a>>,
+}
+
+/// A formal argument of a checker.
+///
+/// For example the `sequence s` in `checker foo(sequence s);`.
+#[moore_derive::node]
+#[indefinite("checker port")]
+#[definite("checker port `{}`", name)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckerPort<'a> {
+    pub kind: CheckerPortKind<'a>,
+    #[name]
+    pub name: Spanned<Name>,
+    pub default: Option<Expr<'a>>,
+}
+
+/// The kind of a checker port.
+#[moore_derive::visit]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckerPortKind<'a> {
+    /// A `sequence` port.
+    Sequence,
+    /// A `property` port.
+    Property,
+    /// An `untyped` port.
+    Untyped,
+    /// A port with an explicit data type.
+    Data(Type<'a>),
+}
+
+impl<'a> CheckerPortKind<'a> {
+    /// Get a human-readable description of the port kind.
+    pub fn desc(&self) -> &'static str {
+        match *self {
+            CheckerPortKind::Sequence => "sequence",
+            CheckerPortKind::Property => "property",
+            CheckerPortKind::Untyped => "untyped",
+            CheckerPortKind::Data(..) => "data",
+        }
+    }
+}
+
 /// A package.
 #[moore_derive::node]
 #[indefinite("package")]
@@ -701,7 +771,7 @@ pub struct Package<'a> {
 
 /// Lifetime specifier for variables, tasks, and functions. Defaults to static.
 #[moore_derive::visit]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Lifetime {
     Static,
     Automatic,
@@ -716,8 +786,17 @@ pub enum Lifetime {
 #[moore_derive::visit]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Timeunit {
-    pub unit: Option<Spanned<Lit>>,
-    pub prec: Option<Spanned<Lit>>,
+    pub unit: Option<Spanned<TimeValue>>,
+    pub prec: Option<Spanned<TimeValue>>,
+}
+
+/// A time unit or precision value, such as the `10ns` in `timeunit 10ns`.
+#[moore_derive::visit]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TimeValue {
+    /// The magnitude of the value; one of 1, 10, or 100.
+    pub magnitude: usize,
+    pub unit: TimeUnit,
 }
 
 /// A type.
@@ -754,6 +833,8 @@ pub enum TypeKind<'a> {
     ImplicitUnsignedType,
 
     // Scoping
+    /// The compilation unit scope, as in `$unit::foo`.
+    UnitType,
     ScopedType {
         ty: Box<Type<'a>>,
         member: bool,
@@ -864,7 +945,7 @@ pub struct Enum<'a> {
 pub struct EnumName<'a> {
     #[name]
     pub name: Spanned<Name>,
-    pub range: Option<Expr<'a>>,
+    pub range: Option<TypeDim<'a>>,
     pub value: Option<Expr<'a>>,
 }
 
@@ -1060,6 +1141,7 @@ pub enum NetType {
     Wire,
     WireAnd,
     WireOr,
+    Interconnect,
 }
 
 impl std::fmt::Display for NetType {
@@ -1077,6 +1159,7 @@ impl std::fmt::Display for NetType {
             NetType::Wire => write!(f, "wire"),
             NetType::WireAnd => write!(f, "wand"),
             NetType::WireOr => write!(f, "wor"),
+            NetType::Interconnect => write!(f, "interconnect"),
         }
     }
 }
@@ -1158,8 +1241,19 @@ pub enum StmtKind<'a> {
     AssertionStmt(Box<Assertion<'a>>),
     WaitExprStmt(Expr<'a>, Box<Stmt<'a>>),
     WaitForkStmt,
+    EventTriggerStmt(Expr<'a>),
     DisableForkStmt,
     DisableStmt(Name),
+    ProceduralAssignStmt {
+        lhs: Expr<'a>,
+        rhs: Expr<'a>,
+    },
+    DeassignStmt(Expr<'a>),
+    ForceStmt {
+        lhs: Expr<'a>,
+        rhs: Expr<'a>,
+    },
+    ReleaseStmt(Expr<'a>),
 }
 
 impl<'a> Stmt<'a> {
@@ -1210,7 +1304,8 @@ pub enum CaseMode {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CaseItem<'a> {
     Default(Box<Stmt<'a>>),
-    Expr(Vec<Expr<'a>>, Box<Stmt<'a>>),
+    /// A list of case labels. Ranges are only allowed in `case ... inside`.
+    Expr(Vec<ValueRange<'a>>, Box<Stmt<'a>>),
 }
 
 #[moore_derive::visit]
@@ -1220,6 +1315,24 @@ pub struct DelayControl<'a> {
     pub expr: Expr<'a>,
 }
 
+/// A delay on a net or continuous assignment.
+///
+/// ```text
+/// "#" delay_value
+/// "#" "(" mintypmax_expression ["," mintypmax_expression ["," mintypmax_expression]] ")"
+/// ```
+///
+/// The three delays apply to rising, falling, and turn-off transitions of the
+/// driven value, respectively.
+#[moore_derive::visit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delay3<'a> {
+    pub span: Span,
+    pub rise: Expr<'a>,
+    pub fall: Option<Expr<'a>>,
+    pub turn_off: Option<Expr<'a>>,
+}
+
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EventControl<'a> {
@@ -1236,14 +1349,17 @@ pub enum EventControlData<'a> {
 
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct CycleDelay {}
+pub struct CycleDelay<'a> {
+    pub span: Span,
+    pub expr: Expr<'a>,
+}
 
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TimingControl<'a> {
     Delay(DelayControl<'a>),
     Event(EventControl<'a>),
-    Cycle(CycleDelay),
+    Cycle(CycleDelay<'a>),
 }
 
 #[moore_derive::visit]
@@ -1264,6 +1380,33 @@ pub enum AssignOp {
     ArithShR,
 }
 
+impl AssignOp {
+    /// Get the operator as it appears in the source text.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AssignOp::Identity => Op::Assign.as_str(),
+            AssignOp::Add => Op::AssignAdd.as_str(),
+            AssignOp::Sub => Op::AssignSub.as_str(),
+            AssignOp::Mul => Op::AssignMul.as_str(),
+            AssignOp::Div => Op::AssignDiv.as_str(),
+            AssignOp::Mod => Op::AssignMod.as_str(),
+            AssignOp::BitAnd => Op::AssignBitAnd.as_str(),
+            AssignOp::BitOr => Op::AssignBitOr.as_str(),
+            AssignOp::BitXor => Op::AssignBitXor.as_str(),
+            AssignOp::LogicShL => Op::AssignLogicShL.as_str(),
+            AssignOp::LogicShR => Op::AssignLogicShR.as_str(),
+            AssignOp::ArithShL => Op::AssignArithShL.as_str(),
+            AssignOp::ArithShR => Op::AssignArithShR.as_str(),
+        }
+    }
+}
+
+impl std::fmt::Display for AssignOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// A variable declaration.
 ///
 /// For example `logic x, y, z`.
@@ -1325,6 +1468,10 @@ pub enum Expr<'a> {
     /// A system identifier, like `$foo`.
     SysIdentExpr(Spanned<Name>),
     ThisExpr,
+    /// The `super` keyword, as in `super.new(...)`.
+    SuperExpr,
+    /// The `local` qualifier, as in `local::foo`.
+    LocalExpr,
     DollarExpr,
     NullExpr,
     ScopeExpr(Box<Expr<'a>>, Spanned<Name>),
@@ -1359,6 +1506,7 @@ pub enum Expr<'a> {
     ArrayNewExpr(Box<Expr<'a>>, Option<Box<Expr<'a>>>),
     EmptyQueueExpr,
     StreamConcatExpr {
+        dir: StreamDir,
         slice: Option<StreamConcatSlice<'a>>,
         exprs: Vec<StreamExpr<'a>>,
     },
@@ -1383,6 +1531,8 @@ pub enum Expr<'a> {
     },
     PatternExpr(Vec<PatternField<'a>>),
     InsideExpr(Box<Expr<'a>>, Vec<ValueRange<'a>>),
+    /// A weighted distribution, like `a dist {0 := 40, [1:3] :/ 60}`.
+    DistExpr(Box<Expr<'a>>, Vec<DistItem<'a>>),
     CastExpr(Type<'a>, Box<Expr<'a>>),
     CastSizeExpr(Box<Expr<'a>>, Box<Expr<'a>>),
     CastSignExpr(Spanned<TypeSign>, Box<Expr<'a>>),
@@ -1406,14 +1556,7 @@ pub enum TypeOrExpr<'a> {
     Expr(&'a Expr<'a>),
 }
 
-impl<'a> AnyNode<'a> for TypeOrExpr<'a> {
-    fn id(&self) -> NodeId {
-        match self {
-            TypeOrExpr::Type(x) => x.id(),
-            TypeOrExpr::Expr(x) => x.id(),
-        }
-    }
-
+impl HasSpan for TypeOrExpr<'_> {
     fn span(&self) -> Span {
         match self {
             TypeOrExpr::Type(x) => x.span(),
@@ -1427,6 +1570,15 @@ impl<'a> AnyNode<'a> for TypeOrExpr<'a> {
             TypeOrExpr::Expr(x) => x.human_span(),
         }
     }
+}
+
+impl<'a> AnyNode<'a> for TypeOrExpr<'a> {
+    fn id(&self) -> NodeId {
+        match self {
+            TypeOrExpr::Type(x) => x.id(),
+            TypeOrExpr::Expr(x) => x.id(),
+        }
+    }
 
     fn order(&self) -> usize {
         match self {
@@ -1500,6 +1652,27 @@ pub enum ValueRange<'a> {
     },
 }
 
+/// One entry of a `dist` list, like `[1:3] :/ 60`.
+#[moore_derive::visit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistItem<'a> {
+    pub span: Span,
+    pub range: ValueRange<'a>,
+    pub kind: DistWeight,
+    /// The weight, or `None` if the default weight of `:= 1` applies.
+    pub weight: Option<Expr<'a>>,
+}
+
+/// How the weight of a `dist` item is applied.
+#[moore_derive::visit]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistWeight {
+    /// `:=`, the weight applies to each value in the range.
+    PerValue,
+    /// `:/`, the weight is divided among the values in the range.
+    PerRange,
+}
+
 #[moore_derive::visit]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RangeMode {
@@ -1523,6 +1696,16 @@ pub struct CallArg<'a> {
     pub expr: Option<Expr<'a>>,
 }
 
+/// The direction of a streaming concatenation.
+#[moore_derive::visit]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamDir {
+    /// A right-streaming concatenation, `{>> ...}`.
+    In,
+    /// A left-streaming concatenation, `{<< ...}`.
+    Out,
+}
+
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StreamConcatSlice<'a> {
@@ -1566,6 +1749,15 @@ pub enum EdgeIdent {
     Negedge,
 }
 
+impl HasSpan for ValueRange<'_> {
+    fn span(&self) -> Span {
+        match *self {
+            ValueRange::Single(ref expr) => expr.span,
+            ValueRange::Range { span, .. } => span,
+        }
+    }
+}
+
 impl HasSpan for EventExpr<'_> {
     fn span(&self) -> Span {
         match *self {
@@ -1621,7 +1813,7 @@ pub enum ClassItemQualifier {
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ClassItemData<'a> {
-    Property,
+    Property(Type<'a>, Vec<VarDeclName<'a>>),
     Typedef(Typedef<'a>),
     SubroutineDecl(SubroutineDecl<'a>),
     ExternSubroutine(SubroutinePrototype<'a>),
@@ -1639,6 +1831,15 @@ pub enum RandomQualifier {
     Randc,
 }
 
+impl std::fmt::Display for RandomQualifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RandomQualifier::Rand => write!(f, "rand"),
+            RandomQualifier::Randc => write!(f, "randc"),
+        }
+    }
+}
+
 /// A type definition.
 ///
 /// For example `typedef int my_type_t`.
@@ -1682,11 +1883,29 @@ pub struct ConstraintItem<'a> {
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConstraintItemData<'a> {
-    If,
-    Foreach,
+    /// A conditional constraint, such as `if (a) b < 4; else { b > 8; }`.
+    If {
+        cond: Expr<'a>,
+        main: Vec<ConstraintItem<'a>>,
+        alt: Option<Vec<ConstraintItem<'a>>>,
+    },
+    /// A constraint applied to each element of an array.
+    Foreach(ConstraintForeach<'a>),
     Expr(Expr<'a>),
 }
 
+/// A constraint applied to each element of an array.
+///
+/// For example `foreach (a[i]) a[i] < 4;`.
+#[moore_derive::node]
+#[indefinite("foreach constraint")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintForeach<'a> {
+    pub array: Expr<'a>,
+    pub indices: Vec<ForeachIndex<'a>>,
+    pub items: Vec<ConstraintItem<'a>>,
+}
+
 /// A function or task declaration.
 #[moore_derive::node]
 #[indefinite("subroutine declaration")]
@@ -1773,7 +1992,7 @@ pub struct NetDecl<'a> {
     pub strength: Option<NetStrength>,
     pub kind: NetKind,
     pub ty: Type<'a>,
-    pub delay: Option<DelayControl<'a>>,
+    pub delay: Option<Delay3<'a>>,
     pub names: Vec<VarDeclName<'a>>,
 }
 
@@ -1807,6 +2026,56 @@ pub enum DriveStrength {
     HighZ1,
 }
 
+impl DriveStrength {
+    /// Get the strength as it appears in the source text.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DriveStrength::Supply0 => "supply0",
+            DriveStrength::Strong0 => "strong0",
+            DriveStrength::Pull0 => "pull0",
+            DriveStrength::Weak0 => "weak0",
+            DriveStrength::HighZ0 => "highz0",
+            DriveStrength::Supply1 => "supply1",
+            DriveStrength::Strong1 => "strong1",
+            DriveStrength::Pull1 => "pull1",
+            DriveStrength::Weak1 => "weak1",
+            DriveStrength::HighZ1 => "highz1",
+        }
+    }
+
+    /// Check whether this is the strength with which a 1 is driven.
+    pub fn is_one(self) -> bool {
+        match self {
+            DriveStrength::Supply1
+            | DriveStrength::Strong1
+            | DriveStrength::Pull1
+            | DriveStrength::Weak1
+            | DriveStrength::HighZ1 => true,
+            _ => false,
+        }
+    }
+
+    /// Get the strength level, from 7 for `supply` down to 0 for `highz`.
+    ///
+    /// Of two drivers, the one with the higher level determines the value of
+    /// the driven net.
+    pub fn level(self) -> u8 {
+        match self {
+            DriveStrength::Supply0 | DriveStrength::Supply1 => 7,
+            DriveStrength::Strong0 | DriveStrength::Strong1 => 6,
+            DriveStrength::Pull0 | DriveStrength::Pull1 => 5,
+            DriveStrength::Weak0 | DriveStrength::Weak1 => 3,
+            DriveStrength::HighZ0 | DriveStrength::HighZ1 => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for DriveStrength {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[moore_derive::visit]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChargeStrength {
@@ -1884,12 +2153,12 @@ pub enum BlockingAssertion<'a> {
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConcurrentAssertion<'a> {
-    AssertProperty(PropSpec, AssertionActionBlock<'a>),
-    AssumeProperty(PropSpec, AssertionActionBlock<'a>),
-    CoverProperty(PropSpec, Stmt<'a>),
+    AssertProperty(PropSpec<'a>, AssertionActionBlock<'a>),
+    AssumeProperty(PropSpec<'a>, AssertionActionBlock<'a>),
+    CoverProperty(PropSpec<'a>, Stmt<'a>),
     CoverSequence,
-    ExpectProperty(PropSpec, AssertionActionBlock<'a>),
-    RestrictProperty(PropSpec),
+    ExpectProperty(PropSpec<'a>, AssertionActionBlock<'a>),
+    RestrictProperty(PropSpec<'a>),
 }
 
 #[moore_derive::visit]
@@ -1911,7 +2180,12 @@ pub struct SeqExpr<'a> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SeqExprData<'a> {
     Expr(Expr<'a>, Option<SeqRep<'a>>),
+    /// A parenthesized sequence with a repetition, such as `(a ##1 b)[*2]`.
+    Paren(Box<SeqExpr<'a>>, SeqRep<'a>),
     BinOp(SeqBinOp, Box<SeqExpr<'a>>, Box<SeqExpr<'a>>),
+    /// A cycle delay, such as `a ##1 b`, or `##[1:3] b` without a left hand
+    /// side.
+    Delay(Option<Box<SeqExpr<'a>>>, SeqDelay<'a>, Box<SeqExpr<'a>>),
     Throughout(Expr<'a>, Box<SeqExpr<'a>>),
     Clocked(EventExpr<'a>, Box<SeqExpr<'a>>),
 }
@@ -1919,11 +2193,21 @@ pub enum SeqExprData<'a> {
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SeqRep<'a> {
-    Consec(Expr<'a>),    // [* expr]
-    ConsecStar,          // [*]
-    ConsecPlus,          // [+]
-    Nonconsec(Expr<'a>), // [= expr]
-    Goto(Expr<'a>),      // [-> expr]
+    Consec(Expr<'a>, Option<Expr<'a>>),    // [* expr] or [* expr:expr]
+    ConsecStar,                            // [*]
+    ConsecPlus,                            // [+]
+    Nonconsec(Expr<'a>, Option<Expr<'a>>), // [= expr] or [= expr:expr]
+    Goto(Expr<'a>, Option<Expr<'a>>),      // [-> expr] or [-> expr:expr]
+}
+
+/// The number of cycles of a cycle delay or a property operator.
+#[moore_derive::visit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeqDelay<'a> {
+    Single(Expr<'a>),          // ##expr or [expr]
+    Range(Expr<'a>, Expr<'a>), // ##[expr:expr] or [expr:expr]
+    Star,                      // ##[*]
+    Plus,                      // ##[+]
 }
 
 #[moore_derive::visit]
@@ -1935,9 +2219,21 @@ pub enum SeqBinOp {
     Within,
 }
 
+/// A property specification.
+///
+/// For example `@(posedge clk) disable iff (rst) a |-> b`.
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct PropSpec;
+pub struct PropSpec<'a> {
+    pub span: Span,
+    /// The optional clocking event.
+    pub clock: Option<EventExpr<'a>>,
+    /// The optional `disable iff` condition.
+    pub disable: Option<Expr<'a>>,
+    /// The property expression, or `None` if it uses constructs that cannot be
+    /// parsed yet.
+    pub prop: Option<PropExpr<'a>>,
+}
 
 #[moore_derive::visit]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1952,10 +2248,23 @@ pub enum PropExprData<'a> {
     SeqOp(PropSeqOp, SeqExpr<'a>),
     SeqBinOp(PropSeqBinOp, PropSeqOp, SeqExpr<'a>, Box<PropExpr<'a>>),
     Not(Box<PropExpr<'a>>),
+    /// A unary operator such as `s_eventually`, with an optional cycle range.
+    UnOp(PropUnOp, Option<SeqDelay<'a>>, Box<PropExpr<'a>>),
     BinOp(PropBinOp, Box<PropExpr<'a>>, Box<PropExpr<'a>>),
     Clocked(EventExpr<'a>, Box<PropExpr<'a>>),
 }
 
+#[moore_derive::visit]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropUnOp {
+    Nexttime,
+    SNexttime,
+    Always,
+    SAlways,
+    Eventually,
+    SEventually,
+}
+
 #[moore_derive::visit]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PropSeqOp {
@@ -2036,6 +2345,24 @@ impl<'a> InstName<'a> {
     }
 }
 
+/// A bind directive.
+///
+/// For example `bind foo bar u0(.*);`, which instantiates `bar` in every
+/// instance of module `foo`, or `bind top.u1 bar u0(.*);`, which only
+/// instantiates it in the instance `top.u1`.
+#[moore_derive::node]
+#[indefinite("bind directive")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bind<'a> {
+    /// The module name or instance path the directive binds into.
+    pub target: Expr<'a>,
+    /// The optional instances of the target module to bind into, as in
+    /// `bind foo: top.u1, top.u2 ...`.
+    pub instances: Vec<Expr<'a>>,
+    /// The instantiation to insert into the target.
+    pub inst: Inst<'a>,
+}
+
 /// A modport declaration in an interface.
 ///
 /// For example `modport in (...), out (...);`.
@@ -2073,6 +2400,11 @@ pub enum ModportPort<'a> {
         dir: Spanned<PortDir>,
         port: Vec<&'a ModportSimplePort<'a>>,
     },
+    /// A task or function port, for example `import f, task g()`.
+    Tf {
+        dir: Spanned<ModportTfDir>,
+        port: Vec<&'a ModportTfPort<'a>>,
+    },
 }
 
 /// A single simple modport port.
@@ -2089,6 +2421,113 @@ pub struct ModportSimplePort<'a> {
     pub expr: Option<&'a Expr<'a>>,
 }
 
+/// A single task or function modport port.
+///
+/// For example the `f` or `task g()` in `import f, task g()`.
+#[moore_derive::node]
+#[indefinite("modport method")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModportTfPort<'a> {
+    /// The name of the task or function.
+    #[name]
+    pub name: Spanned<Name>,
+    /// The optional prototype of the task or function.
+    pub prototype: Option<SubroutinePrototype<'a>>,
+}
+
+/// Whether a modport imports or exports a task or function.
+#[moore_derive::visit]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModportTfDir {
+    Import,
+    Export,
+}
+
+impl std::fmt::Display for ModportTfDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ModportTfDir::Import => write!(f, "import"),
+            ModportTfDir::Export => write!(f, "export"),
+        }
+    }
+}
+
+/// A clocking block.
+///
+/// ```text
+/// ["default"] "clocking" [ident] "@" event_expr ";"
+///     {clocking_item}
+/// "endclocking" [":" ident]
+/// ```
+#[moore_derive::node]
+#[indefinite("clocking block")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockingDecl<'a> {
+    /// Whether this is the default clocking block of its scope.
+    pub default: bool,
+    /// The name of the clocking block.
+    pub name: Option<Spanned<Name>>,
+    /// The event on which signals are sampled and driven.
+    pub event: EventExpr<'a>,
+    /// The skews and signals of the clocking block.
+    pub items: Vec<ClockingItem<'a>>,
+}
+
+/// An item in a clocking block.
+#[moore_derive::visit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClockingItem<'a> {
+    /// The default skews, for example `default input #1step output #2;`.
+    Default {
+        span: Span,
+        input: Option<ClockingSkew<'a>>,
+        output: Option<ClockingSkew<'a>>,
+    },
+    /// A list of signals, for example `input #1 a, b;`.
+    Signals {
+        span: Span,
+        dir: PortDir,
+        input: Option<ClockingSkew<'a>>,
+        output: Option<ClockingSkew<'a>>,
+        signals: Vec<ClockingSignal<'a>>,
+    },
+}
+
+/// A signal in a clocking block.
+///
+/// For example the `a` or `b = top.x` in `input a, b = top.x;`.
+#[moore_derive::node]
+#[indefinite("clocking signal")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockingSignal<'a> {
+    /// The name of the signal.
+    #[name]
+    pub name: Spanned<Name>,
+    /// The signal this name refers to, if it differs from the name.
+    pub expr: Option<Expr<'a>>,
+}
+
+/// The skew of a clocking block signal.
+///
+/// For example `posedge #1`, `#2ns`, or `#1step`.
+#[moore_derive::visit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockingSkew<'a> {
+    pub span: Span,
+    pub edge: Option<EdgeIdent>,
+    pub delay: Option<ClockingDelay<'a>>,
+}
+
+/// The delay of a clocking skew.
+#[moore_derive::visit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClockingDelay<'a> {
+    /// The `#1step` delay, which samples signals just before the clock event.
+    Step,
+    /// An explicit delay, for example `#2ns`.
+    Expr(Expr<'a>),
+}
+
 /// A parameter or localparam declaration.
 ///
 /// ```text
@@ -2153,8 +2592,19 @@ pub struct ParamValueDecl<'a> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContAssign<'a> {
     pub strength: Option<(DriveStrength, DriveStrength)>,
-    pub delay: Option<Expr<'a>>,
-    pub delay_control: Option<DelayControl<'a>>,
+    pub delay: Option<Delay3<'a>>,
+    pub assignments: Vec<(Expr<'a>, Expr<'a>)>,
+}
+
+/// A defparam statement.
+///
+/// For example `defparam top.u1.WIDTH = 8, top.u2.WIDTH = 16;`, which overrides
+/// the parameters of instances elsewhere in the design hierarchy.
+#[moore_derive::node]
+#[indefinite("defparam statement")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Defparam<'a> {
+    /// The hierarchical parameter paths and the values assigned to them.
     pub assignments: Vec<(Expr<'a>, Expr<'a>)>,
 }
 
@@ -2183,8 +2633,17 @@ pub struct GenerateIf<'a> {
 #[moore_derive::node]
 #[indefinite("case-generate statement")]
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct GenerateCase {
-    // TODO
+pub struct GenerateCase<'a> {
+    pub expr: Expr<'a>,
+    pub items: Vec<GenerateCaseItem<'a>>,
+}
+
+/// An item of a `case` generate statement.
+#[moore_derive::visit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateCaseItem<'a> {
+    Default(GenerateBlock<'a>),
+    Expr(Vec<Expr<'a>>, GenerateBlock<'a>),
 }
 
 /// A body of a generate construct.
@@ -2492,6 +2951,35 @@ pub enum PathSegment<'a> {
     Class(Spanned<Name>, Vec<ParamAssignment<'a>>),
 }
 
+/// Implement `HasSpan` for syntax elements that are not nodes but carry a
+/// `span` field.
+macro_rules! impl_has_span {
+    ($($ty:ty),* $(,)?) => {$(
+        impl HasSpan for $ty {
+            fn span(&self) -> Span {
+                self.span
+            }
+        }
+    )*}
+}
+
+impl_has_span!(
+    DelayControl<'_>,
+    Delay3<'_>,
+    EventControl<'_>,
+    Identifier,
+    CallArg<'_>,
+    ClassItem<'_>,
+    Constraint<'_>,
+    ConstraintItem<'_>,
+    SubroutinePortDecl<'_>,
+    Assertion<'_>,
+    SeqExpr<'_>,
+    PropSpec<'_>,
+    PropExpr<'_>,
+    ParamAssignment<'_>,
+);
+
 moore_derive::derive_visitor!(
     /// Called for every node before visiting its children.
     ///
@@ -3,6 +3,7 @@
 //! A lexical analyzer for SystemVerilog files, based on IEEE 1800-2009, section
 //! 5.
 
+use crate::ast::NetType;
 use crate::cat::CatTokenKind;
 use crate::preproc::*;
 pub use crate::token::*;
@@ -17,6 +18,9 @@ pub type TokenAndSpan = (Token, Span);
 pub struct Lexer<'a> {
     input: Preprocessor<'a>,
     peek: [CatTokenAndSpan; 4],
+    /// The keyword set that was active when each of the peeked tokens was
+    /// emitted by the preprocessor.
+    peek_keywords: [Option<KeywordsDirective>; 4],
 }
 
 impl<'a> Lexer<'a> {
@@ -24,18 +28,37 @@ impl<'a> Lexer<'a> {
         Lexer {
             input: input,
             peek: [(CatTokenKind::Eof, INVALID_SPAN); 4],
+            peek_keywords: [None; 4],
         }
     }
 
+    /// Get the net type currently set by `` `default_nettype``, or `None` if
+    /// implicit nets are disabled.
+    pub fn default_nettype(&self) -> Option<NetType> {
+        self.input.default_nettype()
+    }
+
+    /// Collect the warnings produced by the preprocessor so far.
+    pub fn take_warnings(&mut self) -> Vec<DiagBuilder2> {
+        self.input.take_warnings()
+    }
+
+    /// Collect the `` `pragma`` directives encountered by the preprocessor so far.
+    pub fn take_pragmas(&mut self) -> Vec<Pragma> {
+        self.input.take_pragmas()
+    }
+
     pub fn bump(&mut self) -> DiagResult2<()> {
         self.peek[0] = self.peek[1];
         self.peek[1] = self.peek[2];
         self.peek[2] = self.peek[3];
+        self.peek_keywords.rotate_left(1);
         self.peek[3] = match self.input.next() {
             Some(Err(e)) => return Err(e),
             Some(Ok(x)) => x,
             None => (CatTokenKind::Eof, self.peek[2].1),
         };
+        self.peek_keywords[3] = self.input.keywords();
 
         Ok(())
     }
@@ -235,8 +258,11 @@ impl<'a> Lexer<'a> {
                 // IEEE 1800-2009 5.6 Identifiers
                 // IEEE 1800-2009 5.6.2 Keywords
                 (CatTokenKind::Text, _) | (CatTokenKind::Symbol('_'), _) => {
+                    let keywords = self.peek_keywords[0];
                     let (m, msp) = self.match_ident()?;
-                    return match find_keyword(&m) {
+                    let kw =
+                        find_keyword(&m).filter(|&kw| keywords.map_or(true, |k| k.reserves(kw)));
+                    return match kw {
                         Some(Kw::Begin) => Ok((OpenDelim(Bgend), msp)),
                         Some(Kw::End) => Ok((CloseDelim(Bgend), msp)),
                         Some(kw) => Ok((Keyword(kw), msp)),
@@ -319,6 +345,15 @@ impl<'a> Lexer<'a> {
                         self.bump()?; // eat the unit
                         return Ok((Literal(Time(value, frac, unit)), sp));
                     }
+                    if self.peek[0].0 == CatTokenKind::Text
+                        && &*value.as_str() == "1"
+                        && frac.is_none()
+                        && self.peek[0].1.extract() == "step"
+                    {
+                        sp.expand(self.peek[0].1);
+                        self.bump()?; // eat the `step`
+                        return Ok((OneStep, sp));
+                    }
                     if self.peek[0].0 == CatTokenKind::Text {
                         return Err(DiagBuilder2::fatal(format!(
                             "number literal `{}` may not directly be followed by letters `{}`",
@@ -367,9 +402,9 @@ impl<'a> Lexer<'a> {
                                         span.expand(sp);
                                         s.push('"');
                                     }
-                                    (CatTokenKind::Text, sp) => {
+                                    (CatTokenKind::Text, sp) | (CatTokenKind::Digits, sp) => {
                                         span.expand(sp);
-                                        s.push_str(&sp.extract());
+                                        unescape_into(&sp.extract(), &mut s);
                                     }
                                     _ => {
                                         return Err(DiagBuilder2::fatal(
@@ -660,6 +695,47 @@ impl<'a> Iterator for Lexer<'a> {
     }
 }
 
+/// Decode an escape sequence in a string literal.
+///
+/// The `text` is the text or digits token following the backslash. Only the
+/// leading characters form the escape sequence; the remainder is taken
+/// verbatim. Unknown escape sequences produce the escaped character. See IEEE
+/// 1800-2017 5.9.1.
+fn unescape_into(text: &str, into: &mut String) {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some('n') => into.push('\n'),
+        Some('t') => into.push('\t'),
+        Some('v') => into.push('\x0b'),
+        Some('f') => into.push('\x0c'),
+        Some('a') => into.push('\x07'),
+        Some('x') => {
+            let digits = chars.as_str();
+            let len = digits
+                .chars()
+                .take(2)
+                .take_while(|c| c.is_ascii_hexdigit())
+                .count();
+            if len == 0 {
+                into.push('x');
+            } else {
+                let value = u8::from_str_radix(&digits[..len], 16).unwrap();
+                into.push(value as char);
+                chars = digits[len..].chars();
+            }
+        }
+        Some(c) if c.is_digit(8) => {
+            let len = text.chars().take(3).take_while(|c| c.is_digit(8)).count();
+            let value = u32::from_str_radix(&text[..len], 8).unwrap();
+            into.push((value as u8) as char);
+            chars = text[len..].chars();
+        }
+        Some(c) => into.push(c),
+        None => (),
+    }
+    into.push_str(chars.as_str());
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -831,6 +907,19 @@ mod tests {
         );
     }
 
+    /// According to IEEE 1800-2017 5.9.1
+    #[test]
+    fn string_literal_escapes() {
+        check(
+            "\"a\\nb\\tc\\\\d\\\"e\\vf\\fg\\ah\"",
+            &[Literal(Str(name("a\nb\tc\\d\"e\x0bf\x0cg\x07h")))],
+        );
+        check(
+            "\"\\101\\1012\\0\\x41\\x4142\\xg\\q\"",
+            &[Literal(Str(name("AA2\0AA42xgq")))],
+        );
+    }
+
     #[test]
     fn time_literal() {
         check(
@@ -856,4 +945,24 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn one_step() {
+        check("#1step", &[Hashtag, OneStep]);
+    }
+
+    #[test]
+    fn begin_keywords() {
+        check(
+            "global logic `begin_keywords \"1364-2005\" global logic uwire `end_keywords global",
+            &[
+                Keyword(Kw::Global),
+                Keyword(Kw::Logic),
+                Ident(name("global")),
+                Ident(name("logic")),
+                Keyword(Kw::Uwire),
+                Keyword(Kw::Global),
+            ],
+        );
+    }
 }
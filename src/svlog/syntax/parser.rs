@@ -41,6 +41,7 @@ trait AbstractParser<'n> {
     fn last_span(&self) -> Span;
     fn add_diag(&mut self, diag: DiagBuilder2);
     fn severity(&self) -> Severity;
+    fn default_nettype(&self) -> Option<NetType>;
 
     fn try_eat_ident(&mut self) -> Option<(Name, Span)> {
         match self.peek(0) {
@@ -301,6 +302,10 @@ impl<'a, 'n> AbstractParser<'n> for Parser<'a, 'n> {
     fn severity(&self) -> Severity {
         self.severity
     }
+
+    fn default_nettype(&self) -> Option<NetType> {
+        self.input.default_nettype()
+    }
 }
 
 impl<'a, 'n> Parser<'a, 'n> {
@@ -326,6 +331,9 @@ impl<'a, 'n> Parser<'a, 'n> {
                 Ok(tkn) => self.queue.push_back(tkn),
                 Err(x) => self.add_diag(x),
             }
+            for warning in self.input.take_warnings() {
+                self.add_diag(warning);
+            }
         }
     }
 }
@@ -683,12 +691,27 @@ fn parse_time_units<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Timeun
     Ok(Timeunit { unit, prec })
 }
 
-fn parse_time_literal<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Spanned<Lit>> {
+fn parse_time_literal<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Spanned<TimeValue>> {
     let (tkn, sp) = p.peek(0);
     match tkn {
-        Literal(lit @ Time(..)) => {
+        Literal(Time(int, frac, unit)) => {
             p.bump();
-            Ok(Spanned::new(lit, sp))
+            let magnitude = match (&*int.as_str(), frac) {
+                ("1", None) => 1,
+                ("10", None) => 10,
+                ("100", None) => 100,
+                _ => {
+                    p.add_diag(
+                        DiagBuilder2::error(format!(
+                            "time unit must be 1, 10, or 100, instead got `{}`",
+                            sp.extract()
+                        ))
+                        .span(sp),
+                    );
+                    return Err(());
+                }
+            };
+            Ok(Spanned::new(TimeValue { magnitude, unit }, sp))
         }
         _ => {
             p.add_diag(
@@ -712,6 +735,7 @@ fn as_lifetime(tkn: Token) -> Option<Lifetime> {
 
 fn parse_interface_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Interface<'n>> {
     let mut span = p.peek(0).1;
+    let default_nettype = p.default_nettype();
     p.require_reported(Keyword(Kw::Interface))?;
     let result = recovered(p, Keyword(Kw::Endinterface), |p| {
         // Eat the optional lifetime.
@@ -754,6 +778,9 @@ fn parse_interface_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<In
             );
         }
 
+        // Parse the optional timeunits declaration.
+        let timeunits = parse_time_units(p)?;
+
         // Eat the items in the interface.
         let mut items = Vec::new();
         while !p.is_fatal() && p.peek(0).0 != Keyword(Kw::Endinterface) && p.peek(0).0 != Eof {
@@ -768,7 +795,9 @@ fn parse_interface_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<In
             span,
             InterfaceData {
                 lifetime: lifetime,
+                default_nettype,
                 name: Spanned::new(name, name_sp),
+                timeunits,
                 params: param_ports,
                 ports: ports,
                 items: items,
@@ -782,6 +811,114 @@ fn parse_interface_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<In
     result
 }
 
+/// Parse a checker declaration.
+///
+/// ```text
+/// checker_decl: "checker" ident ["(" {checker_port} ")"] ";" {item} "endchecker" [":" ident]
+/// ```
+fn parse_checker_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<CheckerDecl<'n>> {
+    let mut span = p.peek(0).1;
+    p.require_reported(Keyword(Kw::Checker))?;
+    let result = recovered(p, Keyword(Kw::Endchecker), |p| {
+        // Eat the checker name.
+        let (name, name_sp) = p.eat_ident("checker name")?;
+
+        // Eat the optional list of ports.
+        let ports = if p.peek(0).0 == OpenDelim(Paren) {
+            parse_checker_port_list(p)?
+        } else {
+            Vec::new()
+        };
+        p.require_reported(Semicolon)?;
+
+        // Eat the items in the checker.
+        let mut items = Vec::new();
+        while !p.is_fatal() && p.peek(0).0 != Keyword(Kw::Endchecker) && p.peek(0).0 != Eof {
+            if p.try_eat(Semicolon) {
+                continue;
+            }
+            items.push(parse_item(p)?);
+        }
+
+        span.expand(p.last_span());
+        Ok(CheckerDecl::new(
+            span,
+            CheckerDeclData {
+                name: Spanned::new(name, name_sp),
+                ports,
+                items,
+            },
+        ))
+    });
+    p.require_reported(Keyword(Kw::Endchecker))?;
+    if p.try_eat(Colon) {
+        p.eat_ident("checker name")?;
+    }
+    result
+}
+
+/// Parse the port list of a checker.
+///
+/// ```text
+/// checker_port: ["input"] [checker_port_kind] ident ["=" expr]
+/// checker_port_kind: "sequence" | "property" | "untyped" | data_type
+/// ```
+fn parse_checker_port_list<'n>(
+    p: &mut dyn AbstractParser<'n>,
+) -> ReportedResult<Vec<CheckerPort<'n>>> {
+    let mut prev_kind = CheckerPortKind::Untyped;
+    flanked(p, Paren, |p| {
+        comma_list(p, CloseDelim(Paren), "checker port", |p| {
+            let mut span = p.peek(0).1;
+            p.try_eat(Keyword(Kw::Input));
+
+            // Parse the port kind. A port without an explicit kind inherits
+            // the kind of the previous port.
+            let kind = match p.peek(0).0 {
+                Keyword(Kw::Sequence) => {
+                    p.bump();
+                    CheckerPortKind::Sequence
+                }
+                Keyword(Kw::Property) => {
+                    p.bump();
+                    CheckerPortKind::Property
+                }
+                Keyword(Kw::Untyped) => {
+                    p.bump();
+                    CheckerPortKind::Untyped
+                }
+                Ident(..) | EscIdent(..)
+                    if match p.peek(1).0 {
+                        Comma | CloseDelim(Paren) | Operator(Op::Assign) => true,
+                        _ => false,
+                    } =>
+                {
+                    prev_kind.clone()
+                }
+                _ => CheckerPortKind::Data(parse_data_type(p)?),
+            };
+            prev_kind = kind.clone();
+
+            // Parse the port name and optional default value.
+            let (name, name_sp) = p.eat_ident("checker port name")?;
+            let default = if p.try_eat(Operator(Op::Assign)) {
+                Some(parse_expr(p)?)
+            } else {
+                None
+            };
+            span.expand(p.last_span());
+            Ok(CheckerPort::new(
+                span,
+                CheckerPortData {
+                    kind,
+                    name: Spanned::new(name, name_sp),
+                    default,
+                },
+            ))
+        })
+    })
+}
+
 fn parse_parameter_port_list<'n>(
     p: &mut dyn AbstractParser<'n>,
 ) -> ReportedResult<Vec<ParamDecl<'n>>> {
@@ -874,6 +1011,7 @@ fn parse_parameter_port_list<'n>(
 /// already been consumed.
 fn parse_module_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Module<'n>> {
     let mut span = p.peek(0).1;
+    let default_nettype = p.default_nettype();
     p.require_reported(Keyword(Kw::Module))?;
     let result = recovered(p, Keyword(Kw::Endmodule), |p| {
         // Eat the optional lifetime.
@@ -919,6 +1057,9 @@ fn parse_module_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Modul
             );
         }
 
+        // Parse the optional timeunits declaration.
+        let timeunits = parse_time_units(p)?;
+
         // Parse the module items.
         let mut items = Vec::new();
         while !p.is_fatal() && p.peek(0).0 != Keyword(Kw::Endmodule) && p.peek(0).0 != Eof {
@@ -933,7 +1074,9 @@ fn parse_module_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Modul
             span,
             ModuleData {
                 lifetime,
+                default_nettype,
                 name: Spanned::new(name, name_sp),
+                timeunits,
                 imports,
                 params,
                 ports,
@@ -966,11 +1109,8 @@ fn parse_package_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Pack
         let (name, name_span) = p.eat_ident("package name")?;
         p.require_reported(Semicolon)?;
 
-        // TODO: Parse the optional timeunits declaration.
-        let timeunits = Timeunit {
-            unit: None,
-            prec: None,
-        };
+        // Parse the optional timeunits declaration.
+        let timeunits = parse_time_units(p)?;
 
         // Parse the package items.
         let mut items = Vec::new();
@@ -1038,8 +1178,10 @@ fn parse_item_data<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ItemDat
         }
         Keyword(Kw::Class) => return parse_class_decl(p).map(ItemData::ClassDecl),
         Keyword(Kw::Interface) => return parse_interface_decl(p).map(ItemData::InterfaceDecl),
+        Keyword(Kw::Checker) => return parse_checker_decl(p).map(ItemData::CheckerDecl),
         Keyword(Kw::Package) => return parse_package_decl(p).map(ItemData::PackageDecl),
         Keyword(Kw::Program) => return parse_program_decl(p).map(ItemData::ProgramDecl),
+        Keyword(Kw::Bind) => return parse_bind_directive(p).map(ItemData::Bind),
 
         Keyword(Kw::Localparam) | Keyword(Kw::Parameter) => {
             let decl = parse_param_decl(p, false)?;
@@ -1047,6 +1189,10 @@ fn parse_item_data<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ItemDat
             return Ok(ItemData::ParamDecl(decl));
         }
         Keyword(Kw::Modport) => return parse_modport_decl(p).map(|x| ItemData::ModportDecl(x)),
+        Keyword(Kw::Clocking) => {
+            let span = p.peek(0).1;
+            return parse_clocking_decl(p, span, false).map(ItemData::ClockingDecl);
+        }
         Keyword(Kw::Typedef) => return parse_typedef(p).map(|x| ItemData::Typedef(x)),
         Keyword(Kw::Import) if strlit_follows => return parse_dpi_decl(p).map(ItemData::DpiDecl),
         Keyword(Kw::Export) => return parse_dpi_decl(p).map(ItemData::DpiDecl),
@@ -1085,6 +1231,9 @@ fn parse_item_data<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ItemDat
             return parse_continuous_assign(p).map(|x| ItemData::ContAssign(x));
         }
 
+        // Parameter overrides
+        Keyword(Kw::Defparam) => return parse_defparam(p).map(ItemData::Defparam),
+
         // Genvar declaration
         Keyword(Kw::Genvar) => {
             p.bump();
@@ -1121,11 +1270,16 @@ fn parse_item_data<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ItemDat
         Keyword(Kw::Default) => {
             p.bump();
             let mut span = p.last_span();
-            if p.try_eat(Keyword(Kw::Clocking)) {
-                let name = p.eat_ident("clocking identifier")?;
-                p.require_reported(Semicolon)?;
-                span.expand(p.last_span());
-                return Ok(ItemData::Dummy);
+            if p.peek(0).0 == Keyword(Kw::Clocking) {
+                // Either names the default clocking block, as in `default
+                // clocking cb;`, or declares it.
+                if p.peek(2).0 == Semicolon {
+                    p.bump();
+                    let name = parse_identifier_name(p, "clocking block name")?;
+                    p.require_reported(Semicolon)?;
+                    return Ok(ItemData::DefaultClocking(name));
+                }
+                return parse_clocking_decl(p, span, true).map(ItemData::ClockingDecl);
             }
             if p.try_eat(Keyword(Kw::Disable)) {
                 p.require_reported(Keyword(Kw::Iff))?;
@@ -1142,7 +1296,9 @@ fn parse_item_data<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ItemDat
         }
 
         // Unsupported constructs as of now.
-        SysIdent(..) => return parse_elab_system_task(p).map(|_| ItemData::Dummy),
+        SysIdent(..) if p.peek(1).0 != Namespace => {
+            return parse_elab_system_task(p).map(|_| ItemData::Dummy)
+        }
 
         _ => (),
     }
@@ -1408,13 +1564,52 @@ fn parse_modport_ports_decl<'n>(
         )));
     }
 
-    // Attempt to parse a TF port.
-    if p.try_eat(Keyword(Kw::Import)) || p.try_eat(Keyword(Kw::Export)) {
-        // TODO: Parse modport_tf_ports_declaration.
-        p.add_diag(
-            DiagBuilder2::error("modport task/function ports not implemented").span(p.last_span()),
-        );
-        return Err(());
+    // Attempt to parse a TF port introduced by the `import` or `export`
+    // keywords.
+    let tf_dir = match p.peek(0).0 {
+        Keyword(Kw::Import) => Some(ModportTfDir::Import),
+        Keyword(Kw::Export) => Some(ModportTfDir::Export),
+        _ => None,
+    };
+    if let Some(dir) = tf_dir {
+        let dir = Spanned::new(dir, p.peek(0).1);
+        let mut port: Vec<&_> = vec![];
+        p.bump();
+        loop {
+            let mut span = p.peek(0).1;
+            let (name, prototype) = match p.peek(0).0 {
+                Keyword(Kw::Task) | Keyword(Kw::Function) => {
+                    let prototype = parse_subroutine_prototype_inner(p, false)?;
+                    (prototype.name, Some(prototype))
+                }
+                _ => (parse_identifier_name(p, "task or function name")?, None),
+            };
+            span.expand(p.last_span());
+            port.push(p.arena().alloc(ast::ModportTfPort::new(
+                span,
+                ast::ModportTfPortData { name, prototype },
+            )));
+
+            // Continue with the next port unless a keyword introduces a
+            // different port declaration, as for simple ports above.
+            match (p.peek(0).0, p.peek(1).0) {
+                (Comma, Keyword(Kw::Task)) | (Comma, Keyword(Kw::Function)) => {
+                    p.bump();
+                    continue;
+                }
+                (Comma, Keyword(_)) => break,
+                (Comma, _) => {
+                    p.bump();
+                    continue;
+                }
+                _ => break,
+            }
+        }
+        span.expand(p.last_span());
+        return Ok(p.arena().alloc(ast::ModportPort::new(
+            span,
+            ast::ModportPortData::Tf { dir, port },
+        )));
     }
 
     // Attempt to parse a clocking declaration.
@@ -1429,6 +1624,162 @@ fn parse_modport_ports_decl<'n>(
     Err(())
 }
 
+/// Parse a clocking block. The caller provides the span of the leading
+/// `default` keyword, if any.
+///
+/// ```text
+/// clocking_declaration:
+///   ["default"] "clocking" [ident] "@" event_expr ";"
+///     {clocking_item}
+///   "endclocking" [":" ident]
+/// ```
+fn parse_clocking_decl<'n>(
+    p: &mut dyn AbstractParser<'n>,
+    mut span: Span,
+    default: bool,
+) -> ReportedResult<ClockingDecl<'n>> {
+    p.require_reported(Keyword(Kw::Clocking))?;
+    let result = recovered(p, Keyword(Kw::Endclocking), |p| {
+        let name = try_identifier_name(p)?;
+        p.require_reported(At)?;
+        let event = parse_event_expr(p, EventPrecedence::Max)?;
+        p.require_reported(Semicolon)?;
+        let items = repeat_until(p, Keyword(Kw::Endclocking), parse_clocking_item)?;
+        Ok((name, event, items))
+    });
+    p.require_reported(Keyword(Kw::Endclocking))?;
+    if p.try_eat(Colon) {
+        p.eat_ident("clocking block name")?;
+    }
+    let (name, event, items) = result?;
+    span.expand(p.last_span());
+    Ok(ClockingDecl::new(
+        span,
+        ClockingDeclData {
+            default,
+            name,
+            event,
+            items,
+        },
+    ))
+}
+
+/// Parse an item in a clocking block.
+///
+/// ```text
+/// clocking_item:
+///   "default" clocking_direction ";"
+///   clocking_direction clocking_signal {"," clocking_signal} ";"
+/// clocking_direction:
+///   "input" [clocking_skew] ["output" [clocking_skew]]
+///   "output" [clocking_skew]
+///   "inout"
+/// clocking_signal: ident ["=" expr]
+/// ```
+fn parse_clocking_item<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ClockingItem<'n>> {
+    let mut span = p.peek(0).1;
+    let default = p.try_eat(Keyword(Kw::Default));
+
+    // Parse the direction and skews.
+    let (tkn, sp) = p.peek(0);
+    let (dir, input, output) = match tkn {
+        Keyword(Kw::Input) => {
+            p.bump();
+            let input = try_clocking_skew(p)?;
+            if p.try_eat(Keyword(Kw::Output)) {
+                (PortDir::Inout, input, try_clocking_skew(p)?)
+            } else {
+                (PortDir::Input, input, None)
+            }
+        }
+        Keyword(Kw::Output) => {
+            p.bump();
+            (PortDir::Output, None, try_clocking_skew(p)?)
+        }
+        Keyword(Kw::Inout) if !default => {
+            p.bump();
+            (PortDir::Inout, None, None)
+        }
+        _ => {
+            p.add_diag(
+                DiagBuilder2::error(format!(
+                    "expected clocking direction or `endclocking`, but found `{}` instead",
+                    tkn
+                ))
+                .span(sp),
+            );
+            return Err(());
+        }
+    };
+
+    // Default skews are not followed by any signals.
+    if default {
+        if input.is_none() && output.is_none() {
+            let sp = p.peek(0).1;
+            p.add_diag(DiagBuilder2::error("expected default clocking skew").span(sp));
+            return Err(());
+        }
+        p.require_reported(Semicolon)?;
+        span.expand(p.last_span());
+        return Ok(ClockingItem::Default {
+            span,
+            input,
+            output,
+        });
+    }
+
+    let signals = comma_list_nonempty(p, Semicolon, "clocking signal", |p| {
+        let mut span = p.peek(0).1;
+        let name = parse_identifier_name(p, "clocking signal name")?;
+        let expr = if p.try_eat(Operator(Op::Assign)) {
+            Some(parse_expr(p)?)
+        } else {
+            None
+        };
+        span.expand(p.last_span());
+        Ok(ClockingSignal::new(span, ClockingSignalData { name, expr }))
+    })?;
+    p.require_reported(Semicolon)?;
+    span.expand(p.last_span());
+    Ok(ClockingItem::Signals {
+        span,
+        dir,
+        input,
+        output,
+        signals,
+    })
+}
+
+/// Try to parse a clocking skew.
+///
+/// ```text
+/// clocking_skew: edge_identifier [delay_control] | delay_control
+/// ```
+fn try_clocking_skew<'n>(
+    p: &mut dyn AbstractParser<'n>,
+) -> ReportedResult<Option<ClockingSkew<'n>>> {
+    let mut span = p.peek(0).1;
+    let edge = match as_edge_ident(p.peek(0).0) {
+        EdgeIdent::Implicit => None,
+        edge => {
+            p.bump();
+            Some(edge)
+        }
+    };
+    let delay = if p.peek(0).0 == Hashtag && p.peek(1).0 == OneStep {
+        p.bump();
+        p.bump();
+        Some(ClockingDelay::Step)
+    } else {
+        try_delay_control(p)?.map(|dc| ClockingDelay::Expr(dc.expr))
+    };
+    if edge.is_none() && delay.is_none() {
+        return Ok(None);
+    }
+    span.expand(p.last_span());
+    Ok(Some(ClockingSkew { span, edge, delay }))
+}
+
 /// Convert a token to the corresponding PortDir. The token may be one of the
 /// keywords `input`, `output`, `inout`, or `ref`. Otherwise `None` is returned.
 fn as_port_direction(tkn: Token) -> Option<PortDir> {
@@ -1671,6 +2022,12 @@ fn parse_type_data<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<TypeKin
             Ok(ast::NamedType(Spanned::new(n, sp)))
         }
 
+        // The compilation unit scope `$unit`
+        SysIdent(n) if &*n.as_str() == "unit" && p.peek(1).0 == Namespace => {
+            p.bump();
+            Ok(ast::UnitType)
+        }
+
         // Virtual Interface Type
         Keyword(Kw::Virtual) => {
             p.bump();
@@ -1729,8 +2086,15 @@ fn parse_enum_name<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<EnumNam
     // Eat the name.
     let name = parse_identifier_name(p, "enum name")?;
 
-    // Parse the optional range.
-    let range = try_flanked(p, Brack, parse_expr)?;
+    // Parse the optional range, e.g. `[3]` or `[2:4]`.
+    let range = try_flanked(p, Brack, |p| {
+        let first = parse_expr(p)?;
+        if p.try_eat(Colon) {
+            Ok(TypeDim::Range(first, parse_expr(p)?))
+        } else {
+            Ok(TypeDim::Expr(first))
+        }
+    })?;
 
     // Parse the optional value.
     let value = if p.try_eat(Operator(Op::Assign)) {
@@ -2094,7 +2458,7 @@ fn parse_expr_suffix<'n>(
     let (tkn, sp) = p.peek(0);
     match tkn {
         // Index: "[" range_expression "]"
-        OpenDelim(Brack) if precedence <= Precedence::Postfix => {
+        OpenDelim(Brack) if precedence <= Precedence::Postfix && !is_seq_repetition(p) => {
             p.bump();
             let expr = match parse_range_expr(p) {
                 Ok(x) => x,
@@ -2133,9 +2497,14 @@ fn parse_expr_suffix<'n>(
         }
 
         // expr "." ident
+        // expr "." "new"
         Period if precedence <= Precedence::Scope => {
             p.bump();
-            let name = parse_identifier_name(p, "member name")?;
+            let name = if p.try_eat(Keyword(Kw::New)) {
+                Spanned::new(get_name_table().intern("new", true), p.last_span())
+            } else {
+                parse_identifier_name(p, "member name")?
+            };
             let expr = Expr::new(
                 Span::union(prefix.span, p.last_span()),
                 MemberExpr {
@@ -2186,7 +2555,7 @@ fn parse_expr_suffix<'n>(
         }
 
         // expr "?" expr ":" expr
-        Ternary if precedence < Precedence::Ternary => {
+        Ternary if precedence <= Precedence::Ternary => {
             p.bump();
             let true_expr = parse_expr_prec(p, Precedence::Ternary)?;
             p.require_reported(Colon)?;
@@ -2203,23 +2572,10 @@ fn parse_expr_suffix<'n>(
         }
 
         // expr "inside" "{" open_range_list "}"
-        Keyword(Kw::Inside) if precedence <= Precedence::Relational => {
+        Keyword(Kw::Inside) if precedence < Precedence::Relational => {
             p.bump();
             let set = flanked(p, Brace, |p| {
-                comma_list_nonempty(p, CloseDelim(Brace), "range", |p| {
-                    if p.peek(0).0 == OpenDelim(Brack) {
-                        p.require_reported(OpenDelim(Brack))?;
-                        let mut sp = p.last_span();
-                        let lo = parse_expr(p)?;
-                        p.require_reported(Colon)?;
-                        let hi = parse_expr(p)?;
-                        p.require_reported(CloseDelim(Brack))?;
-                        sp.expand(p.last_span());
-                        Ok(ValueRange::Range { lo, hi, span: sp })
-                    } else {
-                        Ok(ValueRange::Single(parse_expr(p)?))
-                    }
-                })
+                comma_list_nonempty(p, CloseDelim(Brace), "range", parse_value_range)
             })?;
             let expr = Expr::new(
                 Span::union(prefix.span, p.last_span()),
@@ -2228,6 +2584,19 @@ fn parse_expr_suffix<'n>(
             return parse_expr_suffix(p, expr, precedence);
         }
 
+        // expr "dist" "{" dist_list "}"
+        Keyword(Kw::Dist) if precedence < Precedence::Relational => {
+            p.bump();
+            let items = flanked(p, Brace, |p| {
+                comma_list_nonempty(p, CloseDelim(Brace), "distribution item", parse_dist_item)
+            })?;
+            let expr = Expr::new(
+                Span::union(prefix.span, p.last_span()),
+                DistExpr(Box::new(prefix), items),
+            );
+            return parse_expr_suffix(p, expr, precedence);
+        }
+
         // expr "'" "(" expr ")"
         Apostrophe if precedence <= Precedence::Postfix => {
             p.bump();
@@ -2258,10 +2627,11 @@ fn parse_expr_suffix<'n>(
         }
     }
 
-    // Try to parse binary operations.
+    // Try to parse binary operations. Implications are right-associative,
+    // everything else is left-associative.
     if let Some(op) = as_binary_operator(tkn) {
         let prec = op.get_precedence();
-        if precedence < prec {
+        if precedence < prec || (precedence == prec && prec == Precedence::Implication) {
             p.bump();
             let rhs = parse_expr_prec(p, prec)?;
             let expr = Expr::new(
@@ -2279,6 +2649,51 @@ fn parse_expr_suffix<'n>(
     Ok(prefix)
 }
 
+/// Parse a single value or a `[lo:hi]` range in an open range list.
+///
+/// ```text
+/// value_range ::= expr | "[" expr ":" expr "]"
+/// ```
+fn parse_value_range<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ValueRange<'n>> {
+    if p.peek(0).0 == OpenDelim(Brack) {
+        p.require_reported(OpenDelim(Brack))?;
+        let mut sp = p.last_span();
+        let lo = parse_expr(p)?;
+        p.require_reported(Colon)?;
+        let hi = parse_expr(p)?;
+        p.require_reported(CloseDelim(Brack))?;
+        sp.expand(p.last_span());
+        Ok(ValueRange::Range { lo, hi, span: sp })
+    } else {
+        Ok(ValueRange::Single(parse_expr(p)?))
+    }
+}
+
+fn parse_dist_item<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<DistItem<'n>> {
+    let mut span = p.peek(0).1;
+    let range = parse_value_range(p)?;
+    let (kind, weight) = match (p.peek(0).0, p.peek(1).0) {
+        (Colon, Operator(Op::Assign)) => {
+            p.bump();
+            p.bump();
+            (DistWeight::PerValue, Some(parse_expr(p)?))
+        }
+        (Colon, Operator(Op::Div)) => {
+            p.bump();
+            p.bump();
+            (DistWeight::PerRange, Some(parse_expr(p)?))
+        }
+        _ => (DistWeight::PerValue, None),
+    };
+    span.expand(p.last_span());
+    Ok(DistItem {
+        span,
+        range,
+        kind,
+        weight,
+    })
+}
+
 fn parse_expr_first<'n>(
     p: &mut dyn AbstractParser<'n>,
     precedence: Precedence,
@@ -2365,6 +2780,18 @@ fn parse_primary_expr<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Expr
             return Ok(Expr::new(sp, ThisExpr));
         }
 
+        // `local::`
+        Keyword(Kw::Local) if p.peek(1).0 == Namespace => {
+            p.bump();
+            return Ok(Expr::new(sp, LocalExpr));
+        }
+
+        // `super`
+        Keyword(Kw::Super) => {
+            p.bump();
+            return Ok(Expr::new(sp, SuperExpr));
+        }
+
         // `$`
         Dollar => {
             p.bump();
@@ -2493,11 +2920,6 @@ fn parse_pattern_field<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Pat
     Ok(PatternField::new(span, data))
 }
 
-pub enum StreamDir {
-    In,
-    Out,
-}
-
 fn parse_concat_expr<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ExprData<'n>> {
     // Streaming concatenations have a "<<" or ">>" following the opening "{".
     let stream = match p.peek(0).0 {
@@ -2550,6 +2972,7 @@ fn parse_concat_expr<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ExprD
         })?;
 
         return Ok(StreamConcatExpr {
+            dir,
             slice: slice_size,
             exprs: exprs,
         });
@@ -3211,6 +3634,15 @@ fn parse_subroutine_kind<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<S
 
 fn parse_subroutine_prototype<'n>(
     p: &mut dyn AbstractParser<'n>,
+) -> ReportedResult<SubroutinePrototype<'n>> {
+    parse_subroutine_prototype_inner(p, true)
+}
+
+/// Parse a subroutine prototype, which is terminated by a semicolon unless it
+/// appears in a modport, where it is followed by a "," or ")" instead.
+fn parse_subroutine_prototype_inner<'n>(
+    p: &mut dyn AbstractParser<'n>,
+    terminated: bool,
 ) -> ReportedResult<SubroutinePrototype<'n>> {
     let mut span = p.peek(0).1;
 
@@ -3228,21 +3660,21 @@ fn parse_subroutine_prototype<'n>(
     // the optional argument list.
     let (retty, (name, args)) = if kind == SubroutineKind::Func {
         if p.peek(0).0 == Keyword(Kw::New) {
-            (None, parse_subroutine_prototype_tail(p)?)
+            (None, parse_subroutine_prototype_tail(p, terminated)?)
         } else {
             let mut pp = ParallelParser::new();
-            pp.add("implicit function return type", |p| {
+            pp.add("implicit function return type", move |p| {
                 let ty = parse_implicit_type(p)?;
-                Ok((Some(ty), parse_subroutine_prototype_tail(p)?))
+                Ok((Some(ty), parse_subroutine_prototype_tail(p, terminated)?))
             });
-            pp.add("explicit function return type", |p| {
+            pp.add("explicit function return type", move |p| {
                 let ty = parse_explicit_type(p)?;
-                Ok((Some(ty), parse_subroutine_prototype_tail(p)?))
+                Ok((Some(ty), parse_subroutine_prototype_tail(p, terminated)?))
             });
             pp.finish(p, "implicit or explicit function return type")?
         }
     } else {
-        (None, parse_subroutine_prototype_tail(p)?)
+        (None, parse_subroutine_prototype_tail(p, terminated)?)
     };
 
     span.expand(p.last_span());
@@ -3260,6 +3692,7 @@ fn parse_subroutine_prototype<'n>(
 
 fn parse_subroutine_prototype_tail<'n>(
     p: &mut dyn AbstractParser<'n>,
+    terminated: bool,
 ) -> ReportedResult<(Spanned<Name>, Vec<SubroutinePort<'n>>)> {
     // Consume the subroutine name, or "new".
     // TODO: Make this accept the full `[interface_identifier "." | class_scope] tf_identifier`.
@@ -3342,7 +3775,17 @@ fn parse_subroutine_prototype_tail<'n>(
     .unwrap_or(Vec::new());
 
     // Wrap things up.
-    p.require_reported(Semicolon)?;
+    if terminated {
+        p.require_reported(Semicolon)?;
+    } else {
+        match p.peek(0) {
+            (Comma, _) | (CloseDelim(Paren), _) => (),
+            (_, sp) => {
+                p.add_diag(DiagBuilder2::error("expected , or ) after modport method").span(sp));
+                return Err(());
+            }
+        }
+    }
     Ok((name, args))
 }
 
@@ -3557,30 +4000,7 @@ fn parse_stmt_kind<'n>(
         }
         Keyword(Kw::Foreach) => {
             p.bump();
-            let (expr, vars) = flanked(p, Paren, |p| {
-                let expr = parse_expr_prec(p, Precedence::Scope)?;
-                let vars = flanked(p, Brack, |p| {
-                    Ok(comma_list(p, CloseDelim(Brack), "loop variables", |p| {
-                        Ok(if p.peek(0).0 != Comma {
-                            Some(parse_identifier_name(p, "loop variable name")?)
-                        } else {
-                            None
-                        })
-                    })?
-                    .into_iter()
-                    .enumerate()
-                    .flat_map(|(i, name)| {
-                        name.map(|name| {
-                            ast::ForeachIndex::new(
-                                name.span,
-                                ast::ForeachIndexData { index: i, name },
-                            )
-                        })
-                    })
-                    .collect())
-                })?;
-                Ok((expr, vars))
-            })?;
+            let (expr, vars) = parse_foreach_header(p)?;
             let stmt = Box::new(parse_stmt(p)?);
             ForeachStmt(expr, vars, stmt)
         }
@@ -3658,6 +4078,14 @@ fn parse_stmt_kind<'n>(
             return Err(());
         }
 
+        // Event trigger statements
+        Operator(Op::LogicImpl) => {
+            p.bump();
+            let expr = parse_expr(p)?;
+            p.require_reported(Semicolon)?;
+            EventTriggerStmt(expr)
+        }
+
         // Disable statements
         Keyword(Kw::Disable) => {
             p.bump();
@@ -3671,6 +4099,31 @@ fn parse_stmt_kind<'n>(
             }
         }
 
+        // Procedural continuous assignments, as per IEEE 1800-2017 section
+        // 10.6.
+        Keyword(Kw::Assign) | Keyword(Kw::Force) => {
+            p.bump();
+            let lhs = parse_expr_prec(p, Precedence::Postfix)?;
+            p.require_reported(Operator(Op::Assign))?;
+            let rhs = parse_expr(p)?;
+            p.require_reported(Semicolon)?;
+            if tkn == Keyword(Kw::Assign) {
+                ProceduralAssignStmt { lhs, rhs }
+            } else {
+                ForceStmt { lhs, rhs }
+            }
+        }
+        Keyword(Kw::Deassign) | Keyword(Kw::Release) => {
+            p.bump();
+            let lhs = parse_expr(p)?;
+            p.require_reported(Semicolon)?;
+            if tkn == Keyword(Kw::Deassign) {
+                DeassignStmt(lhs)
+            } else {
+                ReleaseStmt(lhs)
+            }
+        }
+
         // Everything else needs special treatment as things such as variable
         // declarations look very similar to other expressions.
         _ => {
@@ -3800,8 +4253,8 @@ fn parse_continuous_assign<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult
         }
     })?;
 
-    // Parse the optional delay control.
-    let delay_control = try_delay_control(p)?;
+    // Parse the optional delay.
+    let delay = try_delay3(p)?;
 
     // Parse the names and assignments.
     let assignments = comma_list_nonempty(p, Semicolon, "continuous assignment", parse_assignment)?;
@@ -3812,13 +4265,21 @@ fn parse_continuous_assign<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult
         span,
         ast::ContAssignData {
             strength,
-            delay: None,
-            delay_control,
+            delay,
             assignments,
         },
     ))
 }
 
+fn parse_defparam<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ast::Defparam<'n>> {
+    let mut span = p.peek(0).1;
+    p.require_reported(Keyword(Kw::Defparam))?;
+    let assignments = comma_list_nonempty(p, Semicolon, "parameter override", parse_assignment)?;
+    p.require_reported(Semicolon)?;
+    span.expand(p.last_span());
+    Ok(ast::Defparam::new(span, ast::DefparamData { assignments }))
+}
+
 fn parse_if_or_case<'n>(
     p: &mut dyn AbstractParser<'n>,
     up: Option<UniquePriority>,
@@ -3904,21 +4365,11 @@ fn parse_case<'n>(
         else {
             let mut exprs = Vec::new();
             loop {
-                if p.peek(0).0 == OpenDelim(Brack) {
-                    // TODO(fschuiki): Keep track of results
-                    // TODO(fschuiki): Error recovery
-                    p.require_reported(OpenDelim(Brack))?;
-                    parse_expr(p)?;
-                    p.require_reported(Colon)?;
-                    parse_expr(p)?;
-                    p.require_reported(CloseDelim(Brack))?;
-                } else {
-                    match parse_expr(p) {
-                        Ok(x) => exprs.push(x),
-                        Err(()) => {
-                            p.recover_balanced(&[Colon], false);
-                            break;
-                        }
+                match parse_value_range(p) {
+                    Ok(x) => exprs.push(x),
+                    Err(()) => {
+                        p.recover_balanced(&[Colon], false);
+                        break;
                     }
                 }
 
@@ -4034,6 +4485,59 @@ fn try_delay_control<'n>(
     }))
 }
 
+/// Try to parse a delay with up to three values.
+///
+/// ```text
+/// "#" delay_value
+/// "#" "(" mintypmax_expression ["," mintypmax_expression ["," mintypmax_expression]] ")"
+/// ```
+fn try_delay3<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Option<Delay3<'n>>> {
+    // Try to consume the hashtag which introduces the delay.
+    if !p.try_eat(Hashtag) {
+        return Ok(None);
+    }
+    let mut span = p.last_span();
+
+    // Parse the delay values. These may either be a literal delay value, or a
+    // list of up to three min-typ-max expressions in parenthesis.
+    let (tkn, sp) = p.peek(0);
+    let exprs = match tkn {
+        // Expressions
+        OpenDelim(Paren) => flanked(p, Paren, |p| {
+            comma_list_nonempty(p, CloseDelim(Paren), "delay value", |p| {
+                parse_expr_prec(p, Precedence::MinTypMax)
+            })
+        })?,
+
+        // Literals
+        Literal(Number(..)) | Literal(Time(..)) | Ident(..) => {
+            vec![parse_expr_first(p, Precedence::Max)?]
+        }
+
+        _ => {
+            p.add_diag(DiagBuilder2::error("expected delay value or expression after #").span(sp));
+            return Err(());
+        }
+    };
+    span.expand(p.last_span());
+    if exprs.len() > 3 {
+        p.add_diag(
+            DiagBuilder2::error(format!("expected at most 3 delays, found {}", exprs.len()))
+                .span(span)
+                .add_note("Delays are given as `#(rise, fall, turn-off)`"),
+        );
+        return Err(());
+    }
+
+    let mut exprs = exprs.into_iter();
+    Ok(Some(Delay3 {
+        span,
+        rise: exprs.next().unwrap(),
+        fall: exprs.next(),
+        turn_off: exprs.next(),
+    }))
+}
+
 /// Try to parse an event control as described in IEEE 1800-2009 section 9.4.2.
 fn try_event_control<'n>(
     p: &mut dyn AbstractParser<'n>,
@@ -4077,14 +4581,29 @@ fn try_event_control<'n>(
     }))
 }
 
-fn try_cycle_delay<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Option<CycleDelay>> {
+/// Try to parse a cycle delay.
+///
+/// ```text
+/// cycle_delay: "##" (number | ident | "(" expr ")")
+/// ```
+fn try_cycle_delay<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Option<CycleDelay<'n>>> {
     if !p.try_eat(DoubleHashtag) {
         return Ok(None);
     }
+    let mut span = p.last_span();
 
-    let q = p.last_span();
-    p.add_diag(DiagBuilder2::error("Don't know how to parse cycle delay").span(q));
-    Err(())
+    let (tkn, sp) = p.peek(0);
+    let expr = match tkn {
+        OpenDelim(Paren) => flanked(p, Paren, parse_expr)?,
+        Literal(Number(..)) | Ident(..) => parse_expr_first(p, Precedence::Max)?,
+        _ => {
+            p.add_diag(DiagBuilder2::error("expected number of cycles after ##").span(sp));
+            return Err(());
+        }
+    };
+    span.expand(p.last_span());
+
+    Ok(Some(CycleDelay { span, expr }))
 }
 
 fn parse_assignment<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<(Expr<'n>, Expr<'n>)> {
@@ -4401,16 +4920,30 @@ fn parse_generate_if<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Gener
     ))
 }
 
+/// Parse a generate-case construct.
+/// ```text
+/// "case" "(" expr ")" {case_generate_item} "endcase"
+/// case_generate_item: expr {"," expr} ":" generate_block
+/// case_generate_item: "default" [":"] generate_block
+/// ```
 fn parse_generate_case<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<GenerateCase<'n>> {
     let mut span = p.peek(0).1;
     p.require_reported(Keyword(Kw::Case))?;
-    p.recover_balanced(&[Keyword(Kw::Endcase)], true);
-    if p.try_eat(Colon) {
-        parse_identifier_name(p, "generate block label")?;
+    let expr = flanked(p, Paren, parse_expr)?;
+    let mut items = Vec::new();
+    while p.peek(0).0 != Keyword(Kw::Endcase) && p.peek(0).0 != Eof {
+        if p.try_eat(Keyword(Kw::Default)) {
+            p.try_eat(Colon);
+            items.push(GenerateCaseItem::Default(parse_generate_block(p)?));
+        } else {
+            let exprs = comma_list_nonempty(p, Colon, "case-generate item label", parse_expr)?;
+            p.require_reported(Colon)?;
+            items.push(GenerateCaseItem::Expr(exprs, parse_generate_block(p)?));
+        }
     }
+    p.require_reported(Keyword(Kw::Endcase))?;
     span.expand(p.last_span());
-    p.add_diag(DiagBuilder2::error("case-generate statements not supported").span(span));
-    Ok(GenerateCase::new(span, GenerateCaseData {}))
+    Ok(GenerateCase::new(span, GenerateCaseData { expr, items }))
 }
 
 fn parse_generate_block<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<GenerateBlock<'n>> {
@@ -4666,7 +5199,7 @@ fn parse_class_item<'n>(
                 parse_variable_decl_assignment,
             )?;
             p.require_reported(Semicolon)?;
-            Ok(ClassItemData::Property)
+            Ok(ClassItemData::Property(ty, names))
         });
         if intf {
             pp.add("class function or task prototype", |p| {
@@ -4724,6 +5257,46 @@ fn parse_class_property<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Cl
     Err(())
 }
 
+/// Parse the array and loop variables of a `foreach` statement or constraint.
+///
+/// ```text
+/// "(" expr ("[" [ident] ("," [ident])* "]")+ ")"
+/// ```
+fn parse_foreach_header<'n>(
+    p: &mut dyn AbstractParser<'n>,
+) -> ReportedResult<(Expr<'n>, Vec<ast::ForeachIndex<'n>>)> {
+    flanked(p, Paren, |p| {
+        let expr = parse_expr_prec(p, Precedence::Scope)?;
+        // The loop variables may be given as `[i, j]`, or one
+        // dimension at a time as `[i][j]`.
+        let mut names = vec![];
+        loop {
+            names.extend(flanked(p, Brack, |p| {
+                comma_list(p, CloseDelim(Brack), "loop variables", |p| {
+                    Ok(if p.peek(0).0 != Comma {
+                        Some(parse_identifier_name(p, "loop variable name")?)
+                    } else {
+                        None
+                    })
+                })
+            })?);
+            if p.peek(0).0 != OpenDelim(Brack) {
+                break;
+            }
+        }
+        let vars = names
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, name)| {
+                name.map(|name| {
+                    ast::ForeachIndex::new(name.span, ast::ForeachIndexData { index: i, name })
+                })
+            })
+            .collect();
+        Ok((expr, vars))
+    })
+}
+
 fn parse_constraint<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Constraint<'n>> {
     let mut span = p.peek(0).1;
 
@@ -4792,22 +5365,48 @@ fn parse_constraint_item<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<C
     })
 }
 
+/// Parse a single constraint item, or a list of them in braces.
+fn parse_constraint_set<'n>(
+    p: &mut dyn AbstractParser<'n>,
+) -> ReportedResult<Vec<ConstraintItem<'n>>> {
+    if p.peek(0).0 == OpenDelim(Brace) {
+        flanked(p, Brace, |p| {
+            repeat_until(p, CloseDelim(Brace), parse_constraint_item)
+        })
+    } else {
+        Ok(vec![parse_constraint_item(p)?])
+    }
+}
+
 fn parse_constraint_item_data<'n>(
     p: &mut dyn AbstractParser<'n>,
 ) -> ReportedResult<ConstraintItemData<'n>> {
     // Handle the trivial cases that start with a keyword first.
     if p.try_eat(Keyword(Kw::If)) {
-        let q = p.last_span();
-        p.add_diag(DiagBuilder2::error("Don't know how to parse `if` constraint items").span(q));
-        return Err(());
+        let cond = flanked(p, Paren, parse_expr)?;
+        let main = parse_constraint_set(p)?;
+        let alt = if p.try_eat(Keyword(Kw::Else)) {
+            Some(parse_constraint_set(p)?)
+        } else {
+            None
+        };
+        return Ok(ConstraintItemData::If { cond, main, alt });
     }
 
-    if p.try_eat(Keyword(Kw::Foreach)) {
-        let q = p.last_span();
-        p.add_diag(
-            DiagBuilder2::error("Don't know how to parse `foreach` constraint items").span(q),
-        );
-        return Err(());
+    if p.peek(0).0 == Keyword(Kw::Foreach) {
+        let mut span = p.peek(0).1;
+        p.bump();
+        let (array, indices) = parse_foreach_header(p)?;
+        let items = parse_constraint_set(p)?;
+        span.expand(p.last_span());
+        return Ok(ConstraintItemData::Foreach(ConstraintForeach::new(
+            span,
+            ConstraintForeachData {
+                array,
+                indices,
+                items,
+            },
+        )));
     }
 
     // If we arrive here, the item starts with an expression.
@@ -5025,6 +5624,10 @@ impl<'tp, 'n> AbstractParser<'n> for BranchParser<'tp, 'n> {
     fn severity(&self) -> Severity {
         self.severity
     }
+
+    fn default_nettype(&self) -> Option<NetType> {
+        self.parser.default_nettype()
+    }
 }
 
 fn parse_typedef<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<Typedef<'n>> {
@@ -5159,6 +5762,7 @@ fn as_net_type(tkn: Token) -> Option<NetType> {
         Keyword(Kw::Wire) => Some(NetType::Wire),
         Keyword(Kw::Wand) => Some(NetType::WireAnd),
         Keyword(Kw::Wor) => Some(NetType::WireOr),
+        Keyword(Kw::Interconnect) => Some(NetType::Interconnect),
         _ => None,
     }
 }
@@ -5210,9 +5814,9 @@ fn parse_net_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<NetDecl<
     // This function handles parsing of everything after the type.
     fn tail<'n>(
         p: &mut dyn AbstractParser<'n>,
-    ) -> ReportedResult<(Option<DelayControl<'n>>, Vec<VarDeclName<'n>>)> {
+    ) -> ReportedResult<(Option<Delay3<'n>>, Vec<VarDeclName<'n>>)> {
         // Parse the optional delay.
-        let delay = try_delay_control(p)?;
+        let delay = try_delay3(p)?;
 
         // Parse the names and assignments.
         let names = comma_list_nonempty(
@@ -5578,31 +6182,50 @@ fn parse_assertion_action_block<'n>(
     }
 }
 
-fn parse_property_spec<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<PropSpec> {
+fn parse_property_spec<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<PropSpec<'n>> {
     let mut span = p.peek(0).1;
 
-    // TODO: Actually parse this stuff, rather than just chicken out.
-    p.recover_balanced(&[CloseDelim(Paren)], false);
-    return Ok(PropSpec);
-
-    // // Parse the optional event expression.
-    // let event = if p.try_eat(At) {
-    //     Some(parse_event_expr(p, EventPrecedence::Min)?)
-    // } else {
-    //     None
-    // };
+    // Parse the optional event expression.
+    let clock = if p.try_eat(At) {
+        Some(parse_event_expr(p, EventPrecedence::Min)?)
+    } else {
+        None
+    };
 
-    // // Parse the optional "disable iff" clause.
-    // let disable = if p.try_eat(Keyword(Kw::Disable)) {
-    //     p.require_reported(Keyword(Kw::Iff))?;
-    //     Some(flanked(p, Paren, parse_expr)?)
-    // } else {
-    //     None
-    // };
+    // Parse the optional "disable iff" clause.
+    let disable = if p.try_eat(Keyword(Kw::Disable)) {
+        p.require_reported(Keyword(Kw::Iff))?;
+        Some(flanked(p, Paren, parse_expr)?)
+    } else {
+        None
+    };
 
-    // // Parse the property expression.
-    // let prop = parse_propexpr(p)?;
-    // Ok(PropSpec)
+    // Parse the property expression. Property expressions that use constructs
+    // the parser does not cover yet are skipped, since they are not lowered
+    // anyway.
+    let mut bp = BranchParser::new(p);
+    let prop = match parse_propexpr(&mut bp) {
+        Ok(prop) if !bp.is_error() && bp.peek(0).0 == CloseDelim(Paren) => {
+            bp.commit();
+            Some(prop)
+        }
+        _ => None,
+    };
+    if prop.is_none() {
+        let mut skipped = p.peek(0).1;
+        p.recover_balanced(&[CloseDelim(Paren)], false);
+        skipped.expand(p.last_span());
+        p.add_diag(
+            DiagBuilder2::warning("unsupported: property expression; ignored").span(skipped),
+        );
+    }
+    span.expand(p.last_span());
+    Ok(PropSpec {
+        span,
+        clock,
+        disable,
+        prop,
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -5680,6 +6303,35 @@ fn parse_propexpr_nonseq<'n>(
             return Ok(PropExprData::Not(Box::new(expr)));
         }
 
+        // "nexttime" and "s_nexttime" operators
+        Keyword(Kw::Nexttime) | Keyword(Kw::SNexttime) => {
+            let op = match p.peek(0).0 {
+                Keyword(Kw::Nexttime) => PropUnOp::Nexttime,
+                _ => PropUnOp::SNexttime,
+            };
+            p.bump();
+            let cycles = try_flanked(p, Brack, parse_expr)?.map(SeqDelay::Single);
+            let expr = parse_propexpr_prec(p, PropSeqPrecedence::NotNexttime)?;
+            return Ok(PropExprData::UnOp(op, cycles, Box::new(expr)));
+        }
+
+        // "always", "s_always", "eventually", and "s_eventually" operators
+        Keyword(Kw::Always)
+        | Keyword(Kw::SAlways)
+        | Keyword(Kw::Eventually)
+        | Keyword(Kw::SEventually) => {
+            let op = match p.peek(0).0 {
+                Keyword(Kw::Always) => PropUnOp::Always,
+                Keyword(Kw::SAlways) => PropUnOp::SAlways,
+                Keyword(Kw::Eventually) => PropUnOp::Eventually,
+                _ => PropUnOp::SEventually,
+            };
+            p.bump();
+            let cycles = try_flanked(p, Brack, parse_cycle_range)?;
+            let expr = parse_propexpr_prec(p, PropSeqPrecedence::AlEvIfAccRejSyn)?;
+            return Ok(PropExprData::UnOp(op, cycles, Box::new(expr)));
+        }
+
         // Clocking event
         At => {
             p.bump();
@@ -5779,6 +6431,13 @@ fn parse_seqexpr_prec<'n>(
     let mut pp = ParallelParser::new();
     pp.add_greedy("expression", move |p| parse_seqexpr_expr(p, precedence));
     pp.add_greedy("sequence", move |p| parse_seqexpr_nonexpr(p, precedence));
+    pp.add_greedy("parenthesized sequence", |p| {
+        let expr = flanked(p, Paren, parse_seqexpr)?;
+        Ok(match try_flanked(p, Brack, parse_seqrep)? {
+            Some(rep) => SeqExprData::Paren(Box::new(expr), rep),
+            None => expr.data,
+        })
+    });
     let data = pp.finish(p, "sequence or primary property expression")?;
 
     span.expand(p.last_span());
@@ -5793,6 +6452,13 @@ fn parse_seqexpr_expr<'n>(
     p: &mut dyn AbstractParser<'n>,
     precedence: PropSeqPrecedence,
 ) -> ReportedResult<SeqExprData<'n>> {
+    // Handle cycle delays without a left hand side, e.g. `##1 a`.
+    if p.try_eat(DoubleHashtag) {
+        let delay = parse_seq_delay(p)?;
+        let rhs = parse_seqexpr_prec(p, PropSeqPrecedence::CycleDelay)?;
+        return Ok(SeqExprData::Delay(None, delay, Box::new(rhs)));
+    }
+
     // TODO: Handle all the non-trivial cases.
     let q = p.peek(0).1;
     p.add_diag(
@@ -5830,20 +6496,104 @@ fn parse_seqexpr_suffix<'n>(
     prefix: SeqExpr<'n>,
     precedence: PropSeqPrecedence,
 ) -> ReportedResult<SeqExpr<'n>> {
-    // TODO: Handle all the binary operators.
+    // Handle cycle delays, e.g. `a ##1 b`.
+    if precedence < PropSeqPrecedence::CycleDelay && p.try_eat(DoubleHashtag) {
+        let delay = parse_seq_delay(p)?;
+        let rhs = parse_seqexpr_prec(p, PropSeqPrecedence::CycleDelay)?;
+        let expr = SeqExpr {
+            span: Span::union(prefix.span, rhs.span),
+            data: SeqExprData::Delay(Some(Box::new(prefix)), delay, Box::new(rhs)),
+        };
+        return parse_seqexpr_suffix(p, expr, precedence);
+    }
+
+    // Handle the binary operators, all of which are left-associative.
+    if let Some((op, prec)) = match p.peek(0).0 {
+        Keyword(Kw::Or) => Some((SeqBinOp::Or, PropSeqPrecedence::Or)),
+        Keyword(Kw::And) => Some((SeqBinOp::And, PropSeqPrecedence::And)),
+        Keyword(Kw::Intersect) => Some((SeqBinOp::Intersect, PropSeqPrecedence::Intersect)),
+        Keyword(Kw::Within) => Some((SeqBinOp::Within, PropSeqPrecedence::Within)),
+        _ => None,
+    } {
+        if precedence < prec {
+            p.bump();
+            let rhs = parse_seqexpr_prec(p, prec)?;
+            let expr = SeqExpr {
+                span: Span::union(prefix.span, rhs.span),
+                data: SeqExprData::BinOp(op, Box::new(prefix), Box::new(rhs)),
+            };
+            return parse_seqexpr_suffix(p, expr, precedence);
+        }
+    }
+
     Ok(prefix)
 }
 
+/// Parse the number of cycles of a cycle delay, after the `##`.
+///
+/// ```text
+/// cycle_delay_range: number | ident | "(" expr ")" | "[" cycle_range "]" | "[*]" | "[+]"
+/// ```
+fn parse_seq_delay<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<SeqDelay<'n>> {
+    let (tkn, sp) = p.peek(0);
+    match tkn {
+        OpenDelim(Paren) => Ok(SeqDelay::Single(flanked(p, Paren, parse_expr)?)),
+        Literal(Number(..)) | Ident(..) => {
+            Ok(SeqDelay::Single(parse_expr_first(p, Precedence::Max)?))
+        }
+        OpenDelim(Brack) => flanked(p, Brack, |p| match p.peek(0).0 {
+            Operator(Op::Mul) if p.peek(1).0 == CloseDelim(Brack) => {
+                p.bump();
+                Ok(SeqDelay::Star)
+            }
+            Operator(Op::Add) if p.peek(1).0 == CloseDelim(Brack) => {
+                p.bump();
+                Ok(SeqDelay::Plus)
+            }
+            _ => parse_cycle_range(p),
+        }),
+        _ => {
+            p.add_diag(DiagBuilder2::error("expected number of cycles after ##").span(sp));
+            Err(())
+        }
+    }
+}
+
+/// Parse a cycle range, such as `2` or `1:$`.
+fn parse_cycle_range<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<SeqDelay<'n>> {
+    let lo = parse_expr(p)?;
+    if p.try_eat(Colon) {
+        Ok(SeqDelay::Range(lo, parse_expr(p)?))
+    } else {
+        Ok(SeqDelay::Single(lo))
+    }
+}
+
+/// Check whether the next tokens start a sequence repetition, such as `[*3]`,
+/// rather than an index expression.
+fn is_seq_repetition<'n>(p: &mut dyn AbstractParser<'n>) -> bool {
+    if p.peek(0).0 != OpenDelim(Brack) {
+        return false;
+    }
+    match p.peek(1).0 {
+        Operator(Op::Mul) | Operator(Op::Assign) | Operator(Op::LogicImpl) => true,
+        Operator(Op::Add) => p.peek(2).0 == CloseDelim(Brack),
+        _ => false,
+    }
+}
+
 fn parse_seqrep<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<SeqRep<'n>> {
     match p.peek(0).0 {
         // [*]
         // [* expr]
+        // [* expr:expr]
         Operator(Op::Mul) => {
             p.bump();
             if p.peek(0).0 == CloseDelim(Brack) {
                 Ok(SeqRep::ConsecStar)
             } else {
-                Ok(SeqRep::Consec(parse_expr(p)?))
+                let (lo, hi) = parse_seqrep_range(p)?;
+                Ok(SeqRep::Consec(lo, hi))
             }
         }
 
@@ -5854,15 +6604,19 @@ fn parse_seqrep<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<SeqRep<'n>
         }
 
         // [= expr]
+        // [= expr:expr]
         Operator(Op::Assign) => {
             p.bump();
-            Ok(SeqRep::Nonconsec(parse_expr(p)?))
+            let (lo, hi) = parse_seqrep_range(p)?;
+            Ok(SeqRep::Nonconsec(lo, hi))
         }
 
         // [-> expr]
+        // [-> expr:expr]
         Operator(Op::LogicImpl) => {
             p.bump();
-            Ok(SeqRep::Goto(parse_expr(p)?))
+            let (lo, hi) = parse_seqrep_range(p)?;
+            Ok(SeqRep::Goto(lo, hi))
         }
 
         _ => {
@@ -5878,6 +6632,18 @@ fn parse_seqrep<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<SeqRep<'n>
     }
 }
 
+fn parse_seqrep_range<'n>(
+    p: &mut dyn AbstractParser<'n>,
+) -> ReportedResult<(Expr<'n>, Option<Expr<'n>>)> {
+    let lo = parse_expr(p)?;
+    let hi = if p.try_eat(Colon) {
+        Some(parse_expr(p)?)
+    } else {
+        None
+    };
+    Ok((lo, hi))
+}
+
 fn parse_inst<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ast::Inst<'n>> {
     let mut span = p.peek(0).1;
 
@@ -5917,6 +6683,35 @@ fn parse_inst<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ast::Inst<'n
     ))
 }
 
+fn parse_bind_directive<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ast::Bind<'n>> {
+    let mut span = p.peek(0).1;
+    p.require_reported(Keyword(Kw::Bind))?;
+
+    // Consume the target scope and the optional list of instances.
+    let target = parse_expr(p)?;
+    let mut instances = vec![];
+    if p.try_eat(Colon) {
+        loop {
+            instances.push(parse_expr(p)?);
+            if !p.try_eat(Comma) {
+                break;
+            }
+        }
+    }
+
+    // Consume the instantiation to be inserted.
+    let inst = parse_inst(p)?;
+    span.expand(p.last_span());
+    Ok(ast::Bind::new(
+        span,
+        ast::BindData {
+            target,
+            instances,
+            inst,
+        },
+    ))
+}
+
 fn parse_var_decl<'n>(p: &mut dyn AbstractParser<'n>) -> ReportedResult<ast::VarDecl<'n>> {
     let mut span = p.peek(0).1;
 
@@ -6116,3 +6911,134 @@ fn try_builtin_system_task<'n>(
         _ => None,
     })
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::preproc::Preprocessor;
+
+    /// Parse a source file and dump its AST without node IDs and spans, such
+    /// that ASTs parsed from different sources can be compared.
+    pub(crate) fn parse_tree(name: &str, input: &str) -> String {
+        let source = get_source_manager().add(name, input);
+        let lexer = Lexer::new(Preprocessor::new(source, &[], &[]));
+        let arena = Arena::default();
+        let ast = parse(lexer, &arena).expect("parse failed");
+        dump_ast(&format!("{:1000?}", ast))
+    }
+
+    /// Strip the node IDs and spans from an AST debug dump.
+    fn dump_ast(dump: &str) -> String {
+        let mut out = String::new();
+        let mut rest = dump;
+        while !rest.is_empty() {
+            if rest.starts_with(" #") && rest[2..].starts_with(|c: char| c.is_ascii_digit()) {
+                rest = rest[2..].trim_start_matches(|c: char| c.is_ascii_digit());
+            } else if rest.starts_with("Source(") {
+                rest = &rest[rest.find(')').unwrap() + 1..];
+                rest =
+                    rest.trim_start_matches(|c: char| c.is_ascii_digit() || c == ':' || c == '-');
+            } else {
+                let c = rest.chars().next().unwrap();
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn precedence() {
+        // Each expression must parse into the same tree as its explicitly
+        // parenthesized counterpart.
+        let cases = [
+            ("a + b * c", "a + (b * c)"),
+            ("a * b + c", "(a * b) + c"),
+            ("a - b - c", "(a - b) - c"),
+            ("a / b % c", "(a / b) % c"),
+            ("a ** b ** c", "(a ** b) ** c"),
+            ("a * b ** c", "a * (b ** c)"),
+            ("-a ** b", "(-a) ** b"),
+            ("~a & b", "(~a) & b"),
+            ("!a == b", "(!a) == b"),
+            ("a << b + c", "a << (b + c)"),
+            ("a + b >>> c", "(a + b) >>> c"),
+            ("a < b << c", "a < (b << c)"),
+            ("a == b < c", "a == (b < c)"),
+            ("a < b == c", "(a < b) == c"),
+            ("a & b == c", "a & (b == c)"),
+            ("a | b ^ c & d", "a | (b ^ (c & d))"),
+            ("a ^ b ~^ c", "(a ^ b) ~^ c"),
+            ("a && b | c", "a && (b | c)"),
+            ("a || b && c", "a || (b && c)"),
+            ("a && b || c && d", "(a && b) || (c && d)"),
+            ("a -> b || c", "a -> (b || c)"),
+            ("a -> b -> c", "a -> (b -> c)"),
+            ("a <-> b -> c", "a <-> (b -> c)"),
+            ("a ? b : c ? d : e", "a ? b : (c ? d : e)"),
+            ("a || b ? c : d", "(a || b) ? c : d"),
+            ("a ? b + c : d * e", "a ? (b + c) : (d * e)"),
+            ("a + b inside {1, 2}", "(a + b) inside {1, 2}"),
+            ("a < b inside {1}", "(a < b) inside {1}"),
+            ("a == b inside {[1:2]}", "a == (b inside {[1:2]})"),
+            ("a inside {1} && b", "(a inside {1}) && b"),
+            ("x[0] + y.z * f(a)", "(x[0]) + ((y.z) * (f(a)))"),
+        ];
+        for (i, &(expr, expected)) in cases.iter().enumerate() {
+            let wrap = |e: &str| format!("module foo; assign y = {}; endmodule", e);
+            let actual = parse_tree(&format!("prec{}_actual.sv", i), &wrap(expr));
+            let expected = parse_tree(&format!("prec{}_expected.sv", i), &wrap(expected));
+            assert_eq!(actual, expected, "`{}` parsed incorrectly", expr);
+        }
+    }
+
+    #[test]
+    fn property_expressions() {
+        // Each property must be parsed rather than skipped, and parse into the
+        // same tree as its explicitly parenthesized counterpart.
+        let cases = [
+            ("a |-> ##1 b", "a |-> (##1 b)"),
+            ("a ##1 b |=> c", "(a ##1 b) |=> c"),
+            ("a |-> b[*3]", "a |-> (b[*3])"),
+            ("a |-> ##[1:3] b", "a |-> (##[1:3] b)"),
+            ("a |-> ##[1:$] b", "a |-> (##[1:$] b)"),
+            ("a ##[*] b ##[+] c", "(a ##[*] b) ##[+] c"),
+            ("a[*1:$] ##1 b", "(a[*1:$]) ##1 b"),
+            ("a[=2] ##1 b[->1:3]", "(a[=2]) ##1 (b[->1:3])"),
+            ("a[+] ##1 b[+1]", "(a[+]) ##1 (b[+1])"),
+            ("(a ##1 b)[*2] |-> c", "((a ##1 b)[*2]) |-> c"),
+            ("x[0] ##(n) y.z", "(x[0]) ##(n) (y.z)"),
+            ("a ##1 b ##2 c", "(a ##1 b) ##2 c"),
+            ("a throughout b ##1 c", "a throughout (b ##1 c)"),
+            ("a within b intersect c", "(a within b) intersect c"),
+            ("a ##1 b and c or d", "((a ##1 b) and c) or d"),
+            ("s_eventually a", "s_eventually (a)"),
+            ("a |-> s_eventually b", "a |-> (s_eventually b)"),
+            ("always [1:3] a |-> b", "always [1:3] (a |-> b)"),
+            ("nexttime [2] a and b", "(nexttime [2] a) and b"),
+        ];
+        for (i, &(prop, expected)) in cases.iter().enumerate() {
+            let wrap = |e: &str| {
+                format!(
+                    "module foo; assert property (@(posedge clk) {}); endmodule",
+                    e
+                )
+            };
+            let actual = parse_tree(&format!("prop{}_actual.sv", i), &wrap(prop));
+            let expected = parse_tree(&format!("prop{}_expected.sv", i), &wrap(expected));
+            assert!(!actual.contains("prop: None"), "`{}` was skipped", prop);
+            assert_eq!(actual, expected, "`{}` parsed incorrectly", prop);
+        }
+    }
+
+    #[test]
+    fn unsupported_property_expressions() {
+        // Properties the parser does not cover yet are skipped rather than
+        // rejected.
+        let tree = parse_tree(
+            "prop_unsupported.sv",
+            "module foo; assert property (a |-> if (b) c else d); endmodule",
+        );
+        assert!(tree.contains("prop: None"));
+    }
+}
@@ -4,10 +4,17 @@
 //! tokens generated by a lexer and performs include and macro
 //! resolution.
 
+use crate::ast::NetType;
 use crate::cat::*;
+use crate::token::Kw;
 use moore_common::errors::{DiagBuilder2, DiagResult2};
 use moore_common::source::*;
-use std::{collections::HashMap, fmt, path::Path, rc::Rc};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 use once_cell::sync::Lazy;
 
@@ -30,15 +37,26 @@ pub struct Preprocessor<'a> {
     macro_defs: HashMap<String, Macro>,
     /// The stack used to inject expanded macros into the token stream.
     macro_stack: Vec<TokenAndSpan>,
-    /// The paths that are searched for included files, besides the current
-    /// file's directory.
-    include_paths: &'a [&'a Path],
+    /// The directories that are searched for included files, in order, before
+    /// falling back to the current file's directory.
+    include_dirs: Vec<PathBuf>,
     /// The define conditional stack. Whenever a `ifdef, `ifndef, `else, `elsif,
     /// or `endif directive is encountered, the stack is expanded, modified, or
-    /// reduced to reflect the kind of conditional block we're in.
-    defcond_stack: Vec<Defcond>,
+    /// reduced to reflect the kind of conditional block we're in. Each entry
+    /// also records the span of the `ifdef or `ifndef that opened it.
+    defcond_stack: Vec<(Defcond, Span)>,
     /// Currently enabled directives.
     dirs: Directives,
+    /// Warnings that have been produced but not yet collected.
+    warnings: Vec<DiagBuilder2>,
+    /// The spans of the directives consumed since they were last collected,
+    /// or `None` if directives are not being recorded.
+    directives: Option<Vec<Span>>,
+    /// The pragmas that have been encountered but not yet collected.
+    pragmas: Vec<Pragma>,
+    /// The paths of all files that were successfully included, in the order
+    /// they were first encountered.
+    dependencies: Vec<PathBuf>,
 }
 
 impl<'a> Preprocessor<'a> {
@@ -51,43 +69,133 @@ impl<'a> Preprocessor<'a> {
         let content = source.get_content();
         let content_unbound = unsafe { &*(content.as_ref() as *const dyn SourceContent) };
         let iter = content_unbound.iter();
-        let macro_defs = macro_defs
-            .into_iter()
-            .map(|(name, value)| {
-                let body = match value {
-                    Some(value) => {
-                        // Create dummy sources for each user defined macro.
-                        let src = get_source_manager().add_anonymous(*value);
-                        let span = Span::new(src, 0, value.len());
-                        Cat::new(Box::new(value.char_indices()))
-                            .map(|x| (x.0, span))
-                            .collect()
-                    }
-                    None => Vec::new(),
-                };
-                (
-                    name.to_string(),
-                    Macro {
-                        name: name.to_string(),
-                        span: INVALID_SPAN,
-                        args: Vec::new(),
-                        body: body,
-                    },
-                )
-            })
-            .collect();
-        Preprocessor {
+        let mut pp = Preprocessor {
             stack: vec![Stream {
                 source: source,
+                path: canonical_path(source),
                 iter: Cat::new(iter),
             }],
             contents: vec![content],
             token: None,
-            macro_defs,
+            macro_defs: HashMap::new(),
             macro_stack: Vec::new(),
-            include_paths: include_paths,
+            include_dirs: Vec::new(),
             defcond_stack: Vec::new(),
             dirs: Default::default(),
+            warnings: Vec::new(),
+            directives: None,
+            pragmas: Vec::new(),
+            dependencies: Vec::new(),
+        };
+        for &(name, value) in macro_defs {
+            pp.define(name, value);
+        }
+        pp.with_include_dirs(include_paths.iter().map(|p| p.to_path_buf()).collect())
+    }
+
+    /// Add directories to search for included files.
+    ///
+    /// The directories are searched in order, before falling back to the
+    /// directory of the file that contains the `include directive. This
+    /// mirrors the `+incdir+` option of other tools. Directories that are
+    /// already in the search list are ignored.
+    pub fn with_include_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        let key = |d: &Path| std::fs::canonicalize(d).unwrap_or_else(|_| d.to_path_buf());
+        for dir in dirs {
+            let dir_key = key(&dir);
+            if !self.include_dirs.iter().any(|d| key(d) == dir_key) {
+                self.include_dirs.push(dir);
+            }
+        }
+        self
+    }
+
+    /// Record the directives consumed by the preprocessor.
+    ///
+    /// The recorded directives can be collected with `take_directives`. This
+    /// is useful when dumping the preprocessed source, to show where the
+    /// directives were located in the output.
+    pub fn with_directives(mut self) -> Self {
+        self.directives = Some(Vec::new());
+        self
+    }
+
+    /// Define a macro before preprocessing starts.
+    ///
+    /// This is equivalent to a `-D name=value` command line flag, or `-D name`
+    /// if no value is given. Redefines the macro if it already exists.
+    pub fn define(&mut self, name: &str, value: Option<&str>) {
+        let body = match value {
+            Some(value) => {
+                // Create a dummy source for each user defined macro.
+                let src = get_source_manager().add_anonymous(value);
+                let span = Span::new(src, 0, value.len());
+                Cat::new(Box::new(value.char_indices()))
+                    .map(|x| (x.0, span))
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        self.macro_defs.insert(
+            name.to_string(),
+            Macro {
+                name: name.to_string(),
+                span: INVALID_SPAN,
+                args: Vec::new(),
+                body: body,
+            },
+        );
+    }
+
+    /// Check whether a macro is currently defined.
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.macro_defs.contains_key(name)
+    }
+
+    /// Get the net type currently set by `` `default_nettype``.
+    ///
+    /// Returns `None` if implicit nets have been disabled with
+    /// `` `default_nettype none``.
+    pub fn default_nettype(&self) -> Option<NetType> {
+        self.dirs.default_nettype
+    }
+
+    /// Get the keyword set currently selected by `` `begin_keywords``.
+    ///
+    /// Returns `None` if no `` `begin_keywords`` directive is active, in which
+    /// case all keywords are reserved.
+    pub fn keywords(&self) -> Option<KeywordsDirective> {
+        self.dirs.keywords.last().map(|&(kw, _)| kw)
+    }
+
+    /// Collect the warnings produced since the last call to this function.
+    pub fn take_warnings(&mut self) -> Vec<DiagBuilder2> {
+        std::mem::replace(&mut self.warnings, vec![])
+    }
+
+    /// Collect the pragmas encountered since the last call to this function.
+    pub fn take_pragmas(&mut self) -> Vec<Pragma> {
+        std::mem::replace(&mut self.pragmas, vec![])
+    }
+
+    /// Get the files that were pulled in via `` `include``.
+    ///
+    /// Each file is listed once, in the order it was first included. The list
+    /// is complete once the preprocessor has reached the end of its input, and
+    /// is suitable for emitting Makefile-style dependency files.
+    pub fn dependencies(&self) -> &[PathBuf] {
+        &self.dependencies
+    }
+
+    /// Collect the directives consumed since the last call to this function.
+    ///
+    /// Each span covers the directive from its backtick up to, but excluding,
+    /// the token that follows it. Returns an empty list unless the
+    /// preprocessor was created `with_directives`.
+    pub fn take_directives(&mut self) -> Vec<Span> {
+        match self.directives {
+            Some(ref mut dirs) => std::mem::replace(dirs, vec![]),
+            None => vec![],
         }
     }
 
@@ -189,23 +297,52 @@ impl<'a> Preprocessor<'a> {
                 // stream stack.
                 // TODO: Search only system location if `include <...> is used
                 let included_source = match self.open_include(&filename, &span.source.get_path()) {
-                    Some(src) => src,
-                    None => {
-                        // TODO: Add notes to the message indicating which files have been tried.
-                        return Err(DiagBuilder2::fatal(format!(
+                    Ok(src) => src,
+                    Err(tried) => {
+                        let mut d = DiagBuilder2::fatal(format!(
                             "cannot open included file \"{}\"",
                             filename
                         ))
-                        .span(Span::union(name_p, name_q)));
+                        .span(Span::union(name_p, name_q));
+                        if tried.is_empty() {
+                            d = d.add_note("No include search paths are configured");
+                        } else {
+                            d = d.add_note("The following paths were searched:");
+                            for path in tried {
+                                d = d.add_note(format!("- {}", path.display()));
+                            }
+                        }
+                        return Err(d);
                     }
                 };
 
+                // Reject files that are already being included further up the
+                // include stack, since they would recurse indefinitely.
+                let path = canonical_path(included_source);
+                if self.stack.iter().any(|s| s.path == path) {
+                    let mut d =
+                        DiagBuilder2::fatal(format!("recursive inclusion of \"{}\"", filename))
+                            .span(Span::union(name_p, name_q))
+                            .add_note("The include chain forms a cycle:");
+                    for stream in &self.stack {
+                        d = d.add_note(format!("- {}", stream.path.display()));
+                    }
+                    d = d.add_note(format!("- {}", path.display()));
+                    return Err(d);
+                }
+
+                let dep = PathBuf::from(&*included_source.get_path());
+                if !self.dependencies.contains(&dep) {
+                    self.dependencies.push(dep);
+                }
+
                 let content = included_source.get_content();
                 let content_unbound = unsafe { &*(content.as_ref() as *const dyn SourceContent) };
                 let iter = content_unbound.iter();
                 self.contents.push(content);
                 self.stack.push(Stream {
                     source: included_source,
+                    path,
                     iter: Cat::new(iter),
                 });
 
@@ -242,7 +379,7 @@ impl<'a> Preprocessor<'a> {
                 }
 
                 // Consume the macro name.
-                let (name, _) = match self.try_eat_name() {
+                let (name, name_span) = match self.try_eat_name() {
                     Some(x) => x,
                     None => {
                         return Err(
@@ -252,7 +389,12 @@ impl<'a> Preprocessor<'a> {
                 };
 
                 // Remove the macro definition.
-                self.macro_defs.remove(&name);
+                if self.macro_defs.remove(&name).is_none() {
+                    self.warnings.push(
+                        DiagBuilder2::warning(format!("macro `{}` is not defined", name))
+                            .span(name_span),
+                    );
+                }
                 return Ok(());
             }
 
@@ -286,33 +428,40 @@ impl<'a> Preprocessor<'a> {
                 // Depending on the directive, modify the define conditional
                 // stack.
                 match dir {
-                    Directive::Ifdef => self.defcond_stack.push(if self.is_inactive() {
-                        Defcond::Done
-                    } else if exists {
-                        Defcond::Enabled
-                    } else {
-                        Defcond::Disabled
-                    }),
-                    Directive::Ifndef => self.defcond_stack.push(if self.is_inactive() {
-                        Defcond::Done
-                    } else if exists {
-                        Defcond::Disabled
-                    } else {
-                        Defcond::Enabled
-                    }),
+                    Directive::Ifdef => {
+                        let cond = if self.is_inactive() {
+                            Defcond::Done
+                        } else if exists {
+                            Defcond::Enabled
+                        } else {
+                            Defcond::Disabled
+                        };
+                        self.defcond_stack.push((cond, span))
+                    }
+                    Directive::Ifndef => {
+                        let cond = if self.is_inactive() {
+                            Defcond::Done
+                        } else if exists {
+                            Defcond::Disabled
+                        } else {
+                            Defcond::Enabled
+                        };
+                        self.defcond_stack.push((cond, span))
+                    }
                     Directive::Elsif => {
                         match self.defcond_stack.pop() {
-                            Some(Defcond::Done) | Some(Defcond::Enabled) => {
-                                self.defcond_stack.push(Defcond::Done)
+                            Some((Defcond::Done, opening)) | Some((Defcond::Enabled, opening)) => {
+                                self.defcond_stack.push((Defcond::Done, opening))
                             }
-                            Some(Defcond::Disabled) => {
-                                self.defcond_stack.push(if self.is_inactive() {
+                            Some((Defcond::Disabled, opening)) => {
+                                let cond = if self.is_inactive() {
                                     Defcond::Done
                                 } else if exists {
                                     Defcond::Enabled
                                 } else {
                                     Defcond::Disabled
-                                })
+                                };
+                                self.defcond_stack.push((cond, opening))
                             }
                             None => {
                                 return Err(DiagBuilder2::fatal(
@@ -331,9 +480,11 @@ impl<'a> Preprocessor<'a> {
 
             Directive::Else => {
                 match self.defcond_stack.pop() {
-                    Some(Defcond::Disabled) => self.defcond_stack.push(Defcond::Enabled),
-                    Some(Defcond::Enabled) | Some(Defcond::Done) => {
-                        self.defcond_stack.push(Defcond::Done)
+                    Some((Defcond::Disabled, opening)) => {
+                        self.defcond_stack.push((Defcond::Enabled, opening))
+                    }
+                    Some((Defcond::Enabled, opening)) | Some((Defcond::Done, opening)) => {
+                        self.defcond_stack.push((Defcond::Done, opening))
                     }
                     None => {
                         return Err(DiagBuilder2::fatal(
@@ -394,17 +545,31 @@ impl<'a> Preprocessor<'a> {
                             .extend(makro.body.iter().rev().map(|&(tkn, sp)| (tkn, sp)));
                     } else {
                         let mut replacement = Vec::<TokenAndSpan>::new();
-                        // TODO: Make this work for argument names that contain
-                        // underscores.
-                        for tkn in &makro.body {
-                            match *tkn {
-                                (Text, sp) => match args.get(&sp.extract()) {
-                                    Some(substitute) => {
-                                        replacement.extend(substitute);
+                        let mut body = makro.body.iter().peekable();
+                        while let Some(&tkn) = body.next() {
+                            // Gather the full identifier starting at this
+                            // token, since argument names may span multiple
+                            // text, digit, and underscore tokens.
+                            let mut word = match tkn {
+                                (Text, sp) | (Symbol('_'), sp) => vec![(tkn, sp.extract())],
+                                _ => {
+                                    replacement.push(tkn);
+                                    continue;
+                                }
+                            };
+                            while let Some(&&next) = body.peek() {
+                                match next {
+                                    (Text, sp) | (Digits, sp) | (Symbol('_'), sp) => {
+                                        word.push((next, sp.extract()));
+                                        body.next();
                                     }
-                                    None => replacement.push(*tkn),
-                                },
-                                x => replacement.push(x),
+                                    _ => break,
+                                }
+                            }
+                            let name: String = word.iter().map(|(_, s)| s.as_str()).collect();
+                            match args.get(&name) {
+                                Some(substitute) => replacement.extend(substitute),
+                                None => replacement.extend(word.into_iter().map(|(t, _)| t)),
                             }
                         }
                         self.macro_stack
@@ -427,23 +592,67 @@ impl<'a> Preprocessor<'a> {
                 return Ok(());
             }
 
+            // Record pragmas for later consumers, but otherwise skip them.
+            // See IEEE 1800-2017 §22.11.
+            Directive::Pragma => {
+                match self.token {
+                    Some((Whitespace, _)) => self.bump(),
+                    _ => (),
+                }
+                let (name, name_span) = match self.try_eat_name() {
+                    Some(x) => x,
+                    None => {
+                        return Err(
+                            DiagBuilder2::fatal("expected pragma name after `pragma").span(span)
+                        );
+                    }
+                };
+                let mut text = String::new();
+                let mut pragma_span = Span::union(span, name_span);
+                while let Some((tkn, sp)) = self.token {
+                    if tkn == Newline {
+                        break;
+                    }
+                    text.push_str(&sp.extract());
+                    pragma_span.expand(sp);
+                    self.bump();
+                }
+                if !self.is_inactive() {
+                    self.pragmas.push(Pragma {
+                        span: pragma_span,
+                        name,
+                        args: parse_pragma_args(&text),
+                    });
+                }
+                return Ok(());
+            }
+
             Directive::CurrentFile => {
                 if !self.is_inactive() {
-                    self.macro_stack.push((CatTokenKind::Text, span));
+                    let path = match self.stack.last() {
+                        Some(stream) => stream.source.get_path(),
+                        None => span.source.get_path(),
+                    };
+                    let text = format!("\"{}\"", path.escape_default());
+                    self.inject_text(&text);
                 }
                 return Ok(());
             }
 
             Directive::CurrentLine => {
                 if !self.is_inactive() {
-                    self.macro_stack.push((CatTokenKind::Digits, span));
+                    let line = span.begin().human_line();
+                    self.inject_text(&line.to_string());
                 }
                 return Ok(());
             }
 
             Directive::Resetall => {
                 if !self.is_inactive() {
+                    // The keyword set is not affected by `resetall.
+                    let keywords = std::mem::replace(&mut self.dirs.keywords, vec![]);
                     self.dirs = Default::default();
+                    self.dirs.keywords = keywords;
                 }
                 return Ok(());
             }
@@ -483,18 +692,33 @@ impl<'a> Preprocessor<'a> {
                             .span(span));
                         }
                     };
+                    let net_type = match tkn.1.extract().as_str() {
+                        "none" => None,
+                        "wire" => Some(NetType::Wire),
+                        "tri" => Some(NetType::Tri),
+                        "tri0" => Some(NetType::Tri0),
+                        "tri1" => Some(NetType::Tri1),
+                        "wand" => Some(NetType::WireAnd),
+                        "triand" => Some(NetType::TriAnd),
+                        "wor" => Some(NetType::WireOr),
+                        "trior" => Some(NetType::TriOr),
+                        "trireg" => Some(NetType::TriReg),
+                        "uwire" => Some(NetType::Uwire),
+                        other => {
+                            return Err(DiagBuilder2::fatal(format!(
+                                "`{}` is not a valid net type for `default_nettype",
+                                other
+                            ))
+                            .span(tkn.1));
+                        }
+                    };
 
                     // Store the nettype in the directive set.
-                    self.dirs.default_nettype = if tkn.1.extract() == "none" {
-                        None
-                    } else {
-                        Some(tkn)
-                    };
+                    self.dirs.default_nettype = net_type;
                     debug!(
                         "Set default_nettype to `{}`",
-                        self.dirs
-                            .default_nettype
-                            .map(|(_, sp)| sp.extract())
+                        net_type
+                            .map(|ty| ty.to_string())
                             .unwrap_or_else(|| "none".to_string())
                     );
                 }
@@ -550,7 +774,7 @@ impl<'a> Preprocessor<'a> {
                             .span(span));
                         }
                     };
-                    self.dirs.keywords.push(spec);
+                    self.dirs.keywords.push((spec, span));
                     debug!("Push keywords; now `{:?}`", self.dirs.keywords.last());
                 }
                 return Ok(());
@@ -578,11 +802,19 @@ impl<'a> Preprocessor<'a> {
                     }
 
                     // Consume line number.
-                    // TODO: Actually make use of this.
-                    let _line = match self.token {
+                    let line = match self.token {
                         Some((Digits, sp)) => {
                             self.bump();
-                            sp
+                            match sp.extract().parse::<usize>() {
+                                Ok(line) => line,
+                                Err(_) => {
+                                    return Err(DiagBuilder2::fatal(format!(
+                                        "invalid line number `{}` in `line",
+                                        sp.extract()
+                                    ))
+                                    .span(sp));
+                                }
+                            }
                         }
                         _ => {
                             return Err(
@@ -636,8 +868,7 @@ impl<'a> Preprocessor<'a> {
                     }
 
                     // Consume level.
-                    // TODO: Actually make use of this.
-                    let _level = match self.token {
+                    let level_span = match self.token {
                         Some((Digits, sp)) => {
                             self.bump();
                             sp
@@ -648,8 +879,25 @@ impl<'a> Preprocessor<'a> {
                             );
                         }
                     };
+                    match level_span.extract().as_str() {
+                        "0" | "1" | "2" => (),
+                        level => {
+                            return Err(DiagBuilder2::fatal(format!(
+                                "invalid level `{}` in `line; must be 0, 1, or 2",
+                                level
+                            ))
+                            .span(level_span));
+                        }
+                    }
 
-                    debug!("Ignoring `line directive");
+                    // Remap the lines following the directive.
+                    debug!("Remapping lines after {:?} to {}:{}", span, filename, line);
+                    get_source_manager().add_line_directive(
+                        level_span.source,
+                        level_span.end,
+                        line,
+                        &filename,
+                    );
                 }
                 return Ok(());
             }
@@ -700,23 +948,52 @@ impl<'a> Preprocessor<'a> {
         );
     }
 
-    fn open_include(&mut self, filename: &str, current_file: &str) -> Option<Source> {
-        // println!("Resolving include '{}' from '{}'", filename, current_file);
-        let first = [Path::new(current_file)
-            .parent()
-            .expect("current file path must have a valid parent")];
-        let prefices = first.iter().chain(self.include_paths.iter());
+    /// Inject generated text into the token stream, right before the current
+    /// token.
+    fn inject_text(&mut self, text: &str) {
+        let src = get_source_manager().add_anonymous(text.to_string());
+        let tokens: Vec<_> = Cat::new(Box::new(text.char_indices()))
+            .map(|CatToken(tkn, begin, end)| (tkn, Span::new(src, begin, end)))
+            .collect();
+        if let Some(tkn) = self.token {
+            self.macro_stack.push(tkn);
+        }
+        self.macro_stack.extend(tokens.into_iter().rev());
+        self.bump();
+    }
+
+    /// Locate and open an included file.
+    ///
+    /// Returns the list of paths that were tried if the file cannot be found.
+    fn open_include(&mut self, filename: &str, current_file: &str) -> Result<Source, Vec<PathBuf>> {
+        trace!("Resolving include `{}` from `{}`", filename, current_file);
         let sm = get_source_manager();
+
+        // Absolute paths are opened directly.
+        if Path::new(filename).is_absolute() {
+            return sm
+                .open(filename)
+                .ok_or_else(|| vec![PathBuf::from(filename)]);
+        }
+
+        // Relative paths are looked up in the include directories first, then
+        // relative to the current file.
+        let current_dir = Path::new(current_file).parent();
+        let prefices = self
+            .include_dirs
+            .iter()
+            .map(|p| p.as_path())
+            .chain(current_dir);
+        let mut tried = vec![];
         for prefix in prefices {
-            let mut buf = prefix.to_path_buf();
-            buf.push(filename);
-            // println!("  trying {}", buf.to_str().unwrap());
-            let src = sm.open(buf.to_str().unwrap());
-            if src.is_some() {
-                return src;
+            let buf = prefix.join(filename);
+            trace!("  Trying `{}`", buf.display());
+            if let Some(src) = sm.open(&buf.to_string_lossy()) {
+                return Ok(src);
             }
+            tried.push(buf);
         }
-        return None;
+        Err(tried)
     }
 
     /// Check whether we are inside a disabled define conditional. That is,
@@ -724,7 +1001,7 @@ impl<'a> Preprocessor<'a> {
     /// disabled the subsequent code.
     fn is_inactive(&self) -> bool {
         match self.defcond_stack.last() {
-            Some(&Defcond::Enabled) | None => false,
+            Some(&(Defcond::Enabled, _)) | None => false,
             _ => true,
         }
     }
@@ -800,11 +1077,11 @@ impl<'a> Preprocessor<'a> {
                 }
                 // Some((Whitespace, _)) => self.bump(),
                 // Some((Comment, _)) => self.bump(),
-                Some((Symbol('\\'), _)) => {
+                Some(x @ (Symbol('\\'), _)) => {
                     self.bump();
                     match self.token {
                         Some((Newline, _)) => self.bump(),
-                        _ => (),
+                        _ => makro.body.push(x),
                     };
                 }
                 Some(x) => {
@@ -864,8 +1141,38 @@ impl<'a> Preprocessor<'a> {
                     self.skip_whitespace();
                     let mut tokens = vec![];
                     let mut nesting = 0;
+                    let mut in_string = false;
                     loop {
                         match self.token {
+                            // Treat string literals the same way as in the
+                            // macro arguments.
+                            Some(x @ (Symbol('"'), _)) => {
+                                in_string = !in_string;
+                                all_span.expand(x.1);
+                                tokens.push(x);
+                                self.bump();
+                            }
+                            Some(x @ (Symbol('\\'), _)) if in_string => {
+                                all_span.expand(x.1);
+                                tokens.push(x);
+                                self.bump();
+                                if let Some(y) = self.token {
+                                    all_span.expand(y.1);
+                                    tokens.push(y);
+                                    self.bump();
+                                }
+                            }
+                            Some(x @ (Newline, _)) if in_string => {
+                                return Err(DiagBuilder2::fatal(
+                                    "unterminated string literal in macro argument default",
+                                )
+                                .span(x.1));
+                            }
+                            Some(x) if in_string => {
+                                all_span.expand(x.1);
+                                tokens.push(x);
+                                self.bump();
+                            }
                             Some((Symbol(','), _)) | Some((Symbol(')'), _)) if nesting == 0 => {
                                 match tokens.last() {
                                     Some((Whitespace, _)) => {
@@ -976,8 +1283,38 @@ impl<'a> Preprocessor<'a> {
         'outer: loop {
             let mut arg_tokens = Vec::<TokenAndSpan>::new();
             let mut nesting = 0;
+            let mut in_string = false;
             loop {
                 match self.token {
+                    // Inside string literals, commas and parentheses carry no
+                    // meaning. Escaped characters are taken verbatim.
+                    Some(x @ (Symbol('"'), _)) => {
+                        in_string = !in_string;
+                        arg_tokens.push(x);
+                        self.bump();
+                        all_span.expand(x.1);
+                    }
+                    Some(x @ (Symbol('\\'), _)) if in_string => {
+                        arg_tokens.push(x);
+                        self.bump();
+                        all_span.expand(x.1);
+                        if let Some(y) = self.token {
+                            arg_tokens.push(y);
+                            self.bump();
+                            all_span.expand(y.1);
+                        }
+                    }
+                    Some(x @ (Newline, _)) if in_string => {
+                        return Err(DiagBuilder2::fatal(
+                            "unterminated string literal in macro argument",
+                        )
+                        .span(x.1));
+                    }
+                    Some(x) if in_string => {
+                        arg_tokens.push(x);
+                        self.bump();
+                        all_span.expand(x.1);
+                    }
                     Some((Symbol(','), sp)) if nesting == 0 => {
                         args.push(arg_tokens);
                         all_span.expand(sp);
@@ -1109,10 +1446,24 @@ impl<'a> Iterator for Preprocessor<'a> {
                         // call upon the handle_directive function to
                         // perform the necessary actions.
                         let dir_span = Span::union(sp_backtick, sp);
+                        let is_macro = !DIRECTIVES_TABLE.contains_key(name.as_str());
                         match self.handle_directive(name, dir_span) {
                             Err(x) => return Some(Err(x)),
                             _ => (),
                         }
+                        if let (Some(dirs), false) = (self.directives.as_mut(), is_macro) {
+                            // Extend the span up to the next token if it is
+                            // still in the same file, such that it covers the
+                            // directive's arguments.
+                            dirs.push(match self.token {
+                                Some((_, sp))
+                                    if sp.source == dir_span.source && sp.begin >= dir_span.end =>
+                                {
+                                    Span::new(dir_span.source, dir_span.begin, sp.begin)
+                                }
+                                _ => dir_span,
+                            });
+                        }
                         continue;
                     } else if let Some(tkn @ (Symbol('"'), _)) = self.token {
                         // emit the '"'
@@ -1127,7 +1478,10 @@ impl<'a> Iterator for Preprocessor<'a> {
                             return Some(Ok(tkn));
                         }
                     } else if let Some((Symbol('`'), _)) = self.token {
-                        self.bump(); // consume the second backtick and ignore
+                        // Token pasting. Dropping the "``" joins the adjacent
+                        // tokens, which may be any tokens, e.g. `/``*` to
+                        // form the start of a comment.
+                        self.bump();
                     } else {
                         return Some(Err(DiagBuilder2::fatal(
                             "expected compiler directive after '`', or '``', '`\"', or '`\\'",
@@ -1135,6 +1489,29 @@ impl<'a> Iterator for Preprocessor<'a> {
                         .span(sp_backtick)));
                     }
                 }
+                // Warn about keyword sets that are never closed.
+                None if !self.dirs.keywords.is_empty() => {
+                    for (_, opening) in std::mem::replace(&mut self.dirs.keywords, vec![]) {
+                        self.warnings.push(
+                            DiagBuilder2::warning(format!(
+                                "{} without a corresponding `end_keywords",
+                                opening.extract()
+                            ))
+                            .span(opening),
+                        );
+                    }
+                    continue;
+                }
+                // Report any define conditionals that are still open at the
+                // end of the input.
+                None if !self.defcond_stack.is_empty() => {
+                    let (_, opening) = self.defcond_stack.pop().unwrap();
+                    return Some(Err(DiagBuilder2::fatal(format!(
+                        "found {} without a corresponding `endif",
+                        opening.extract()
+                    ))
+                    .span(opening)));
+                }
                 _ => {
                     // All tokens other than preprocessor directives are
                     // emitted, unless we're currently inside a disabled define
@@ -1154,9 +1531,19 @@ impl<'a> Iterator for Preprocessor<'a> {
 
 struct Stream<'a> {
     source: Source,
+    /// The canonicalized path of the source, used to detect include cycles.
+    path: PathBuf,
     iter: Cat<'a>,
 }
 
+/// Determine the canonical path of a source file.
+///
+/// Falls back to the path as given for sources that do not exist on disk.
+fn canonical_path(source: Source) -> PathBuf {
+    let path = PathBuf::from(&*source.get_path());
+    std::fs::canonicalize(&path).unwrap_or(path)
+}
+
 /// The different compiler directives recognized by the preprocessor.
 #[derive(Debug, Clone, Copy)]
 enum Directive {
@@ -1181,6 +1568,7 @@ enum Directive {
     Line,
     UnconnectedDrive,
     NoUnconnectedDrive,
+    Pragma,
     Unknown,
 }
 
@@ -1208,6 +1596,7 @@ impl fmt::Display for Directive {
             Directive::Line => write!(f, "`line"),
             Directive::UnconnectedDrive => write!(f, "`unconnected_drive"),
             Directive::NoUnconnectedDrive => write!(f, "`nounconnected_drive"),
+            Directive::Pragma => write!(f, "`pragma"),
             Directive::Unknown => write!(f, "unknown"),
         }
     }
@@ -1236,9 +1625,81 @@ static DIRECTIVES_TABLE: Lazy<HashMap<&'static str, Directive>> = Lazy::new(|| {
     table.insert("unconnected_drive", Directive::UnconnectedDrive);
     table.insert("nounconnected_drive", Directive::NoUnconnectedDrive);
     table.insert("timescale", Directive::Timescale);
+    table.insert("pragma", Directive::Pragma);
     table
 });
 
+/// A `` `pragma`` directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pragma {
+    /// The span of the entire directive.
+    pub span: Span,
+    /// The pragma name, e.g. `protect`.
+    pub name: String,
+    /// The comma-separated pragma expressions following the name.
+    pub args: Vec<PragmaArg>,
+}
+
+/// A pragma expression of the form `keyword`, `keyword = value`, or `value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PragmaArg {
+    /// The keyword or value before the `=`.
+    pub key: String,
+    /// The value after the `=`, if any.
+    pub value: Option<String>,
+}
+
+/// Split the text after a pragma name into its expressions.
+///
+/// Commas and equal signs are only considered outside of string literals and
+/// parentheses, such that values like `("a", "b")` remain intact.
+fn parse_pragma_args(text: &str) -> Vec<PragmaArg> {
+    let mut args = vec![];
+    let mut key = String::new();
+    let mut value: Option<String> = None;
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut push = |key: &mut String, value: &mut Option<String>| {
+        let k = key.trim();
+        if !k.is_empty() {
+            args.push(PragmaArg {
+                key: k.to_string(),
+                value: value.take().map(|v| v.trim().to_string()),
+            });
+        }
+        key.clear();
+        *value = None;
+    };
+    for c in text.chars() {
+        if !quoted && depth == 0 && c == ',' {
+            push(&mut key, &mut value);
+            continue;
+        }
+        if !quoted && depth == 0 && c == '=' && value.is_none() {
+            value = Some(String::new());
+            continue;
+        }
+        if quoted {
+            quoted = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else {
+            match c {
+                '"' => quoted = true,
+                '(' => depth += 1,
+                ')' if depth > 0 => depth -= 1,
+                _ => (),
+            }
+        }
+        match value {
+            Some(ref mut v) => v.push(c),
+            None => key.push(c),
+        }
+    }
+    push(&mut key, &mut value);
+    args
+}
+
 #[derive(Debug)]
 struct Macro {
     name: String,
@@ -1271,23 +1732,36 @@ enum Defcond {
     Disabled,
 }
 
-#[derive(Default)]
 struct Directives {
     celldefine: bool,
-    default_nettype: Option<TokenAndSpan>,
-    keywords: Vec<KeywordsDirective>,
+    default_nettype: Option<NetType>,
+    keywords: Vec<(KeywordsDirective, Span)>,
     unconnected_drive: Option<UnconnectedDrive>,
 }
 
+impl Default for Directives {
+    fn default() -> Self {
+        Directives {
+            celldefine: false,
+            default_nettype: Some(NetType::Wire),
+            keywords: vec![],
+            unconnected_drive: None,
+        }
+    }
+}
+
+/// A keyword set selected by `` `begin_keywords``.
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
-enum KeywordsDirective {
-    Ieee1800_2009,
-    Ieee1800_2005,
-    Ieee1364_2005,
-    Ieee1364_2001,
-    Ieee1364_2001_Noconfig,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeywordsDirective {
     Ieee1364_1995,
+    Ieee1364_2001_Noconfig,
+    Ieee1364_2001,
+    Ieee1364_2005,
+    Ieee1800_2005,
+    Ieee1800_2009,
+    Ieee1800_2012,
+    Ieee1800_2017,
 }
 
 #[derive(Debug)]
@@ -1299,6 +1773,8 @@ enum UnconnectedDrive {
 impl KeywordsDirective {
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
+            "1800-2017" => Some(Self::Ieee1800_2017),
+            "1800-2012" => Some(Self::Ieee1800_2012),
             "1800-2009" => Some(Self::Ieee1800_2009),
             "1800-2005" => Some(Self::Ieee1800_2005),
             "1364-2005" => Some(Self::Ieee1364_2005),
@@ -1308,8 +1784,199 @@ impl KeywordsDirective {
             _ => None,
         }
     }
+
+    /// Check whether a keyword is reserved in this keyword set.
+    ///
+    /// See IEEE 1800-2017 Table 22-1 through 22-7.
+    pub fn reserves(self, kw: Kw) -> bool {
+        use KeywordsDirective::*;
+        let name = kw.as_str();
+        let since = if KEYWORDS_1364_1995.contains(&name) {
+            Ieee1364_1995
+        } else if KEYWORDS_1364_2001_CONFIG.contains(&name) {
+            // The configuration keywords are the only difference between the
+            // 1364-2001 and 1364-2001-noconfig sets.
+            return self >= Ieee1364_2001 && self != Ieee1364_2001_Noconfig;
+        } else if KEYWORDS_1364_2001.contains(&name) {
+            Ieee1364_2001_Noconfig
+        } else if name == "uwire" {
+            Ieee1364_2005
+        } else if KEYWORDS_1800_2009.contains(&name) {
+            Ieee1800_2009
+        } else if KEYWORDS_1800_2012.contains(&name) {
+            Ieee1800_2012
+        } else {
+            Ieee1800_2005
+        };
+        self >= since
+    }
 }
 
+/// The keywords reserved in IEEE 1364-1995.
+static KEYWORDS_1364_1995: &[&str] = &[
+    "always",
+    "and",
+    "assign",
+    "begin",
+    "buf",
+    "bufif0",
+    "bufif1",
+    "case",
+    "casex",
+    "casez",
+    "cmos",
+    "deassign",
+    "default",
+    "defparam",
+    "disable",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endfunction",
+    "endmodule",
+    "endprimitive",
+    "endspecify",
+    "endtable",
+    "endtask",
+    "event",
+    "for",
+    "force",
+    "forever",
+    "fork",
+    "function",
+    "highz0",
+    "highz1",
+    "if",
+    "ifnone",
+    "initial",
+    "inout",
+    "input",
+    "integer",
+    "join",
+    "large",
+    "macromodule",
+    "medium",
+    "module",
+    "nand",
+    "negedge",
+    "nmos",
+    "nor",
+    "not",
+    "notif0",
+    "notif1",
+    "or",
+    "output",
+    "parameter",
+    "pmos",
+    "posedge",
+    "primitive",
+    "pull0",
+    "pull1",
+    "pulldown",
+    "pullup",
+    "rcmos",
+    "real",
+    "realtime",
+    "reg",
+    "release",
+    "repeat",
+    "rnmos",
+    "rpmos",
+    "rtran",
+    "rtranif0",
+    "rtranif1",
+    "scalared",
+    "small",
+    "specify",
+    "specparam",
+    "strong0",
+    "strong1",
+    "supply0",
+    "supply1",
+    "table",
+    "task",
+    "time",
+    "tran",
+    "tranif0",
+    "tranif1",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "vectored",
+    "wait",
+    "wand",
+    "weak0",
+    "weak1",
+    "while",
+    "wire",
+    "wor",
+    "xnor",
+    "xor",
+];
+
+/// The keywords added in IEEE 1364-2001, excluding the configuration keywords.
+static KEYWORDS_1364_2001: &[&str] = &[
+    "automatic",
+    "endgenerate",
+    "generate",
+    "genvar",
+    "localparam",
+    "noshowcancelled",
+    "pulsestyle_ondetect",
+    "pulsestyle_onevent",
+    "showcancelled",
+    "signed",
+    "unsigned",
+];
+
+/// The configuration keywords added in IEEE 1364-2001.
+static KEYWORDS_1364_2001_CONFIG: &[&str] = &[
+    "cell",
+    "config",
+    "design",
+    "endconfig",
+    "incdir",
+    "include",
+    "instance",
+    "liblist",
+    "library",
+    "use",
+];
+
+/// The keywords added in IEEE 1800-2009.
+static KEYWORDS_1800_2009: &[&str] = &[
+    "accept_on",
+    "checker",
+    "endchecker",
+    "eventually",
+    "global",
+    "implies",
+    "let",
+    "nexttime",
+    "reject_on",
+    "restrict",
+    "s_always",
+    "s_eventually",
+    "s_nexttime",
+    "s_until",
+    "s_until_with",
+    "strong",
+    "sync_accept_on",
+    "sync_reject_on",
+    "unique0",
+    "until",
+    "until_with",
+    "untyped",
+    "weak",
+];
+
+/// The keywords added in IEEE 1800-2012.
+static KEYWORDS_1800_2012: &[&str] = &["implements", "interconnect", "nettype", "soft"];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1373,6 +2040,213 @@ mod tests {
         }
     }
 
+    #[test]
+    fn nested_conditionals() {
+        check_str(
+            "`define A\n`ifdef A\n`ifndef B\nx\n`elsif A\ny\n`else\nz\n`endif\n`endif\n",
+            "\n\nx\n\n\n",
+        );
+    }
+
+    #[test]
+    fn unterminated_conditional() {
+        let pp = preproc("`ifdef A\nx\n");
+        let errs: Vec<_> = pp.filter_map(|x| x.err()).collect();
+        assert_eq!(errs.len(), 1);
+    }
+
+    #[test]
+    fn predefined_and_undef() {
+        let sm = get_source_manager();
+        let source = sm.add("undef.sv", "`FOO\n`undef FOO\n`ifdef FOO\nx\n`endif\n");
+        let mut pp = Preprocessor::new(source, &[], &[]);
+        pp.define("FOO", Some("42"));
+        assert!(pp.is_defined("FOO"));
+        let actual: String = (&mut pp).map(|x| x.unwrap().1.extract()).collect();
+        assert_eq!(actual, "42\n\n\n");
+        assert!(!pp.is_defined("FOO"));
+        assert!(pp.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn record_directives() {
+        let mut pp = preproc("`define FOO 1\n`ifdef FOO\nx\n`endif\n").with_directives();
+        let actual: String = (&mut pp).map(|x| x.unwrap().1.extract()).collect();
+        assert_eq!(actual, "\nx\n\n");
+        let dirs: Vec<_> = pp.take_directives().iter().map(|d| d.extract()).collect();
+        assert_eq!(dirs, vec!["`define FOO 1\n", "`ifdef FOO", "`endif"]);
+        assert!(pp.take_directives().is_empty());
+    }
+
+    #[test]
+    fn undef_unknown_macro() {
+        let mut pp = preproc("`undef FOO\nx");
+        let actual: String = (&mut pp).map(|x| x.unwrap().1.extract()).collect();
+        assert_eq!(actual, "\nx");
+        assert_eq!(pp.take_warnings().len(), 1);
+    }
+
+    #[test]
+    fn include_dirs_deduplicated() {
+        let pp = preproc("")
+            .with_include_dirs(vec![PathBuf::from("inc"), PathBuf::from("other")])
+            .with_include_dirs(vec![PathBuf::from("inc")]);
+        assert_eq!(
+            pp.include_dirs,
+            vec![PathBuf::from("inc"), PathBuf::from("other")]
+        );
+    }
+
+    #[test]
+    fn include_cycle() {
+        let sm = get_source_manager();
+        sm.add("cycle_a.sv", "`include \"cycle_b.sv\"\n");
+        sm.add("cycle_b.sv", "`include \"cycle_a.sv\"\n");
+        let pp = Preprocessor::new(sm.open("cycle_a.sv").unwrap(), &[], &[]);
+        assert!(pp.take(100).any(|x| x.is_err()));
+    }
+
+    #[test]
+    fn begin_keywords_unclosed() {
+        let mut pp = preproc("`begin_keywords \"1364-2001\"\nx\n");
+        assert!((&mut pp).all(|x| x.is_ok()));
+        assert_eq!(pp.take_warnings().len(), 1);
+        assert_eq!(pp.keywords(), None);
+    }
+
+    #[test]
+    fn pragma() {
+        let mut pp = preproc(
+            "`pragma protect begin_protected, key_keyowner = \"Foo, Inc.\", x = (a, b)\ny\n`pragma reset\n",
+        );
+        let actual: String = (&mut pp).map(|x| x.unwrap().1.extract()).collect();
+        assert_eq!(actual, "\ny\n\n");
+        let pragmas = pp.take_pragmas();
+        assert_eq!(pragmas.len(), 2);
+        assert_eq!(pragmas[0].name, "protect");
+        assert_eq!(
+            pragmas[0].args,
+            vec![
+                PragmaArg {
+                    key: "begin_protected".to_string(),
+                    value: None,
+                },
+                PragmaArg {
+                    key: "key_keyowner".to_string(),
+                    value: Some("\"Foo, Inc.\"".to_string()),
+                },
+                PragmaArg {
+                    key: "x".to_string(),
+                    value: Some("(a, b)".to_string()),
+                },
+            ]
+        );
+        assert_eq!(pragmas[1].name, "reset");
+        assert!(pragmas[1].args.is_empty());
+    }
+
+    #[test]
+    fn pragma_without_name() {
+        let mut pp = preproc("`pragma\n");
+        assert!(pp.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn include_dependencies() {
+        let sm = get_source_manager();
+        sm.add("deps_b.svh", "b\n");
+        sm.add("deps_c.svh", "`include \"deps_b.svh\"\n");
+        let source = sm.add(
+            "deps_a.sv",
+            "`include \"deps_b.svh\"\n`include \"deps_c.svh\"\n",
+        );
+        let mut pp = Preprocessor::new(source, &[], &[]);
+        assert!((&mut pp).all(|x| x.is_ok()));
+        assert_eq!(
+            pp.dependencies(),
+            &[PathBuf::from("deps_b.svh"), PathBuf::from("deps_c.svh")]
+        );
+    }
+
+    #[test]
+    fn include_not_found() {
+        let mut pp = preproc("`include \"missing.svh\"\n");
+        assert!(pp.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn macro_token_pasting() {
+        check_str("`define SIG(p, n) p``_``n``_q\n`SIG(data, 3)", "data_3_q");
+    }
+
+    #[test]
+    fn macro_stringification() {
+        check_str(
+            "`define STR(x) `\"x is `\\`\"x`\\`\"`\"\n`STR(foo)",
+            "\"foo is \\\"foo\\\"\"",
+        );
+    }
+
+    #[test]
+    fn macro_paste_comment() {
+        // Pasting may join any tokens, e.g. to form the comments of the
+        // synopsys pragma idiom.
+        check_str(
+            "`define FF(q) /``* synopsys sync_set_reset `\"q`\" *``/ q\n`FF(rst)",
+            "/* synopsys sync_set_reset \"rst\" */ rst",
+        );
+    }
+
+    #[test]
+    fn current_file_and_line() {
+        let sm = get_source_manager();
+        sm.add("file_line_inc.sv", "`__FILE__ `__LINE__");
+        sm.add(
+            "file_line.sv",
+            "`__FILE__\n`__LINE__\n`include \"file_line_inc.sv\"",
+        );
+        let pp = Preprocessor::new(sm.open("file_line.sv").unwrap(), &[], &[]);
+        let actual: String = pp.map(|x| x.unwrap().1.extract()).collect();
+        assert_eq!(actual, "\"file_line.sv\"\n2\n\"file_line_inc.sv\" 1");
+    }
+
+    #[test]
+    fn line_directive() {
+        let sm = get_source_manager();
+        let source = sm.add("line_directive.sv", "a\n`line 42 \"foo.sv\" 0\nb\nc");
+        let pp = Preprocessor::new(source, &[], &[]);
+        let spans: Vec<_> = pp
+            .map(|x| x.unwrap())
+            .filter(|x| x.0 == Text)
+            .map(|x| {
+                let (file, line) = x.1.begin().human_file_and_line();
+                format!("{}:{}", file, line)
+            })
+            .collect();
+        assert_eq!(spans, &["line_directive.sv:1", "foo.sv:42", "foo.sv:43"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid level `3` in `line")]
+    fn line_directive_invalid_level() {
+        let mut pp = preproc("`line 1 \"foo.sv\" 3\n");
+        while let Some(tkn) = pp.next() {
+            tkn.unwrap();
+        }
+    }
+
+    #[test]
+    fn default_nettype() {
+        let mut pp = preproc("`default_nettype tri\na\n`default_nettype none\nb\n`resetall\nc");
+        let mut nettypes = vec![];
+        while let Some(tkn) = pp.next() {
+            if tkn.unwrap().0 == Text {
+                nettypes.push(pp.default_nettype());
+            }
+        }
+        assert_eq!(nettypes, &[Some(NetType::Tri), None, Some(NetType::Wire)]);
+    }
+
     #[test]
     fn macro_args() {
         check_str(
@@ -1391,6 +2265,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn macro_args_with_underscores() {
+        check_str(
+            "`define MAX(a_1, b_1) ((a_1) > (b_1) ? (a_1) : (b_1))\n`MAX(x, y_2)",
+            "((x) > (y_2) ? (x) : (y_2))",
+        );
+    }
+
+    #[test]
+    fn macro_args_with_defaults() {
+        check_str(
+            "`define foo(x, y = 2, z = (1, 2)) {x, y, z}\n`foo(1)\n`foo(1, , 3)",
+            "{1, 2, (1, 2)}\n{1, 2, 3}",
+        );
+    }
+
+    #[test]
+    fn macro_args_with_escaped_string_defaults() {
+        check_str(
+            "`define foo(x = \"a\\\", (b\", y = 2) x y\n`foo()",
+            "\"a\\\", (b\" 2",
+        );
+    }
+
+    #[test]
+    fn macro_args_with_nested_commas() {
+        check_str(
+            "`define foo(x, y) x y\n`foo(f(a, b), \"c, (d\")",
+            "f(a, b) \"c, (d\"",
+        );
+    }
+
+    #[test]
+    fn macro_args_count_mismatch() {
+        let mut pp = preproc("`define foo(x) x\n`foo(1, 2)");
+        assert!(pp.any(|x| x.is_err()));
+    }
+
     #[test]
     fn macro_name_with_digits_and_underscores() {
         check_str("`define AXI_BUS21_SV 42\n`AXI_BUS21_SV", "42");
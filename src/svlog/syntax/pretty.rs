@@ -0,0 +1,1820 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! A pretty printer that renders the AST back into SystemVerilog source.
+//!
+//! Modules, interfaces, packages, statements, expressions, and types are
+//! printed in a canonical format. Constructs the printer does not handle yet,
+//! such as classes and assertions, are reproduced verbatim from the source
+//! text they were parsed from.
+
+use crate::ast::*;
+use crate::token::{Lit, Precedence, TimeUnit};
+use moore_common::{name::Name, source::Span};
+
+/// Render a node as SystemVerilog source, indented by four spaces per level.
+pub fn pretty<T: Pretty + ?Sized>(node: &T) -> String {
+    let mut p = Printer::new();
+    p.print(node);
+    p.finish()
+}
+
+/// A node that can be rendered as SystemVerilog source.
+pub trait Pretty {
+    /// Render the node into a printer.
+    fn pretty(&self, p: &mut Printer);
+}
+
+/// An accumulator for rendered SystemVerilog source.
+pub struct Printer {
+    out: String,
+    indent: String,
+    level: usize,
+    line_start: bool,
+}
+
+impl Default for Printer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Printer {
+    /// Create a printer that indents by four spaces per level.
+    pub fn new() -> Self {
+        Printer {
+            out: String::new(),
+            indent: "    ".to_string(),
+            level: 0,
+            line_start: true,
+        }
+    }
+
+    /// Use a different string for one level of indentation.
+    pub fn with_indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// Render a node into the printer.
+    pub fn print<T: Pretty + ?Sized>(&mut self, node: &T) -> &mut Self {
+        node.pretty(self);
+        self
+    }
+
+    /// Consume the printer and return the rendered source.
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.line_start {
+            for _ in 0..self.level {
+                self.out.push_str(&self.indent);
+            }
+            self.line_start = false;
+        }
+        self.out.push_str(text);
+    }
+
+    fn name(&mut self, name: Name) {
+        self.write(&name.as_str());
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        self.line_start = true;
+    }
+
+    fn nested(&mut self, f: impl FnOnce(&mut Self)) {
+        self.level += 1;
+        f(self);
+        self.level -= 1;
+    }
+
+    fn list<T>(&mut self, items: &[T], sep: &str, mut f: impl FnMut(&mut Self, &T)) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.write(sep);
+            }
+            f(self, item);
+        }
+    }
+
+    /// Reproduce the source text of a span verbatim.
+    fn verbatim(&mut self, span: Span) {
+        for (i, line) in span.extract().lines().enumerate() {
+            if i > 0 {
+                self.newline();
+                self.out.push_str(line);
+                self.line_start = false;
+            } else {
+                self.write(line);
+            }
+        }
+    }
+
+    fn items(&mut self, items: &[Item]) {
+        for item in items {
+            item.pretty(self);
+            self.newline();
+        }
+    }
+
+    fn timeunits(&mut self, timeunits: &Timeunit) {
+        if let Some(unit) = timeunits.unit {
+            self.write("timeunit ");
+            self.time_value(unit.value);
+            self.write(";");
+            self.newline();
+        }
+        if let Some(prec) = timeunits.prec {
+            self.write("timeprecision ");
+            self.time_value(prec.value);
+            self.write(";");
+            self.newline();
+        }
+    }
+
+    fn time_value(&mut self, value: TimeValue) {
+        self.write(&value.magnitude.to_string());
+        self.write(time_unit_str(value.unit));
+    }
+
+    fn lifetime(&mut self, lifetime: Lifetime) {
+        match lifetime {
+            Lifetime::Static => (),
+            Lifetime::Automatic => self.write("automatic "),
+        }
+    }
+
+    /// Print a type followed by a space, or nothing if the type is implicit
+    /// and has neither sign nor dimensions.
+    fn type_prefix(&mut self, ty: &Type) {
+        if !is_empty_type(ty) {
+            ty.pretty(self);
+            self.write(" ");
+        }
+    }
+
+    fn dims(&mut self, dims: &[TypeDim]) {
+        for dim in dims {
+            dim.pretty(self);
+        }
+    }
+
+    fn var_names(&mut self, names: &[VarDeclName]) {
+        self.list(names, ", ", |p, name| name.pretty(p));
+    }
+
+    /// Print an expression, wrapped in parentheses if requested.
+    fn operand(&mut self, expr: &Expr, parens: bool) {
+        if parens {
+            self.write("(");
+        }
+        expr.pretty(self);
+        if parens {
+            self.write(")");
+        }
+    }
+
+    fn call_args(&mut self, args: &[CallArg]) {
+        self.list(args, ", ", |p, arg| arg.pretty(p));
+    }
+
+    /// Print the value of a `#` delay, parenthesized unless it is primary.
+    fn delay(&mut self, expr: &Expr) {
+        self.write("#");
+        self.operand(expr, !is_primary(expr));
+    }
+
+    fn delay3(&mut self, delay: &Delay3) {
+        if delay.fall.is_none() && is_primary(&delay.rise) {
+            return self.delay(&delay.rise);
+        }
+        self.write("#(");
+        delay.rise.pretty(self);
+        if let Some(ref fall) = delay.fall {
+            self.write(", ");
+            fall.pretty(self);
+        }
+        if let Some(ref turn_off) = delay.turn_off {
+            self.write(", ");
+            turn_off.pretty(self);
+        }
+        self.write(")");
+    }
+
+    fn drive_strength(&mut self, s0: DriveStrength, s1: DriveStrength) {
+        self.write("(");
+        self.write(s0.as_str());
+        self.write(", ");
+        self.write(s1.as_str());
+        self.write(")");
+    }
+
+    fn param_assignments(&mut self, params: &[ParamAssignment]) {
+        self.write("#(");
+        self.list(params, ", ", |p, param| match param.name {
+            Some(name) => {
+                p.write(".");
+                p.name(name.name);
+                p.write("(");
+                param.expr.pretty(p);
+                p.write(")");
+            }
+            None => param.expr.pretty(p),
+        });
+        self.write(")");
+    }
+
+    fn module_header(&mut self, params: &[ParamDecl], ports: &[Port]) {
+        if !params.is_empty() {
+            self.write(" #(");
+            self.list(params, ", ", |p, param| param.pretty(p));
+            self.write(")");
+        }
+        if !ports.is_empty() {
+            self.write(" (");
+            self.newline();
+            self.nested(|p| {
+                for (i, port) in ports.iter().enumerate() {
+                    port.pretty(p);
+                    if i + 1 < ports.len() {
+                        p.write(",");
+                    }
+                    p.newline();
+                }
+            });
+            self.write(")");
+        }
+        self.write(";");
+        self.newline();
+    }
+}
+
+impl<T: Pretty + ?Sized> Pretty for &T {
+    fn pretty(&self, p: &mut Printer) {
+        (**self).pretty(p)
+    }
+}
+
+impl<T: Pretty> Pretty for Box<T> {
+    fn pretty(&self, p: &mut Printer) {
+        self.as_ref().pretty(p)
+    }
+}
+
+impl Pretty for SourceFile<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.timeunits(&self.timeunits);
+        p.items(&self.items);
+    }
+}
+
+impl Pretty for Item<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self.data {
+            ItemData::Dummy => (),
+            ItemData::ModuleDecl(ref x) => x.pretty(p),
+            ItemData::InterfaceDecl(ref x) => x.pretty(p),
+            ItemData::PackageDecl(ref x) => x.pretty(p),
+            ItemData::ImportDecl(ref x) => x.pretty(p),
+            ItemData::ParamDecl(ref x) => {
+                x.pretty(p);
+                p.write(";");
+            }
+            ItemData::Typedef(ref x) => x.pretty(p),
+            ItemData::PortDecl(ref x) => x.pretty(p),
+            ItemData::Procedure(ref x) => x.pretty(p),
+            ItemData::SubroutineDecl(ref x) => x.pretty(p),
+            ItemData::ContAssign(ref x) => x.pretty(p),
+            ItemData::GenvarDecl(ref decls) => {
+                p.write("genvar ");
+                p.list(decls, ", ", |p, decl| decl.pretty(p));
+                p.write(";");
+            }
+            ItemData::GenerateRegion(_, ref items) => {
+                p.write("generate");
+                p.newline();
+                p.nested(|p| p.items(items));
+                p.write("endgenerate");
+            }
+            ItemData::GenerateFor(ref x) => x.pretty(p),
+            ItemData::GenerateIf(ref x) => x.pretty(p),
+            ItemData::GenerateCase(ref x) => x.pretty(p),
+            ItemData::NetDecl(ref x) => x.pretty(p),
+            ItemData::VarDecl(ref x) => {
+                x.pretty(p);
+                p.write(";");
+            }
+            ItemData::Inst(ref x) => x.pretty(p),
+            ItemData::Bind(ref x) => x.pretty(p),
+            ItemData::Defparam(ref x) => x.pretty(p),
+            ItemData::CheckerDecl(..)
+            | ItemData::ClassDecl(..)
+            | ItemData::ProgramDecl(..)
+            | ItemData::DpiDecl(..)
+            | ItemData::ModportDecl(..)
+            | ItemData::ClockingDecl(..)
+            | ItemData::DefaultClocking(..)
+            | ItemData::Assertion(..) => p.verbatim(self.span),
+        }
+    }
+}
+
+impl Pretty for Module<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("module ");
+        p.lifetime(self.lifetime);
+        p.name(self.name.value);
+        for import in &self.imports {
+            p.write(" ");
+            import.pretty(p);
+        }
+        p.module_header(&self.params, &self.ports);
+        p.nested(|p| {
+            p.timeunits(&self.timeunits);
+            p.items(&self.items);
+        });
+        p.write("endmodule");
+    }
+}
+
+impl Pretty for Interface<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("interface ");
+        p.lifetime(self.lifetime);
+        p.name(self.name.value);
+        p.module_header(&self.params, &self.ports);
+        p.nested(|p| {
+            p.timeunits(&self.timeunits);
+            p.items(&self.items);
+        });
+        p.write("endinterface");
+    }
+}
+
+impl Pretty for Package<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("package ");
+        p.lifetime(self.lifetime);
+        p.name(self.name.value);
+        p.write(";");
+        p.newline();
+        p.nested(|p| {
+            p.timeunits(&self.timeunits);
+            p.items(&self.items);
+        });
+        p.write("endpackage");
+    }
+}
+
+impl Pretty for ImportDecl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("import ");
+        p.list(&self.items, ", ", |p, item| {
+            p.name(item.pkg.value);
+            p.write("::");
+            match item.name {
+                Some(name) => p.name(name.value),
+                None => p.write("*"),
+            }
+        });
+        p.write(";");
+    }
+}
+
+impl Pretty for ParamDecl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write(if self.local {
+            "localparam "
+        } else {
+            "parameter "
+        });
+        match self.kind {
+            ParamKind::Type(ref decls) => {
+                p.write("type ");
+                p.list(decls, ", ", |p, decl| {
+                    p.name(decl.name.value);
+                    if let Some(ref ty) = decl.ty {
+                        p.write(" = ");
+                        ty.pretty(p);
+                    }
+                });
+            }
+            ParamKind::Value(ref decls) => {
+                if let Some(first) = decls.first() {
+                    p.type_prefix(&first.ty);
+                }
+                p.list(decls, ", ", |p, decl| {
+                    p.name(decl.name.value);
+                    p.dims(&decl.dims);
+                    if let Some(ref expr) = decl.expr {
+                        p.write(" = ");
+                        expr.pretty(p);
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl Pretty for Port<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self.data {
+            PortData::Intf {
+                modport,
+                name,
+                ref dims,
+                ref expr,
+            } => {
+                p.write("interface");
+                if let Some(modport) = modport {
+                    p.write(".");
+                    p.name(modport.value);
+                }
+                p.write(" ");
+                p.name(name.value);
+                p.dims(dims);
+                if let Some(expr) = expr {
+                    p.write(" = ");
+                    expr.pretty(p);
+                }
+            }
+            PortData::Explicit {
+                dir,
+                name,
+                ref expr,
+            } => {
+                if let Some(dir) = dir {
+                    p.write(&format!("{} ", dir));
+                }
+                p.write(".");
+                p.name(name.value);
+                p.write("(");
+                if let Some(expr) = expr {
+                    expr.pretty(p);
+                }
+                p.write(")");
+            }
+            PortData::Named {
+                dir,
+                kind,
+                ref ty,
+                name,
+                ref dims,
+                ref expr,
+            } => {
+                if let Some(dir) = dir {
+                    p.write(&format!("{} ", dir));
+                }
+                if let Some(kind) = kind {
+                    p.write(&format!("{} ", kind));
+                }
+                p.type_prefix(ty);
+                p.name(name.value);
+                p.dims(dims);
+                if let Some(expr) = expr {
+                    p.write(" = ");
+                    expr.pretty(p);
+                }
+            }
+            PortData::Implicit(ref expr) => expr.pretty(p),
+        }
+    }
+}
+
+impl Pretty for PortDecl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write(&format!("{} ", self.dir));
+        if let Some(kind) = self.kind {
+            p.write(&format!("{} ", kind));
+        }
+        p.type_prefix(&self.ty);
+        p.var_names(&self.names);
+        p.write(";");
+    }
+}
+
+impl Pretty for Typedef<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("typedef ");
+        self.ty.pretty(p);
+        p.write(" ");
+        p.name(self.name.value);
+        p.dims(&self.dims);
+        p.write(";");
+    }
+}
+
+impl Pretty for Procedure<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write(match self.kind {
+            ProcedureKind::Initial => "initial ",
+            ProcedureKind::Always => "always ",
+            ProcedureKind::AlwaysComb => "always_comb ",
+            ProcedureKind::AlwaysLatch => "always_latch ",
+            ProcedureKind::AlwaysFf => "always_ff ",
+            ProcedureKind::Final => "final ",
+        });
+        self.stmt.pretty(p);
+    }
+}
+
+impl Pretty for SubroutineDecl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        self.prototype.pretty(p);
+        p.write(";");
+        p.newline();
+        p.nested(|p| {
+            for item in &self.items {
+                match item {
+                    SubroutineItem::PortDecl(decl) => {
+                        p.write(subroutine_port_dir_str(decl.dir));
+                        p.write(" ");
+                        if decl.var {
+                            p.write("var ");
+                        }
+                        p.type_prefix(&decl.ty);
+                        p.var_names(&decl.names);
+                        p.write(";");
+                    }
+                    SubroutineItem::Stmt(stmt) => stmt.pretty(p),
+                }
+                p.newline();
+            }
+        });
+        p.write(match self.prototype.kind {
+            SubroutineKind::Func => "endfunction",
+            SubroutineKind::Task => "endtask",
+        });
+    }
+}
+
+impl Pretty for SubroutinePrototype<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write(match self.kind {
+            SubroutineKind::Func => "function ",
+            SubroutineKind::Task => "task ",
+        });
+        if let Some(lifetime) = self.lifetime {
+            match lifetime {
+                Lifetime::Static => p.write("static "),
+                Lifetime::Automatic => p.write("automatic "),
+            }
+        }
+        if let Some(ref retty) = self.retty {
+            p.type_prefix(retty);
+        }
+        p.name(self.name.value);
+        if !self.args.is_empty() {
+            p.write("(");
+            p.list(&self.args, ", ", |p, arg| {
+                let mut parts = false;
+                if let Some(dir) = arg.dir {
+                    p.write(subroutine_port_dir_str(dir));
+                    parts = true;
+                }
+                if arg.var {
+                    p.write(if parts { " var" } else { "var" });
+                    parts = true;
+                }
+                if !is_empty_type(&arg.ty) {
+                    if parts {
+                        p.write(" ");
+                    }
+                    arg.ty.pretty(p);
+                    parts = true;
+                }
+                if let Some(ref name) = arg.name {
+                    if parts {
+                        p.write(" ");
+                    }
+                    p.name(name.name.value);
+                    p.dims(&name.dims);
+                    if let Some(ref expr) = name.expr {
+                        p.write(" = ");
+                        expr.pretty(p);
+                    }
+                }
+            });
+            p.write(")");
+        }
+    }
+}
+
+impl Pretty for Defparam<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("defparam ");
+        p.list(&self.assignments, ", ", |p, (lhs, rhs)| {
+            lhs.pretty(p);
+            p.write(" = ");
+            rhs.pretty(p);
+        });
+        p.write(";");
+    }
+}
+
+impl Pretty for ContAssign<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("assign ");
+        if let Some((s0, s1)) = self.strength {
+            p.drive_strength(s0, s1);
+            p.write(" ");
+        }
+        if let Some(ref delay) = self.delay {
+            p.delay3(delay);
+            p.write(" ");
+        }
+        p.list(&self.assignments, ", ", |p, (lhs, rhs)| {
+            lhs.pretty(p);
+            p.write(" = ");
+            rhs.pretty(p);
+        });
+        p.write(";");
+    }
+}
+
+impl Pretty for GenvarDecl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.name(self.name.value);
+        if let Some(ref init) = self.init {
+            p.write(" = ");
+            init.pretty(p);
+        }
+    }
+}
+
+impl Pretty for GenerateFor<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("for (");
+        self.init.pretty(p);
+        p.write(" ");
+        self.cond.pretty(p);
+        p.write("; ");
+        self.step.pretty(p);
+        p.write(") ");
+        self.block.pretty(p);
+    }
+}
+
+impl Pretty for GenerateIf<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("if (");
+        self.cond.pretty(p);
+        p.write(") ");
+        self.main_block.pretty(p);
+        if let Some(ref else_block) = self.else_block {
+            p.write(" else ");
+            else_block.pretty(p);
+        }
+    }
+}
+
+impl Pretty for GenerateCase<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("case (");
+        self.expr.pretty(p);
+        p.write(")");
+        p.newline();
+        p.nested(|p| {
+            for item in &self.items {
+                match item {
+                    GenerateCaseItem::Default(block) => {
+                        p.write("default: ");
+                        block.pretty(p);
+                    }
+                    GenerateCaseItem::Expr(exprs, block) => {
+                        p.list(exprs, ", ", |p, expr| expr.pretty(p));
+                        p.write(": ");
+                        block.pretty(p);
+                    }
+                }
+                p.newline();
+            }
+        });
+        p.write("endcase");
+    }
+}
+
+impl Pretty for GenerateBlock<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("begin");
+        if let Some(label) = self.label {
+            p.write(" : ");
+            p.name(label.value);
+        }
+        p.newline();
+        p.nested(|p| p.items(&self.items));
+        p.write("end");
+    }
+}
+
+impl Pretty for NetDecl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write(&format!("{} ", self.net_type));
+        match self.strength {
+            Some(NetStrength::Drive(s0, s1)) => {
+                p.drive_strength(s0, s1);
+                p.write(" ");
+            }
+            Some(NetStrength::Charge(charge)) => p.write(match charge {
+                ChargeStrength::Small => "(small) ",
+                ChargeStrength::Medium => "(medium) ",
+                ChargeStrength::Large => "(large) ",
+            }),
+            None => (),
+        }
+        match self.kind {
+            NetKind::Vectored => p.write("vectored "),
+            NetKind::Scalared => p.write("scalared "),
+            NetKind::None => (),
+        }
+        p.type_prefix(&self.ty);
+        if let Some(ref delay) = self.delay {
+            p.delay3(delay);
+            p.write(" ");
+        }
+        p.var_names(&self.names);
+        p.write(";");
+    }
+}
+
+impl Pretty for VarDecl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        if self.konst {
+            p.write("const ");
+        }
+        if self.var {
+            p.write("var ");
+        }
+        match self.lifetime {
+            Some(Lifetime::Static) => p.write("static "),
+            Some(Lifetime::Automatic) => p.write("automatic "),
+            None => (),
+        }
+        p.type_prefix(&self.ty);
+        p.var_names(&self.names);
+    }
+}
+
+impl Pretty for VarDeclName<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.name(self.name);
+        p.dims(&self.dims);
+        if let Some(ref init) = self.init {
+            p.write(" = ");
+            init.pretty(p);
+        }
+    }
+}
+
+impl Pretty for Inst<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.name(self.target.value);
+        if !self.params.is_empty() {
+            p.write(" ");
+            p.param_assignments(&self.params);
+        }
+        p.write(" ");
+        p.list(&self.names, ", ", |p, inst| {
+            p.name(inst.name.value);
+            p.dims(&inst.dims);
+            p.write("(");
+            p.list(&inst.conns, ", ", |p, conn| match conn.data {
+                PortConnData::Auto => p.write(".*"),
+                PortConnData::Named(name, ref mode) => {
+                    p.write(".");
+                    p.name(name.value);
+                    match mode {
+                        PortConnMode::Auto => (),
+                        PortConnMode::Unconnected => p.write("()"),
+                        PortConnMode::Connected(expr) => {
+                            p.write("(");
+                            expr.pretty(p);
+                            p.write(")");
+                        }
+                    }
+                }
+                PortConnData::Positional(ref expr) => expr.pretty(p),
+            });
+            p.write(")");
+        });
+        p.write(";");
+    }
+}
+
+impl Pretty for Bind<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("bind ");
+        self.target.pretty(p);
+        if !self.instances.is_empty() {
+            p.write(": ");
+            p.list(&self.instances, ", ", |p, x| x.pretty(p));
+        }
+        p.write(" ");
+        self.inst.pretty(p);
+    }
+}
+
+impl Pretty for Stmt<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        // Statements the printer does not handle are reproduced verbatim,
+        // including their label.
+        match self.kind {
+            AssertionStmt(..) => {
+                return p.verbatim(self.span);
+            }
+            _ => (),
+        }
+
+        if let Some(label) = self.label {
+            p.name(label);
+            p.write(": ");
+        }
+        match self.kind {
+            NullStmt => p.write(";"),
+            SequentialBlock(ref stmts) => {
+                p.write("begin");
+                p.newline();
+                p.nested(|p| {
+                    for stmt in stmts {
+                        stmt.pretty(p);
+                        p.newline();
+                    }
+                });
+                p.write("end");
+            }
+            ParallelBlock(ref stmts, join) => {
+                p.write("fork");
+                p.newline();
+                p.nested(|p| {
+                    for stmt in stmts {
+                        stmt.pretty(p);
+                        p.newline();
+                    }
+                });
+                p.write(match join {
+                    JoinKind::All => "join",
+                    JoinKind::Any => "join_any",
+                    JoinKind::None => "join_none",
+                });
+            }
+            IfStmt {
+                up,
+                ref cond,
+                ref main_stmt,
+                ref else_stmt,
+            } => {
+                if let Some(up) = up {
+                    p.write(unique_priority_str(up));
+                }
+                p.write("if (");
+                cond.pretty(p);
+                p.write(") ");
+
+                // Wrap an inner `if` without `else` in a block, such that our
+                // `else` does not bind to it.
+                let dangling = match main_stmt.kind {
+                    IfStmt {
+                        else_stmt: None, ..
+                    } => else_stmt.is_some(),
+                    _ => false,
+                };
+                if dangling {
+                    p.write("begin");
+                    p.newline();
+                    p.nested(|p| main_stmt.pretty(p));
+                    p.newline();
+                    p.write("end");
+                } else {
+                    main_stmt.pretty(p);
+                }
+
+                if let Some(else_stmt) = else_stmt {
+                    match main_stmt.kind {
+                        _ if dangling => p.write(" "),
+                        SequentialBlock(..) => p.write(" "),
+                        _ => p.newline(),
+                    }
+                    p.write("else ");
+                    else_stmt.pretty(p);
+                }
+            }
+            BlockingAssignStmt {
+                ref lhs,
+                ref rhs,
+                op,
+            } => {
+                lhs.pretty(p);
+                p.write(" ");
+                p.write(op.as_str());
+                p.write(" ");
+                rhs.pretty(p);
+                p.write(";");
+            }
+            NonblockingAssignStmt {
+                ref lhs,
+                ref rhs,
+                ref delay,
+                ..
+            } => {
+                lhs.pretty(p);
+                p.write(" <= ");
+                if let Some(delay) = delay {
+                    p.delay(&delay.expr);
+                    p.write(" ");
+                }
+                rhs.pretty(p);
+                p.write(";");
+            }
+            TimedStmt(ref control, ref stmt) => {
+                control.pretty(p);
+                match stmt.kind {
+                    NullStmt if stmt.label.is_none() => (),
+                    _ => p.write(" "),
+                }
+                stmt.pretty(p);
+            }
+            CaseStmt {
+                up,
+                kind,
+                ref expr,
+                mode,
+                ref items,
+            } => {
+                if let Some(up) = up {
+                    p.write(unique_priority_str(up));
+                }
+                p.write(match kind {
+                    CaseKind::Normal => "case (",
+                    CaseKind::DontCareZ => "casez (",
+                    CaseKind::DontCareXZ => "casex (",
+                });
+                expr.pretty(p);
+                p.write(")");
+                match mode {
+                    CaseMode::Normal => (),
+                    CaseMode::Inside => p.write(" inside"),
+                    CaseMode::Pattern => p.write(" matches"),
+                }
+                p.newline();
+                p.nested(|p| {
+                    for item in items {
+                        match item {
+                            CaseItem::Default(stmt) => {
+                                p.write("default: ");
+                                stmt.pretty(p);
+                            }
+                            CaseItem::Expr(ranges, stmt) => {
+                                p.list(ranges, ", ", |p, range| range.pretty(p));
+                                p.write(": ");
+                                stmt.pretty(p);
+                            }
+                        }
+                        p.newline();
+                    }
+                });
+                p.write("endcase");
+            }
+            ForeverStmt(ref stmt) => {
+                p.write("forever ");
+                stmt.pretty(p);
+            }
+            RepeatStmt(ref expr, ref stmt) => {
+                p.write("repeat (");
+                expr.pretty(p);
+                p.write(") ");
+                stmt.pretty(p);
+            }
+            WhileStmt(ref expr, ref stmt) => {
+                p.write("while (");
+                expr.pretty(p);
+                p.write(") ");
+                stmt.pretty(p);
+            }
+            DoStmt(ref stmt, ref expr) => {
+                // The trailing semicolon is parsed as a separate null
+                // statement, so don't emit it here.
+                p.write("do ");
+                stmt.pretty(p);
+                p.write(" while (");
+                expr.pretty(p);
+                p.write(")");
+            }
+            ForStmt(ref init, ref cond, ref step, ref stmt) => {
+                p.write("for (");
+                init.pretty(p);
+                p.write(" ");
+                cond.pretty(p);
+                p.write("; ");
+                step.pretty(p);
+                p.write(") ");
+                stmt.pretty(p);
+            }
+            ForeachStmt(ref expr, ref indices, ref stmt) => {
+                p.write("foreach (");
+                p.operand(expr, expr_precedence(expr) < Precedence::Scope);
+                p.write("[");
+                let num = indices.iter().map(|i| i.index + 1).max().unwrap_or(0);
+                for i in 0..num {
+                    if i > 0 {
+                        p.write(", ");
+                    }
+                    if let Some(index) = indices.iter().find(|x| x.index == i) {
+                        p.name(index.name.value);
+                    }
+                }
+                p.write("]) ");
+                stmt.pretty(p);
+            }
+            ExprStmt(ref expr) => {
+                expr.pretty(p);
+                p.write(";");
+            }
+            VarDeclStmt(ref decl) => {
+                decl.pretty(p);
+                p.write(";");
+            }
+            GenvarDeclStmt(ref decls) => {
+                p.write("genvar ");
+                p.list(decls, ", ", |p, decl| decl.pretty(p));
+                p.write(";");
+            }
+            ContinueStmt => p.write("continue;"),
+            BreakStmt => p.write("break;"),
+            ReturnStmt(ref expr) => {
+                p.write("return");
+                if let Some(expr) = expr {
+                    p.write(" ");
+                    expr.pretty(p);
+                }
+                p.write(";");
+            }
+            ImportStmt(ref decl) => decl.pretty(p),
+            WaitExprStmt(ref expr, ref stmt) => {
+                p.write("wait (");
+                expr.pretty(p);
+                p.write(")");
+                match stmt.kind {
+                    NullStmt if stmt.label.is_none() => (),
+                    _ => p.write(" "),
+                }
+                stmt.pretty(p);
+            }
+            WaitForkStmt => p.write("wait fork;"),
+            EventTriggerStmt(ref expr) => {
+                p.write("-> ");
+                expr.pretty(p);
+                p.write(";");
+            }
+            DisableForkStmt => p.write("disable fork;"),
+            ProceduralAssignStmt { ref lhs, ref rhs } => {
+                p.write("assign ");
+                lhs.pretty(p);
+                p.write(" = ");
+                rhs.pretty(p);
+                p.write(";");
+            }
+            DeassignStmt(ref lhs) => {
+                p.write("deassign ");
+                lhs.pretty(p);
+                p.write(";");
+            }
+            ForceStmt { ref lhs, ref rhs } => {
+                p.write("force ");
+                lhs.pretty(p);
+                p.write(" = ");
+                rhs.pretty(p);
+                p.write(";");
+            }
+            ReleaseStmt(ref lhs) => {
+                p.write("release ");
+                lhs.pretty(p);
+                p.write(";");
+            }
+            DisableStmt(name) => {
+                p.write("disable ");
+                p.name(name);
+                p.write(";");
+            }
+            AssertionStmt(..) => unreachable!(),
+        }
+    }
+}
+
+impl Pretty for TimingControl<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self {
+            TimingControl::Delay(delay) => p.delay(&delay.expr),
+            TimingControl::Event(event) => match event.data {
+                EventControlData::Implicit => p.write("@*"),
+                EventControlData::Expr(ref expr) => {
+                    p.write("@(");
+                    expr.pretty(p);
+                    p.write(")");
+                }
+            },
+            TimingControl::Cycle(cycle) => {
+                // Only numbers and identifiers may follow the `##` without
+                // parentheses.
+                let simple = match cycle.expr.data {
+                    LiteralExpr(Lit::Number(..)) | IdentExpr(..) => true,
+                    _ => false,
+                };
+                p.write("##");
+                p.operand(&cycle.expr, !simple);
+            }
+        }
+    }
+}
+
+impl Pretty for EventExpr<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self {
+            EventExpr::Edge { edge, value, .. } => {
+                p.write(match edge {
+                    EdgeIdent::Implicit => "",
+                    EdgeIdent::Edge => "edge ",
+                    EdgeIdent::Posedge => "posedge ",
+                    EdgeIdent::Negedge => "negedge ",
+                });
+                value.pretty(p);
+            }
+            EventExpr::Iff { expr, cond, .. } => {
+                expr.pretty(p);
+                p.write(" iff ");
+                cond.pretty(p);
+            }
+            EventExpr::Or { lhs, rhs, .. } => {
+                lhs.pretty(p);
+                p.write(" or ");
+                rhs.pretty(p);
+            }
+        }
+    }
+}
+
+impl Pretty for Type<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        let mut any = type_kind(p, &self.kind);
+        let sign = match self.sign {
+            TypeSign::None => None,
+            TypeSign::Signed => Some("signed"),
+            TypeSign::Unsigned => Some("unsigned"),
+        };
+        if let Some(sign) = sign {
+            if any {
+                p.write(" ");
+            }
+            p.write(sign);
+            any = true;
+        }
+        if !self.dims.is_empty() {
+            if any {
+                p.write(" ");
+            }
+            p.dims(&self.dims);
+        }
+    }
+}
+
+/// Print the type kind and return whether anything was printed.
+fn type_kind(p: &mut Printer, kind: &TypeKind) -> bool {
+    match kind.data {
+        ImplicitType => return false,
+        VoidType => p.write("void"),
+        NamedType(name) => p.name(name.value),
+        StringType => p.write("string"),
+        ChandleType => p.write("chandle"),
+        VirtIntfType(name) => {
+            p.write("virtual ");
+            p.name(name);
+        }
+        EventType => p.write("event"),
+        MailboxType => p.write("mailbox"),
+        ImplicitSignedType => p.write("signed"),
+        ImplicitUnsignedType => p.write("unsigned"),
+        UnitType => p.write("$unit"),
+        ScopedType {
+            ref ty,
+            member,
+            name,
+        } => {
+            ty.pretty(p);
+            p.write(if member { "." } else { "::" });
+            p.name(name.value);
+        }
+        ForwardType { .. } => p.verbatim(kind.span),
+        BitType => p.write("bit"),
+        LogicType => p.write("logic"),
+        RegType => p.write("reg"),
+        ByteType => p.write("byte"),
+        ShortIntType => p.write("shortint"),
+        IntType => p.write("int"),
+        IntegerType => p.write("integer"),
+        LongIntType => p.write("longint"),
+        TimeType => p.write("time"),
+        ShortRealType => p.write("shortreal"),
+        RealType => p.write("real"),
+        RealtimeType => p.write("realtime"),
+        EnumType(ref enm) => {
+            p.write("enum ");
+            if let Some(ref base) = enm.base_type {
+                base.pretty(p);
+                p.write(" ");
+            }
+            p.write("{ ");
+            p.list(&enm.variants, ", ", |p, variant| {
+                p.name(variant.name.value);
+                if let Some(ref range) = variant.range {
+                    range.pretty(p);
+                }
+                if let Some(ref value) = variant.value {
+                    p.write(" = ");
+                    value.pretty(p);
+                }
+            });
+            p.write(" }");
+        }
+        StructType(ref strukt) => {
+            p.write(&strukt.kind.to_string());
+            if strukt.packed {
+                p.write(" packed");
+            }
+            match strukt.signing {
+                TypeSign::None => (),
+                TypeSign::Signed => p.write(" signed"),
+                TypeSign::Unsigned => p.write(" unsigned"),
+            }
+            p.write(" { ");
+            for member in &strukt.members {
+                match member.rand_qualifier {
+                    Some(RandomQualifier::Rand) => p.write("rand "),
+                    Some(RandomQualifier::Randc) => p.write("randc "),
+                    None => (),
+                }
+                member.ty.pretty(p);
+                p.write(" ");
+                p.var_names(&member.names);
+                p.write("; ");
+            }
+            p.write("}");
+        }
+        SpecializedType(ref ty, ref params) => {
+            ty.pretty(p);
+            p.write(" ");
+            p.param_assignments(params);
+        }
+        TypeRef(ref arg) => {
+            p.write("type(");
+            arg.pretty(p);
+            p.write(")");
+        }
+    }
+    true
+}
+
+impl Pretty for TypeDim<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        p.write("[");
+        match self {
+            TypeDim::Expr(expr) => expr.pretty(p),
+            TypeDim::Range(lhs, rhs) => {
+                lhs.pretty(p);
+                p.write(":");
+                rhs.pretty(p);
+            }
+            TypeDim::Queue(None) => p.write("$"),
+            TypeDim::Queue(Some(expr)) => {
+                p.write("$:");
+                expr.pretty(p);
+            }
+            TypeDim::Unsized => (),
+            TypeDim::Associative(None) => p.write("*"),
+            TypeDim::Associative(Some(ty)) => ty.pretty(p),
+        }
+        p.write("]");
+    }
+}
+
+impl Pretty for TypeOrExpr<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self {
+            TypeOrExpr::Type(ty) => ty.pretty(p),
+            TypeOrExpr::Expr(expr) => expr.pretty(p),
+        }
+    }
+}
+
+impl Pretty for CallArg<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self.name {
+            Some(name) => {
+                p.write(".");
+                p.name(name);
+                p.write("(");
+                if let Some(ref expr) = self.expr {
+                    expr.pretty(p);
+                }
+                p.write(")");
+            }
+            None => {
+                if let Some(ref expr) = self.expr {
+                    expr.pretty(p);
+                }
+            }
+        }
+    }
+}
+
+impl Pretty for Expr<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self.data {
+            DummyExpr => (),
+            LiteralExpr(lit) => lit.pretty(p),
+            IdentExpr(name) => p.name(name.value),
+            SysIdentExpr(name) => {
+                p.write("$");
+                p.name(name.value);
+            }
+            ThisExpr => p.write("this"),
+            SuperExpr => p.write("super"),
+            LocalExpr => p.write("local"),
+            DollarExpr => p.write("$"),
+            NullExpr => p.write("null"),
+            ScopeExpr(ref expr, name) => {
+                p.operand(expr, expr_precedence(expr) < Precedence::Scope);
+                p.write("::");
+                p.name(name.value);
+            }
+            IndexExpr {
+                ref indexee,
+                ref index,
+            } => {
+                p.operand(indexee, expr_precedence(indexee) < Precedence::Postfix);
+                p.write("[");
+                index.pretty(p);
+                p.write("]");
+            }
+            UnaryExpr {
+                op,
+                ref expr,
+                postfix: false,
+            } => {
+                p.write(op.as_str());
+                let parens = match expr.data {
+                    UnaryExpr { postfix: false, .. } => true,
+                    _ => expr_precedence(expr) < Precedence::Unary,
+                };
+                p.operand(expr, parens);
+            }
+            UnaryExpr {
+                op,
+                ref expr,
+                postfix: true,
+            } => {
+                p.operand(expr, expr_precedence(expr) < Precedence::Postfix);
+                p.write(op.as_str());
+            }
+            BinaryExpr {
+                op,
+                ref lhs,
+                ref rhs,
+            } => {
+                // Implications are right-associative, everything else is
+                // left-associative.
+                let prec = op.get_precedence();
+                let right = prec == Precedence::Implication;
+                let lhs_prec = expr_precedence(lhs);
+                let rhs_prec = expr_precedence(rhs);
+                p.operand(lhs, lhs_prec < prec || (right && lhs_prec == prec));
+                p.write(" ");
+                p.write(op.as_str());
+                p.write(" ");
+                p.operand(rhs, rhs_prec < prec || (!right && rhs_prec == prec));
+            }
+            TernaryExpr {
+                ref cond,
+                ref true_expr,
+                ref false_expr,
+            } => {
+                p.operand(cond, expr_precedence(cond) <= Precedence::Ternary);
+                p.write(" ? ");
+                p.operand(true_expr, expr_precedence(true_expr) < Precedence::Ternary);
+                p.write(" : ");
+                p.operand(
+                    false_expr,
+                    expr_precedence(false_expr) < Precedence::Ternary,
+                );
+            }
+            AssignExpr {
+                op,
+                ref lhs,
+                ref rhs,
+            } => {
+                p.operand(lhs, expr_precedence(lhs) <= Precedence::Assignment);
+                p.write(" ");
+                p.write(op.as_str());
+                p.write(" ");
+                p.operand(rhs, expr_precedence(rhs) < Precedence::Assignment);
+            }
+            CallExpr(ref callee, ref args) => {
+                p.operand(callee, expr_precedence(callee) < Precedence::Postfix);
+                p.write("(");
+                p.call_args(args);
+                p.write(")");
+            }
+            TypeExpr(ref ty) => ty.pretty(p),
+            ConstructorCallExpr(ref args) => {
+                p.write("new(");
+                p.call_args(args);
+                p.write(")");
+            }
+            ClassNewExpr(ref expr) => {
+                p.write("new");
+                if let Some(expr) = expr {
+                    p.write(" ");
+                    expr.pretty(p);
+                }
+            }
+            ArrayNewExpr(ref dim, ref init) => {
+                p.write("new[");
+                dim.pretty(p);
+                p.write("]");
+                if let Some(init) = init {
+                    p.write("(");
+                    init.pretty(p);
+                    p.write(")");
+                }
+            }
+            EmptyQueueExpr => p.write("{}"),
+            StreamConcatExpr {
+                dir,
+                ref slice,
+                ref exprs,
+            } => {
+                p.write(match dir {
+                    StreamDir::In => "{>> ",
+                    StreamDir::Out => "{<< ",
+                });
+                match slice {
+                    Some(StreamConcatSlice::Expr(expr)) => {
+                        expr.pretty(p);
+                        p.write(" ");
+                    }
+                    Some(StreamConcatSlice::Type(ty)) => {
+                        ty.pretty(p);
+                        p.write(" ");
+                    }
+                    None => (),
+                }
+                p.write("{");
+                p.list(exprs, ", ", |p, stream| {
+                    stream.expr.pretty(p);
+                    if let Some(ref range) = stream.range {
+                        p.write(" with [");
+                        range.pretty(p);
+                        p.write("]");
+                    }
+                });
+                p.write("}}");
+            }
+            ConcatExpr {
+                ref repeat,
+                ref exprs,
+            } => {
+                p.write("{");
+                if let Some(repeat) = repeat {
+                    p.operand(repeat, !is_primary(repeat));
+                    p.write("{");
+                }
+                p.list(exprs, ", ", |p, expr| expr.pretty(p));
+                if repeat.is_some() {
+                    p.write("}");
+                }
+                p.write("}");
+            }
+            MinTypMaxExpr {
+                ref min,
+                ref typ,
+                ref max,
+            } => {
+                min.pretty(p);
+                p.write(":");
+                typ.pretty(p);
+                p.write(":");
+                max.pretty(p);
+            }
+            RangeExpr {
+                mode,
+                ref lhs,
+                ref rhs,
+            } => {
+                lhs.pretty(p);
+                p.write(match mode {
+                    RangeMode::Absolute => ":",
+                    RangeMode::RelativeUp => "+:",
+                    RangeMode::RelativeDown => "-:",
+                });
+                rhs.pretty(p);
+            }
+            MemberExpr { ref expr, name } => {
+                p.operand(expr, expr_precedence(expr) < Precedence::Postfix);
+                p.write(".");
+                p.name(name.value);
+            }
+            PatternExpr(ref fields) => {
+                p.write("'{");
+                p.list(fields, ", ", |p, field| match field.data {
+                    PatternFieldData::Default(ref expr) => {
+                        p.write("default: ");
+                        expr.pretty(p);
+                    }
+                    PatternFieldData::Member(ref key, ref value) => {
+                        key.pretty(p);
+                        p.write(": ");
+                        value.pretty(p);
+                    }
+                    PatternFieldData::Type(ref ty, ref value) => {
+                        ty.pretty(p);
+                        p.write(": ");
+                        value.pretty(p);
+                    }
+                    PatternFieldData::Expr(ref expr) => expr.pretty(p),
+                    PatternFieldData::Repeat(ref count, ref exprs) => {
+                        p.operand(count, !is_primary(count));
+                        p.write("{");
+                        p.list(exprs, ", ", |p, expr| expr.pretty(p));
+                        p.write("}");
+                    }
+                });
+                p.write("}");
+            }
+            InsideExpr(ref expr, ref ranges) => {
+                p.operand(expr, expr_precedence(expr) < Precedence::Relational);
+                p.write(" inside {");
+                p.list(ranges, ", ", |p, range| range.pretty(p));
+                p.write("}");
+            }
+            DistExpr(ref expr, ref items) => {
+                p.operand(expr, expr_precedence(expr) < Precedence::Relational);
+                p.write(" dist {");
+                p.list(items, ", ", |p, item| {
+                    item.range.pretty(p);
+                    if let Some(ref weight) = item.weight {
+                        p.write(match item.kind {
+                            DistWeight::PerValue => " := ",
+                            DistWeight::PerRange => " :/ ",
+                        });
+                        weight.pretty(p);
+                    }
+                });
+                p.write("}");
+            }
+            CastExpr(ref ty, ref expr) => {
+                ty.pretty(p);
+                p.write("'(");
+                expr.pretty(p);
+                p.write(")");
+            }
+            CastSizeExpr(ref size, ref expr) => {
+                p.operand(size, !is_primary(size));
+                p.write("'(");
+                expr.pretty(p);
+                p.write(")");
+            }
+            CastSignExpr(sign, ref expr) => {
+                p.write(match sign.value {
+                    TypeSign::Signed => "signed'(",
+                    TypeSign::Unsigned => "unsigned'(",
+                    TypeSign::None => "'(",
+                });
+                expr.pretty(p);
+                p.write(")");
+            }
+            BitsExpr { name, ref arg } => {
+                p.write("$");
+                p.name(name.value);
+                p.write("(");
+                arg.pretty(p);
+                p.write(")");
+            }
+        }
+    }
+}
+
+impl Pretty for ValueRange<'_> {
+    fn pretty(&self, p: &mut Printer) {
+        match self {
+            ValueRange::Single(expr) => expr.pretty(p),
+            ValueRange::Range { lo, hi, .. } => {
+                p.write("[");
+                lo.pretty(p);
+                p.write(":");
+                hi.pretty(p);
+                p.write("]");
+            }
+        }
+    }
+}
+
+impl Pretty for Lit {
+    fn pretty(&self, p: &mut Printer) {
+        match *self {
+            Lit::Str(value) => {
+                let mut s = String::from("\"");
+                for c in value.as_str().chars() {
+                    match c {
+                        '\\' => s.push_str("\\\\"),
+                        '"' => s.push_str("\\\""),
+                        '\n' => s.push_str("\\n"),
+                        '\t' => s.push_str("\\t"),
+                        '\x0b' => s.push_str("\\v"),
+                        '\x0c' => s.push_str("\\f"),
+                        '\x07' => s.push_str("\\a"),
+                        c if c.is_control() => s.push_str(&format!("\\{:03o}", c as u32)),
+                        c => s.push(c),
+                    }
+                }
+                s.push('"');
+                p.write(&s);
+            }
+            Lit::BasedInteger(size, signed, base, value) => {
+                if let Some(size) = size {
+                    p.name(size);
+                }
+                p.write(if signed { "'s" } else { "'" });
+                p.write(&base.to_string());
+                p.name(value);
+            }
+            Lit::UnbasedUnsized(c) => p.write(&format!("'{}", c)),
+            Lit::Number(int, frac) => {
+                p.name(int);
+                if let Some(frac) = frac {
+                    p.write(".");
+                    p.name(frac);
+                }
+            }
+            Lit::Time(int, frac, unit) => {
+                p.name(int);
+                if let Some(frac) = frac {
+                    p.write(".");
+                    p.name(frac);
+                }
+                p.write(time_unit_str(unit));
+            }
+        }
+    }
+}
+
+/// Determine the binding strength of an expression's outermost operator.
+fn expr_precedence(expr: &Expr) -> Precedence {
+    match expr.data {
+        BinaryExpr { op, .. } => op.get_precedence(),
+        UnaryExpr { postfix: false, .. } => Precedence::Unary,
+        UnaryExpr { postfix: true, .. } => Precedence::Postfix,
+        TernaryExpr { .. } => Precedence::Ternary,
+        AssignExpr { .. } => Precedence::Assignment,
+        InsideExpr(..) | DistExpr(..) => Precedence::Relational,
+        MinTypMaxExpr { .. } => Precedence::MinTypMax,
+        RangeExpr { .. } => Precedence::Min,
+        _ => Precedence::Max,
+    }
+}
+
+/// Check whether an expression is a literal or identifier.
+fn is_primary(expr: &Expr) -> bool {
+    match expr.data {
+        LiteralExpr(..) | IdentExpr(..) | SysIdentExpr(..) => true,
+        _ => false,
+    }
+}
+
+/// Check whether a type would print as nothing.
+fn is_empty_type(ty: &Type) -> bool {
+    let implicit = match ty.kind.data {
+        ImplicitType => true,
+        _ => false,
+    };
+    implicit && ty.sign == TypeSign::None && ty.dims.is_empty()
+}
+
+fn time_unit_str(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Second => "s",
+        TimeUnit::MilliSecond => "ms",
+        TimeUnit::MicroSecond => "us",
+        TimeUnit::NanoSecond => "ns",
+        TimeUnit::PicoSecond => "ps",
+        TimeUnit::FemtoSecond => "fs",
+    }
+}
+
+fn unique_priority_str(up: UniquePriority) -> &'static str {
+    match up {
+        UniquePriority::Unique => "unique ",
+        UniquePriority::Unique0 => "unique0 ",
+        UniquePriority::Priority => "priority ",
+    }
+}
+
+fn subroutine_port_dir_str(dir: SubroutinePortDir) -> &'static str {
+    match dir {
+        SubroutinePortDir::Input => "input",
+        SubroutinePortDir::Output => "output",
+        SubroutinePortDir::Inout => "inout",
+        SubroutinePortDir::Ref => "ref",
+        SubroutinePortDir::ConstRef => "const ref",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lexer::Lexer,
+        parser::{self, tests::parse_tree},
+        preproc::Preprocessor,
+    };
+    use moore_common::source::get_source_manager;
+
+    fn parse_and_print(name: &str, input: &str) -> String {
+        let source = get_source_manager().add(name, input);
+        let lexer = Lexer::new(Preprocessor::new(source, &[], &[]));
+        let arena = Arena::default();
+        let ast = parser::parse(lexer, &arena).expect("parse failed");
+        pretty(&ast)
+    }
+
+    fn check_roundtrip(name: &str, input: &str) {
+        let printed = parse_and_print(&format!("{}_first.sv", name), input);
+        let expected = parse_tree(&format!("{}_expected.sv", name), input);
+        let actual = parse_tree(&format!("{}_second.sv", name), &printed);
+        assert_eq!(actual, expected, "printed source:\n{}", printed);
+    }
+
+    #[test]
+    fn module() {
+        check_roundtrip(
+            "module",
+            "
+            module foo #(parameter int N = 4) (input logic [N-1:0] a, output logic y);
+                localparam K = N * 2 + 1;
+                typedef enum logic [1:0] { A, B = 2 } state_t;
+                state_t s;
+                wire [3:0] w;
+                assign y = &a ? (a[0] ^ a[1]) : a[N-1-:2] == 2'b01;
+                bar #(.W(N)) i_bar (.x(a), .y());
+            endmodule
+            ",
+        );
+    }
+
+    #[test]
+    fn statements() {
+        check_roundtrip(
+            "statements",
+            "
+            module foo;
+                int x, y;
+                always_ff @(posedge clk or negedge rst) begin
+                    if (x) if (y) x <= 1; else x <= 0;
+                    case (x)
+                        0, 1: y = -(-x);
+                        default: y = {2{x}};
+                    endcase
+                    for (int i = 0; i < 4; i++) x += i;
+                    repeat (3) #1 y = x;
+                    ##2 x = 1;
+                    ##(x + 1);
+                    $display(\"a\\tb\\n\\001\\\"c\\\\\");
+                end
+                function automatic int add(input int a, input int b);
+                    return a + (b - 1);
+                endfunction
+            endmodule
+            ",
+        );
+    }
+
+    #[test]
+    fn indentation() {
+        let source = get_source_manager().add(
+            "indentation.sv",
+            "module foo; initial begin x = 1; end endmodule",
+        );
+        let lexer = Lexer::new(Preprocessor::new(source, &[], &[]));
+        let arena = Arena::default();
+        let ast = parser::parse(lexer, &arena).unwrap();
+        let mut p = Printer::new().with_indent("\t");
+        p.print(&ast);
+        assert_eq!(
+            p.finish(),
+            "module foo;\n\tinitial begin\n\t\tx = 1;\n\tend\nendmodule\n"
+        );
+    }
+}
@@ -37,6 +37,8 @@ pub enum Token {
 
     /// A literal
     Literal(Lit),
+    /// The `1step` delay of clocking skews
+    OneStep,
     /// A system task or function identifier, e.g. "$display"
     SysIdent(Name),
     /// A compiler directive, e.g. "`timescale"
@@ -87,6 +89,7 @@ impl Token {
             Keyword(kw) => kw.as_str(),
 
             Literal(_) => "literal",
+            OneStep => "1step",
             SysIdent(_) => "system identifier",
             CompDir(_) => "compiler directive",
             Ident(_) => "identifier",
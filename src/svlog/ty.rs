@@ -1268,6 +1268,25 @@ impl<'a> UnpackedType<'a> {
         unsafe { std::mem::transmute(ty) }
     }
 
+    /// Create a `real` type.
+    pub fn make_real() -> &'a Self {
+        static TYPE: Lazy<UnpackedType> =
+            Lazy::new(|| UnpackedType::new(UnpackedCore::Real(RealType::Real)));
+        let ty: &UnpackedType = &TYPE;
+        // SAFETY: This is safe since the cell which causes 'a to need to
+        // outlive 'static is actually never mutated after AST construction.
+        unsafe { std::mem::transmute(ty) }
+    }
+
+    /// Create a `string` type.
+    pub fn make_string() -> &'a Self {
+        static TYPE: Lazy<UnpackedType> = Lazy::new(|| UnpackedType::new(UnpackedCore::String));
+        let ty: &UnpackedType = &TYPE;
+        // SAFETY: This is safe since the cell which causes 'a to need to
+        // outlive 'static is actually never mutated after AST construction.
+        unsafe { std::mem::transmute(ty) }
+    }
+
     /// Internalize this type in a context and resolve it.
     pub fn intern(mut self, cx: &impl TypeContext<'a>) -> &'a Self {
         let inner = match self.core {
@@ -1451,6 +1470,19 @@ impl<'a> UnpackedType<'a> {
         self.dims.is_empty() && self.resolve_full().core == UnpackedCore::String
     }
 
+    /// Check if this type is an `event`.
+    pub fn is_event(&self) -> bool {
+        self.dims.is_empty() && self.resolve_full().core == UnpackedCore::Event
+    }
+
+    /// Check if this type is `void`.
+    pub fn is_void(&self) -> bool {
+        match self.resolve_full().get_packed().map(|ty| ty.resolve_full()) {
+            Some(ty) => ty.dims.is_empty() && ty.core == PackedCore::Void,
+            None => false,
+        }
+    }
+
     /// Check if this type will coalesce to a scalar type in LLHD, like `i42`.
     pub fn coalesces_to_llhd_scalar(&self) -> bool {
         self.get_packed()
@@ -1886,15 +1918,27 @@ impl<'a> StructType<'a> {
 
     /// Compute the size of this struct in bits.
     ///
-    /// Returns `None` if any member of the type has a `[]` dimension.
+    /// The members of a union overlay each other, such that its size is the
+    /// size of its largest member. Returns `None` if any member of the type has
+    /// a `[]` dimension.
     pub fn get_bit_size(&self) -> Option<usize> {
         let mut size = 0;
         for m in &self.members {
-            size += m.ty.get_bit_size()?;
+            let member_size = m.ty.get_bit_size()?;
+            if self.is_union() {
+                size = std::cmp::max(size, member_size);
+            } else {
+                size += member_size;
+            }
         }
         Some(size)
     }
 
+    /// Check whether this is a `union` or `union tagged`.
+    pub fn is_union(&self) -> bool {
+        self.kind != ast::StructKind::Struct
+    }
+
     /// Helper function to format this struct.
     fn format(
         &self,
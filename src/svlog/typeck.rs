@@ -30,10 +30,12 @@
 
 use crate::crate_prelude::*;
 use crate::{
+    ast_map::AstNode,
     common::arenas::Alloc,
     hir::HirNode,
+    inst_details::is_inst_elaborated,
     port_list,
-    resolver::{DefNode, InstTarget},
+    resolver::{DefNode, HierarchicalKind, InstTarget},
     syntax::ast::BasicNode,
     ty::{
         Domain, IntAtomType, IntVecType, PackedCore, PackedType, RealType, SbvType, Sign,
@@ -72,7 +74,7 @@ pub(crate) fn type_of<'a>(
         HirNode::IntPort(p) => Ok(cx.type_of_int_port(Ref(p), env)),
         HirNode::ExtPort(p) => Ok(cx.type_of_ext_port(Ref(p), env)),
         HirNode::Expr(_) => Ok(cx.cast_type(node_id, env).unwrap().ty),
-        HirNode::GenvarDecl(_) => {
+        HirNode::GenvarDecl(_) | HirNode::ForeachIndex(_) => {
             Ok(SbvType::nice(ty::Domain::TwoValued, ty::Sign::Signed, 32).to_unpacked(cx))
         }
         HirNode::EnumVariant(v) => {
@@ -229,9 +231,131 @@ pub(crate) fn type_of_net_decl<'a>(
         .as_all()
         .get_net_decl()
         .expect("parent not a NetDecl");
+    if ast_decl.net_type == ast::NetType::Interconnect {
+        return type_of_interconnect(cx, ast_decl, ast, env);
+    }
     type_of_varlike(cx, ast_decl, &ast_decl.ty, ast, &ast.dims, env)
 }
 
+/// Determine the type of an `interconnect` net.
+///
+/// Interconnect nets are untyped; their type is inferred from the ports of the
+/// instances they are connected to. All connected ports must agree on the type.
+fn type_of_interconnect<'a>(
+    cx: &impl Context<'a>,
+    ast_decl: &'a ast::NetDecl<'a>,
+    ast_name: &'a ast::VarDeclName<'a>,
+    env: ParamEnv,
+) -> &'a UnpackedType<'a> {
+    if !ast_decl.ty.is_implicit() {
+        cx.emit(
+            DiagBuilder2::error(format!(
+                "interconnect `{}` cannot have a data type",
+                ast_name.name
+            ))
+            .span(ast_decl.ty.span())
+            .add_note("The type of an interconnect is inferred from its connections"),
+        );
+        return UnpackedType::make_error();
+    }
+
+    // Find the module or generate block that declares the net. The parent
+    // chain of the declaration runs through the preceding declarations.
+    let net_id = ast_name.id();
+    let mut parent_id = cx.parent_node_id(net_id);
+    let blocks: Vec<&hir::ModuleBlock> = loop {
+        let id = match parent_id {
+            Some(id) => id,
+            None => break vec![],
+        };
+        match cx.hir_of(id) {
+            Ok(HirNode::Module(m)) => break vec![&m.block],
+            Ok(HirNode::Gen(gen)) => {
+                break match gen.kind {
+                    hir::GenKind::If {
+                        ref main_body,
+                        ref else_body,
+                        ..
+                    } => std::iter::once(main_body).chain(else_body.iter()).collect(),
+                    hir::GenKind::For { ref body, .. } => vec![body],
+                    hir::GenKind::Case {
+                        ref ways,
+                        ref default,
+                        ..
+                    } => ways
+                        .iter()
+                        .map(|(_, body)| body)
+                        .chain(default.iter())
+                        .collect(),
+                }
+            }
+            _ => parent_id = cx.parent_node_id(id),
+        }
+    };
+
+    // Collect the types of all instance ports the net is connected to.
+    let mut found: Option<(&'a UnpackedType<'a>, Span)> = None;
+    for block in blocks {
+        for &inst_id in &block.insts {
+            let inst = match cx.hir_of(inst_id) {
+                Ok(HirNode::Inst(x)) => x,
+                _ => continue,
+            };
+            if !is_inst_elaborated(cx, inst, env) {
+                continue;
+            }
+            let details = match cx.inst_details(Ref(inst), env) {
+                Ok(x) => x,
+                Err(()) => return UnpackedType::make_error(),
+            };
+            for &(Ref(port), assigned) in &details.ports.0 {
+                match cx.hir_of(assigned.id()) {
+                    Ok(HirNode::Expr(hir::Expr {
+                        kind: hir::ExprKind::Ident(..),
+                        ..
+                    })) => (),
+                    _ => continue,
+                }
+                if cx.resolve_node(assigned.id(), assigned.env()) != Ok(net_id) {
+                    continue;
+                }
+                let ty = cx.type_of_ext_port(Ref(port), details.inner_env);
+                if ty.is_error() {
+                    return ty;
+                }
+                let span = cx.span(assigned.id());
+                match found {
+                    None => found = Some((ty, span)),
+                    Some((prev_ty, prev_span)) if prev_ty != ty => {
+                        cx.emit(
+                            DiagBuilder2::error(format!(
+                                "interconnect `{}` connects ports of conflicting types `{}` and `{}`",
+                                ast_name.name, prev_ty, ty
+                            ))
+                            .span(span)
+                            .add_note(format!("Previous `{}` connection here:", prev_ty))
+                            .span(prev_span),
+                        );
+                        return UnpackedType::make_error();
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+    }
+
+    // Unconnected interconnects default to a single bit.
+    match found {
+        Some((ty, _)) => ty,
+        None => cx.unpacked_type_from_ast(
+            Ref(&ast_decl.ty),
+            Ref(&ast_name.dims),
+            env,
+            Some(ty::PackedCore::IntVec(ty::IntVecType::Logic)),
+        ),
+    }
+}
+
 /// Determine the type of a struct member.
 #[moore_derive::query]
 pub(crate) fn type_of_struct_member<'a>(
@@ -345,6 +469,34 @@ pub(crate) fn type_of_value_param<'a>(
     UnpackedType::make_error()
 }
 
+/// Determine the type of a hierarchical name.
+fn type_of_hierarchical_name<'a>(
+    cx: &impl Context<'a>,
+    expr_id: NodeId,
+    env: ParamEnv,
+) -> &'a UnpackedType<'a> {
+    let target = match cx.resolve_hierarchical_name(expr_id, env) {
+        Ok(Some(x)) => x,
+        _ => return UnpackedType::make_error(),
+    };
+    let what = match target.kind {
+        HierarchicalKind::Value => {
+            return cx
+                .type_of(target.node, target.env)
+                .unwrap_or(UnpackedType::make_error())
+        }
+        HierarchicalKind::Scope(scope) => format!("{}", scope),
+        HierarchicalKind::Loop(..) => "a generate loop".to_string(),
+    };
+    let span = cx.span(expr_id);
+    cx.emit(
+        DiagBuilder2::error(format!("`{}` cannot be used as a value", span.extract()))
+            .span(span)
+            .add_note(format!("`{}` refers to {}", span.extract(), what)),
+    );
+    UnpackedType::make_error()
+}
+
 /// Determine the type of an instance.
 #[moore_derive::query]
 pub(crate) fn type_of_inst<'a>(
@@ -592,6 +744,12 @@ pub(crate) fn packed_type_from_ast<'a>(
                         cx.unpacked_type_from_ast(Ref(&member.ty), Ref(&name.dims), env, None)
                     };
 
+                    if let Some(qualifier) = member.rand_qualifier {
+                        crate::hir::lowering::check_random_qualifier(
+                            cx, qualifier, &member.ty, name,
+                        );
+                    }
+
                     def.members.push(ty::StructMember {
                         name: Spanned::new(name.name, name.span),
                         ty,
@@ -647,13 +805,17 @@ pub(crate) fn packed_type_from_ast<'a>(
                 None => SbvType::nice(ty::Domain::TwoValued, ty::Sign::Signed, 32).to_packed(cx),
             };
             let base_explicit = enm.base_type.is_some();
+            let variants = match cx.enum_variants(Ref(enm)) {
+                Ok(x) => x.iter().map(|&(name, _, ast)| (name, ast)).collect(),
+                Err(()) => return UnpackedType::make_error(),
+            };
 
             // Assemble the enum type.
             let def = ty::EnumType {
                 ast: enm,
                 base,
                 base_explicit,
-                variants: enm.variants.iter().map(|v| (v.name, v)).collect(),
+                variants,
             };
 
             // Package up.
@@ -717,6 +879,13 @@ pub(crate) fn packed_type_from_ast<'a>(
                 };
                 packed_type_from_def(cx, def, ast.span(), env)
             }
+            ast::UnitType => {
+                let def = match cx.resolve_unit_or_error(name, ast) {
+                    Ok(def) => def,
+                    _ => return UnpackedType::make_error(),
+                };
+                packed_type_from_def(cx, def, ast.span(), env)
+            }
             _ => {
                 cx.emit(
                     DiagBuilder2::error(format!("`{}` is not a package", ty.span().extract()))
@@ -801,7 +970,7 @@ pub(crate) fn packed_type_from_ast<'a>(
             }
         }
 
-        ast::VirtIntfType { .. } | ast::MailboxType | ast::SpecializedType(..) => {
+        ast::VirtIntfType { .. } | ast::MailboxType | ast::SpecializedType(..) | ast::UnitType => {
             bug_span!(ast.span(), cx, "type {:#1?} not implemented", ast.kind)
         }
     };
@@ -968,6 +1137,11 @@ fn apply_unpacked_dims<'a>(
                     }
                 }
             }
+            ast::TypeDim::Queue(Some(init_size))
+                if value::is_unbounded(cx, init_size.id(), env) =>
+            {
+                dims.push(ty::UnpackedDim::Queue(None))
+            }
             ast::TypeDim::Queue(Some(init_size)) => {
                 match size_from_bounds_expr(cx, init_size.id(), env, span) {
                     Ok(s) => dims.push(ty::UnpackedDim::Queue(Some(s))),
@@ -1014,10 +1188,14 @@ pub(crate) fn type_of_expr<'a>(
         // These expressions have a fully self-determined type.
         hir::ExprKind::IntConst { .. }
         | hir::ExprKind::TimeConst(..)
+        | hir::ExprKind::RealConst(..)
         | hir::ExprKind::StringConst(..)
+        | hir::ExprKind::Unbounded
         | hir::ExprKind::Ident(..)
         | hir::ExprKind::Scope(..)
+        | hir::ExprKind::UnitScope(..)
         | hir::ExprKind::Concat(..)
+        | hir::ExprKind::StreamConcat(..)
         | hir::ExprKind::Cast(..)
         | hir::ExprKind::CastSign(..)
         | hir::ExprKind::CastSize(..)
@@ -1026,10 +1204,23 @@ pub(crate) fn type_of_expr<'a>(
         | hir::ExprKind::Builtin(hir::BuiltinCall::Clog2(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::Bits(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::CountOnes(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::CountBits(..))
         | hir::ExprKind::Builtin(hir::BuiltinCall::OneHot(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::OneHot0(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::IsUnknown(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::IsUnbounded(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::ArrayDim(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::Sformatf(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::Display(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::Finish(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::Random(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::Urandom(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::UrandomRange(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::ReadMem { .. })
+        | hir::ExprKind::Builtin(hir::BuiltinCall::FileOpen(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::FileClose(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::DumpFile(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::DumpVars(_))
         | hir::ExprKind::Field(..)
         | hir::ExprKind::Index(..)
         | hir::ExprKind::Assign { .. } => cx.need_self_determined_type(expr.id, env),
@@ -1106,7 +1297,8 @@ pub(crate) fn type_of_expr<'a>(
         // Other things simply evaluate to their self-determined type.
         hir::ExprKind::Builtin(hir::BuiltinCall::Signed(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::Unsigned(_))
-        | hir::ExprKind::FunctionCall(..) => cx.need_self_determined_type(expr.id, env),
+        | hir::ExprKind::FunctionCall(..)
+        | hir::ExprKind::MethodCall(..) => cx.need_self_determined_type(expr.id, env),
 
         // Pattern expressions require a type context.
         hir::ExprKind::PositionalPattern(..)
@@ -1535,6 +1727,14 @@ fn self_determined_expr_type<'gcx>(
         // Time constants are of time type.
         hir::ExprKind::TimeConst(_) => Some(UnpackedType::make_time()),
 
+        // Real constants are of real type.
+        hir::ExprKind::RealConst(_) => Some(UnpackedType::make_real()),
+
+        // The unbounded value `$` behaves like an integer.
+        hir::ExprKind::Unbounded => {
+            Some(PackedType::make(cx, ty::IntAtomType::Int).to_unpacked(cx))
+        }
+
         // String literals behave like a packed array containing the characters.
         hir::ExprKind::StringConst(string) => Some(
             ty::PackedType::make_dims(
@@ -1551,17 +1751,18 @@ fn self_determined_expr_type<'gcx>(
 
         // Identifiers and scoped identifiers inherit their type from the bound
         // node.
-        hir::ExprKind::Ident(_) | hir::ExprKind::Scope(..) => Some(
+        hir::ExprKind::Ident(_) | hir::ExprKind::Scope(..) | hir::ExprKind::UnitScope(..) => Some(
             cx.resolve_node(expr.id, env)
                 .and_then(|x| cx.type_of(x, env))
                 .unwrap_or(UnpackedType::make_error()),
         ),
 
         // Concatenation yields an unsigned logic vector whose bit width is the
-        // sum of the simple bit vector types of each argument.
+        // sum of the simple bit vector types of each argument. The same holds
+        // for streaming concatenations, which merely reorder the bits.
         //
         // See §11.8.1 "Rules for expression types".
-        hir::ExprKind::Concat(repeat, ref exprs) => {
+        hir::ExprKind::Concat(_, ref exprs) | hir::ExprKind::StreamConcat(_, _, ref exprs) => {
             let mut failed = false;
 
             // Determine the cumulative width of all fields.
@@ -1597,15 +1798,17 @@ fn self_determined_expr_type<'gcx>(
             }
 
             // Determine the repetition factor.
-            let repeat = match repeat {
-                Some(repeat) => match cx.constant_int_value_of(repeat, env) {
-                    Ok(r) => r.to_usize().unwrap(),
-                    Err(()) => {
-                        failed = true;
-                        0
+            let repeat = match expr.kind {
+                hir::ExprKind::Concat(Some(repeat), _) => {
+                    match constant_count(cx, repeat, env, "replication count") {
+                        Ok(r) => r,
+                        Err(()) => {
+                            failed = true;
+                            0
+                        }
                     }
-                },
-                None => 1,
+                }
+                _ => 1,
             };
 
             // Package up the result.
@@ -1637,8 +1840,8 @@ fn self_determined_expr_type<'gcx>(
         // Sign casts trivially evaluate to the size-converted inner type.
         hir::ExprKind::CastSize(size, arg) => {
             // Determine the actual size.
-            let size = match cx.constant_int_value_of(size, env) {
-                Ok(r) => r.to_usize().unwrap(),
+            let size = match constant_count(cx, size, env, "cast size") {
+                Ok(r) => r,
                 Err(_) => {
                     return Some(UnpackedType::make_error());
                 }
@@ -1679,17 +1882,50 @@ fn self_determined_expr_type<'gcx>(
         | hir::ExprKind::Builtin(hir::BuiltinCall::Clog2(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::Bits(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::CountOnes(_))
-        | hir::ExprKind::Builtin(hir::BuiltinCall::ArrayDim(..)) => {
+        | hir::ExprKind::Builtin(hir::BuiltinCall::CountBits(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::ArrayDim(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::Random(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::FileOpen(..)) => {
             Some(PackedType::make(cx, ty::IntAtomType::Int).to_unpacked(cx))
         }
 
+        // The unsigned random number functions evaluate to `int unsigned`.
+        hir::ExprKind::Builtin(hir::BuiltinCall::Urandom(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::UrandomRange(..)) => Some(
+            PackedType::make_sign(cx, ty::IntAtomType::Int, ty::Sign::Unsigned, true)
+                .to_unpacked(cx),
+        ),
+
         // These builtin functions evaluate to the bit type.
         hir::ExprKind::Builtin(hir::BuiltinCall::OneHot(_))
         | hir::ExprKind::Builtin(hir::BuiltinCall::OneHot0(_))
-        | hir::ExprKind::Builtin(hir::BuiltinCall::IsUnknown(_)) => {
+        | hir::ExprKind::Builtin(hir::BuiltinCall::IsUnknown(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::IsUnbounded(_)) => {
             Some(PackedType::make(cx, ty::IntVecType::Bit).to_unpacked(cx))
         }
 
+        // String formatting evaluates to a string.
+        hir::ExprKind::Builtin(hir::BuiltinCall::Sformatf(..)) => Some(UnpackedType::make_string()),
+
+        // The display, simulation control, file, and waveform dump tasks do not
+        // produce a value.
+        hir::ExprKind::Builtin(hir::BuiltinCall::Display(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::Finish(..))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::ReadMem { .. })
+        | hir::ExprKind::Builtin(hir::BuiltinCall::FileClose(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::DumpFile(_))
+        | hir::ExprKind::Builtin(hir::BuiltinCall::DumpVars(_)) => Some(UnpackedType::make_void()),
+
+        // Hierarchical names resolve to the type of the node they refer to.
+        hir::ExprKind::Field(..) | hir::ExprKind::Index(..)
+            if cx
+                .resolve_hierarchical_name(expr.id, env)
+                .map(|target| target.is_some())
+                .unwrap_or(true) =>
+        {
+            Some(type_of_hierarchical_name(cx, expr.id, env))
+        }
+
         // Member field accesses resolve to the type of the member.
         hir::ExprKind::Field(target, name) => {
             let target_ty = cx.self_determined_type(target, env)?;
@@ -1889,22 +2125,15 @@ fn self_determined_expr_type<'gcx>(
         }
 
         // Function calls resolve to the function's return type.
-        hir::ExprKind::FunctionCall(target, _) => Some(
-            cx.hir_of(target)
-                .and_then(|hir| {
-                    let hir = match hir {
-                        HirNode::Subroutine(s) => s,
-                        _ => unreachable!(),
-                    };
-                    match hir.retty {
-                        Some(retty_id) => Ok(cx.packed_type_from_ast(
-                            Ref(cx.ast_for_id(retty_id).as_all().get_type().unwrap()),
-                            env,
-                            None,
-                        )),
-                        None => Ok(UnpackedType::make_void()),
-                    }
-                })
+        hir::ExprKind::FunctionCall(target, _) => {
+            Some(subroutine_return_type(cx, target, env).unwrap_or(UnpackedType::make_error()))
+        }
+
+        // Method calls through an interface resolve to the return type of the
+        // interface's function.
+        hir::ExprKind::MethodCall(..) => Some(
+            cx.resolve_intf_method(expr.id, env)
+                .and_then(|(target, env)| subroutine_return_type(cx, target, env))
                 .unwrap_or(UnpackedType::make_error()),
         ),
 
@@ -1919,6 +2148,26 @@ fn self_determined_expr_type<'gcx>(
     }
 }
 
+/// Determine the return type of a task or function.
+fn subroutine_return_type<'gcx>(
+    cx: &impl Context<'gcx>,
+    target: NodeId,
+    env: ParamEnv,
+) -> Result<&'gcx UnpackedType<'gcx>> {
+    let hir = match cx.hir_of(target)? {
+        HirNode::Subroutine(s) => s,
+        _ => unreachable!(),
+    };
+    match hir.retty {
+        Some(retty_id) => Ok(cx.packed_type_from_ast(
+            Ref(cx.ast_for_id(retty_id).as_all().get_type().unwrap()),
+            env,
+            None,
+        )),
+        None => Ok(UnpackedType::make_void()),
+    }
+}
+
 fn self_determined_sign_cast_type<'gcx>(
     cx: &impl Context<'gcx>,
     sign: Sign,
@@ -1962,6 +2211,7 @@ pub(crate) fn operation_type<'a>(
     };
     let expr = match hir {
         HirNode::Expr(x) => x,
+        HirNode::Stmt(x) => return stmt_operation_type(cx, x, env),
         _ => return None,
     };
     match expr.kind {
@@ -2108,18 +2358,7 @@ pub(crate) fn operation_type<'a>(
         // The inside expression uses an operation type for its comparisons. It
         // is determined in the same way as for comparisons.
         hir::ExprKind::Inside(lhs, ref ranges) => {
-            let tlhs = cx.self_determined_type(lhs, env);
-            let tranges = ranges.iter().flat_map(|r| {
-                let (a, b) = match r.value {
-                    hir::InsideRange::Single(rhs) => (cx.self_determined_type(rhs, env), None),
-                    hir::InsideRange::Range(lo, hi) => (
-                        cx.self_determined_type(lo, env),
-                        cx.self_determined_type(hi, env),
-                    ),
-                };
-                a.into_iter().chain(b.into_iter())
-            });
-            unify_operator_types(cx, env, tlhs.into_iter().chain(tranges))
+            inside_operation_type(cx, lhs, ranges.iter(), env)
         }
 
         // Bit- and part-select expressions map their target to an internal type
@@ -2191,6 +2430,43 @@ fn unify_operator_types<'gcx>(
     width.map(|w| SbvType::nice(domain, sign, w).to_unpacked(cx))
 }
 
+/// Get the operation type of a statement.
+fn stmt_operation_type<'a>(
+    cx: &impl Context<'a>,
+    stmt: &'a hir::Stmt,
+    env: ParamEnv,
+) -> Option<&'a UnpackedType<'a>> {
+    match stmt.kind {
+        // The items of a `case ... inside` are compared against the expression
+        // in the same way as for the inside expression.
+        hir::StmtKind::CaseInside { expr, ref ways, .. } => {
+            inside_operation_type(cx, expr, ways.iter().flat_map(|(x, _)| x.iter()), env)
+        }
+        _ => None,
+    }
+}
+
+/// Determine the operation type of a set membership check.
+fn inside_operation_type<'a, 'r>(
+    cx: &impl Context<'a>,
+    lhs: NodeId,
+    ranges: impl Iterator<Item = &'r Spanned<hir::InsideRange>>,
+    env: ParamEnv,
+) -> Option<&'a UnpackedType<'a>> {
+    let tlhs = cx.self_determined_type(lhs, env);
+    let tranges = ranges.flat_map(|r| {
+        let (a, b) = match r.value {
+            hir::InsideRange::Single(rhs) => (cx.self_determined_type(rhs, env), None),
+            hir::InsideRange::Range(lo, hi) => (
+                cx.self_determined_type(lo, env),
+                cx.self_determined_type(hi, env),
+            ),
+        };
+        a.into_iter().chain(b.into_iter())
+    });
+    unify_operator_types(cx, env, tlhs.into_iter().chain(tranges))
+}
+
 /// Require a node to have an operation type.
 ///
 /// Emits an error if the node has no operation type.
@@ -2212,6 +2488,40 @@ pub(crate) fn need_operation_type<'a>(
     }
 }
 
+/// Check whether a node is the value assigned in a defparam.
+fn is_defparam_value<'a>(cx: &impl Context<'a>, node_id: NodeId) -> bool {
+    match cx.ast_of(node_id) {
+        Ok(AstNode::Expr(expr)) => match expr.get_parent() {
+            Some(parent) => parent.as_all().is_defparam(),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Evaluate a constant count, such as a replication count or the size of a
+/// cast, which must not be negative.
+fn constant_count<'a>(
+    cx: &impl Context<'a>,
+    node_id: NodeId,
+    env: ParamEnv,
+    what: &str,
+) -> Result<usize> {
+    let value = cx.constant_int_value_of(node_id, env)?;
+    match value.to_usize() {
+        Some(x) => Ok(x),
+        None => {
+            let span = cx.span(node_id);
+            cx.emit(
+                DiagBuilder2::error(format!("{} `{}` is negative", what, span.extract()))
+                    .span(span)
+                    .add_note(format!("`{}` evaluates to {}", span.extract(), value)),
+            );
+            Err(())
+        }
+    }
+}
+
 /// Get the type context of a node.
 #[moore_derive::query]
 pub(crate) fn type_context<'a>(
@@ -2254,7 +2564,8 @@ pub(crate) fn type_context<'a>(
                 None
             }
         }
-        HirNode::ValueParam(v) if v.default == Some(onto) => {
+        // Defparams assign their value to the parameter they override.
+        HirNode::ValueParam(v) if v.default == Some(onto) || is_defparam_value(cx, onto) => {
             let ty = cx.ast_for_id(v.ty).as_all().get_type().unwrap();
             if !ty.is_implicit() {
                 Some(
@@ -2451,6 +2762,14 @@ fn type_context_imposed_by_expr<'gcx>(
         // The ternary operator imposes a boolean context on its condition.
         hir::ExprKind::Ternary(cond, _, _) if onto == cond => Some(TypeContext::Bool),
 
+        // Casts to `void` discard the value of their argument, which is
+        // therefore self-determined.
+        hir::ExprKind::Cast(_, arg)
+            if onto == arg && cx.need_self_determined_type(expr.id, env).is_void() =>
+        {
+            None
+        }
+
         // Static casts are *not* assignment-like contexts. See §10.8
         // "Assignment-like contexts". We use a trick here to get the implicit
         // casting logic to do the cast for us: we determine the type of the
@@ -2465,9 +2784,12 @@ fn type_context_imposed_by_expr<'gcx>(
             Some(cx.need_self_determined_type(expr.id, env).into())
         }
 
+        // The slice size of a streaming concatenation is self-determined.
+        hir::ExprKind::StreamConcat(_, Some(slice), _) if slice == onto => None,
+
         // Concatenations require their arguments (including repetition counts)
         // to map to a corresponding SBVT.
-        hir::ExprKind::Concat(..) => {
+        hir::ExprKind::Concat(..) | hir::ExprKind::StreamConcat(..) => {
             let ty = cx.need_self_determined_type(onto, env);
             if ty.is_error() {
                 return Some(ty.into());
@@ -2598,6 +2920,9 @@ fn type_context_imposed_by_stmt<'gcx>(
         // If statements and do/while loops require a boolean condition.
         hir::StmtKind::If { cond, .. } if onto == cond => Some(TypeContext::Bool),
 
+        // Wait statements block until their condition is true.
+        hir::StmtKind::Wait { cond, .. } if onto == cond => Some(TypeContext::Bool),
+
         // Do/while loops require a boolean condition.
         hir::StmtKind::Loop { kind, .. } => {
             match kind {
@@ -2625,6 +2950,17 @@ fn type_context_imposed_by_stmt<'gcx>(
                 None
             }
         }
+        hir::StmtKind::CaseInside { expr, ref ways, .. } => {
+            let is_label = |r: &Spanned<hir::InsideRange>| match r.value {
+                hir::InsideRange::Single(x) => x == onto,
+                hir::InsideRange::Range(lo, hi) => lo == onto || hi == onto,
+            };
+            if expr == onto || ways.iter().flat_map(|(x, _)| x.iter()).any(is_label) {
+                Some(cx.need_operation_type(stmt.id, env).into())
+            } else {
+                None
+            }
+        }
 
         _ => None,
     }
@@ -2716,6 +3052,8 @@ pub(crate) fn expr_is_lvalue<'gcx>(cx: &impl Context<'gcx>, onto: NodeId, _env:
         },
         HirNode::Stmt(s) => match s.kind {
             hir::StmtKind::Assign { lhs, .. } => lhs == onto,
+            hir::StmtKind::Release { target, .. } => target == onto,
+            hir::StmtKind::Trigger(target) => target == onto,
             _ => false,
         },
         HirNode::Assign(a) => a.lhs == onto,
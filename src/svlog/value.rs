@@ -14,12 +14,13 @@
 use crate::{
     crate_prelude::*,
     hir::HirNode,
-    ty::{SbvType, UnpackedType},
+    ty::{Domain, SbvType, UnpackedType},
     ParamEnv, ParamEnvBinding,
 };
 use bit_vec::BitVec;
 use itertools::Itertools;
 use num::{BigInt, BigRational, Integer, One, ToPrimitive, Zero};
+use std::{collections::HashMap, sync::Arc};
 
 /// A verilog value.
 pub type Value<'t> = &'t ValueData<'t>;
@@ -280,15 +281,9 @@ fn constant_value_of_inner<'a>(cx: &impl Context<'a>, node_id: NodeId, env: Para
             );
             cx.intern_value(make_error(UnpackedType::make_error()))
         }
-        HirNode::EnumVariant(var) => match var.value {
-            Some(v) => cx.constant_value_of(v, env),
-            None => {
-                let ty = match cx.type_of(node_id, env) {
-                    Ok(x) => x,
-                    _ => return cx.intern_value(make_error(UnpackedType::make_error())),
-                };
-                cx.intern_value(make_int(ty, var.index.into()))
-            }
+        HirNode::EnumVariant(var) => match cx.enum_variant_values(var.enum_id, env) {
+            Ok(values) => values[var.index],
+            Err(()) => cx.intern_value(make_error(UnpackedType::make_error())),
         },
         _ => {
             cx.emit(
@@ -300,6 +295,108 @@ fn constant_value_of_inner<'a>(cx: &impl Context<'a>, node_id: NodeId, env: Para
     }
 }
 
+/// Determine the values of the variants of an enum type.
+///
+/// Variants without an explicit value are assigned the value of the preceding
+/// variant plus one, or zero if they come first. Emits diagnostics for values
+/// that do not fit into the enum's base type, and for variants whose value is
+/// already used by an earlier variant.
+#[moore_derive::query]
+pub(crate) fn enum_variant_values<'a>(
+    cx: &impl Context<'a>,
+    enum_id: NodeId,
+    env: ParamEnv,
+) -> Result<Arc<Vec<Value<'a>>>> {
+    let variants = match cx.hir_of(enum_id)? {
+        HirNode::Type(hir::Type {
+            kind: hir::TypeKind::Enum(ref variants, _),
+            ..
+        }) => variants,
+        x => bug_span!(cx.span(enum_id), cx, "values of non-enum {:?}", x),
+    };
+    let mut values: Vec<Value<'a>> = vec![];
+    let mut used: HashMap<BigInt, &hir::EnumVariant> = HashMap::new();
+    let mut failed = false;
+    for &(_, var_id) in variants {
+        let var = match cx.hir_of(var_id)? {
+            HirNode::EnumVariant(x) => x,
+            x => bug_span!(cx.span(var_id), cx, "enum variant is {:?}", x),
+        };
+        let ty = cx.type_of(var_id, env)?;
+
+        // Determine the value, auto-incrementing the preceding one if none is
+        // given explicitly.
+        let value = match (var.value, values.last().map(|v| &v.kind)) {
+            (Some(v), _) => cx.constant_value_of(v, env),
+            (None, None) => cx.intern_value(make_int(ty, BigInt::zero())),
+            (None, Some(ValueKind::Int(prev, special, _))) if !special.any() => {
+                cx.intern_value(make_int(ty, prev + 1))
+            }
+            (None, Some(_)) => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "value of {} cannot be incremented from `{}`",
+                        var.desc_full(),
+                        values.last().unwrap().kind
+                    ))
+                    .span(var.human_span())
+                    .add_note("Assign an explicit value to the variant."),
+                );
+                return Err(());
+            }
+        };
+        if value.is_error() {
+            return Err(());
+        }
+
+        // Check that the value fits into the base type and is unique.
+        if let ValueKind::Int(ref v, ref special, _) = value.kind {
+            if !special.any() {
+                if let Some(width) = ty.get_bit_size().filter(|&w| w > 0) {
+                    let (min, max) = if ty.sign().is_signed() {
+                        let half = BigInt::one() << (width - 1);
+                        (-half.clone(), half - 1)
+                    } else {
+                        (BigInt::zero(), (BigInt::one() << width) - 1)
+                    };
+                    if *v < min || *v > max {
+                        cx.emit(
+                            DiagBuilder2::error(format!(
+                                "value `{}` of {} does not fit into `{}`",
+                                v,
+                                var.desc_full(),
+                                ty
+                            ))
+                            .span(var.human_span()),
+                        );
+                        failed = true;
+                    }
+                }
+                if let Some(prev) = used.insert(v.clone(), var) {
+                    cx.emit(
+                        DiagBuilder2::error(format!(
+                            "{} has the same value `{}` as {}",
+                            var.desc_full(),
+                            v,
+                            prev.desc_full()
+                        ))
+                        .span(var.human_span())
+                        .add_note(format!("{} declared here:", prev.desc_full()))
+                        .span(prev.human_span()),
+                    );
+                    failed = true;
+                }
+            }
+        }
+        values.push(value);
+    }
+    if failed {
+        Err(())
+    } else {
+        Ok(Arc::new(values))
+    }
+}
+
 /// Determine the constant integer value of an MIR rvalue.
 ///
 /// Emits a diagnostic if the value is not an integer.
@@ -367,26 +464,59 @@ fn const_mir_rvalue_inner<'a>(cx: &impl Context<'a>, mir: &'a mir::Rvalue<'a>) -
     }
 
     match mir.kind {
-        // TODO: Casts are just transparent at the moment. That's pretty bad.
-        mir::RvalueKind::CastValueDomain { value, .. }
-        | mir::RvalueKind::CastSign(_, value)
-        | mir::RvalueKind::Truncate(_, value)
-        | mir::RvalueKind::ZeroExtend(_, value)
-        | mir::RvalueKind::SignExtend(_, value) => {
-            warn!(
-                "Cast ignored during constant evaluation: `{}` from `{}` to `{}`",
-                value.span.extract(),
-                value.ty,
-                mir.ty
-            );
+        // Domain casts leave the value untouched.
+        mir::RvalueKind::CastValueDomain { value, .. } => {
             let v = cx.const_mir_rvalue(value.into());
-            // TODO: This is an incredibly ugly hack.
             cx.intern_value(ValueData {
                 ty: mir.ty,
                 kind: v.kind.clone(),
             })
         }
 
+        // Sign casts, truncations, and extensions reinterpret the bits of the
+        // value at the new width and sign.
+        mir::RvalueKind::CastSign(_, value)
+        | mir::RvalueKind::Truncate(_, value)
+        | mir::RvalueKind::ZeroExtend(_, value)
+        | mir::RvalueKind::SignExtend(_, value) => {
+            let v = cx.const_mir_rvalue(value.into());
+            if v.is_error() {
+                return cx.intern_value(make_error(mir.ty));
+            }
+            match (
+                &v.kind,
+                value.ty.get_simple_bit_vector(),
+                mir.ty.get_simple_bit_vector(),
+            ) {
+                (ValueKind::Int(int, special, _), Some(from), Some(to)) if special.none() => {
+                    let int = match mir.kind {
+                        mir::RvalueKind::ZeroExtend(..) => {
+                            wrap_int(int, from.change_sign(ty::Sign::Unsigned))
+                        }
+                        mir::RvalueKind::SignExtend(..) => {
+                            wrap_int(int, from.change_sign(ty::Sign::Signed))
+                        }
+                        _ => int.clone(),
+                    };
+                    cx.intern_value(make_int(mir.ty, wrap_int(&int, to)))
+                }
+                _ => {
+                    warn!(
+                        "Cast ignored during constant evaluation: `{}` from `{}` to `{}`",
+                        value.span.extract(),
+                        value.ty,
+                        mir.ty
+                    );
+                    // TODO: Values with X and Z bits are passed through as
+                    // they are.
+                    cx.intern_value(ValueData {
+                        ty: mir.ty,
+                        kind: v.kind.clone(),
+                    })
+                }
+            }
+        }
+
         mir::RvalueKind::Transmute(value) => {
             let v = cx.const_mir_rvalue(value.into());
             cx.intern_value(ValueData {
@@ -483,17 +613,40 @@ fn const_mir_rvalue_inner<'a>(cx: &impl Context<'a>, mir: &'a mir::Rvalue<'a>) -
                 return cx.intern_value(make_error(mir.ty));
             }
             match (&lhs_val.kind, &rhs_val.kind) {
-                (ValueKind::Int(lhs_int, ..), ValueKind::Int(rhs_int, ..)) => {
-                    cx.intern_value(make_int(
-                        mir.ty,
-                        const_binary_arith_int(
-                            cx,
-                            mir.ty.simple_bit_vector(cx, mir.span),
-                            op,
-                            lhs_int,
-                            rhs_int,
-                        ),
-                    ))
+                (
+                    ValueKind::Int(lhs_int, lhs_special, ..),
+                    ValueKind::Int(rhs_int, rhs_special, ..),
+                ) => {
+                    let sbvt = mir.ty.simple_bit_vector(cx, mir.span);
+                    let result = match const_binary_arith_int(cx, sbvt, op, lhs_int, rhs_int) {
+                        Some(x) => x,
+                        None => {
+                            // Division or modulo by zero yields x, which
+                            // becomes zero in a two-valued type (see §11.4.2).
+                            cx.emit(
+                                DiagBuilder2::warning(format!(
+                                    "`{}` divides by zero",
+                                    mir.span.extract()
+                                ))
+                                .span(mir.span)
+                                .add_note(format!("`{}` evaluates to zero", rhs.span.extract()))
+                                .span(rhs.span),
+                            );
+                            return cx.intern_value(match sbvt.domain {
+                                Domain::TwoValued => make_int(mir.ty, BigInt::zero()),
+                                Domain::FourValued => make_int_special(
+                                    mir.ty,
+                                    BigInt::zero(),
+                                    BitVec::from_elem(sbvt.size, true),
+                                    BitVec::from_elem(sbvt.size, true),
+                                ),
+                            });
+                        }
+                    };
+                    if cx.sess().opts.warn_overflow && lhs_special.none() && rhs_special.none() {
+                        check_const_overflow(cx, mir, sbvt, op, &result);
+                    }
+                    cx.intern_value(make_int(mir.ty, result))
                 }
                 _ => unreachable!(),
             }
@@ -548,12 +701,54 @@ fn const_mir_rvalue_inner<'a>(cx: &impl Context<'a>, mir: &'a mir::Rvalue<'a>) -
             cx.intern_value(make_int(mir.ty, result))
         }
 
-        mir::RvalueKind::Assignment { .. }
-        | mir::RvalueKind::Var(_)
-        | mir::RvalueKind::Port(_)
-        | mir::RvalueKind::IntfSignal(..)
-        | mir::RvalueKind::Intf(..) => {
-            cx.emit(DiagBuilder2::error("value is not constant").span(mir.span));
+        mir::RvalueKind::Assignment { .. } => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "assignment `{}` cannot be used in a constant expression",
+                    mir.span.extract()
+                ))
+                .span(mir.span),
+            );
+            cx.intern_value(make_error(mir.ty))
+        }
+
+        mir::RvalueKind::Print { .. }
+        | mir::RvalueKind::Finish { .. }
+        | mir::RvalueKind::Random(_)
+        | mir::RvalueKind::FileOpen { .. }
+        | mir::RvalueKind::FileClose(_)
+        | mir::RvalueKind::DumpFile(_)
+        | mir::RvalueKind::DumpVars(..) => {
+            cx.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` cannot be used in a constant expression",
+                    mir.span.extract()
+                ))
+                .span(mir.span),
+            );
+            cx.intern_value(make_error(mir.ty))
+        }
+
+        mir::RvalueKind::Var(id)
+        | mir::RvalueKind::Port(id)
+        | mir::RvalueKind::IntfSignal(_, id)
+        | mir::RvalueKind::Intf(id) => {
+            let mut d = DiagBuilder2::error(format!("`{}` is not constant", mir.span.extract()))
+                .span(mir.span);
+            if let Ok(hir) = cx.hir_of(id) {
+                d = d
+                    .add_note(format!(
+                        "`{}` refers to {}, which may change during simulation; constant \
+                         expressions can only refer to parameters, genvars, and enum variants:",
+                        mir.span.extract(),
+                        hir.desc_full()
+                    ))
+                    .span(hir.human_span());
+            }
+            if is_target_replication_count(cx, mir.origin, mir.env) {
+                d = d.add_note("Replication counts in assignment targets must be constant");
+            }
+            cx.emit(d);
             cx.intern_value(make_error(mir.ty))
         }
 
@@ -573,12 +768,15 @@ fn const_mir_rvalue_inner<'a>(cx: &impl Context<'a>, mir: &'a mir::Rvalue<'a>) -
             true_value,
             false_value,
         } => {
+            // Only the selected operand is evaluated, such that the other one
+            // may be invalid, as in `N > 0 ? W / N : 0`.
             let cond_val = cx.const_mir_rvalue(cond.into());
-            let true_val = cx.const_mir_rvalue(true_value.into());
-            let false_val = cx.const_mir_rvalue(false_value.into());
+            if cond_val.is_error() {
+                return cx.intern_value(make_error(mir.ty));
+            }
             match cond_val.is_true() {
-                true => true_val,
-                false => false_val,
+                true => cx.const_mir_rvalue(true_value.into()),
+                false => cx.const_mir_rvalue(false_value.into()),
             }
         }
 
@@ -740,6 +938,121 @@ fn const_mir_rvalue_inner<'a>(cx: &impl Context<'a>, mir: &'a mir::Rvalue<'a>) -
     }
 }
 
+/// Warn if a constant arithmetic operation overflows its result width.
+///
+/// Operations that are the direct operand of a cast are considered to wrap
+/// intentionally and are not reported.
+fn check_const_overflow<'a>(
+    cx: &impl Context<'a>,
+    mir: &'a mir::Rvalue<'a>,
+    sbvt: SbvType,
+    op: mir::IntBinaryArithOp,
+    result: &BigInt,
+) {
+    match op {
+        mir::IntBinaryArithOp::Add
+        | mir::IntBinaryArithOp::Sub
+        | mir::IntBinaryArithOp::Mul
+        | mir::IntBinaryArithOp::Pow => (),
+        _ => return,
+    }
+    let modulus = BigInt::one() << sbvt.size;
+    let (min, max) = if sbvt.is_signed() {
+        let half = BigInt::one() << (sbvt.size - 1);
+        (-half.clone(), half - 1)
+    } else {
+        (BigInt::zero(), &modulus - 1)
+    };
+    if *result >= min && *result <= max {
+        return;
+    }
+    if is_explicitly_wrapped(cx, mir.origin) {
+        return;
+    }
+
+    // Determine the value the result wraps around to.
+    let mut wrapped = ((result % &modulus) + &modulus) % &modulus;
+    if wrapped > max {
+        wrapped -= &modulus;
+    }
+    cx.emit(
+        DiagBuilder2::warning(format!(
+            "`{}` overflows its {}-bit result",
+            mir.span.extract(),
+            sbvt.size
+        ))
+        .span(mir.span)
+        .add_note(format!(
+            "The result is {}, which wraps around to {}",
+            result, wrapped
+        ))
+        .add_note("Cast the expression to its type if the wrapping is intended"),
+    );
+}
+
+/// Check whether an expression is the direct operand of a cast.
+fn is_explicitly_wrapped<'a>(cx: &impl Context<'a>, node_id: NodeId) -> bool {
+    let parent = match cx.parent_node_id(node_id) {
+        Some(x) => x,
+        None => return false,
+    };
+    match cx.hir_of(parent) {
+        Ok(HirNode::Expr(expr)) => match expr.kind {
+            hir::ExprKind::Cast(..) | hir::ExprKind::CastSign(..) | hir::ExprKind::CastSize(..) => {
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Check whether an expression is part of the replication count of a
+/// concatenation that is the target of an assignment.
+fn is_target_replication_count<'a>(cx: &impl Context<'a>, node_id: NodeId, env: ParamEnv) -> bool {
+    // Find the concatenation whose replication count contains the expression.
+    let mut prev = node_id;
+    let mut concat = loop {
+        let parent = match cx.parent_node_id(prev) {
+            Some(x) => x,
+            None => return false,
+        };
+        match cx.hir_of(parent) {
+            Ok(HirNode::Expr(expr)) => match expr.kind {
+                hir::ExprKind::Concat(Some(repeat), _) if repeat == prev => break parent,
+                _ => prev = parent,
+            },
+            _ => return false,
+        }
+    };
+
+    // Step out of any concatenations the replication is nested in.
+    while let Some(parent) = cx.parent_node_id(concat) {
+        match cx.hir_of(parent) {
+            Ok(HirNode::Expr(hir::Expr {
+                kind: hir::ExprKind::Concat(..),
+                ..
+            })) => concat = parent,
+            _ => break,
+        }
+    }
+    crate::typeck::expr_is_lvalue(cx, concat, env)
+}
+
+/// Wrap an integer around to the width of a simple bit vector type.
+///
+/// The result is in the range of values the type can represent, which
+/// includes negative values if the type is signed.
+fn wrap_int(value: &BigInt, ty: SbvType) -> BigInt {
+    let modulus = BigInt::one() << ty.size;
+    let value = value.mod_floor(&modulus);
+    if ty.is_signed() && ty.size > 0 && value >= (&modulus >> 1) {
+        value - modulus
+    } else {
+        value
+    }
+}
+
 fn const_unary_bitwise_int<'gcx>(
     _cx: &impl Context<'gcx>,
     ty: SbvType,
@@ -776,29 +1089,38 @@ fn const_unary_arith_int<'gcx>(
     }
 }
 
+/// Evaluate an integer binary arithmetic operator.
+///
+/// Returns `None` for a division or modulo by zero.
 fn const_binary_arith_int<'gcx>(
     _cx: &impl Context<'gcx>,
     _ty: SbvType,
     op: mir::IntBinaryArithOp,
     lhs: &BigInt,
     rhs: &BigInt,
-) -> BigInt {
-    match op {
+) -> Option<BigInt> {
+    Some(match op {
         mir::IntBinaryArithOp::Add => lhs + rhs,
         mir::IntBinaryArithOp::Sub => lhs - rhs,
         mir::IntBinaryArithOp::Mul => lhs * rhs,
+        mir::IntBinaryArithOp::Div | mir::IntBinaryArithOp::Mod if rhs.is_zero() => return None,
         mir::IntBinaryArithOp::Div => lhs / rhs,
         mir::IntBinaryArithOp::Mod => lhs % rhs,
-        mir::IntBinaryArithOp::Pow => {
-            let mut result = num::one();
-            let mut cnt = rhs.clone();
-            while !cnt.is_zero() {
-                result = result * lhs;
-                cnt = cnt - 1;
+        mir::IntBinaryArithOp::Pow => match rhs.to_usize() {
+            Some(exp) => num::pow(lhs.clone(), exp),
+            // Negative exponents yield zero, except for bases 1 and -1 (see
+            // §11.4.3).
+            None if lhs.is_one() => BigInt::one(),
+            None if lhs == &-BigInt::one() => {
+                if rhs.is_even() {
+                    BigInt::one()
+                } else {
+                    -BigInt::one()
+                }
             }
-            result
-        }
-    }
+            None => BigInt::zero(),
+        },
+    })
 }
 
 fn const_comp_int<'gcx>(
@@ -873,6 +1195,262 @@ fn const_comp_string<'gcx>(
     }
 }
 
+/// Format a list of constant values according to a `$sformatf` format string.
+///
+/// Supports the `%d`, `%h`, `%x`, `%o`, `%b`, `%s`, and `%p` specifiers, as
+/// well as `%%` for a literal percent sign. Field widths are accepted but
+/// ignored.
+pub(crate) fn format_values<'a>(
+    cx: &impl Context<'a>,
+    fmt: Spanned<Name>,
+    args: &[(Value<'a>, Span)],
+) -> Result<Vec<u8>> {
+    let fmt_str = fmt.value.as_str();
+    let mut out = vec![];
+    let mut args = args.iter();
+    let mut chars = fmt_str.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            let mut buf = [0; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let fspec = parse_format_spec(&mut chars);
+        let spec = match fspec.conv {
+            Some('%') => {
+                out.push(b'%');
+                continue;
+            }
+            Some(c) => c,
+            None => {
+                cx.emit(
+                    DiagBuilder2::error("format string ends with an incomplete `%` specifier")
+                        .span(fmt.span),
+                );
+                return Err(());
+            }
+        };
+        let (value, span) = match args.next() {
+            Some(&x) => x,
+            None => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "no argument given for format specifier `%{}`",
+                        spec
+                    ))
+                    .span(fmt.span),
+                );
+                return Err(());
+            }
+        };
+        if value.is_error() {
+            return Err(());
+        }
+        let formatted = match spec {
+            'd' => format_int(value, 10, fspec.width),
+            'h' | 'x' => format_int(value, 16, fspec.width),
+            'o' => format_int(value, 8, fspec.width),
+            'b' => format_int(value, 2, fspec.width),
+            's' => format_str(value),
+            'p' => format_pattern(value),
+            _ => None,
+        };
+        match formatted {
+            Some(x) => out.extend(x),
+            None => {
+                cx.emit(
+                    DiagBuilder2::error(format!(
+                        "value of type `{}` cannot be formatted with `%{}`",
+                        value.ty, spec
+                    ))
+                    .span(span),
+                );
+                return Err(());
+            }
+        }
+    }
+    if let Some(&(_, span)) = args.next() {
+        cx.emit(
+            DiagBuilder2::error("argument has no corresponding format specifier")
+                .span(span)
+                .add_note("Format string is:")
+                .span(fmt.span),
+        );
+        return Err(());
+    }
+    Ok(out)
+}
+
+/// A format specifier such as `%0h`.
+pub(crate) struct FormatSpec {
+    /// The characters between the `%` and the conversion character.
+    pub text: String,
+    /// The field width, if one was given.
+    pub width: Option<usize>,
+    /// The lowercase conversion character, or `None` if the format string
+    /// ended before one.
+    pub conv: Option<char>,
+}
+
+/// Parse the format specifier following a `%` in a format string.
+///
+/// Consumes the field width and precision digits, and the conversion
+/// character.
+pub(crate) fn parse_format_spec(chars: &mut std::str::Chars) -> FormatSpec {
+    let mut text = String::new();
+    let conv = loop {
+        match chars.next() {
+            Some(c) if c.is_ascii_digit() || c == '.' => text.push(c),
+            c => break c.map(|c| c.to_ascii_lowercase()),
+        }
+    };
+    let width = text.split('.').next().and_then(|w| w.parse().ok());
+    FormatSpec { text, width, conv }
+}
+
+/// Format an integer value in the given radix.
+///
+/// Decimal formatting honors the sign of the value's type. Without a field
+/// width the result is padded to the largest possible value of the type, with
+/// zeros for hex, octal, and binary, and with spaces for decimal. A width of
+/// zero drops the padding. See §21.2.1.3 "Size of displayed data".
+///
+/// Hex, octal, and binary digits with x or z bits are formatted as `x` or `z`
+/// if all of their bits are x or z, and as `X` or `Z` otherwise. Decimal
+/// values follow the same scheme for the value as a whole.
+fn format_int(value: Value, radix: u32, width: Option<usize>) -> Option<Vec<u8>> {
+    let (v, special, x) = match value.kind {
+        ValueKind::Int(ref v, ref special, ref x) => (v, special, x),
+        _ => return None,
+    };
+    let w = value.ty.get_bit_size().unwrap_or(0);
+    let signed = value
+        .ty
+        .get_simple_bit_vector()
+        .map(|sbv| sbv.is_signed())
+        .unwrap_or(false);
+    // The special bits are stored MSB first and may be fewer than the bits of
+    // the value, in which case they are aligned to the LSB.
+    let len = special.len();
+    let is_special = |i: usize| i < len && special[len - 1 - i];
+    let is_x = |i: usize| is_special(i) && x.get(len - 1 - i).unwrap_or(false);
+    let special_digit = |bits: std::ops::Range<usize>| {
+        let num_special = bits.clone().filter(|&i| is_special(i)).count();
+        let any_x = bits.clone().any(|i| is_x(i));
+        if num_special == 0 {
+            None
+        } else if num_special == bits.len() && bits.clone().all(|i| is_x(i)) {
+            Some('x')
+        } else if num_special == bits.len() && !any_x {
+            Some('z')
+        } else if any_x {
+            Some('X')
+        } else {
+            Some('Z')
+        }
+    };
+
+    // Format the digits, and determine the full width and padding character.
+    let (digits, full_width, pad) = if radix == 10 {
+        let digits = match special_digit(0..w) {
+            Some(c) => c.to_string(),
+            None if signed && w > 0 && (v >> (w - 1)).is_odd() => {
+                (v - (BigInt::one() << w)).to_str_radix(10)
+            }
+            None => v.to_str_radix(10),
+        };
+        let full_width = if signed && w > 0 {
+            (BigInt::one() << (w - 1)).to_str_radix(10).len() + 1
+        } else {
+            ((BigInt::one() << w) - BigInt::one())
+                .to_str_radix(10)
+                .len()
+        };
+        (digits, full_width, ' ')
+    } else {
+        let bits_per_digit = radix.trailing_zeros() as usize;
+        let num_digits = std::cmp::max((w + bits_per_digit - 1) / bits_per_digit, 1);
+        let mut digits: String = (0..num_digits)
+            .rev()
+            .map(|d| {
+                let bits = d * bits_per_digit..std::cmp::min((d + 1) * bits_per_digit, w);
+                special_digit(bits.clone()).unwrap_or_else(|| {
+                    let digit = (v >> bits.start) % (BigInt::one() << bits.len());
+                    std::char::from_digit(digit.to_u32().unwrap_or(0), radix).unwrap()
+                })
+            })
+            .collect();
+        // Strip the leading zeros, since they are added back as padding.
+        let nonzero = digits.find(|c: char| c != '0').unwrap_or(digits.len() - 1);
+        digits.drain(..nonzero);
+        (digits, num_digits, '0')
+    };
+    let width = width.unwrap_or(full_width);
+    let mut out = String::new();
+    for _ in digits.len()..width {
+        out.push(pad);
+    }
+    out.push_str(&digits);
+    Some(out.into_bytes())
+}
+
+/// Format a value as a string.
+///
+/// Integers are interpreted as a sequence of 8 bit characters, with leading
+/// zero bytes dropped.
+fn format_str(value: Value) -> Option<Vec<u8>> {
+    match value.kind {
+        ValueKind::String(ref bytes) => Some(bytes.clone()),
+        ValueKind::Int(ref v, ..) => Some(
+            v.to_bytes_be()
+                .1
+                .into_iter()
+                .skip_while(|&b| b == 0)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Format a value as an assignment pattern.
+///
+/// Structs are formatted as `'{name:value, ...}` and arrays as `'{value, ...}`,
+/// recursing into the individual fields and elements. Strings are quoted, and
+/// all other values fall back to decimal formatting.
+fn format_pattern(value: Value) -> Option<Vec<u8>> {
+    match value.kind {
+        ValueKind::StructOrArray(ref values) => {
+            let strukt = if value.ty.dims().next().is_none() {
+                value.ty.get_struct()
+            } else {
+                None
+            };
+            let mut out = b"'{".to_vec();
+            for (i, v) in values.iter().enumerate() {
+                if i > 0 {
+                    out.extend_from_slice(b", ");
+                }
+                if let Some(member) = strukt.and_then(|s| s.members.get(i)) {
+                    out.extend_from_slice(member.name.value.as_str().as_bytes());
+                    out.push(b':');
+                }
+                out.extend(format_pattern(v)?);
+            }
+            out.push(b'}');
+            Some(out)
+        }
+        ValueKind::String(ref bytes) => {
+            let mut out = vec![b'"'];
+            out.extend_from_slice(bytes);
+            out.push(b'"');
+            Some(out)
+        }
+        ValueKind::Time(ref v) => Some(v.to_string().into_bytes()),
+        ValueKind::Int(..) => format_int(value, 10, Some(0)),
+        ValueKind::Void | ValueKind::Error => None,
+    }
+}
+
 /// Check if a node has a constant value.
 #[moore_derive::query]
 pub(crate) fn is_constant<'a>(cx: &impl Context<'a>, node_id: NodeId) -> Result<bool> {
@@ -885,6 +1463,37 @@ pub(crate) fn is_constant<'a>(cx: &impl Context<'a>, node_id: NodeId) -> Result<
     })
 }
 
+/// Check whether a node evaluates to the unbounded value `$`.
+///
+/// Identifiers are followed through parameter assignments and defaults, such
+/// that a parameter declared as `parameter int X = $` is considered unbounded
+/// unless it is overridden.
+pub(crate) fn is_unbounded<'a>(cx: &impl Context<'a>, node_id: NodeId, env: ParamEnv) -> bool {
+    match cx.hir_of(node_id) {
+        Ok(HirNode::Expr(expr)) => match expr.kind {
+            hir::ExprKind::Unbounded => true,
+            hir::ExprKind::Ident(..) | hir::ExprKind::Scope(..) | hir::ExprKind::UnitScope(..) => {
+                match cx.resolve_node(node_id, env) {
+                    Ok(binding) => is_unbounded(cx, binding, env),
+                    Err(()) => false,
+                }
+            }
+            _ => false,
+        },
+        Ok(HirNode::ValueParam(param)) => match cx.param_env_data(env).find_value(node_id) {
+            Some(ParamEnvBinding::Indirect(assigned_id)) => {
+                is_unbounded(cx, assigned_id.id(), assigned_id.env())
+            }
+            Some(ParamEnvBinding::Direct(_)) => false,
+            None => param
+                .default
+                .map(|default| is_unbounded(cx, default, env))
+                .unwrap_or(false),
+        },
+        _ => false,
+    }
+}
+
 /// Determine the default value of a type.
 #[moore_derive::query]
 pub(crate) fn type_default_value<'a>(cx: &impl Context<'a>, ty: &'a UnpackedType<'a>) -> Value<'a> {
@@ -0,0 +1,59 @@
+// RUN: moore %s -e foo
+
+module foo;
+    bit a, b, c;
+    bit [7:0] x, y;
+
+    // The declaration assignment of a net is delayed like a continuous
+    // assignment.
+    wire #4ns w = a;
+    // CHECK: %1 = const time 4ns
+    // CHECK: %2 = const time 0s 1e
+    // CHECK:     %value = prb i1$ %in
+    // CHECK:     drv i1$ %out, %value, %2
+
+    // Continuous assignment and net delays are inertial. The 2ns pulse on `a`
+    // below is shorter than the 5ns delay of `b`, so it never reaches `b`: the
+    // delay process samples `a`, waits for 5ns or a change of `a`, and only
+    // drives `b` if `a` still holds the sampled value.
+    initial begin
+        #10ns a = 1;
+        #2ns a = 0;
+    end
+
+    assign #5ns b = a;
+    // CHECK: %1 = const time 5ns
+    // CHECK: %2 = const time 0s 1e
+    // CHECK:     br %sample
+    // CHECK: sample:
+    // CHECK:     %value = prb i1$ %in
+    // CHECK:     wait %check for %1, %in
+    // CHECK: check:
+    // CHECK:     %current = prb i1$ %in
+    // CHECK:     %held = eq i1 %value, %current
+    // CHECK:     br %held, %sample, %apply
+    // CHECK: apply:
+    // CHECK:     drv i1$ %out, %value, %2
+    // CHECK:     wait %sample, %in
+
+    // Transitions to zero use the fall delay.
+    assign #(2ns, 3ns) c = a;
+    // CHECK: %1 = const time 2ns
+    // CHECK: %2 = const time 3ns
+    // CHECK: %3 = const time 0s 1e
+    // CHECK:     %value = prb i1$ %in
+    // CHECK:     %4 = const i1 0
+    // CHECK:     %5 = eq i1 %value, %4
+    // CHECK:     %6 = [time %1, %2]
+    // CHECK:     %7 = mux [2 x time] %6, i1 %5
+    // CHECK:     wait %check for %7, %in
+
+    // The turn-off delay has no effect on two-valued signals.
+    assign #(1ns, 2ns, 3ns) y = x;
+    // CHECK:     %value = prb i8$ %in
+    // CHECK:     drv i8$ %out, %value, %3
+
+    // The entity connects the signals through the delay processes.
+    // CHECK: entity @foo () -> () {
+    // CHECK: %w = sig i1 %0
+endmodule
@@ -0,0 +1,26 @@
+// RUN: moore %s -e foo
+
+module foo;
+    class Packet;
+        rand bit [7:0] len;
+        rand bit [7:0] data [4];
+        bit [7:0] limit;
+        rand bit mode;
+
+        constraint c_len { len < limit; len > 0; }
+        constraint c_mode {
+            if (mode) len < 16;
+            else { len >= 16; len < 64; }
+        }
+        constraint c_data {
+            foreach (data[i]) data[i] < len;
+        }
+        constraint c_data2 {
+            foreach (data[i]) { data[i] != 0; }
+        }
+        constraint c_dist {
+            len dist {0 := 40, [1:3] :/ 60, 8};
+            mode -> len inside {[4:7], 12};
+        }
+    endclass
+endmodule
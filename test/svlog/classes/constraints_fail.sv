@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    class Packet;
+        rand bit [7:0] len;
+        string name;
+        bit [7:0] limit;
+
+        constraint c_name { name != ""; }
+        constraint c_limit { limit > 4; }
+    endclass
+endmodule
+// CHECK: error: `name` of type `string` cannot be used in a constraint
+// CHECK: warning: constraint does not involve any random property
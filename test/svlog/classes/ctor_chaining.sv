@@ -0,0 +1,36 @@
+// RUN: moore %s -e foo
+
+module foo;
+    class A;
+        int x;
+        function new(int x);
+            this.x = x;
+        endfunction
+    endclass
+
+    class B extends A(42);
+    endclass
+
+    class C extends A;
+        function new();
+            super.new(42);
+        endfunction
+    endclass
+
+    class D;
+        int y;
+        function new(int y = 0);
+            this.y = y;
+        endfunction
+    endclass
+
+    class E extends D;
+    endclass
+
+    class F extends A;
+        function new();
+            int y = 42;
+            super.new(y);
+        endfunction
+    endclass
+endmodule
@@ -0,0 +1,29 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    class A;
+        function new(int x);
+        endfunction
+    endclass
+
+    class B extends A;
+    endclass
+    // CHECK: error: constructor of base class `A` requires 1 argument(s)
+
+    class C extends A(1);
+        function new();
+            super.new(2);
+        endfunction
+    endclass
+    // CHECK: error: base class constructor called twice
+
+    class D extends A;
+        function new();
+            int y;
+            y = 2;
+            super.new(y);
+        endfunction
+    endclass
+    // CHECK: error: `super.new` must be the first statement in a constructor
+endmodule
@@ -0,0 +1,9 @@
+// RUN: moore %s -e foo
+
+module foo;
+    int len;
+    class Packet;
+        rand int len;
+        constraint c { local::len < 16; }
+    endclass
+endmodule
@@ -0,0 +1,13 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    int len, x;
+    class Packet;
+        rand int len;
+        constraint c { local::size < 16; }
+        // CHECK: error: `size` not found in class `Packet`
+    endclass
+    assign x = local::len;
+    // CHECK: error: `local::` can only be used inside a constraint
+endmodule
@@ -0,0 +1,20 @@
+// RUN: moore %s -e foo
+
+module foo;
+    class Base;
+        int count;
+    endclass
+
+    class Packet extends Base;
+        static const int WIDTH = 8;
+        static int total = WIDTH * 2;
+        const int id;
+        int count;
+
+        function new(int i);
+            super.new();
+            this.id = i;
+        endfunction
+    endclass
+endmodule
+// CHECK: warning: property `count` shadows a property of base class `Base`
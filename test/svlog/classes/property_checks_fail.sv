@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    class Packet;
+        static const int WIDTH;
+        const int id;
+        int len;
+        static int max_len = len;
+    endclass
+endmodule
+// CHECK: error: static const property `WIDTH` must have an initializer
+// CHECK: error: const property `id` is never initialized
+// CHECK: error: static property `max_len` cannot refer to non-static property `len`
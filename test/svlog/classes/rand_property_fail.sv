@@ -0,0 +1,11 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    class Packet;
+        rand bit [7:0] addr;
+        randc bit [3:0] id;
+        rand string name;
+    endclass
+endmodule
+// CHECK: error: `rand` cannot be applied to `name` of type `string`
@@ -177,6 +177,15 @@ module foo;
     // CHECK: %0 = const i32 42
     // CHECK: %0 = const i32 42
     // CHECK: %0 = const i32 42
+
+    // Nested Ternary Operators
+
+    bar #(a != b ? 1 : z ? 2 : 3) i78();
+    bar #(a != b ? z ? 1 : 2 : 3) i79();
+    bar #({z ? 4'd1 : 4'd2, 4'd3}) i80();
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 2
+    // CHECK: %0 = const i32 35
 endmodule
 
 module bar #(parameter int X);
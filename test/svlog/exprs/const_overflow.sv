@@ -0,0 +1,13 @@
+// RUN: moore %s -e foo --warn-overflow
+
+module foo;
+    localparam logic [7:0] A = 8'd200 + 8'd100;
+    // CHECK: warning: `8'd200 + 8'd100` overflows its 8-bit result
+    localparam logic [15:0] B = 8'd200 + 8'd100;
+    localparam logic [7:0] C = 8'(8'd200 + 8'd100);
+    localparam logic [7:0] D = 8'bx + 8'd1;
+    bit [7:0] a = A;
+    bit [15:0] b = B;
+    bit [7:0] c = C;
+    logic [7:0] d = D;
+endmodule
@@ -0,0 +1,24 @@
+// RUN: moore %s -e bar
+
+module foo #(parameter int N = 1);
+    function int f();
+        return N;
+    endfunction
+    function void g();
+    endfunction
+    task t();
+        f();
+        g();
+    endtask
+endmodule
+
+// The warning is emitted once, not once for every parameterization.
+module bar;
+    foo #(1) i0();
+    foo #(2) i1();
+endmodule
+
+// CHECK: warning: return value of function `f` is discarded
+// CHECK: = note: Use a `void'(...)` cast to discard it explicitly
+// CHECK-NOT: warning: return value of function `f` is discarded
+// CHECK-NOT: warning: return value of function `g` is discarded
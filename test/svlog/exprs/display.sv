@@ -0,0 +1,47 @@
+// RUN: moore %s -e foo -e bar -e baz -e qux
+// See §21.2 "Display system tasks".
+
+// The format string is passed to `llhd.print` as an array of bytes.
+module foo (input logic [7:0] a);
+    initial $write("%h", a);
+endmodule
+// CHECK: %1 = const i8 37
+// CHECK: %2 = const i8 104
+// CHECK: %3 = [i8 %1, %2]
+// CHECK: %a.prb = prb i8$ %a
+// CHECK: call void @llhd.print ([2 x i8] %3, i8 %a.prb)
+
+// Signed values are printed with `%i`, and `$display` appends a newline.
+module bar (input int b);
+    initial $display("%0d", b);
+endmodule
+// CHECK: %1 = const i8 37
+// CHECK: %2 = const i8 48
+// CHECK: %3 = const i8 105
+// CHECK: %4 = const i8 10
+// CHECK: %5 = [i8 %1, %2, %3, %4]
+// CHECK: %b.prb = prb i32$ %b
+// CHECK: call void @llhd.print ([4 x i8] %5, i32 %b.prb)
+
+// Arguments without a format string are printed in decimal, and empty
+// arguments as a space.
+module baz (input logic [7:0] a, input int b);
+    initial $display(a, , b);
+endmodule
+// CHECK: %6 = [i8 %1, %2, %3, %1, %4, %5]
+// CHECK: %a.prb = prb i8$ %a
+// CHECK: %b.prb = prb i32$ %b
+// CHECK: call void @llhd.print ([6 x i8] %6, i8 %a.prb, i32 %b.prb)
+
+// `$monitor` prints again whenever one of its arguments changes.
+module qux (input logic [7:0] a);
+    initial $monitor("%d", a);
+endmodule
+// CHECK: br %body
+// CHECK: body:
+// CHECK: %4 = [i8 %1, %2, %3]
+// CHECK: %a.prb = prb i8$ %a
+// CHECK: call void @llhd.print ([3 x i8] %4, i8 %a.prb)
+// CHECK: br %check
+// CHECK: check:
+// CHECK: wait %body, %a
@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo
+// See §21.2.1 "The display and write tasks".
+
+module foo (input int a, input int b);
+    initial begin
+        $display("%d %d", a);
+        $display("%d", a, b);
+        $display("%q", a);
+    end
+endmodule
+
+// CHECK: warning: no argument given for format specifier `%d`
+// CHECK: warning: argument `b` has no corresponding format specifier
+// CHECK: warning: unsupported format specifier `%q`
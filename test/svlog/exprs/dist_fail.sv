@@ -0,0 +1,8 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+  logic [3:0] x;
+  bit z;
+  initial z = x dist {0 := 1, [1:3] :/ 2};
+endmodule
+// CHECK: error: `dist` can only be used in a constraint
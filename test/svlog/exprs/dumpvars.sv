@@ -0,0 +1,51 @@
+// RUN: moore %s -e foo -e bar -e baz
+// See §21.7 "Value change dump (VCD) files".
+
+// Every variable is passed with its path, VCD type, and width.
+module foo;
+    logic [7:0] x;
+    initial $dumpvars(0, x);
+endmodule
+// CHECK: %1 = const i32 8
+// CHECK: %2 = const i8 120
+// CHECK: %3 = [i8 %2]
+// CHECK: %7 = [i8 %4, %5, %6]
+// CHECK: call void @llhd.dumpvar ([1 x i8] %3, [3 x i8] %7, i32 %1)
+
+// Without scopes, the calling instance and all instances below it are dumped.
+module bar;
+    wire w;
+    integer i;
+    leaf u();
+    initial begin
+        $dumpfile("w");
+        $dumpvars;
+    end
+endmodule
+
+module leaf;
+    real r;
+    logic [3:0] y;
+endmodule
+// CHECK: %1 = const i8 119
+// CHECK: %2 = [i8 %1]
+// CHECK: call void @llhd.dumpfile ([1 x i8] %2)
+// CHECK: %3 = const i32 1
+// CHECK: call void @llhd.dumpvar ([1 x i8] %2, [4 x i8] %7, i32 %3)
+// CHECK: %8 = const i32 32
+// CHECK: call void @llhd.dumpvar ([1 x i8] %9, [7 x i8] %13, i32 %8)
+// CHECK: %14 = const i32 64
+// CHECK: call void @llhd.dumpvar ([3 x i8] %17, [4 x i8] %20, i32 %14)
+// CHECK: %21 = const i32 4
+// CHECK: call void @llhd.dumpvar ([3 x i8] %23, [3 x i8] %24, i32 %21)
+
+// Indices into generate loops are resolved.
+module baz;
+    for (genvar i = 0; i < 2; i++) begin : g
+        logic [1:0] z;
+    end
+    initial $dumpvars(0, g[1].z);
+endmodule
+// CHECK: %1 = const i32 2
+// CHECK: %8 = [i8 %2, %3, %4, %5, %6, %7]
+// CHECK: call void @llhd.dumpvar ([6 x i8] %8, [3 x i8] %11, i32 %1)
@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo -e bar
+// FAIL
+// See §21.7.1.2 "Specifying variables to be dumped ($dumpvars)".
+
+module foo;
+    localparam int P = 1;
+    initial $dumpvars(0, P);
+endmodule
+
+module bar;
+    initial $dumpvars(-1);
+endmodule
+
+// CHECK: error: `$dumpvars` requires an instance or a variable, but `P` is neither
+// CHECK: error: `$dumpvars` levels `-1` must be a constant non-negative integer
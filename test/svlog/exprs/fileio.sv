@@ -0,0 +1,30 @@
+// RUN: moore %s -e foo -e bar
+// See §21.3 "File input/output system tasks and system functions".
+
+// The file name and mode are passed as arrays of bytes.
+module foo (output int fd);
+    initial fd = $fopen("o", "w");
+endmodule
+// CHECK: %1 = const i8 111
+// CHECK: %2 = [i8 %1]
+// CHECK: %3 = const i8 119
+// CHECK: %4 = [i8 %3]
+// CHECK: %5 = call i32 @llhd.fopen ([1 x i8] %2, [1 x i8] %4)
+// CHECK: %6 = const time 0s 1e
+// CHECK: drv i32$ %fd, %5, %6
+
+// Prints into a file pass the file descriptor first.
+module bar (input int fd, input logic [7:0] a);
+    initial begin
+        $fwrite(fd, "%b", a);
+        $fclose(fd);
+    end
+endmodule
+// CHECK: %fd.prb = prb i32$ %fd
+// CHECK: %1 = const i8 37
+// CHECK: %2 = const i8 98
+// CHECK: %3 = [i8 %1, %2]
+// CHECK: %a.prb = prb i8$ %a
+// CHECK: call void @llhd.fprint (i32 %fd.prb, [2 x i8] %3, i8 %a.prb)
+// CHECK: %fd.prb1 = prb i32$ %fd
+// CHECK: call void @llhd.fclose (i32 %fd.prb1)
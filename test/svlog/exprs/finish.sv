@@ -0,0 +1,36 @@
+// RUN: moore %s -e foo -e bar -e baz
+// See §20.2 "Simulation control system tasks" and §20.10 "Severity tasks".
+
+// The process halts after `$finish`, so the following statements never run.
+module foo;
+    initial begin
+        $finish(2);
+        $display("unreachable");
+    end
+endmodule
+// CHECK: 0:
+// CHECK: %1 = const i32 0
+// CHECK: %2 = const i32 2
+// CHECK: call void @llhd.finish (i32 %1, i32 %2)
+// CHECK: halt
+// CHECK: unreachable:
+
+// The process continues after `$stop`.
+module bar;
+    initial $stop;
+endmodule
+// CHECK: 0:
+// CHECK: %1 = const i32 0
+// CHECK: %2 = const i32 1
+// CHECK: call void @llhd.stop (i32 %1, i32 %2)
+// CHECK: halt
+
+// `$fatal` prints its message before finishing with status 1.
+module baz;
+    initial $fatal(1, "x");
+endmodule
+// CHECK: %9 = [i8 %1, %2, %3, %2, %4, %5, %6, %7, %8]
+// CHECK: call void @llhd.print ([9 x i8] %9)
+// CHECK: %10 = const i32 1
+// CHECK: call void @llhd.finish (i32 %10, i32 %10)
+// CHECK: halt
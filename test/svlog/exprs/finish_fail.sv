@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo -e bar
+// FAIL
+// See §20.2 "Simulation control system tasks".
+
+module foo;
+    initial $finish(3);
+endmodule
+
+module bar;
+    initial $stop(0, 1);
+endmodule
+
+// CHECK: error: finish number `3` must be 0, 1, or 2
+// CHECK: error: `$stop` takes at most one argument
@@ -0,0 +1,43 @@
+// RUN: moore %s -e foo -O0
+// See §11.5.1 "Vector bit-select and part-select addressing".
+
+module foo;
+    localparam logic [7:0] a = 8'b10110010;
+    localparam logic [0:7] b = 8'b10110010;
+    localparam logic [8:1] c = 8'b10110010;
+    localparam logic [1:8] d = 8'b10110010;
+
+    // Descending ranges
+    bar #(a[5 -: 3]) i0();
+    bar #(a[1 +: 3]) i1();
+    bar #(a[6]) i2();
+    // CHECK: %0 = const i32 6
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 0
+
+    // Ascending ranges
+    bar #(b[0 +: 3]) i3();
+    bar #(b[5 -: 3]) i4();
+    bar #(b[2:4]) i5();
+    bar #(b[6]) i6();
+    // CHECK: %0 = const i32 5
+    // CHECK: %0 = const i32 4
+    // CHECK: %0 = const i32 6
+    // CHECK: %0 = const i32 1
+
+    // Descending ranges with an offset
+    bar #(c[6 -: 3]) i7();
+    bar #(c[2 +: 3]) i8();
+    // CHECK: %0 = const i32 6
+    // CHECK: %0 = const i32 1
+
+    // Ascending ranges with an offset
+    bar #(d[1 +: 3]) i9();
+    bar #(d[6 -: 3]) i10();
+    // CHECK: %0 = const i32 5
+    // CHECK: %0 = const i32 4
+endmodule
+
+module bar #(parameter int X);
+    int x = X;
+endmodule
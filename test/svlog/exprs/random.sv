@@ -0,0 +1,20 @@
+// RUN: moore %s -e foo -e bar
+// See §18.13 "Random number system functions and methods" and §20.15
+// "Probabilistic distribution functions".
+
+module foo (input int seed, output int a);
+    initial a = $random(seed);
+endmodule
+// CHECK: %seed.prb = prb i32$ %seed
+// CHECK: %1 = call i32 @llhd.random (i32 %seed.prb)
+// CHECK: %2 = const time 0s 1e
+// CHECK: drv i32$ %a, %1, %2
+
+// Without a seed, the seed is derived from `--seed` and the procedure. The
+// range bounds are swapped, since `min > max`.
+module bar (output int unsigned b, output int unsigned c);
+    initial begin
+        b = $urandom;
+        c = $urandom_range(3, 10);
+    end
+endmodule
@@ -0,0 +1,16 @@
+// RUN: moore %s -e foo -e bar
+// FAIL
+// See §18.13 "Random number system functions and methods".
+
+module foo;
+    int unsigned x;
+    initial x = $urandom(1, 2);
+endmodule
+
+module bar;
+    int unsigned x;
+    initial x = $urandom_range();
+endmodule
+
+// CHECK: error: `$urandom` takes at most one argument
+// CHECK: error: `$urandom_range` takes one or two arguments
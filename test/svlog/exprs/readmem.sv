@@ -0,0 +1,28 @@
+// RUN: moore %s -e foo -e bar
+// See §21.4 "Loading memory array data from a file".
+
+// The file loads 01 and 23 at addresses 0 and 1, then skips ahead to load ab
+// and cdef at addresses 4 and 5. Addresses 2 and 3 keep their value.
+module foo;
+    logic [15:0] a [0:5];
+    initial $readmemh("readmem.hex", a);
+endmodule
+// CHECK: %1 = const i16 1
+// CHECK: %2 = const i16 35
+// CHECK: %a.prb = prb [6 x i16]$ %a
+// CHECK: %11 = const i16 171
+// CHECK: %12 = const i16 52719
+// CHECK: %13 = [i16 %1, %2, %7, %10, %11, %12]
+// CHECK: %14 = const time 0s 1e
+// CHECK: drv [6 x i16]$ %a, %13, %14
+
+// The words are loaded from address 2 down to address 1.
+module bar;
+    logic [7:0] b [1:2];
+    initial $readmemb("readmem.bin", b, 2, 1);
+endmodule
+// CHECK: %1 = const i8 2
+// CHECK: %2 = const i8 1
+// CHECK: %3 = [i8 %1, %2]
+// CHECK: %4 = const time 0s 1e
+// CHECK: drv [2 x i8]$ %b, %3, %4
@@ -0,0 +1,22 @@
+// RUN: moore %s -e foo -e bar -e baz
+// FAIL
+// See §21.4 "Loading memory array data from a file".
+
+module foo;
+    logic [7:0] a [0:3];
+    initial $readmemh("missing.hex", a);
+endmodule
+
+module bar;
+    logic [7:0] a [0:3];
+    initial $readmemh("readmem.hex", a, 0, 4);
+endmodule
+
+module baz;
+    logic [7:0] a [0:3];
+    initial $readmemh("readmem.hex");
+endmodule
+
+// CHECK: error: cannot find memory file `missing.hex`
+// CHECK: error: `$readmemh` address `4` is outside the memory range [0:3]
+// CHECK: error: `$readmemh` takes a file name, a memory, and an optional start and end address
@@ -0,0 +1,7 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+    bit a;
+    bit b = a.triggered;
+    // CHECK: error: `.triggered` can only be applied to a sequence
+endmodule
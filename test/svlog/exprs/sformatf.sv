@@ -0,0 +1,34 @@
+// RUN: moore %s -e foo -O0
+module foo;
+    typedef struct { int a; bit [3:0] b; } pair_t;
+    localparam pair_t P = '{a: -3, b: 4'hA};
+    localparam int Q [2] = '{7, 9};
+
+    localparam string s0 = $sformatf("%p", P);
+    localparam string s1 = $sformatf("%p", Q);
+    localparam string s2 = $sformatf("%p", 42);
+    localparam string s3 = $sformatf("x=%0d h=%h b=%b %%", 5, 8'hAB, 3'd5);
+    // See §21.2.1.3 "Size of displayed data".
+    localparam string s4 = $sformatf("%b %h %o %d %d", 8'd5, 8'h0A, 6'o7, 8'd5, -3);
+    localparam string s5 = $sformatf("%0b %0h %0d %3h", 8'd5, 8'h0A, 8'd5, 8'h0A);
+    localparam string s6 = $sformatf("%b %h %h %d %d", 4'b10xz, 8'bxxxxzzzz, 8'b01x0zzzz, 4'bxxxx, 4'b10z0);
+
+    bar #(s0 == "'{a:-3, b:10}") i0();
+    bar #(s1 == "'{7, 9}") i1();
+    bar #(s2 == "42") i2();
+    bar #(s3 == "x=5 h=ab b=101 %") i3();
+    bar #(s4 == "00000101 0a 07   5          -3") i4();
+    bar #(s5 == "101 a 5 00a") i5();
+    bar #(s6 == "10xz xz Xz  x  Z") i6();
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 1
+    // CHECK: %0 = const i32 1
+endmodule
+
+module bar #(parameter int X);
+    int x = X;
+endmodule
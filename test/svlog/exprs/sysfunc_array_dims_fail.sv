@@ -0,0 +1,21 @@
+// RUN: moore %s -e t0 -e t1 -e t2
+// FAIL
+// See §20.7 "Array querying functions".
+
+module t0;
+    int i;
+    int x = $left(i, 2);
+endmodule
+// CHECK: error: value of type `int` does not have a dimension 2
+
+module t1;
+    bit [3:0] b;
+    int x = $size(b, 0);
+endmodule
+// CHECK: error: value of type `bit [3:0]` does not have a dimension 0
+
+module t2;
+    bit [3:0] b;
+    int x = $high(b, -1);
+endmodule
+// CHECK: error: value of type `bit [3:0]` does not have a dimension -1
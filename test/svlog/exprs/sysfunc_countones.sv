@@ -0,0 +1,26 @@
+// RUN: moore %s -e foo -O0
+// See §20.8.1 "Integer math functions" and §20.9 "Bit vector system
+// functions".
+
+module foo;
+    int v0 = $countones(8'b1011_0010);
+    // CHECK: %0 = const i32 4
+    int v1 = $countones(0);
+    // CHECK: %1 = const i32 0
+    int v2 = $countones(-1);
+    // CHECK: %2 = const i32 32
+    int v3 = $onehot(4'b0100);
+    // CHECK: %3 = const i32 1
+    int v4 = $onehot(4'b0110);
+    // CHECK: %4 = const i32 0
+    int v5 = $onehot0(4'b0000);
+    // CHECK: %5 = const i32 1
+    int v6 = $onehot0(4'b0011);
+    // CHECK: %6 = const i32 0
+    int v7 = $countbits(8'b1011_0010, '0);
+    // CHECK: %7 = const i32 4
+    int v8 = $countbits(8'b1011_0010, '1, '0);
+    // CHECK: %8 = const i32 8
+    int v9 = $countbits(8'b1011_0010, 'x, 'z);
+    // CHECK: %9 = const i32 0
+endmodule
@@ -0,0 +1,20 @@
+// RUN: moore %s -e foo
+// See §20.8.1 "Integer math functions" and §20.9 "Bit vector system
+// functions".
+
+module foo (
+    input logic [3:0] a,
+    output int b,
+    output int c,
+    output bit d,
+    output bit e,
+    output int f
+);
+    assign b = $countones(a);
+    assign c = $countbits(a, '0);
+    assign d = $onehot(a);
+    assign e = $onehot0(a);
+    assign f = $clog2(a);
+endmodule
+
+// CHECK: entity @foo (i4$ %a) -> (i32$ %b, i32$ %c, i1$ %d, i1$ %e, i32$ %f) {
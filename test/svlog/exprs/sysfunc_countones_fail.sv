@@ -0,0 +1,22 @@
+// RUN: moore %s -e t0 -e t1 -e t2
+// FAIL
+// See §20.9 "Bit vector system functions".
+
+module t0;
+    string s;
+    int x = $countones(s);
+endmodule
+// CHECK: error: `$countones` requires an integral argument, but `s` has type `string`
+
+module t1;
+    logic [3:0] a;
+    int x = $countbits(a);
+endmodule
+// CHECK: error: `$countbits` requires an expression and at least one control bit
+
+module t2;
+    logic [3:0] a;
+    logic b;
+    int x = $countbits(a, b);
+endmodule
+// CHECK: error: `$countbits` control bit `b` is not constant
@@ -0,0 +1,18 @@
+// RUN: moore %s -e A0
+
+module A0;
+    bit x;
+    A1 #(4) i1(x);
+    A1 i2(x);
+endmodule
+
+module A1 #(int K = $) (output bit k = $isunbounded(K));
+endmodule
+
+// CHECK: entity @A1.param1 () -> (i1$ %k) {
+// CHECK:     %0 = const i1 0
+// CHECK: }
+// CHECK:
+// CHECK: entity @A1.param2 () -> (i1$ %k) {
+// CHECK:     %0 = const i1 1
+// CHECK: }
@@ -0,0 +1,7 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo #(parameter int X = $);
+    int y = X + 1;
+    // CHECK: error: `$` cannot be used as a value
+endmodule
@@ -0,0 +1,18 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+    int a, b;
+    function int f();
+        return a;
+    endfunction
+    task t();
+        void'(f());
+    endtask
+    initial begin
+        void'(a++);
+        void'(b = a + 1);
+    end
+endmodule
+
+// CHECK: entity @foo () -> () {
+// CHECK-NOT: warning: return value of function `f` is discarded
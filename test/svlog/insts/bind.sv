@@ -0,0 +1,35 @@
+// RUN: moore %s -e top --list-instances
+// See §23.11 "Binding auxiliary code to scopes or instances".
+
+module top;
+    leaf u1();
+    leaf u2();
+    for (genvar i = 0; i < 2; i++) begin : g
+        leaf u();
+    end
+endmodule
+
+module leaf;
+    logic [3:0] a;
+endmodule
+
+module mon (input logic [3:0] x);
+endmodule
+
+bind leaf mon m_all(.x(a));
+bind top.u2 mon m_one(.x(a));
+bind top.g[1].u mon m_gen(.x(a));
+bind leaf: top.u1, top.g[0].u mon m_list(.x(a));
+
+// CHECK: top.g[0].u leaf
+// CHECK: top.g[0].u.m_all mon
+// CHECK: top.g[0].u.m_list mon
+// CHECK: top.g[1].u leaf
+// CHECK: top.g[1].u.m_all mon
+// CHECK: top.g[1].u.m_gen mon
+// CHECK: top.u1 leaf
+// CHECK: top.u1.m_all mon
+// CHECK: top.u1.m_list mon
+// CHECK: top.u2 leaf
+// CHECK: top.u2.m_all mon
+// CHECK: top.u2.m_one mon
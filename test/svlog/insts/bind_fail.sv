@@ -0,0 +1,32 @@
+// RUN: moore %s -e top
+// FAIL
+// See §23.11 "Binding auxiliary code to scopes or instances".
+
+module top;
+    leaf u1();
+    other u2();
+    for (genvar i = 0; i < 2; i++) begin : g
+        leaf u();
+    end
+endmodule
+
+module leaf;
+    logic a;
+endmodule
+
+module other;
+endmodule
+
+module mon (input logic x);
+endmodule
+
+bind top.u9 mon m0(.x(a));
+// CHECK: error: `u9` not found in module `top`
+bind top.g.u mon m1(.x(a));
+// CHECK: error: `top.g` is a generate loop; select one of its blocks with an index such as `top.g[0]`
+bind leaf: top.u2 mon m2(.x(a));
+// CHECK: error: `top.u2` is not an instance of module `leaf`
+bind top.u1.a mon m3(.x(a));
+// CHECK: error: `top.u1.a` is not a module instance
+bind top.u1 mon m4(.x(b));
+// CHECK: error: `b` not found
@@ -0,0 +1,16 @@
+// RUN: moore %s -e foo -O0
+checker req_ack (sequence req, property ack, untyped clk, logic en = 1);
+    assert property (@(posedge clk) en |-> ack);
+    inner i0 (req, ack);
+endchecker : req_ack
+
+checker inner (sequence s, property p);
+endchecker
+
+module foo;
+    logic clk, a, b;
+    req_ack c0 (a, b, clk);
+    req_ack c1 (.req(a), .ack(b), .clk(clk), .en(1'b0));
+endmodule
+
+// CHECK: entity @foo () -> () {
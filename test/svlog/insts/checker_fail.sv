@@ -0,0 +1,19 @@
+// RUN: moore %s -e foo
+// FAIL
+checker inner (sequence s, logic en);
+endchecker
+
+checker outer (property p, sequence s);
+    inner i0 (p, 1'b1);
+    // CHECK: error: cannot pass property `p` to sequence port `s`
+    inner i1 (s, s);
+    // CHECK: error: cannot pass sequence `s` to data port `en`
+endchecker
+
+module foo;
+    logic a;
+    inner c0 (a, a, a);
+    // CHECK: error: checker `inner` has 2 ports, but 3 were given
+    inner c1 (.s(a), .x(a));
+    // CHECK: error: checker `inner` has no port `x`
+endmodule
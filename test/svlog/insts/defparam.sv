@@ -0,0 +1,29 @@
+// RUN: moore %s -e top --list-instances
+// See §23.10.1 "defparam statement".
+
+module top;
+    localparam int N = 3;
+    fifo a();
+    fifo #(.WIDTH(2), .DEPTH(16)) b();
+    fifo c();
+    mid m();
+    defparam a.WIDTH = 8, b.WIDTH = N + 1;
+    defparam top.c.DEPTH = 2;
+    defparam c.DEPTH = 32;
+endmodule
+
+module mid;
+    fifo f();
+endmodule
+
+module fifo #(parameter int WIDTH = 1, parameter int DEPTH = 4);
+endmodule
+
+defparam top.m.f.WIDTH = 5;
+
+// CHECK: warning: parameter `DEPTH` of instance `c` overridden by multiple defparams
+// CHECK: top.a fifo #(WIDTH=8, DEPTH=4)
+// CHECK: top.b fifo #(WIDTH=4, DEPTH=16)
+// CHECK: top.c fifo #(WIDTH=1, DEPTH=32)
+// CHECK: top.m mid
+// CHECK: top.m.f fifo #(WIDTH=5, DEPTH=4)
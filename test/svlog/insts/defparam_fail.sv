@@ -0,0 +1,39 @@
+// RUN: moore %s -e t0 -e t1 -e t2 -e t3 -e t4
+// FAIL
+// See §23.10.1 "defparam statement".
+
+module fifo #(parameter int WIDTH = 1, localparam int BITS = 8, parameter type T = logic);
+    parameter int LIMIT = 4;
+    logic x;
+endmodule
+
+module t0;
+    fifo a();
+    defparam a.SIZE = 4;
+endmodule
+// CHECK: error: no parameter `SIZE` in module `fifo`
+
+module t1;
+    fifo a();
+    defparam a.BITS = 4;
+endmodule
+// CHECK: error: cannot override local parameter `BITS` of module `fifo`
+
+module t2;
+    fifo a();
+    defparam a.LIMIT = 4;
+endmodule
+// CHECK: error: cannot override local parameter `LIMIT` of module `fifo`
+
+module t3;
+    fifo a();
+    defparam a.T = 4;
+endmodule
+// CHECK: error: defparam cannot override type parameter `T`
+
+module t4;
+    fifo a();
+    defparam a.x = 4, WIDTH = 2;
+endmodule
+// CHECK: error: `a.x` is not a parameter
+// CHECK: error: `WIDTH` is not a hierarchical parameter path
@@ -0,0 +1,30 @@
+// RUN: moore %s -e top --dump-hierarchy=json
+
+module top (input logic clk, output logic [7:0] q);
+    leaf #(4) a(.x(q[3:0]));
+    for (genvar i = 0; i < 2; i++) begin : g
+        leaf #(i + 1) u();
+    end
+endmodule
+
+module leaf #(parameter int N = 0) (output logic [N-1:0] x);
+endmodule
+
+// CHECK: "name": "top",
+// CHECK: "path": "top",
+// CHECK: "target": "top",
+// CHECK: "name": "clk",
+// CHECK: "dir": "input",
+// CHECK: "type": "logic"
+// CHECK: "name": "q",
+// CHECK: "dir": "output",
+// CHECK: "type": "logic [7:0]"
+// CHECK: "children": [
+// CHECK: "path": "top.a",
+// CHECK: "target": "leaf",
+// CHECK: "name": "N",
+// CHECK: "value": "4"
+// CHECK: "type": "logic [3:0]"
+// CHECK: "path": "top.g[0].u",
+// CHECK: "path": "top.g[1].u",
+// CHECK: "type": "logic [1:0]"
@@ -0,0 +1,34 @@
+// RUN: moore %s -e top --list-instances
+// See §27.5 "Conditional generate constructs".
+
+module top;
+    generate
+        for (genvar i = 0; i < 3; i++) begin : g
+            case (i)
+                0: begin : first
+                    leaf #(10) u();
+                end
+                1, 2: begin : other
+                    for (genvar j = 0; j < i; j++) begin : h
+                        leaf #(i * 10 + j) u();
+                    end
+                end
+            endcase
+        end
+    endgenerate
+    case (4)
+        1: leaf #(1) never();
+        default: begin
+            leaf #(99) fallback();
+        end
+    endcase
+endmodule
+
+module leaf #(parameter int N = 0);
+endmodule
+
+// CHECK: top.g[0].first.u leaf #(N=10)
+// CHECK: top.g[1].other.h[0].u leaf #(N=10)
+// CHECK: top.g[2].other.h[0].u leaf #(N=20)
+// CHECK: top.g[2].other.h[1].u leaf #(N=21)
+// CHECK: top.genblk2.fallback leaf #(N=99)
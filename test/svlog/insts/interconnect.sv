@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo -O0
+module foo;
+    interconnect bus;
+    bar a (.x(bus));
+    baz b (.y(bus));
+endmodule
+
+module bar (output logic [7:0] x);
+endmodule
+
+module baz (input logic [7:0] y);
+endmodule
+
+// CHECK: entity @foo () -> () {
+// CHECK:     %bus = sig i8 %0
@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+    interconnect bus;
+    bar a (.x(bus));
+    baz b (.y(bus));
+    // CHECK: error: interconnect `bus` connects ports of conflicting types `logic [7:0]` and `logic [3:0]`
+endmodule
+
+module bar (output logic [7:0] x);
+endmodule
+
+module baz (input logic [3:0] y);
+endmodule
@@ -0,0 +1,30 @@
+// RUN: moore %s -e top --list-instances
+
+module top;
+    leaf #(1) a();
+    for (genvar i = 0; i < 2; i++) begin : g
+        leaf #(i + 2) u();
+    end
+    if (1) begin
+        mid m();
+    end
+    for (genvar i = 8; i < 11; i++) begin : h
+        leaf #(i) u();
+    end
+endmodule
+
+module mid;
+    leaf #(7) x();
+endmodule
+
+module leaf #(parameter int N = 0);
+endmodule
+
+// CHECK: top.a leaf #(N=1)
+// CHECK: top.g[0].u leaf #(N=2)
+// CHECK: top.g[1].u leaf #(N=3)
+// CHECK: top.genblk2.m mid
+// CHECK: top.genblk2.m.x leaf #(N=7)
+// CHECK: top.h[8].u leaf #(N=8)
+// CHECK: top.h[9].u leaf #(N=9)
+// CHECK: top.h[10].u leaf #(N=10)
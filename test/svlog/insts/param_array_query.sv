@@ -0,0 +1,32 @@
+// RUN: moore %s -e top --list-instances
+// See §20.6.2 "Expression size system function" and §20.7 "Array querying
+// functions".
+
+typedef struct packed {
+    logic [3:0] a;
+    logic [11:0] b;
+} pair_t;
+
+typedef logic [7:0] word_t [0:3];
+
+module top;
+    foo a();
+    foo #(.W(5)) b();
+endmodule
+
+module foo #(
+    parameter int BITS = $bits(pair_t),
+    parameter int WORDS = $size(word_t),
+    parameter int WIDTH = $size(word_t, 2),
+    parameter int LEFT = $left(word_t, 2),
+    parameter int HIGH = $high(word_t),
+    parameter int W = 3,
+    parameter logic [W:0] V = 0,
+    parameter int VS = $size(V),
+    parameter int WL = $left(W),
+    parameter int WR = $right(W, 1)
+);
+endmodule
+
+// CHECK: top.a foo #(BITS=16, WORDS=4, WIDTH=8, LEFT=7, HIGH=3, W=3, V=0, VS=4, WL=31, WR=0)
+// CHECK: top.b foo #(BITS=16, WORDS=4, WIDTH=8, LEFT=7, HIGH=3, W=5, V=0, VS=6, WL=31, WR=0)
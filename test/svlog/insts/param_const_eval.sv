@@ -0,0 +1,23 @@
+// RUN: moore %s -e top --list-instances
+// See §11.2.1 "Constant expressions".
+
+module top;
+    fifo a();
+    fifo #(.DEPTH(5)) b();
+    fifo #(.DEPTH(0)) c();
+endmodule
+
+module fifo #(
+    parameter int DEPTH = 16,
+    parameter int AW = $clog2(DEPTH),
+    parameter int BW = $bits(logic [AW:0]),
+    parameter int PER = DEPTH > 0 ? 64 / DEPTH : 0,
+    parameter bit [3:0] LOW = 8'hAB,
+    parameter logic [7:0] U = -1,
+    parameter int Z = U
+);
+endmodule
+
+// CHECK: top.a fifo #(DEPTH=16, AW=4, BW=5, PER=4, LOW=11, U=255, Z=255)
+// CHECK: top.b fifo #(DEPTH=5, AW=3, BW=4, PER=12, LOW=11, U=255, Z=255)
+// CHECK: top.c fifo #(DEPTH=0, AW=0, BW=1, PER=0, LOW=11, U=255, Z=255)
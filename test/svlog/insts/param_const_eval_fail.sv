@@ -0,0 +1,16 @@
+// RUN: moore %s -e t0 -e t2
+// FAIL
+// See §11.2.1 "Constant expressions".
+
+module t0;
+    int x;
+    localparam int P = x + 1;
+    logic [P:0] y;
+endmodule
+// CHECK: error: `x` is not constant
+
+module t2;
+    logic [7:0] a, b;
+    assign a = {-1{b}};
+endmodule
+// CHECK: error: replication count `-1` is negative
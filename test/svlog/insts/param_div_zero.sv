@@ -0,0 +1,17 @@
+// RUN: moore %s -e foo
+// See §11.4.2 "Arithmetic operators".
+
+module foo;
+    localparam int Z = 0;
+    // Division by zero yields x, which is zero in a two-valued type.
+    localparam int P = 8 / Z;
+    localparam int Q = 8 % Z;
+    logic [P:Q] y;
+    // A guarded division by zero is legal.
+    localparam int R = Z != 0 ? 8 / Z : 4;
+    logic [R:0] z;
+endmodule
+// CHECK: warning: `8 / Z` divides by zero
+// CHECK: warning: `8 % Z` divides by zero
+// CHECK: %y = sig i1 %0
+// CHECK: %z = sig i5 %1
@@ -0,0 +1,25 @@
+// RUN: moore %s -e top --list-instances
+// See §23.10 "Overriding module parameters".
+
+module top;
+    fifo #(.WIDTH(8), .DEPTH(16)) a();
+    fifo #(4, 32) b();
+    fifo #(.DEPTH(2)) c();
+    fifo #(5) d();
+    fifo e();
+    regs #(3) f();
+endmodule
+
+module fifo #(parameter int WIDTH = 1, localparam int BITS = 8, parameter int DEPTH = 4);
+endmodule
+
+module regs;
+    parameter int N = 1;
+endmodule
+
+// CHECK: top.a fifo #(WIDTH=8, DEPTH=16)
+// CHECK: top.b fifo #(WIDTH=4, DEPTH=32)
+// CHECK: top.c fifo #(WIDTH=1, DEPTH=2)
+// CHECK: top.d fifo #(WIDTH=5, DEPTH=4)
+// CHECK: top.e fifo #(WIDTH=1, DEPTH=4)
+// CHECK: top.f regs
@@ -0,0 +1,37 @@
+// RUN: moore %s -e t0 -e t1 -e t2 -e t3 -e t4 -e t5
+// FAIL
+// See §23.10 "Overriding module parameters".
+
+module fifo #(parameter int WIDTH = 1, localparam int BITS = 8, parameter int DEPTH = 4);
+    parameter int LIMIT = DEPTH - 1;
+endmodule
+
+module t0;
+    fifo #(8, .DEPTH(16)) a();
+endmodule
+// CHECK: error: cannot mix positional and named parameter assignments
+
+module t1;
+    fifo #(1, 2, 3) a();
+endmodule
+// CHECK: error: module `fifo` only has 2 parameter(s)
+
+module t2;
+    fifo #(.SIZE(4)) a();
+endmodule
+// CHECK: error: no parameter `SIZE` in module `fifo`
+
+module t3;
+    fifo #(.BITS(4)) a();
+endmodule
+// CHECK: error: cannot override local parameter `BITS` of module `fifo`
+
+module t4;
+    fifo #(.WIDTH(1), .WIDTH(2)) a();
+endmodule
+// CHECK: error: parameter `WIDTH` assigned more than once
+
+module t5;
+    fifo #(.LIMIT(2)) a();
+endmodule
+// CHECK: error: cannot override local parameter `LIMIT` of module `fifo`
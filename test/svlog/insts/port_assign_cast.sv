@@ -0,0 +1,33 @@
+// RUN: moore %s -e foo
+
+module Y (output logic [7:0] y);
+endmodule
+
+module foo;
+    logic signed [7:0] a;
+    logic [3:0] b;
+    logic [15:0] c;
+    Y i0 (a);
+    Y i1 (b);
+    Y i2 (c);
+endmodule
+
+// CHECK: entity @foo () -> () {
+// CHECK:     %0 = const i8 0
+// CHECK:     %a = sig i8 %0
+// CHECK:     %1 = const i4 0
+// CHECK:     %b = sig i4 %1
+// CHECK:     %2 = const i16 0
+// CHECK:     %c = sig i16 %2
+// CHECK:     inst @Y.param1 () -> (i8$ %a)
+// CHECK:     %3 = sig i8 %0
+// CHECK:     %4 = exts i4$, i8$ %3, 0, 4
+// CHECK:     con i4$ %b, %4
+// CHECK:     inst @Y.param1 () -> (i8$ %3)
+// CHECK:     %5 = sig i8 %0
+// CHECK:     %6 = prb i8$ %5
+// CHECK:     %zext = inss i16 %2, i8 %6, 0, 8
+// CHECK:     %7 = const time 0s 1e
+// CHECK:     drv i16$ %c, %zext, %7
+// CHECK:     inst @Y.param1 () -> (i8$ %5)
+// CHECK: }
@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo (bar.in x);
+	assign x.data = 42;
+endmodule
+
+interface bar;
+	logic [31:0] data;
+	logic ready;
+
+	modport in (input data, output ready);
+endinterface
+
+// CHECK: error: `data` cannot be assigned through modport `in`
@@ -0,0 +1,23 @@
+// RUN: moore %s -e foo
+// See §25.7 "Tasks and functions in interfaces".
+
+module foo (bar.mp x, output int y);
+    assign y = $bits(x.get());
+endmodule
+
+interface bar;
+    logic [7:0] data;
+
+    function logic [7:0] get();
+        return data;
+    endfunction
+
+    task put(logic [7:0] value);
+        data = value;
+    endtask
+
+    modport mp (input data, import get, task put(logic [7:0] value));
+    modport other (export get);
+endinterface
+
+// CHECK: entity @foo (i8$ %x.data) -> (i32$ %y) {
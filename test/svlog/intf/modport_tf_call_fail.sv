@@ -0,0 +1,19 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §25.7 "Tasks and functions in interfaces".
+
+module foo (bar.mp x, output logic [7:0] y);
+    assign y = x.get();
+endmodule
+
+interface bar;
+    logic [7:0] data;
+
+    function logic [7:0] get();
+        return data;
+    endfunction
+
+    modport mp (input data, import get);
+endinterface
+
+// CHECK: error: unsupported: call to `get` of interface `x`
@@ -0,0 +1,13 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §25.7.4 "Exporting tasks and functions".
+
+module foo (bar.mp x);
+endmodule
+
+interface bar;
+    logic [7:0] data;
+    modport mp (input data, export task put(logic [7:0] value));
+endinterface
+
+// CHECK: error: unsupported: modport `mp` exports `put`
@@ -0,0 +1,19 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §25.7 "Tasks and functions in interfaces".
+
+module foo (bar.mp x, output int y);
+    assign y = $bits(x.get());
+endmodule
+
+interface bar;
+    logic [7:0] data;
+
+    function logic [7:0] get();
+        return data;
+    endfunction
+
+    modport mp (input data);
+endinterface
+
+// CHECK: error: `get` is not imported by modport `mp`
@@ -0,0 +1,19 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §25.7 "Tasks and functions in interfaces".
+
+module foo (bar.mp x);
+    initial x.put(8'd42);
+endmodule
+
+interface bar;
+    logic [7:0] data;
+
+    task put(logic [7:0] value);
+        data = value;
+    endtask
+
+    modport mp (output data, import put);
+endinterface
+
+// CHECK: error: unsupported: call to `put` of interface `x`
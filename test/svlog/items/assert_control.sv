@@ -0,0 +1,12 @@
+// RUN: moore %s -e foo
+
+module foo;
+    initial begin
+        $assertpasson();
+        // CHECK: warning: unsupported: assertion control task `$assertpasson`; ignored
+        $assertoff(0);
+        // CHECK: warning: unsupported: assertion control task `$assertoff`; ignored
+        $assertcontrol(3);
+        // CHECK: warning: unsupported: assertion control task `$assertcontrol`; ignored
+    end
+endmodule
@@ -0,0 +1,19 @@
+// RUN: moore %s -e foo
+
+module foo;
+    task automatic fact(input int n, output int r);
+        int tmp;
+        if (n <= 1) begin
+            r = 1;
+        end else begin
+            fact(n - 1, tmp);
+            r = n * tmp;
+        end
+    endtask
+
+    function automatic int fib(int n);
+        static int calls = 0;
+        calls++;
+        return n < 2 ? n : fib(n - 1) + fib(n - 2);
+    endfunction
+endmodule
@@ -0,0 +1,10 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    task automatic delayed(input int n);
+        int tmp;
+        tmp <= n;
+    endtask
+endmodule
+// CHECK: error: automatic variable `tmp` cannot be assigned with a nonblocking assignment
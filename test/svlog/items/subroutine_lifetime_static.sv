@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo
+
+module foo;
+    task fact(input int n, output int r);
+        int tmp;
+        if (n <= 1) begin
+            r = 1;
+        end else begin
+            fact(n - 1, tmp);
+            r = n * tmp;
+        end
+    endtask
+endmodule
+// CHECK: warning: recursive call to static task `fact`
@@ -0,0 +1,8 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    real r;
+    initial r <<= 1;
+endmodule
+// CHECK: error: `<<=` cannot be applied to `r` of type `real`
@@ -0,0 +1,8 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    real r, s;
+    initial r += s;
+endmodule
+// CHECK: error: unsupported: `+=` on `r` of type `real`
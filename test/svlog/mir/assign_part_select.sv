@@ -0,0 +1,48 @@
+// RUN: moore %s -e foo
+// See §11.5.1 "Vector bit-select and part-select addressing".
+
+module foo;
+    logic [7:0] a;
+    logic [0:7] b;
+    logic [8:1] c;
+    logic [1:8] d;
+    int i;
+
+    initial begin
+        a[5 -: 3] = 5;
+        // CHECK: %1 = const i32 3
+        // CHECK: %2 = const i8 0
+        // CHECK: %3 = sig i8 %2
+        // CHECK: %4 = shr i8$ %a, i8$ %3, i32 %1
+        // CHECK: %5 = exts i3$, i8$ %4, 0, 3
+        // CHECK: %6 = const i3 5
+        // CHECK: %7 = const time 0s 1e
+        // CHECK: drv i3$ %5, %6, %7
+        b[0 +: 3] = 5;
+        // CHECK: %8 = const i32 5
+        // CHECK: %9 = sig i8 %2
+        // CHECK: %10 = shr i8$ %b, i8$ %9, i32 %8
+        // CHECK: %11 = exts i3$, i8$ %10, 0, 3
+        // CHECK: drv i3$ %11, %6, %7
+        c[6 -: 3] = 5;
+        // CHECK: %12 = sig i8 %2
+        // CHECK: %13 = shr i8$ %c, i8$ %12, i32 %1
+        // CHECK: %14 = exts i3$, i8$ %13, 0, 3
+        // CHECK: drv i3$ %14, %6, %7
+        d[2 +: 3] = 5;
+        // CHECK: %15 = const i32 4
+        // CHECK: %16 = sig i8 %2
+        // CHECK: %17 = shr i8$ %d, i8$ %16, i32 %15
+        // CHECK: %18 = exts i3$, i8$ %17, 0, 3
+        // CHECK: drv i3$ %18, %6, %7
+        a[i +: 3] = 5;
+        // CHECK: %i.prb = prb i32$ %i
+        // CHECK: %19 = sig i8 %2
+        // CHECK: %20 = shr i8$ %a, i8$ %19, i32 %i.prb
+        // CHECK: %21 = exts i3$, i8$ %20, 0, 3
+        // CHECK: drv i3$ %21, %6, %7
+        b[i -: 3] = 5;
+        c[i +: 3] = 5;
+        d[i -: 3] = 5;
+    end
+endmodule
@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    int q[$];
+    int a[int];
+    initial begin
+        q[0] = 42;
+        a[1] = 42;
+    end
+endmodule
+
+// CHECK: error: unsupported: assignment to an element of a queue
+// CHECK: error: unsupported: assignment to an element of an associative array
@@ -0,0 +1,10 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    int n;
+    logic [7:0] a;
+    initial {n{a}} = 16'h1234;
+endmodule
+// CHECK: error: `n` is not constant
+// CHECK: = note: Replication counts in assignment targets must be constant
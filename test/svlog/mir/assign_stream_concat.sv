@@ -0,0 +1,56 @@
+// RUN: moore %s -e foo
+// See §11.4.14.3 "Streaming concatenation as an assignment target".
+
+module foo;
+    logic [31:0] packet;
+    logic [7:0] a, b;
+    logic [15:0] c;
+    logic [11:0] d;
+    logic [5:0] e, f;
+    logic [3:0] g, h;
+    initial begin
+        // The size is not a multiple of the slice size, such that the block
+        // at the MSB of the target is shorter.
+        {<< 4 {e}} = f;
+        // e[5:4] = f[1:0]
+        // CHECK: %1 = const i32 4
+        // CHECK: %2 = const i6 0
+        // CHECK: %3 = sig i6 %2
+        // CHECK: %4 = shr i6$ %e, i6$ %3, i32 %1
+        // CHECK: %5 = exts i2$, i6$ %4, 0, 2
+        // CHECK: %f.prb = prb i6$ %f
+        // CHECK: %6 = exts i2, i6 %f.prb, 0, 2
+        // CHECK: %7 = const time 0s 1e
+        // CHECK: drv i2$ %5, %6, %7
+        // e[3:0] = f[5:2]
+        // CHECK: %8 = exts i4$, i6$ %e, 0, 4
+        // CHECK: %9 = exts i4, i6 %f.prb, 2, 4
+        // CHECK: %10 = inss i6 %2, i4 %9, 0, 4
+        // CHECK: %11 = exts i4, i6 %10, 0, 4
+        // CHECK: drv i4$ %8, %11, %7
+
+        // The size is a multiple of the slice size.
+        {<< 2 {g}} = h;
+        // g[3:2] = h[1:0]
+        // CHECK: %12 = const i32 2
+        // CHECK: %13 = const i4 0
+        // CHECK: %14 = sig i4 %13
+        // CHECK: %15 = shr i4$ %g, i4$ %14, i32 %12
+        // CHECK: %16 = exts i2$, i4$ %15, 0, 2
+        // CHECK: %h.prb = prb i4$ %h
+        // CHECK: %17 = exts i2, i4 %h.prb, 0, 2
+        // CHECK: drv i2$ %16, %17, %7
+        // g[1:0] = h[3:2]
+        // CHECK: %18 = exts i2$, i4$ %g, 0, 2
+        // CHECK: %19 = exts i2, i4 %h.prb, 2, 2
+        // CHECK: %20 = inss i4 %13, i2 %19, 0, 2
+        // CHECK: %21 = exts i2, i4 %20, 0, 2
+        // CHECK: drv i2$ %18, %21, %7
+
+        {>> {a, b, c}} = packet;
+        {<< {a, b, c}} = packet;
+        {<< 8 {a, b, c}} = packet;
+        {<< byte {c, a, b}} = packet;
+        {<< 8 {d}} = packet[11:0];
+    end
+endmodule
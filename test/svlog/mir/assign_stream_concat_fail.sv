@@ -0,0 +1,9 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    logic [15:0] packet;
+    logic [7:0] a, b;
+    initial {<< 0 {a, b}} = packet;
+endmodule
+// CHECK: error: slice size of streaming concatenation must be a positive constant, but `0` is not
@@ -0,0 +1,21 @@
+// RUN: moore %s -e foo
+
+module foo;
+    union packed {
+        logic [7:0] a;
+        bit [7:0] b;
+    } u;
+    logic [3:0] x;
+    initial begin
+        // All members of the union are assigned.
+        {u, x} = 12'h123;
+        // CHECK: %1 = const i4 3
+        // CHECK: %2 = const time 0s 1e
+        // CHECK: drv i4$ %x, %1, %2
+        // CHECK: %3 = extf i8$, {i8, i8}$ %u, 0
+        // CHECK: %4 = const i8 18
+        // CHECK: drv i8$ %3, %4, %2
+        // CHECK: %5 = extf i8$, {i8, i8}$ %u, 1
+        // CHECK: drv i8$ %5, %4, %2
+    end
+endmodule
@@ -0,0 +1,12 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    union {
+        logic [7:0] a;
+        logic [3:0] b;
+    } u;
+    logic [11:0] x;
+    initial x = {u, 4'h0};
+endmodule
+// CHECK: error: members of `union { logic [7:0] a; logic [3:0] b; }` must all have the same width to be packed
@@ -0,0 +1,26 @@
+// RUN: moore %s -e foo
+
+module foo (
+    input logic [15:0] v,
+    output logic [3:0] a,
+    output logic [11:0] b
+);
+    typedef struct packed {
+        logic [3:0] x;
+        logic [11:0] y;
+    } s_t;
+
+    // CHECK: entity @foo (i16$ %v) -> (i4$ %a, i12$ %b) {
+    // CHECK: %0 = const time 0s 1e
+
+    // The first field occupies the most significant bits.
+    assign a = s_t'(v).x;
+    // CHECK: %v.prb = prb i16$ %v
+    // CHECK: %1 = exts i4, i16 %v.prb, 12, 4
+    // CHECK: drv i4$ %a, %1, %0
+
+    assign b = s_t'(v).y;
+    // CHECK: %2 = exts i12, i16 %v.prb, 0, 12
+    // CHECK: drv i12$ %b, %2, %0
+endmodule
+// CHECK: }
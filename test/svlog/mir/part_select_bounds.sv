@@ -0,0 +1,20 @@
+// RUN: moore %s -e foo
+
+module foo;
+    logic [7:0] a;
+    logic [8:1] b;
+    logic [3:0] z;
+    int i;
+
+    assign z = a[4 +: 4];
+    assign z = a[3 -: 4];
+    assign z = b[5 +: 4];
+    assign z = a[i +: 4];
+
+    assign z = a[6 +: 4];
+    // CHECK: warning: part-select `a[6 +: 4]` is out of bounds
+    assign z = a[2 -: 4];
+    // CHECK: warning: part-select `a[2 -: 4]` is out of bounds
+    assign b[6 +: 4] = z;
+    // CHECK: warning: part-select `b[6 +: 4]` is out of bounds
+endmodule
@@ -0,0 +1,31 @@
+// RUN: moore %s -e a0 -e a1
+// 10.9 "Assignment patterns" used as the target of an assignment
+
+module a0;
+	struct { logic [3:0] x; logic y; } s;
+	logic [3:0] a;
+	logic b;
+	assign '{a, b} = s;
+endmodule
+
+// The pattern is split into one assignment per member.
+// CHECK: %3 = const time 0s 1e
+// CHECK: %s.prb = prb {i4, i1}$ %s
+// CHECK: %4 = extf i4, {i4, i1} %s.prb, 0
+// CHECK: drv i4$ %a, %4, %3
+// CHECK: %5 = extf i1, {i4, i1} %s.prb, 1
+// CHECK: drv i1$ %b, %5, %3
+
+module a1;
+	int q [2];
+	int c, d;
+	struct packed { byte x; byte y; } p;
+	byte e, f;
+	initial begin
+		'{c, d} = q;
+		'{y: f, x: e} = p;
+	end
+endmodule
+
+// CHECK: %q.prb = prb [2 x i32]$ %q
+// CHECK: %p.prb = prb i16$ %p
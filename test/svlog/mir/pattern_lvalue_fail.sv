@@ -0,0 +1,16 @@
+// RUN: moore %s -e a0 -e a1
+// FAIL
+
+module a0;
+	struct { int x; int y; } s;
+	int a;
+	initial '{x: a, default: a} = s;
+endmodule
+// CHECK: error: `default:` cannot be used in a pattern that is assigned to
+
+module a1;
+	int q [2];
+	int a;
+	initial '{2{a}} = q;
+endmodule
+// CHECK: error: replication cannot be used in a pattern that is assigned to
@@ -0,0 +1,28 @@
+// RUN: moore %s -e a0
+// 10.9.1 "Array assignment patterns"
+
+module a0;
+	int a [4];
+	byte b [2:0];
+	assign a = '{1: 5, int: 7};
+	assign b = '{byte: 1, default: 0};
+endmodule
+
+// CHECK: %0 = const i32 0
+// CHECK: %1 = [i32 %0, %0, %0, %0]
+// CHECK: %a = sig [4 x i32] %1
+// CHECK: %2 = const i8 0
+// CHECK: %3 = [i8 %2, %2, %2]
+// CHECK: %b = sig [3 x i8] %3
+// CHECK: %4 = const time 0s 1e
+
+// a = '{7, 5, 7, 7}
+// CHECK: %5 = const i32 7
+// CHECK: %6 = const i32 5
+// CHECK: %7 = [i32 %5, %6, %5, %5]
+// CHECK: drv [4 x i32]$ %a, %7, %4
+
+// b = '{1, 1, 1}
+// CHECK: %8 = const i8 1
+// CHECK: %9 = [i8 %8, %8, %8]
+// CHECK: drv [3 x i8]$ %b, %9, %4
@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo
+
+module foo (input logic [7:0] a, output logic [7:0] x);
+    logic [7:0] y;
+
+    // Overlapping drivers are flagged.
+    assign y[4:0] = a[4:0];
+    always_comb y[7:3] = a[7:3];
+
+    // Output ports are checked as well.
+    assign x = a;
+    always_comb x = ~a;
+endmodule
+// CHECK: warning: `y` is driven by multiple combinational processes
+// CHECK: warning: `x` is driven by multiple combinational processes
@@ -0,0 +1,19 @@
+// RUN: moore %s -e foo
+
+module foo (input logic [7:0] a, output logic [7:0] x);
+    typedef struct packed { logic [3:0] hi; logic [3:0] lo; } pair_t;
+    logic [7:0] z;
+    pair_t p;
+
+    // Disjoint part-selects and struct fields are fine.
+    assign z[3:0] = a[3:0];
+    always_comb z[7:4] = a[7:4];
+    assign p.hi = a[7:4];
+    always_comb p.lo = a[3:0];
+    assign x[3:0] = a[3:0];
+    always_comb x[7:4] = a[7:4];
+endmodule
+// CHECK: entity @foo (i8$ %a) -> (i8$ %x) {
+// CHECK-NOT: warning: `z` is driven by multiple combinational processes
+// CHECK-NOT: warning: `p` is driven by multiple combinational processes
+// CHECK-NOT: warning: `x` is driven by multiple combinational processes
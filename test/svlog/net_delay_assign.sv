@@ -0,0 +1,26 @@
+// RUN: moore %s -e foo
+
+module foo;
+    bit a;
+
+    // The delay of a net applies to continuous assignments to it, not just to
+    // its declaration assignment.
+    wire #5ns w;
+    assign w = a;
+    // CHECK: %1 = const time 5ns
+    // CHECK: %2 = const time 0s 1e
+    // CHECK:     %value = prb i1$ %in
+    // CHECK:     wait %check for %1, %in
+    // CHECK:     drv i1$ %out, %value, %2
+
+    // An assignment delay is applied before the net delay.
+    wire #5ns v;
+    assign #3ns v = a;
+    // CHECK: %1 = const time 3ns
+    // CHECK:     wait %check for %1, %in
+    // CHECK: %1 = const time 5ns
+    // CHECK:     wait %check for %1, %in
+
+    // CHECK: entity @foo () -> () {
+    // CHECK: %w = sig i1 %0
+endmodule
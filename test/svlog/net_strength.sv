@@ -0,0 +1,11 @@
+// RUN: moore %s -e foo
+module foo (output wire y, output wire z);
+    supply0 gnd;
+    supply1 vdd;
+    wire (weak0, weak1) w = 1'b0;
+    assign w = 1'b1;
+    assign (pull1, strong0) y = 1'b1;
+    assign y = vdd;
+    trireg (medium) t;
+    assign z = w;
+endmodule
@@ -0,0 +1,10 @@
+// RUN: moore %s -e foo
+module foo;
+    wire w;
+    assign w = 1'b0;
+    assign (weak0, weak1) w = 1'b1;
+    wire v;
+    assign v = 1'b0;
+    assign v = 1'b1;
+endmodule
+// CHECK: warning: net `v` is driven to different values with equal strength
@@ -0,0 +1,8 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+    wire (strong0, pull0) w;
+    wire (small) v;
+endmodule
+// CHECK: error: invalid drive strength `(strong0, pull0)`
+// CHECK: error: charge strength can only be given for `trireg` nets
@@ -0,0 +1,9 @@
+// RUN: moore %s
+// See §22.14 "`begin_keywords, `end_keywords".
+
+`begin_keywords "1364-2005"
+module foo;
+    wire global, soft;
+    assign global = soft;
+endmodule
+`end_keywords
@@ -0,0 +1,9 @@
+// RUN: moore %s
+// FAIL
+// See §22.14 "`begin_keywords, `end_keywords".
+
+`begin_keywords "1364-2099"
+module foo;
+endmodule
+`end_keywords
+// CHECK: fatal: unknown `begin_keywords version specifier `1364-2099`
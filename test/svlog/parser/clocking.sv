@@ -0,0 +1,28 @@
+// RUN: moore %s
+// See §14 "Clocking blocks".
+
+interface bus (input logic clk);
+    logic req, gnt;
+    logic [7:0] data;
+
+    clocking cb @(posedge clk);
+        default input #1step output #2;
+        input gnt;
+        output req, data;
+    endclocking : cb
+
+    clocking mon @(negedge clk);
+        input #0 req, gnt;
+        inout data;
+    endclocking
+
+    default clocking cb;
+endinterface
+
+module foo (input logic clk);
+    logic a, b;
+    default clocking @(posedge clk);
+        input a;
+        input #0 output #1ns b;
+    endclocking
+endmodule
@@ -0,0 +1,11 @@
+// RUN: moore %s --pp --pp-directives
+
+`define WIDTH 8
+`ifdef WIDTH
+logic [`WIDTH-1:0] x;
+`else
+logic y;
+`endif
+// CHECK: /* `define WIDTH 8 */
+// CHECK: /* `ifdef WIDTH */
+// CHECK: logic [8-1:0] x;
@@ -0,0 +1,6 @@
+// RUN: moore %s --pp --pp-directives
+
+// Directives that share a line with code must not comment out the rest of
+// the line.
+module m(input a `ifndef FOO , input b `endif ); endmodule
+// CHECK: module m(input a /* `ifndef FOO */ , input b /* `endif */ ); endmodule
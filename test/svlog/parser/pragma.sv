@@ -0,0 +1,7 @@
+// RUN: moore %s
+// See §22.11 "`pragma".
+
+`pragma protect author = "Foo", author_info = "Bar"
+`pragma reset protect
+module foo;
+endmodule
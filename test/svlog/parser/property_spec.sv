@@ -0,0 +1,15 @@
+// RUN: moore %s
+
+module foo (input logic clk, input logic rst, input logic a, input logic b, input logic c);
+    assert property (@(posedge clk) disable iff (rst) a |-> b);
+    assume property (@(posedge clk) a |=> b);
+    cover property (@(posedge clk) not (a and b));
+    assert property (a);
+    assert property (@(posedge clk) a |-> ##1 b);
+    assert property (@(posedge clk) a ##1 b |=> c);
+    assert property (@(posedge clk) a |-> b[*3]);
+    assert property (@(posedge clk) a |-> ##[1:3] b);
+    assert property (@(posedge clk) a[*1:$] ##1 b);
+    assert property (@(posedge clk) a throughout b ##1 c);
+    assert property (@(posedge clk) s_eventually a);
+endmodule
@@ -0,0 +1,13 @@
+// RUN: moore %s
+
+module foo;
+    logic [7:0] a, b;
+    logic [15:0] c;
+    initial begin
+        c = {>> {a, b}};
+        c = {<< {a, b}};
+        c = {<< 4 {a, b}};
+        c = {<< byte {a, b}};
+        c = {>> {a with [0 +: 2], b}};
+    end
+endmodule
@@ -0,0 +1,17 @@
+// RUN: moore %s
+
+timeunit 1ns;
+timeprecision 10ps;
+
+package foo;
+    timeunit 100us / 1ns;
+endpackage
+
+module bar;
+    timeunit 10ns;
+    timeprecision 1fs;
+endmodule
+
+interface baz;
+    timeunit 1ms / 1us;
+endinterface
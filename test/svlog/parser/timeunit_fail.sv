@@ -0,0 +1,7 @@
+// RUN: moore %s
+// FAIL
+
+module foo;
+    timeunit 5ns;
+    // CHECK: error: time unit must be 1, 10, or 100, instead got `5ns`
+endmodule
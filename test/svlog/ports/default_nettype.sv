@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo -e bar -Vports
+`default_nettype tri
+module foo (input [7:0] a, output b);
+    // CHECK: Ports of `foo`:
+    // CHECK: 0: input tri logic [7:0] a
+    // CHECK: 1: output tri logic b
+endmodule
+
+`default_nettype none
+module bar (input wire a, input logic b);
+    // CHECK: Ports of `bar`:
+    // CHECK: 0: input wire logic a
+    // CHECK: 1: input var logic b
+endmodule
+`resetall
@@ -0,0 +1,6 @@
+// RUN: moore %s -e foo
+// FAIL
+`default_nettype none
+module foo (input a);
+    // CHECK: error: port `a` has no net type, but implicit nets are disabled
+endmodule
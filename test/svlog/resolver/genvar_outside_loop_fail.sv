@@ -0,0 +1,10 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+  genvar i;
+  for (i = 0; i < 2; i++) begin
+    int x = i;
+  end
+  int y = i;
+  // CHECK: error: genvar `i` used outside of a generate loop
+endmodule
@@ -0,0 +1,18 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+    // Sibling loops may declare genvars of the same name.
+    for (genvar i = 0; i < 2; i++) begin
+        int x = i;
+    end
+    for (genvar i = 4; i < 6; i++) begin
+        int y = i;
+    end
+    // A module-level genvar may be reused across loops and shadowed.
+    genvar k;
+    for (k = 0; k < 2; k++) begin
+        for (genvar k = 8; k < 9; k++) begin
+            int z = k;
+        end
+    end
+endmodule
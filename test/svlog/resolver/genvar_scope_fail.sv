@@ -0,0 +1,9 @@
+// RUN: moore %s
+// FAIL
+module foo;
+  for (genvar i = 0; i < 2; i++) begin
+    int x = i;
+  end
+  int y = i;
+  // CHECK: error: `i` not found
+endmodule
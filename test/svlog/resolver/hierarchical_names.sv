@@ -0,0 +1,22 @@
+// RUN: moore %s -e top -O0
+// See §23.6 "Hierarchical names".
+
+module top (output logic [7:0] y);
+    if (1) begin : g_on
+        logic [7:0] q;
+    end
+    sub #(5) u_sub();
+    initial top.g_on.q = 8'd42;
+    assign y = g_on.q + u_sub.g_lanes[1].K;
+endmodule
+
+module sub #(parameter int N = 0);
+    for (genvar i = 0; i < 2; i++) begin : g_lanes
+        localparam int K = N + i;
+    end
+endmodule
+
+// CHECK: entity @top () -> (i8$ %y) {
+// CHECK: %0 = const i8 0
+// CHECK: %q = sig i8 %0
+// CHECK: %q.prb = prb i8$ %q
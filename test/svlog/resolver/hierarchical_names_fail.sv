@@ -0,0 +1,30 @@
+// RUN: moore %s -e top
+// FAIL
+// See §23.6 "Hierarchical names".
+
+module top;
+    sub #(5) u_sub();
+    if (0) begin : g_off
+        int v;
+    end
+    int x;
+    initial begin
+        x = u_sub.g_lanes[2].K;
+        // CHECK: error: `u_sub.g_lanes[2]` does not exist; generate loop `u_sub.g_lanes` has no block with index 2
+        x = u_sub.nope;
+        // CHECK: error: `nope` not found in module `sub`
+        x = g_off.v;
+        // CHECK: error: `g_off` is not elaborated in this design
+        x = u_sub.g_lanes.K;
+        // CHECK: error: `u_sub.g_lanes` is a generate loop; select one of its blocks with an index such as `u_sub.g_lanes[0]`
+        x = u_sub.g_lanes[0].v;
+        // CHECK: error: unsupported: reference to `u_sub.g_lanes[0].v` in another instance or generate loop iteration
+    end
+endmodule
+
+module sub #(parameter int N = 0);
+    for (genvar i = 0; i < 2; i++) begin : g_lanes
+        localparam int K = N + i;
+        int v;
+    end
+endmodule
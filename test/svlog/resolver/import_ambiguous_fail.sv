@@ -0,0 +1,13 @@
+// RUN: moore %s -e foo -O0
+// FAIL
+
+package pkgA; localparam int X = 1; endpackage
+package pkgB; localparam int X = 3; endpackage
+
+module foo;
+    import pkgA::*;
+    import pkgB::*;
+
+    int x = X;
+endmodule
+// CHECK: error: `X` is ambiguous; it is imported from both `pkgA` and `pkgB`
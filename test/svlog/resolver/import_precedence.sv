@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo -O0
+
+package pkgA; localparam int X = 1; localparam int Y = 2; endpackage
+package pkgB; localparam int X = 3; endpackage
+
+module foo;
+    import pkgA::*;
+    import pkgB::*;
+    import pkgB::X;
+
+    // The explicit import of `X` takes precedence over both wildcard imports.
+    int x = X;
+    // Only `pkgA` provides `Y`.
+    int y = Y;
+endmodule
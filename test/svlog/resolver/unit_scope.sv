@@ -0,0 +1,11 @@
+// RUN: moore %s -e foo -O0
+typedef logic [7:0] word_t;
+
+module foo;
+    typedef logic [3:0] word_t;
+    word_t a;
+    $unit::word_t b;
+endmodule
+
+// CHECK: %a = sig i4 %0
+// CHECK: %b = sig i8 %1
@@ -0,0 +1,6 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+    $unit::nope_t a;
+    // CHECK: error: `nope_t` not found in compilation unit `$unit`
+endmodule
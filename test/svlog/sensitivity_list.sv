@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo
+
+module foo (input logic clk, input logic a, input logic b, input logic c);
+    logic x, y, z, q;
+
+    // Complete lists and edge-sensitive procedures are fine.
+    always @(a or b) x = a & b;
+    always @(posedge clk) q <= a & c;
+    always @* z = a | c;
+
+    // `c` is missing.
+    always @(a, b) y = a & b & c;
+endmodule
+// CHECK: warning: sensitivity list is missing `c`
@@ -0,0 +1,20 @@
+// RUN: moore %s -e foo
+
+module foo (input logic clk, input logic a, output logic q, output logic r, output logic s);
+    // Nonblocking assignments in sequential logic are fine, as are blocking
+    // assignments to local temporaries.
+    always_ff @(posedge clk) begin
+        automatic logic t;
+        t = a;
+        q <= t;
+    end
+
+    always_ff @(posedge clk) r = a;
+
+    always @(posedge clk) begin
+        s = a;
+        s <= ~a;
+    end
+endmodule
+// CHECK: warning: blocking assignment to `r` in `always_ff`
+// CHECK: warning: `s` is assigned with both blocking and nonblocking assignments
@@ -1,10 +1,12 @@
-// RUN: moore %s
+// RUN: moore %s -e foo
 module foo;
-  int x, y;
+  int x, y, z;
   localparam int MAX = 42;
 
   initial case (x) inside
     [0:15]: y <= 15;
     [16:MAX]: y <= x * 2;
+    z, 100, [200:z]: y <= 1;
+    default: y <= 0;
   endcase
 endmodule
@@ -0,0 +1,26 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+    logic [3:0] a;
+    logic [1:0] y;
+
+    // The case expression is evaluated once, and every item is compared
+    // against that value.
+    initial case (a) inside
+        4'd1: y = 2'd0;
+        4'd2: y = 2'd1;
+    endcase
+    // CHECK: 0:
+    // CHECK: %a.prb = prb i4$ %a
+    // CHECK: %1 = const i1 0
+    // CHECK: %2 = const i4 1
+    // CHECK: %3 = eq i4 %a.prb, %2
+    // CHECK: %4 = or i1 %1, %3
+    // CHECK: br %4, %5, %case_body
+    // CHECK: case_body:
+    // CHECK: 5:
+    // CHECK: %8 = const i1 0
+    // CHECK: %9 = const i4 2
+    // CHECK: %10 = eq i4 %a.prb, %9
+    // CHECK: %11 = or i1 %8, %10
+endmodule
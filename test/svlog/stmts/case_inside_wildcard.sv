@@ -0,0 +1,23 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+    logic [3:0] a;
+    logic [1:0] y;
+
+    // The `x`, `z`, and `?` bits of an item match anything, and items wider
+    // than the case expression widen the comparison.
+    always_comb case (a) inside
+        4'b1?0?: y = 0;
+        8'h10: y = 1;
+        default: y = 2;
+    endcase
+    // CHECK: %a.prb = prb i4$ %a
+    // CHECK: %1 = const i1 0
+    // CHECK: %2 = const i8 0
+    // CHECK: %zext = inss i8 %2, i4 %a.prb, 0, 4
+    // CHECK: %3 = const i8 250
+    // CHECK: %4 = and i8 %zext, %3
+    // CHECK: %5 = const i8 8
+    // CHECK: %6 = eq i8 %4, %5
+    // CHECK: %7 = or i1 %1, %6
+endmodule
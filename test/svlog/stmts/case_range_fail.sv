@@ -0,0 +1,10 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+  int x, y;
+  initial case (x)
+    [0:15]: y = 1;
+  endcase
+endmodule
+// CHECK: error: value ranges are only allowed in `case ... inside`
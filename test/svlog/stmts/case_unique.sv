@@ -0,0 +1,37 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+    logic [1:0] s;
+    logic [3:0] t;
+    int y;
+
+    // These are fine.
+    always_comb unique case (s)
+        0: y = 1;
+        1: y = 2;
+        2, 3: y = 3;
+    endcase
+    always_comb unique0 case (s)
+        0: y = 1;
+        1: y = 2;
+    endcase
+    always_comb priority casez (t)
+        4'b1???: y = 1;
+        4'b01??: y = 2;
+        default: y = 3;
+    endcase
+
+    always_comb unique casez (t)
+        4'b1???: y = 1;
+        4'b11??: y = 2;
+        default: y = 3;
+    endcase
+    // CHECK: warning: items of `unique case` overlap
+
+    always_comb priority case (s)
+        0: y = 1;
+        1: y = 2;
+        3: y = 3;
+    endcase
+    // CHECK: warning: `priority case` does not cover all values
+endmodule
@@ -0,0 +1,52 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+    logic [3:0] req;
+    logic [1:0] idx;
+
+    // One-hot priority decoder using `?` as don't-care.
+    always_comb casez (req)
+        4'b???1: idx = 0;
+        4'b??10: idx = 1;
+        4'b?100: idx = 2;
+        4'b1000: idx = 3;
+        default: idx = 0;
+    endcase
+    // CHECK: %req.prb = prb i4$ %req
+    // CHECK: %1 = const i1 0
+    // CHECK: %2 = const i4 1
+    // CHECK: %3 = const i4 1
+    // CHECK: %4 = and i4 %req.prb, %3
+    // CHECK: %5 = and i4 %2, %3
+    // CHECK: %6 = eq i4 %4, %5
+    // CHECK: %7 = or i1 %1, %6
+
+    // `casex` also ignores `x` bits in the labels.
+    always_comb casex (req)
+        4'bx?1?: idx = 0;
+        4'bx010: idx = 1;
+        default: idx = 2;
+    endcase
+    // CHECK: %req.prb = prb i4$ %req
+    // CHECK: %1 = const i1 0
+    // CHECK: %2 = const i4 2
+    // CHECK: %3 = const i4 2
+    // CHECK: %4 = and i4 %req.prb, %3
+    // CHECK: %5 = and i4 %2, %3
+    // CHECK: %6 = eq i4 %4, %5
+    // CHECK: %7 = or i1 %1, %6
+
+    // `casez` compares `x` bits in the labels.
+    always_comb casez (req)
+        4'bx?1?: idx = 0;
+        default: idx = 2;
+    endcase
+    // CHECK: %req.prb = prb i4$ %req
+    // CHECK: %1 = const i1 0
+    // CHECK: %2 = const i4 2
+    // CHECK: %3 = const i4 10
+    // CHECK: %4 = and i4 %req.prb, %3
+    // CHECK: %5 = and i4 %2, %3
+    // CHECK: %6 = eq i4 %4, %5
+    // CHECK: %7 = or i1 %1, %6
+endmodule
@@ -0,0 +1,22 @@
+// RUN: moore %s -e foo
+// See §14 "Clocking blocks".
+
+module foo (input logic clk, input logic ack, output logic req);
+    default clocking cb @(posedge clk);
+        default output #2ns;
+        input ack;
+        output req;
+    endclocking
+
+    initial begin
+        cb.req <= 1;
+        @(cb);
+        ##2;
+        if (cb.ack) cb.req <= 0;
+    end
+endmodule
+
+// The output skew delays the drive to `cb.req`.
+// CHECK: %1 = const time 2ns
+// CHECK: %2 = const i1 1
+// CHECK: drv i1$ %req, %2, %1
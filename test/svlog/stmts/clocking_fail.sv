@@ -0,0 +1,25 @@
+// RUN: moore %s -e a -e b -e c
+// FAIL
+// See §14 "Clocking blocks".
+
+module a (input logic clk, input logic x);
+    clocking cb @(posedge clk);
+        input x;
+    endclocking
+    initial cb.x <= 1;
+endmodule
+
+module b (input logic clk, output logic y);
+    clocking cb @(posedge clk);
+        output y;
+    endclocking
+    initial cb.y = 1;
+endmodule
+
+module c;
+    initial ##1;
+endmodule
+
+// CHECK: error: cannot drive `cb.x`, which is an input of the clocking block
+// CHECK: error: clocking block signal `cb.y` can only be driven with a nonblocking assignment
+// CHECK: error: cycle delay `##1` requires a default clocking block
@@ -0,0 +1,7 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    initial #(-2);
+endmodule
+// CHECK: error: delay `-2` is negative
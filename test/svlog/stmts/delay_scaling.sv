@@ -0,0 +1,33 @@
+// RUN: moore %s -e foo
+// See §3.14 "Simulation time units and precision".
+
+module foo;
+    timeunit 10ns / 1ns;
+    bit a, b, c;
+    int n;
+
+    assign #2 b = a;
+    // CHECK: %1 = const time 20ns
+    // CHECK: %2 = const time 0s 1e
+    // CHECK: drv i1$ %out, %value, %2
+
+    assign #2.54 c = a;
+    // CHECK: %1 = const time 25ns
+    // CHECK: %2 = const time 0s 1e
+    // CHECK: drv i1$ %out, %value, %2
+
+    // Non-constant delays wait for one power of two of the time unit for
+    // every bit set in the delay. Zero and negative delays take a single
+    // epsilon step.
+    initial begin
+        #3;
+        #2.5;
+        #1.5ns;
+        #n;
+    end
+    // CHECK: %n.prb = prb i32$ %n
+    // CHECK: delay_zero:
+    // CHECK: delay_bits:
+    // CHECK: delay_step:
+    // CHECK: delay_bits1:
+endmodule
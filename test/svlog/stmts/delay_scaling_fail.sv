@@ -0,0 +1,9 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    bit a, b;
+    int n;
+    initial b <= #n a;
+endmodule
+// CHECK: error: unsupported: non-constant intra-assignment delay `n`
@@ -0,0 +1,17 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+	int a;
+	initial begin : outer
+		for (a = 0; a < 10; a++) begin
+			if (a == 5) disable outer;
+		end
+		a = 42;
+	end
+	initial begin
+		search: for (a = 0; a < 10; a++) begin : body
+			if (a == 2) disable body;
+			if (a == 7) disable search;
+		end
+	end
+endmodule
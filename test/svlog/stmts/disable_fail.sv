@@ -0,0 +1,14 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+	int a;
+	initial begin : first
+		a = 1;
+	end
+	initial begin
+		disable first;
+	end
+endmodule
+
+// CHECK: error: no enclosing block or statement labeled `first`
@@ -0,0 +1,9 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §10.6 "Procedural continuous assignments".
+
+module foo;
+    logic a;
+    initial force a = 1;
+endmodule
+// CHECK: error: unsupported: `force` cannot hold its value until `release`
@@ -0,0 +1,9 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §10.6 "Procedural continuous assignments".
+
+module foo;
+    wire a;
+    initial assign a = 0;
+endmodule
+// CHECK: error: `assign` cannot target the net `a`
@@ -0,0 +1,9 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §10.6 "Procedural continuous assignments".
+
+module foo;
+    wire [3:0] b;
+    initial release b[0];
+endmodule
+// CHECK: error: unsupported: `release` cannot restore the value before `force`
@@ -0,0 +1,15 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+    bit [7:0] mem [4][2];
+    bit [3:0] x;
+    int sum;
+    initial foreach (mem[i, j]) mem[i][j] = i + j;
+    initial foreach (mem[i][j]) sum += mem[i][j];
+    initial foreach (mem[, j]) sum += j;
+    initial foreach (x[k]) x[k] = 1;
+    // CHECK: %1 = const i32 0
+    // CHECK: %i = var i32 %1
+    // CHECK: %5 = const i32 0
+    // CHECK: %j = var i32 %5
+endmodule
@@ -0,0 +1,8 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+    bit mem [4];
+    initial foreach (mem[i, j]) mem[i] = j;
+    // CHECK: error: index variable `j` refers to dimension 2, but the array only has 1
+endmodule
@@ -0,0 +1,18 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+	int a, b;
+	initial begin
+		fork
+			a = 1;
+			b = 2;
+		join
+		fork
+			a = b;
+			begin
+				b = 3;
+				a += b;
+			end
+		join_any
+	end
+endmodule
@@ -0,0 +1,11 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+	int a;
+	initial fork
+		a = 1;
+	join_none
+endmodule
+
+// CHECK: error: unsupported: `fork ... join_none`
@@ -0,0 +1,12 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+	int a;
+	initial fork
+		a = 1;
+		#1 a = 2;
+	join
+endmodule
+
+// CHECK: error: unsupported: `fork` with branches that suspend
@@ -0,0 +1,18 @@
+// RUN: moore %s -e foo
+// See §9.4.2.2 "Implicit event_expression list".
+
+module foo (input logic a, input logic b, output logic x);
+    always @* x = a & b;
+endmodule
+// CHECK: init:
+// CHECK: %1 = prb i1$ %a
+// CHECK: %2 = prb i1$ %b
+// CHECK: wait %check, %a, %b
+// CHECK: check:
+// CHECK: %3 = prb i1$ %a
+// CHECK: %impledge = neq i1 %1, %3
+// CHECK: %4 = prb i1$ %b
+// CHECK: %impledge1 = neq i1 %2, %4
+// CHECK: %event_or = or i1 %impledge, %impledge1
+// CHECK: br %event_or, %init, %event
+// CHECK: event:
@@ -0,0 +1,26 @@
+// RUN: moore %s -e foo
+
+module foo (input logic a, input logic [1:0] s, input logic [7:0] b, output logic [7:0] x, output logic [7:0] y, output logic [7:0] z);
+    // Assigned on all paths.
+    always_comb begin
+        if (a)
+            x = b;
+        else
+            x = ~b;
+    end
+
+    // `unique case` without a default is assumed to be full.
+    always_comb begin
+        unique case (s)
+            2'd0: z = b;
+            2'd1: z = ~b;
+        endcase
+    end
+
+    // Not assigned if `a` is false.
+    always_comb begin
+        if (a)
+            y = b;
+    end
+endmodule
+// CHECK: warning: `y` is not assigned on all paths through the `always_comb`; this infers a latch
@@ -0,0 +1,44 @@
+// RUN: moore %s -e foo -O0
+
+module foo;
+	int a;
+	int b;
+	bit [7:0] mem [4][2];
+
+	initial for (a = 0; a < 4; a++) begin
+		for (b = 0; b < 4; b++) begin
+			if (b == 2) break;
+			if (b == 1) continue;
+		end
+		if (a == 3) break;
+	end
+
+	initial while (a < 10) begin
+		a++;
+		if (a == 5) continue;
+		repeat (3) begin
+			if (b > 1) break;
+			b++;
+		end
+	end
+
+	initial do begin
+		a--;
+		if (a < 0) break;
+	end while (1);
+
+	initial foreach (mem[i, j]) begin
+		if (j == 1) continue;
+		if (i == 2) break;
+		mem[i][j] = 1;
+	end
+endmodule
+
+// The inner `break` leaves the inner loop only, and the inner `continue`
+// proceeds with the next iteration of the inner loop.
+// CHECK: if_true:
+// CHECK: br %loop_exit1
+// CHECK: if_false:
+// CHECK: if_true1:
+// CHECK: br %loop_next1
+// CHECK: if_false1:
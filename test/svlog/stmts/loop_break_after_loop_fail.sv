@@ -0,0 +1,12 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+	int a;
+	initial begin
+		for (a = 0; a < 4; a++) a = a;
+		break;
+	end
+endmodule
+
+// CHECK: error: `break` outside of a loop
@@ -0,0 +1,12 @@
+// RUN: moore %s -e foo
+// FAIL
+
+module foo;
+	int a;
+	initial begin
+		a = 1;
+		break;
+	end
+endmodule
+
+// CHECK: error: `break` outside of a loop
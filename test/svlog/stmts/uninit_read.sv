@@ -0,0 +1,19 @@
+// RUN: moore %s -e foo
+
+module foo (input logic a, input logic [7:0] b, output logic [7:0] z);
+    typedef struct packed { logic [3:0] hi; logic [3:0] lo; } pair_t;
+
+    always_comb begin
+        automatic logic [7:0] x;
+        automatic logic [7:0] y;
+        automatic pair_t p;
+        if (a)
+            x = b;
+        else
+            x = ~b;
+        p.lo = b[3:0];
+        y = {p.lo, x[3:0]};
+        p.hi = y[3:0];
+        z = p;
+    end
+endmodule
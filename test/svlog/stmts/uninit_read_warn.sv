@@ -0,0 +1,20 @@
+// RUN: moore %s -e foo
+
+module foo (input logic a, input logic [7:0] b, output logic [7:0] z, output logic [3:0] w);
+    typedef struct packed { logic [3:0] hi; logic [3:0] lo; } pair_t;
+
+    always_comb begin
+        automatic logic [7:0] x;
+        if (a)
+            x = b;
+        z = x;
+    end
+
+    always_comb begin
+        automatic pair_t p;
+        p.lo = b[3:0];
+        w = p.hi;
+    end
+endmodule
+// CHECK: warning: `x` may be read before it is assigned
+// CHECK: warning: `p.hi` may be read before it is assigned
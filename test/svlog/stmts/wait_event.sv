@@ -0,0 +1,22 @@
+// RUN: moore %s -e foo
+// See §9.4.3 "Level-sensitive event control" and §15.5 "Named events".
+
+module foo;
+    event ev;
+    logic ready;
+    int count;
+    initial begin
+        wait (ready) -> ev;
+        wait (count == 3);
+    end
+    always @(ev) count++;
+endmodule
+
+// CHECK: wait_check:
+// CHECK: wait_suspend:
+// CHECK: wait %wait_check, %ready
+// CHECK: wait_resume:
+// CHECK: wait_check1:
+// CHECK: wait_suspend1:
+// CHECK: wait %wait_check1, %count
+// CHECK: wait_resume1:
@@ -0,0 +1,9 @@
+// RUN: moore %s -e foo
+// FAIL
+// See §15.5.1 "Triggering an event".
+
+module foo;
+    logic a;
+    initial -> a;
+endmodule
+// CHECK: error: `a` is not an event; only named events can be triggered
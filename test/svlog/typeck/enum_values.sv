@@ -0,0 +1,33 @@
+// RUN: moore %s -e foo
+module foo;
+    typedef enum logic [2:0] {
+        IDLE, START = 3, RUN, S[2], DONE
+    } state_t;
+    state_t s0, s1, s2, s3, s4;
+    localparam state_t D = DONE;
+    initial begin
+        s0 = IDLE;
+        // CHECK: %1 = const i3 0
+        // CHECK: %2 = const time 0s 1e
+        // CHECK: drv i3$ %s0, %1, %2
+        s1 = RUN;
+        // CHECK: %3 = const i3 4
+        // CHECK: drv i3$ %s1, %3, %2
+        s2 = S0;
+        // CHECK: %4 = const i3 5
+        // CHECK: drv i3$ %s2, %4, %2
+        s3 = S1;
+        // CHECK: %5 = const i3 6
+        // CHECK: drv i3$ %s3, %5, %2
+        s4 = D;
+        // CHECK: %6 = const i3 7
+        // CHECK: drv i3$ %s4, %6, %2
+    end
+
+    // A1 = 10, A2 = 11, A3 = 12, B = 13
+    enum { A[1:3] = 10, B } e;
+    initial e = B;
+    // CHECK: %1 = const i32 13
+    // CHECK: %2 = const time 0s 1e
+    // CHECK: drv i32$ %e, %1, %2
+endmodule
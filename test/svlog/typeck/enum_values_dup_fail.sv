@@ -0,0 +1,7 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+    enum { A = 2, B = 1, C } e;
+    initial e = C;
+endmodule
+// CHECK: error: enum variant `C` has the same value `2` as enum variant `A`
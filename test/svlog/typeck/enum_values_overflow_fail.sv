@@ -0,0 +1,7 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+    enum bit [1:0] { A = 2, B, C } e;
+    initial e = A;
+endmodule
+// CHECK: error: value `4` of enum variant `C` does not fit into `enum bit [1:0] { A, B, C }`
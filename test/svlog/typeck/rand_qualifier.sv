@@ -0,0 +1,10 @@
+// RUN: moore %s -e foo
+module foo;
+    struct {
+        rand bit [7:0] a;
+        randc logic [3:0] b;
+        rand int c [4];
+        rand struct { rand int x; } d;
+    } s;
+    // CHECK: %s = sig {i8, i4, [4 x i32], {i32}} %5
+endmodule
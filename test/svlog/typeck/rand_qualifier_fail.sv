@@ -0,0 +1,10 @@
+// RUN: moore %s -e foo
+// FAIL
+module foo;
+    struct {
+        rand real a;
+        randc struct { int x; } b;
+    } s;
+endmodule
+// CHECK: error: `rand` cannot be applied to `a` of type `real`
+// CHECK: error: `randc` cannot be applied to `b` of type `struct { int x; }`